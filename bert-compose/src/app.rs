@@ -4,7 +4,7 @@
 
 use crate::circuit::{Circuit, DeclaredSubstance, Node, NodeKind, Wire, SUBSTANCES};
 use crate::ladder::Rung;
-use crate::{askhal, examples, export, theme, ui};
+use crate::{askhal, examples, export, report, theme, ui};
 use bert_core::SubstanceType;
 use eframe::egui;
 use egui::{pos2, vec2, Pos2, Vec2};
@@ -138,10 +138,12 @@ impl App {
     /// Load a BERT model (ours or any compose-shaped one) back onto the
     /// canvas — the other half of the Save round-trip.
     pub fn load_model_file(&mut self, path: &std::path::Path) {
-        let outcome = std::fs::read_to_string(path)
+        let outcome = std::fs::read(path)
             .map_err(|e| e.to_string())
-            .and_then(|s| {
-                serde_json::from_str::<bert_core::WorldModel>(&s).map_err(|e| e.to_string())
+            .and_then(|b| {
+                bert_core::migrate::from_slice(&b)
+                    .map(|(m, _)| m)
+                    .map_err(|e| e.to_string())
             })
             .and_then(|m| export::from_world_model(&m).map(|c| (c, export::model_name(&m))));
         match outcome {
@@ -244,16 +246,9 @@ impl App {
         self.pending_wire = None;
     }
 
-    /// A node's name as the active lens reads it — so a domain run's exported
-    /// data says "Quorum gate / Treasury", not "Modulating 2 / Buffering 3".
-    fn node_label(&self, i: usize) -> String {
-        let node = &self.circuit.nodes[i];
-        crate::lens::display_name(self.lens, node.kind, &node.name)
-    }
-
     /// The recorded run as CSV, labeled by the active lens.
     fn labeled_csv(&self) -> String {
-        self.circuit.csv_with(|i| self.node_label(i))
+        report::labeled_csv(&self.circuit, self.lens)
     }
 
     /// First free path: name.ext, name-1.ext, name-2.ext, …
@@ -298,93 +293,17 @@ impl App {
         }
         let _ = std::fs::write(format!("{dir}/latest.csv"), self.labeled_csv());
         let model = export::to_world_model(&self.circuit, &self.name);
-        if let Ok(j) = serde_json::to_string_pretty(&model) {
-            let _ = std::fs::write(format!("{dir}/latest.json"), j);
-        }
+        let _ = std::fs::write(
+            format!("{dir}/latest.json"),
+            bert_core::to_canonical_json(&model),
+        );
         let _ = std::fs::write(format!("{dir}/latest.md"), self.run_summary());
     }
 
-    /// A compact human/LLM-readable digest of the current run. Names follow
-    /// the active lens, so a domain run reads in domain terms.
+    /// A compact human/LLM-readable digest of the current run, pointing at
+    /// the latest-run files.
     pub fn run_summary(&self) -> String {
-        let c = &self.circuit;
-        let lens_note = if self.lens != 0 {
-            format!(" · {} lens", crate::lens::LENSES[self.lens].name)
-        } else {
-            String::new()
-        };
-        let mut s = format!(
-            "# {} — bert-compose run{}\n\n{} components, {} bonds, {} ticks, diversity {}.\n\n## Components\n",
-            self.name,
-            lens_note,
-            c.nodes.len(),
-            c.wires.len(),
-            c.history.len(),
-            c.diversity(),
-        );
-        for (i, node) in c.nodes.iter().enumerate() {
-            let mut detail = if node.storage.abs() > 1e-6 {
-                format!(", stored {:.2}", node.storage)
-            } else if node.total.abs() > 1e-6 {
-                format!(", total {:.2}", node.total)
-            } else {
-                String::new()
-            };
-            // Capacity legibility: report the ceiling and whether it ever bit
-            // (a regulated stock often never reaches its capacity — see the
-            // history max vs the ceiling).
-            if matches!(
-                node.kind,
-                NodeKind::Process(bert_core::ProcessPrimitive::Buffering)
-            ) && node.capacity > 0.0
-            {
-                let max_seen = c
-                    .history
-                    .iter()
-                    .filter_map(|r| r.get(i * 3 + 2).copied())
-                    .fold(0.0f32, f32::max);
-                let bind = if max_seen >= node.capacity - 0.01 {
-                    "binding"
-                } else {
-                    "dormant"
-                };
-                detail.push_str(&format!(", capacity {:.0} ({bind})", node.capacity));
-            }
-            s.push_str(&format!(
-                "- {} ({}): activity {:.2}{}\n",
-                self.node_label(i),
-                node.kind.label(),
-                node.activity,
-                detail,
-            ));
-        }
-        s.push_str("\n## Wiring\n");
-        for w in &c.wires {
-            s.push_str(&format!(
-                "- {} → {} ({})\n",
-                self.node_label(w.from),
-                self.node_label(w.to),
-                c.nodes[w.from].out_substance.label(),
-            ));
-        }
-        if c.tick > 0 {
-            let baseline: f32 = c.nodes.iter().map(|n| n.initial_storage).sum();
-            s.push_str(&format!(
-                "\n## Conservation\nemitted {:.2} + initial stocks {:.2} = stored {:.2} + sunk {:.2} + in-flight {:.2} + dissipated {:.2} (residual {:+.3})\n",
-                c.emitted, baseline, c.stored(), c.sunk, c.in_flight(), c.dissipated, c.balance(),
-            ));
-        }
-        let mm = c.substance_mismatches();
-        if !mm.is_empty() {
-            s.push_str("\n## Warnings\n");
-            for (i, wants, got) in mm {
-                s.push_str(&format!(
-                    "- {} consumes {wants:?} but is fed {} (flow ignored)\n",
-                    c.nodes[i].name,
-                    got.label(),
-                ));
-            }
-        }
+        let mut s = report::run_summary(&self.circuit, &self.name, self.lens);
         s.push_str("\nFull per-tick data: latest.csv. Model: latest.json.\n");
         s
     }
@@ -397,10 +316,7 @@ impl App {
             &self.name.replace(' ', "-"),
             "json",
         );
-        match serde_json::to_string_pretty(&model)
-            .map_err(|e| e.to_string())
-            .and_then(|s| std::fs::write(&path, s).map_err(|e| e.to_string()))
-        {
+        match std::fs::write(&path, bert_core::to_canonical_json(&model)) {
             Ok(()) => self.status = format!("saved {path} — open it in BERT"),
             Err(e) => self.status = format!("save failed: {e}"),
         }
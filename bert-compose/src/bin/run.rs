@@ -0,0 +1,140 @@
+//! `bert-compose-run` — run a compose model without the canvas.
+//!
+//! Usage:
+//!
+//! ```sh
+//! bert-compose-run homeostat.json --ticks 200 --lens ecology \
+//!     --csv run.csv --ledger ledger.csv --summary run.md
+//! bert-compose-run homeostat.json --ledger - | tail -1
+//! ```
+//!
+//! Loads the model the way the app's Load does (migration, then
+//! `export::from_world_model`), steps it, and writes the same artifacts the
+//! app's latest-run contract does. `-` sends an output to stdout; with no
+//! outputs named, the summary goes there.
+
+use bert_compose::{export, lens, report};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Run a compose-shaped BERT model for N ticks and write what it did.
+#[derive(Parser, Debug)]
+#[command(name = "bert-compose-run", version)]
+struct Args {
+    /// Model to run (any file the app's Load accepts).
+    model: PathBuf,
+
+    /// Ticks to step.
+    #[arg(short, long, default_value_t = 100)]
+    ticks: usize,
+
+    /// Lens that names the nodes in the CSV and summary: systems, political
+    /// economy, neuromorphics, protocol science or ecology.
+    #[arg(long, value_parser = parse_lens, default_value = "systems")]
+    lens: usize,
+
+    /// Per-tick activity/storage/total per node, as CSV.
+    #[arg(long, value_name = "PATH")]
+    csv: Option<PathBuf>,
+
+    /// Per-tick conservation ledger (emitted, delivered, stored,
+    /// dissipated), as CSV.
+    #[arg(long, value_name = "PATH")]
+    ledger: Option<PathBuf>,
+
+    /// Markdown digest of the run.
+    #[arg(long, value_name = "PATH")]
+    summary: Option<PathBuf>,
+}
+
+/// Lens names match case-insensitively, with `-` or `_` for spaces.
+fn parse_lens(s: &str) -> Result<usize, String> {
+    let wanted = s.to_lowercase().replace(['-', '_'], " ");
+    lens::LENSES
+        .iter()
+        .position(|l| l.name.to_lowercase() == wanted)
+        .ok_or_else(|| {
+            let names: Vec<String> = lens::LENSES.iter().map(|l| l.name.to_lowercase()).collect();
+            format!("unknown lens (expected one of: {})", names.join(", "))
+        })
+}
+
+fn is_stdout(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if is_stdout(path) {
+        print!("{contents}");
+        Ok(())
+    } else {
+        std::fs::write(path, contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let stdout = PathBuf::from("-");
+    let summary = match (&args.csv, &args.ledger, &args.summary) {
+        (None, None, None) => Some(&stdout),
+        _ => args.summary.as_ref(),
+    };
+    let outputs = [args.csv.as_ref(), args.ledger.as_ref(), summary];
+    if outputs.iter().flatten().filter(|p| is_stdout(p)).count() > 1 {
+        return Err("only one output can go to stdout".to_string());
+    }
+
+    let path = &args.model;
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let (model, migration) = bert_core::migrate::from_slice(&bytes)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+    if !migration.is_noop() {
+        eprintln!(
+            "bert-compose-run: migrated {}: version {} → {}",
+            path.display(),
+            migration.original_version,
+            migration.final_version
+        );
+    }
+    let mut circuit = export::from_world_model(&model)
+        .map_err(|e| format!("{} is not a compose model: {e}", path.display()))?;
+    let name = export::model_name(&model);
+
+    for _ in 0..args.ticks {
+        circuit.step();
+    }
+
+    if let Some(csv) = &args.csv {
+        write(csv, &report::labeled_csv(&circuit, args.lens))?;
+    }
+    if let Some(ledger) = &args.ledger {
+        write(ledger, &report::ledger_csv(&circuit))?;
+    }
+    if let Some(summary) = summary {
+        let mut s = report::run_summary(&circuit, &name, args.lens);
+        let files: Vec<String> = [("Full per-tick data", &args.csv), ("Ledger", &args.ledger)]
+            .into_iter()
+            .filter_map(|(what, p)| {
+                let p = p.as_ref().filter(|p| !is_stdout(p))?;
+                Some(format!("{what}: {}.", p.display()))
+            })
+            .collect();
+        if !files.is_empty() {
+            s.push_str(&format!("\n{}\n", files.join(" ")));
+        }
+        write(summary, &s)?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("bert-compose-run: error: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -249,7 +249,7 @@ pub const PALETTE: &[NodeKind] = &[
 pub struct Node {
     pub kind: NodeKind,
     pub name: String,
-    pub pos: egui::Pos2,
+    pub pos: emath::Pos2,
     /// Output substance (wires created from this node inherit it). A
     /// declared name + unit over a conserved base kind; dynamics read
     /// `.base` only.
@@ -305,7 +305,7 @@ pub struct Node {
 }
 
 impl Node {
-    pub fn new(kind: NodeKind, n: usize, pos: egui::Pos2) -> Self {
+    pub fn new(kind: NodeKind, n: usize, pos: emath::Pos2) -> Self {
         Self {
             kind,
             name: format!("{} {}", kind.label(), n),
@@ -864,7 +864,6 @@ impl Circuit {
     /// The recorded run as CSV with raw node names. (The app exports via
     /// `csv_with` to carry lens names; this is the raw form used by tests and
     /// the sweep emitter.)
-    #[allow(dead_code)]
     pub fn csv(&self) -> String {
         self.csv_with(|i| self.nodes[i].name.clone())
     }
@@ -1003,7 +1002,7 @@ impl Circuit {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use egui::pos2;
+    use emath::pos2;
 
     fn node(kind: NodeKind) -> Node {
         Node::new(kind, 0, pos2(0.0, 0.0))
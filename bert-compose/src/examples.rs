@@ -10,7 +10,7 @@
 
 use crate::circuit::{Circuit, DeclaredSubstance, Node, NodeKind, Wire, SUBSTANCES};
 use bert_core::{ProcessPrimitive::*, SubstanceType};
-use egui::pos2;
+use emath::pos2;
 
 /// Look up a curated substance by name — examples speak human (water, money,
 /// news), not just Energy/Material/Message.
@@ -508,7 +508,8 @@ fn food_chain() -> Circuit {
     c
 }
 
-/// The universal homeostat, exposed for the lens-invariance test in lens.rs.
+/// The universal homeostat, exposed for the lens-invariance test in lens.rs
+/// and the report tests.
 #[cfg(test)]
 pub fn universal_homeostat_for_test() -> Circuit {
     universal_homeostat()
@@ -14,10 +14,10 @@
 use crate::circuit::{Circuit, DeclaredSubstance, FlowMode, Node, NodeKind, Wire};
 use bert_core::{
     AgentKind, AgentModel, Boundary, Complexity, Environment, ExternalEntity, ExternalEntityType,
-    Id, IdType, Info, Interaction, InteractionType, InteractionUsability, Parameter,
+    History, Id, IdType, Info, Interaction, InteractionType, InteractionUsability, Parameter,
     ProcessPrimitive, Substance, System, Transform2d, WorldModel,
 };
-use egui::pos2;
+use emath::pos2;
 use std::collections::HashMap;
 
 /// Canvas px → model px on save; the inverse on load.
@@ -81,10 +81,10 @@ pub fn to_world_model(circuit: &Circuit, name: &str) -> WorldModel {
         radius: 400.0,
         transform: transform(0.0, 0.0),
         equivalence: String::new(),
-        history: String::new(),
-        transformation: String::new(),
+        history: History::default(),
+        transformation: None,
         member_autonomy: 1.0,
-        time_constant: String::new(),
+        time_constant: None,
         archetype: None,
         agent: None,
     });
@@ -146,10 +146,10 @@ pub fn to_world_model(circuit: &Circuit, name: &str) -> WorldModel {
                     radius: 50.0,
                     transform: transform(x, y),
                     equivalence: String::new(),
-                    history: String::new(),
-                    transformation: String::new(),
+                    history: History::default(),
+                    transformation: None,
                     member_autonomy: 1.0,
-                    time_constant: String::new(),
+                    time_constant: None,
                     archetype: None,
                     agent: Some(AgentModel {
                         kind: AgentKind::Reactive,
@@ -261,13 +261,14 @@ pub fn to_world_model(circuit: &Circuit, name: &str) -> WorldModel {
     }
 
     WorldModel {
-        version: 1,
+        version: bert_core::CURRENT_FILE_VERSION,
         // Absent ≡ Full; compose exports carry the dynamical face.
         mode: None,
         environment,
         systems,
         interactions,
         hidden_entities: Vec::new(),
+        validation: Default::default(),
     }
 }
 
@@ -411,7 +412,7 @@ mod tests {
     use crate::circuit::{Node, Wire};
     use bert_core::validate::{validate, Severity};
     use bert_core::ProcessPrimitive;
-    use egui::pos2;
+    use emath::pos2;
 
     #[test]
     fn emitted_model_validates_and_round_trips() {
@@ -20,16 +20,14 @@ use crate::circuit::{Circuit, Node, NodeKind, Wire};
 use bert_core::ProcessPrimitive::*;
 
 fn n(kind: NodeKind, num: usize, x: f32, y: f32) -> Node {
-    Node::new(kind, num, egui::pos2(x, y))
+    Node::new(kind, num, emath::pos2(x, y))
 }
 
 /// One rung: a Troncale process, its primitive realization, and where it sits
 /// in the four-bucket sweep (see `sweep.rs`).
 ///
-/// `slug`/`provenance`/`bucket`/`ticks` are read by the sweep harness and
-/// artifact emitter, which are `#[cfg(test)]` — so the release bin sees them
-/// as unused. They're metadata of record, kept on the rung deliberately.
-#[allow(dead_code)]
+/// `slug`/`provenance`/`bucket`/`ticks` are read only by the sweep harness and
+/// artifact emitter. They're metadata of record, kept on the rung deliberately.
 pub struct Rung {
     /// File-name slug for the sweep artifact bundle.
     pub slug: &'static str,
@@ -0,0 +1,16 @@
+//! The bert-compose engine without the canvas: `circuit` (all physics),
+//! `export` (the only JSON path, both directions), the content layers
+//! (`ladder`, `examples`, `lens`, `docs`) and `report` (what a run leaves
+//! behind). Nothing here depends on eframe, so it builds headless — the
+//! `bert-compose-run` binary and the sweep use it as is, and the app in
+//! `main.rs` puts a UI on top.
+
+pub mod circuit;
+pub mod docs;
+pub mod examples;
+pub mod export;
+pub mod ladder;
+pub mod lens;
+pub mod report;
+#[cfg(test)]
+mod sweep;
@@ -9,27 +9,20 @@
 //! No error states exist by construction: every wiring action produces a
 //! valid system.
 //!
-//! Layout: `circuit` is the engine (all physics, no UI), `export` the only
-//! JSON path (both directions), `app` the state + persistence, `ui/*` one
-//! module per panel.
+//! Layout: the engine, content and JSON path live in the `bert_compose`
+//! library (see `lib.rs`); this binary adds `app`, the state + persistence,
+//! and `ui/*`, one module per panel.
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
 mod askhal;
-mod circuit;
-mod docs;
-mod examples;
-mod export;
 mod glyph;
-mod ladder;
-mod lens;
-#[cfg(test)]
-mod sweep;
 mod theme;
 mod ui;
 
 use app::App;
+use bert_compose::{circuit, docs, examples, export, ladder, lens, report};
 use eframe::egui;
 
 fn main() -> eframe::Result<()> {
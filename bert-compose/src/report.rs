@@ -0,0 +1,156 @@
+//! What a run leaves behind: the lens-labeled per-tick CSV, the conservation
+//! ledger history, and the markdown digest. Shared by the app (export, the
+//! latest-run contract, Ask hal) and the headless `bert-compose-run`, so a
+//! run reads the same whichever of them produced it.
+
+use crate::circuit::{Circuit, NodeKind};
+use crate::lens;
+
+/// Node `i`'s name as lens `lens` reads it — so a domain run's exported data
+/// says "Quorum gate / Treasury", not "Modulating 2 / Buffering 3".
+pub fn node_label(c: &Circuit, lens: usize, i: usize) -> String {
+    let node = &c.nodes[i];
+    lens::display_name(lens, node.kind, &node.name)
+}
+
+/// The recorded run as CSV, labeled by `lens`.
+pub fn labeled_csv(c: &Circuit, lens: usize) -> String {
+    c.csv_with(|i| node_label(c, lens, i))
+}
+
+/// The ledger history as CSV: one row per recorded tick, the four series the
+/// conservation chart plots.
+pub fn ledger_csv(c: &Circuit) -> String {
+    let mut out = String::from("tick,emitted,delivered,stored,dissipated\n");
+    for (row, l) in c.history.iter().zip(&c.ledger_history) {
+        out.push_str(&format!("{},{},{},{},{}\n", row[0], l[0], l[1], l[2], l[3]));
+    }
+    out
+}
+
+/// A compact human/LLM-readable digest of the run. Names follow `lens`, so a
+/// domain run reads in domain terms. Callers append where the full data went.
+pub fn run_summary(c: &Circuit, name: &str, lens: usize) -> String {
+    let lens_note = if lens != 0 {
+        format!(" · {} lens", lens::LENSES[lens].name)
+    } else {
+        String::new()
+    };
+    let mut s = format!(
+        "# {} — bert-compose run{}\n\n{} components, {} bonds, {} ticks, diversity {}.\n\n## Components\n",
+        name,
+        lens_note,
+        c.nodes.len(),
+        c.wires.len(),
+        c.history.len(),
+        c.diversity(),
+    );
+    for (i, node) in c.nodes.iter().enumerate() {
+        let mut detail = if node.storage.abs() > 1e-6 {
+            format!(", stored {:.2}", node.storage)
+        } else if node.total.abs() > 1e-6 {
+            format!(", total {:.2}", node.total)
+        } else {
+            String::new()
+        };
+        // Capacity legibility: report the ceiling and whether it ever bit
+        // (a regulated stock often never reaches its capacity — see the
+        // history max vs the ceiling).
+        if matches!(
+            node.kind,
+            NodeKind::Process(bert_core::ProcessPrimitive::Buffering)
+        ) && node.capacity > 0.0
+        {
+            let max_seen = c
+                .history
+                .iter()
+                .filter_map(|r| r.get(i * 3 + 2).copied())
+                .fold(0.0f32, f32::max);
+            let bind = if max_seen >= node.capacity - 0.01 {
+                "binding"
+            } else {
+                "dormant"
+            };
+            detail.push_str(&format!(", capacity {:.0} ({bind})", node.capacity));
+        }
+        s.push_str(&format!(
+            "- {} ({}): activity {:.2}{}\n",
+            node_label(c, lens, i),
+            node.kind.label(),
+            node.activity,
+            detail,
+        ));
+    }
+    s.push_str("\n## Wiring\n");
+    for w in &c.wires {
+        s.push_str(&format!(
+            "- {} → {} ({})\n",
+            node_label(c, lens, w.from),
+            node_label(c, lens, w.to),
+            c.nodes[w.from].out_substance.label(),
+        ));
+    }
+    if c.tick > 0 {
+        let baseline: f32 = c.nodes.iter().map(|n| n.initial_storage).sum();
+        s.push_str(&format!(
+            "\n## Conservation\nemitted {:.2} + initial stocks {:.2} = stored {:.2} + sunk {:.2} + in-flight {:.2} + dissipated {:.2} (residual {:+.3})\n",
+            c.emitted, baseline, c.stored(), c.sunk, c.in_flight(), c.dissipated, c.balance(),
+        ));
+    }
+    let mm = c.substance_mismatches();
+    if !mm.is_empty() {
+        s.push_str("\n## Warnings\n");
+        for (i, wants, got) in mm {
+            s.push_str(&format!(
+                "- {} consumes {wants:?} but is fed {} (flow ignored)\n",
+                c.nodes[i].name,
+                got.label(),
+            ));
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::universal_homeostat_for_test;
+
+    #[test]
+    fn ledger_rows_follow_the_history() {
+        let mut c = universal_homeostat_for_test();
+        assert_eq!(
+            ledger_csv(&c).lines().count(),
+            1,
+            "header only before a run"
+        );
+        for _ in 0..12 {
+            c.step();
+        }
+        let csv = ledger_csv(&c);
+        assert_eq!(csv.lines().count(), 1 + c.history.len());
+        let last: Vec<f32> = csv
+            .lines()
+            .last()
+            .unwrap()
+            .split(',')
+            .map(|v| v.parse().unwrap())
+            .collect();
+        assert_eq!(last[0], c.tick as f32);
+        assert_eq!(last[1..], c.ledger_history.last().unwrap()[..]);
+    }
+
+    #[test]
+    fn summary_reads_in_the_lens() {
+        let mut c = universal_homeostat_for_test();
+        for _ in 0..5 {
+            c.step();
+        }
+        let neutral = run_summary(&c, "Homeostat", 0);
+        let domain = run_summary(&c, "Homeostat", 1);
+        assert!(neutral.contains("## Conservation"));
+        assert!(domain.contains(&format!("· {} lens", lens::LENSES[1].name)));
+        assert!(domain.contains(&node_label(&c, 1, 0)));
+        assert_ne!(node_label(&c, 1, 0), node_label(&c, 0, 0));
+    }
+}
@@ -202,7 +202,7 @@ mod tests {
         // Predation flow with both stocks held at x (prey) and y (predator).
         let flow = |x: f32, y: f32| -> f32 {
             let mut c = Circuit::default();
-            let node = |kind, num, px, py| Node::new(kind, num, egui::pos2(px, py));
+            let node = |kind, num, px, py| Node::new(kind, num, emath::pos2(px, py));
             c.nodes.push(node(NodeKind::Process(Buffering), 1, 240.0, 240.0)); // 0 prey
             c.nodes
                 .push(node(NodeKind::Process(Modulating), 2, 420.0, 240.0)); // 1 valve
@@ -573,7 +573,7 @@ fn emit_sweep_artifacts() {
         let model = to_world_model(&c, rung.slug);
         std::fs::write(
             format!("{dir}/{}.json", rung.slug),
-            serde_json::to_string_pretty(&model).unwrap(),
+            bert_core::to_canonical_json(&model),
         )
         .unwrap();
         std::fs::write(format!("{dir}/{}.csv", rung.slug), c.csv()).unwrap();
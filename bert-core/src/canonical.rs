@@ -0,0 +1,224 @@
+//! Canonical form for model files: one byte sequence per model.
+//!
+//! Saving the same model twice must not produce a diff. [`canonicalize`]
+//! fixes every degree of freedom the serializer would otherwise leave to
+//! chance — element order, float noise in layout fields, whitespace-only
+//! strings, duplicate entries in set-like id lists — and
+//! [`to_canonical_json`] renders the result. Every writer (the editor's save
+//! path, bert-compose, the CLIs) goes through it, so files stay stable in git.
+//!
+//! Canonicalization never renumbers ids or changes model meaning; it is
+//! idempotent, and a canonical file reloads and re-saves byte-identically.
+
+use crate::*;
+
+/// Decimal places kept for positions and radii (pixels at 100% zoom).
+const PIXEL_DECIMALS: i32 = 2;
+/// Decimal places kept for rotations and boundary angles (radians).
+const ANGLE_DECIMALS: i32 = 4;
+
+/// A canonical copy of `model`.
+///
+/// - systems, interfaces, sources, sinks and interactions sorted by [`Id`]
+///   (hierarchy first, so parents precede children);
+/// - `exports_to`, `receives_from` and `hidden_entities` sorted and deduplicated;
+/// - transforms, radii, interface angles and endpoint offsets rounded to fixed
+///   precision, with `-0.0` and non-finite values folded to `0.0`;
+/// - whitespace-only text fields replaced by the empty string, and a
+///   transformation left with nothing in it dropped.
+pub fn canonicalize(model: &WorldModel) -> WorldModel {
+    let mut m = model.clone();
+
+    canonical_info(&mut m.environment.info);
+    canonical_externals(&mut m.environment.sources);
+    canonical_externals(&mut m.environment.sinks);
+
+    for system in &mut m.systems {
+        canonical_info(&mut system.info);
+        canonical_info(&mut system.boundary.info);
+        canonical_externals(&mut system.sources);
+        canonical_externals(&mut system.sinks);
+        system.radius = round(system.radius, PIXEL_DECIMALS);
+        canonical_transform(&mut system.transform);
+        blank_to_empty(&mut system.equivalence);
+        if let Some(t) = &mut system.transformation {
+            blank_to_empty(&mut t.description);
+        }
+        if system
+            .transformation
+            .as_ref()
+            .is_some_and(Transformation::is_empty)
+        {
+            system.transformation = None;
+        }
+        for event in &mut system.history.0 {
+            blank_to_empty(&mut event.timestamp);
+        }
+        for iface in &mut system.boundary.interfaces {
+            canonical_info(&mut iface.info);
+            blank_to_empty(&mut iface.protocol);
+            sort_dedup(&mut iface.exports_to);
+            sort_dedup(&mut iface.receives_from);
+            iface.angle = iface.angle.map(|a| round(a, ANGLE_DECIMALS));
+        }
+        system
+            .boundary
+            .interfaces
+            .sort_by(|a, b| a.info.id.cmp(&b.info.id));
+    }
+    m.systems.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+
+    for ix in &mut m.interactions {
+        canonical_info(&mut ix.info);
+        blank_to_empty(&mut ix.substance.sub_type);
+        blank_to_empty(&mut ix.unit);
+        for p in &mut ix.parameters {
+            blank_to_empty(&mut p.name);
+            blank_to_empty(&mut p.value);
+            blank_to_empty(&mut p.unit);
+        }
+        if let Some(offset) = &mut ix.endpoint_offset {
+            offset.start_angle = offset.start_angle.map(|a| round(a, ANGLE_DECIMALS));
+            offset.end_angle = offset.end_angle.map(|a| round(a, ANGLE_DECIMALS));
+        }
+        // An all-`None` offset serializes as noise; absent is the canonical "no offset".
+        if ix.endpoint_offset == Some(EndpointOffset::default()) {
+            ix.endpoint_offset = None;
+        }
+    }
+    m.interactions.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+
+    sort_dedup(&mut m.hidden_entities);
+    m
+}
+
+/// Pretty-printed JSON of [`canonicalize`]`(model)`, newline-terminated.
+pub fn to_canonical_json(model: &WorldModel) -> String {
+    let mut json = serde_json::to_string_pretty(&canonicalize(model))
+        // Every map in the model is string-keyed and canonical floats are
+        // finite, so serialization cannot fail.
+        .expect("canonical model serializes");
+    json.push('\n');
+    json
+}
+
+fn canonical_info(info: &mut Info) {
+    blank_to_empty(&mut info.name);
+    blank_to_empty(&mut info.description);
+}
+
+fn canonical_externals(entities: &mut [ExternalEntity]) {
+    for ent in entities.iter_mut() {
+        canonical_info(&mut ent.info);
+        blank_to_empty(&mut ent.equivalence);
+        blank_to_empty(&mut ent.model);
+        canonical_transform(&mut ent.transform);
+    }
+    entities.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+}
+
+fn canonical_transform(transform: &mut Option<Transform2d>) {
+    if let Some(t) = transform {
+        t.translation.x = round(t.translation.x, PIXEL_DECIMALS);
+        t.translation.y = round(t.translation.y, PIXEL_DECIMALS);
+        t.rotation = round(t.rotation, ANGLE_DECIMALS);
+    }
+}
+
+/// Round half away from zero in `f64`, so re-rounding a rounded value is a no-op.
+fn round(x: f32, decimals: i32) -> f32 {
+    if !x.is_finite() {
+        return 0.0;
+    }
+    let scale = 10f64.powi(decimals);
+    let r = ((x as f64) * scale).round() / scale;
+    // `+ 0.0` folds -0.0 into 0.0.
+    r as f32 + 0.0
+}
+
+fn blank_to_empty(s: &mut String) {
+    if !s.is_empty() && s.trim().is_empty() {
+        s.clear();
+    }
+}
+
+fn sort_dedup(ids: &mut Vec<Id>) {
+    ids.sort();
+    ids.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, examples};
+
+    fn load(bytes: &[u8]) -> WorldModel {
+        crate::migrate::from_slice(bytes).unwrap().0
+    }
+
+    #[test]
+    fn load_save_load_save_is_byte_identical_for_every_example() {
+        for (name, model) in examples() {
+            let first = to_canonical_json(&model);
+            let second = to_canonical_json(&load(first.as_bytes()));
+            assert_eq!(first, second, "{name} is not save-stable");
+        }
+    }
+
+    #[test]
+    fn canonicalization_preserves_model_content() {
+        for (name, model) in examples() {
+            let d = crate::diff::diff(&model, &canonicalize(&model));
+            assert!(
+                !d.has_semantic_changes(),
+                "{name}: canonical form changed meaning:\n{d}"
+            );
+        }
+    }
+
+    #[test]
+    fn element_order_does_not_affect_output() {
+        let model = example("bitcoin");
+        let mut shuffled = model.clone();
+        shuffled.systems.reverse();
+        shuffled.interactions.reverse();
+        shuffled.environment.sources.reverse();
+        shuffled.systems[0].boundary.interfaces.reverse();
+        assert_eq!(to_canonical_json(&model), to_canonical_json(&shuffled));
+    }
+
+    #[test]
+    fn float_noise_and_blank_strings_are_normalized() {
+        let mut model = example("blank");
+        model.systems[0].transform = Some(Transform2d {
+            translation: Vec2::new(12.5, -3.25),
+            rotation: 0.0,
+        });
+        model.systems[0].info.description.clear();
+        let clean = to_canonical_json(&model);
+
+        let t = model.systems[0].transform.as_mut().unwrap();
+        t.translation.x += 0.000_01;
+        t.rotation = -0.0;
+        model.systems[0].radius += 0.000_1;
+        model.systems[0].info.description = "   ".to_string();
+        assert_eq!(to_canonical_json(&model), clean);
+
+        model.systems[0].radius = f32::NAN;
+        assert!(to_canonical_json(&model).contains("\"radius\": 0.0"));
+    }
+
+    #[test]
+    fn agent_maps_serialize_in_key_order() {
+        let mut agent = AgentModel::default();
+        for key in ["zeta", "alpha", "mu", "beta", "omega", "gamma"] {
+            agent.cognitive_params.insert(key.to_string(), 1.0);
+        }
+        let json = serde_json::to_string(&agent).unwrap();
+        let keys: Vec<usize> = ["alpha", "beta", "gamma", "mu", "omega", "zeta"]
+            .iter()
+            .map(|k| json.find(k).unwrap())
+            .collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]), "{json}");
+    }
+}
@@ -0,0 +1,767 @@
+//! Assembling a larger model from separately built ones: [`embed`] puts the
+//! System of Interest of a guest model into a host model as a subsystem.
+//!
+//! Inside the host, the guest's environment is the host itself. Every
+//! environment source or sink of the guest turns into a port on the new
+//! subsystem's boundary — the interface its flows already entered through, or
+//! a new one — and a [`Binding`] says what sits on the other side of that port:
+//!
+//! - a host source or sink: the same entity, the way `is_same_as_id` marks
+//!   duplicates within one model, so the guest's flows now come from (or go to)
+//!   it;
+//! - a host system or interface: the guest's flows connect to it directly;
+//! - a host interaction: that interaction is rerouted to the port and stands
+//!   for the guest's own flow, which is dropped.
+//!
+//! Unbound externals are carried into `parent` as its own sources and sinks,
+//! so no guest flow is lost.
+
+use crate::renumber::{self, RenumberError};
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+/// Guest environment source or sink `external` stands for the host element `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub external: Id,
+    pub to: Id,
+}
+
+/// Parses `EXTERNAL=TARGET`, e.g. `Src-1.0=Src-1.2`.
+impl std::str::FromStr for Binding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (external, to) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected EXTERNAL=TARGET, got '{s}'"))?;
+        let parse = |id: &str| {
+            id.trim()
+                .parse::<Id>()
+                .map_err(|e| format!("'{id}' is not an id: {e}"))
+        };
+        Ok(Self {
+            external: parse(external)?,
+            to: parse(to)?,
+        })
+    }
+}
+
+/// The host model with the guest embedded, and the id the guest's root took.
+pub struct Composition {
+    pub model: WorldModel,
+    pub subsystem: Id,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComposeError {
+    /// The id names no system in the host model.
+    UnknownSystem(Id),
+    /// The guest model has no root system, or more than one.
+    NoSingleRoot,
+    /// A binding names something other than an environment source or sink of the guest.
+    NotAnExternal(Id),
+    /// A binding's target names no external entity, system, interface or
+    /// interaction of the host.
+    UnknownTarget(Id),
+    /// A guest source bound to a host sink, or a guest sink to a host source.
+    Mismatch { external: Id, to: Id },
+    /// The same guest external is bound twice.
+    DuplicateBinding(Id),
+    /// The guest model's ids or references are inconsistent.
+    Renumber(RenumberError),
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSystem(id) => write!(f, "'{id}' is not a system in the host model"),
+            Self::NoSingleRoot => write!(f, "guest model must have exactly one root system"),
+            Self::NotAnExternal(id) => write!(
+                f,
+                "'{id}' is not an environment source or sink of the guest model"
+            ),
+            Self::UnknownTarget(id) => write!(f, "'{id}' names nothing in the host model"),
+            Self::Mismatch { external, to } => {
+                write!(
+                    f,
+                    "cannot bind '{external}' to '{to}': source and sink swapped"
+                )
+            }
+            Self::DuplicateBinding(id) => write!(f, "'{id}' is bound more than once"),
+            Self::Renumber(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ComposeError {}
+
+impl From<RenumberError> for ComposeError {
+    fn from(e: RenumberError) -> Self {
+        Self::Renumber(e)
+    }
+}
+
+/// Embed the System of Interest of `guest` into `host` as a subsystem of `parent`.
+///
+/// The guest is compacted first; its root takes the next free slot under
+/// `parent` and everything beneath it is re-based there, levels recomputed.
+/// Binding ids refer to the guest as passed in. A guest flow that reached its
+/// root without an interface gets a port named after the external, on the side
+/// of the boundary the flow enters from.
+pub fn embed(
+    host: &WorldModel,
+    parent: &Id,
+    guest: &WorldModel,
+    bindings: &[Binding],
+) -> Result<Composition, ComposeError> {
+    attach(host, parent, guest, bindings, true)
+}
+
+/// What a guest external is bound to.
+enum Target {
+    /// An external entity, or a system with the interface to attach through.
+    Endpoint(Id, Option<Id>),
+    /// An interaction rerouted to the port.
+    Flow(Id),
+}
+
+/// [`embed`], optionally without adding ports: [`extract::embed_subsystem`]
+/// restores flows exactly as they were.
+pub(crate) fn attach(
+    host: &WorldModel,
+    parent: &Id,
+    guest: &WorldModel,
+    bindings: &[Binding],
+    add_ports: bool,
+) -> Result<Composition, ComposeError> {
+    let compacted = renumber::compact(guest)?;
+    let guest = &compacted.model;
+    let guest_env = &guest.environment.info.id;
+    let roots: Vec<&System> = guest
+        .systems
+        .iter()
+        .filter(|s| &s.parent == guest_env)
+        .collect();
+    let [guest_root] = roots[..] else {
+        return Err(ComposeError::NoSingleRoot);
+    };
+    let base = renumber::free_slot(host, parent)
+        .ok_or_else(|| ComposeError::UnknownSystem(parent.clone()))?;
+
+    // After `compact` the root is `S0` and every non-environment id starts at 0.
+    let root = Id {
+        ty: IdType::System,
+        indices: vec![0],
+    };
+    let subsystem = Id {
+        ty: IdType::Subsystem,
+        indices: base.clone(),
+    };
+    let rebase = |id: &Id| -> Id {
+        if id.indices.first() != Some(&0) {
+            return id.clone();
+        }
+        if *id == root {
+            return subsystem.clone();
+        }
+        let mut indices = base.clone();
+        indices.extend_from_slice(&id.indices[1..]);
+        Id { ty: id.ty, indices }
+    };
+    let rebase_info = |info: &mut Info| {
+        info.id = rebase(&info.id);
+        info.level = renumber::level_of(&info.id);
+    };
+
+    let externals: HashMap<&Id, &ExternalEntity> = guest
+        .environment
+        .sources
+        .iter()
+        .chain(&guest.environment.sinks)
+        .map(|e| (&e.info.id, e))
+        .collect();
+    let mut bound: HashMap<Id, Target> = HashMap::new();
+    for binding in bindings {
+        let external = compacted
+            .renamed
+            .get(&binding.external)
+            .unwrap_or(&binding.external);
+        let entity = externals
+            .get(external)
+            .ok_or_else(|| ComposeError::NotAnExternal(binding.external.clone()))?;
+        let target = target(host, &binding.to)
+            .ok_or_else(|| ComposeError::UnknownTarget(binding.to.clone()))?;
+        if let Target::Endpoint(e, None) = &target {
+            if matches!(
+                (entity.ty, e.ty),
+                (ExternalEntityType::Source, IdType::Sink)
+                    | (ExternalEntityType::Sink, IdType::Source)
+            ) {
+                return Err(ComposeError::Mismatch {
+                    external: binding.external.clone(),
+                    to: binding.to.clone(),
+                });
+            }
+        }
+        if bound.insert(external.clone(), target).is_some() {
+            return Err(ComposeError::DuplicateBinding(binding.external.clone()));
+        }
+    }
+
+    let mut merged = host.clone();
+    for mut system in guest.systems.iter().cloned() {
+        rebase_info(&mut system.info);
+        system.parent = if &system.parent == guest_env {
+            parent.clone()
+        } else {
+            rebase(&system.parent)
+        };
+        rebase_info(&mut system.boundary.info);
+        system.boundary.parent_interface = system.boundary.parent_interface.as_ref().map(rebase);
+        for e in system.sources.iter_mut().chain(&mut system.sinks) {
+            rebase_info(&mut e.info);
+        }
+        for iface in &mut system.boundary.interfaces {
+            rebase_info(&mut iface.info);
+            iface.paired_with = iface.paired_with.as_ref().map(rebase);
+            // Environment references are re-added below, as each flow is wired.
+            for list in [&mut iface.exports_to, &mut iface.receives_from] {
+                *list = list
+                    .iter()
+                    .filter(|i| !externals.contains_key(i))
+                    .map(rebase)
+                    .collect();
+            }
+        }
+        merged.systems.push(system);
+    }
+
+    let carried = carry_unbound(&mut merged, parent, &externals, &bound);
+
+    let mut next_port = guest
+        .systems
+        .iter()
+        .filter(|s| s.parent == root)
+        .map(|s| &s.info.id)
+        .chain(guest_root.boundary.interfaces.iter().map(|i| &i.info.id))
+        .filter_map(|id| id.indices.last())
+        .map(|i| i + 1)
+        .max()
+        .unwrap_or(0);
+    let mut ports: HashMap<&Id, Id> = HashMap::new();
+    let mut rerouted: HashSet<&Id> = HashSet::new();
+
+    for ix in &guest.interactions {
+        let mut ix = ix.clone();
+        let Some(external) = [&ix.source, &ix.sink]
+            .into_iter()
+            .find(|id| externals.contains_key(id))
+            .cloned()
+        else {
+            rebase_info(&mut ix.info);
+            ix.source = rebase(&ix.source);
+            ix.sink = rebase(&ix.sink);
+            ix.source_interface = ix.source_interface.as_ref().map(rebase);
+            ix.sink_interface = ix.sink_interface.as_ref().map(rebase);
+            merged.interactions.push(ix);
+            continue;
+        };
+        let (external, entity) = externals.get_key_value(&external).expect("found above");
+        let incoming = ix.source == **external;
+
+        let (inside, mut inside_iface) = if incoming {
+            (rebase(&ix.sink), ix.sink_interface.as_ref().map(rebase))
+        } else {
+            (rebase(&ix.source), ix.source_interface.as_ref().map(rebase))
+        };
+        if add_ports && inside_iface.is_none() && inside == subsystem {
+            let port = ports.entry(external).or_insert_with(|| {
+                let mut indices = base.clone();
+                indices.push(next_port);
+                next_port += 1;
+                let id = Id {
+                    ty: IdType::Interface,
+                    indices,
+                };
+                let system = merged
+                    .systems
+                    .iter_mut()
+                    .find(|s| s.info.id == subsystem)
+                    .expect("grafted above");
+                system
+                    .boundary
+                    .interfaces
+                    .push(port_for(entity, id.clone(), incoming));
+                id
+            });
+            inside_iface = Some(port.clone());
+        }
+
+        let (outside, outside_iface) = match bound.get(*external) {
+            Some(Target::Flow(f)) if rerouted.insert(f) => {
+                reroute(&mut merged, f, incoming, inside, inside_iface);
+                continue;
+            }
+            Some(Target::Flow(f)) => {
+                let host_flow = host
+                    .interactions
+                    .iter()
+                    .find(|h| &h.info.id == f)
+                    .expect("resolved by `target`");
+                if incoming {
+                    (host_flow.source.clone(), host_flow.source_interface.clone())
+                } else {
+                    (host_flow.sink.clone(), host_flow.sink_interface.clone())
+                }
+            }
+            Some(Target::Endpoint(e, i)) => (e.clone(), i.clone()),
+            None => (carried[*external].clone(), None),
+        };
+
+        link(&mut merged, &inside_iface, &outside, incoming);
+        link(&mut merged, &outside_iface, &inside, !incoming);
+        if incoming {
+            (ix.source, ix.source_interface) = (outside, outside_iface);
+            (ix.sink, ix.sink_interface) = (inside, inside_iface);
+        } else {
+            (ix.source, ix.source_interface) = (inside, inside_iface);
+            (ix.sink, ix.sink_interface) = (outside, outside_iface);
+        }
+        ix.info.id = renumber::next_interaction_id(&merged, &ix.source, &ix.sink);
+        ix.info.level = renumber::level_of(&ix.info.id);
+        merged.interactions.push(ix);
+    }
+
+    merged.hidden_entities.extend(
+        guest
+            .hidden_entities
+            .iter()
+            .filter(|i| i.indices.first() == Some(&0))
+            .map(rebase),
+    );
+    Ok(Composition {
+        model: merged,
+        subsystem,
+    })
+}
+
+/// The host element `id` names, if any.
+pub(crate) fn resolves(host: &WorldModel, id: &Id) -> bool {
+    target(host, id).is_some()
+}
+
+fn target(host: &WorldModel, id: &Id) -> Option<Target> {
+    if host.interactions.iter().any(|ix| &ix.info.id == id) {
+        return Some(Target::Flow(id.clone()));
+    }
+    let env = &host.environment;
+    if env
+        .sources
+        .iter()
+        .chain(&env.sinks)
+        .any(|e| &e.info.id == id)
+    {
+        return Some(Target::Endpoint(id.clone(), None));
+    }
+    for system in &host.systems {
+        if &system.info.id == id
+            || system
+                .sources
+                .iter()
+                .chain(&system.sinks)
+                .any(|e| &e.info.id == id)
+        {
+            return Some(Target::Endpoint(id.clone(), None));
+        }
+        if system.boundary.interfaces.iter().any(|i| &i.info.id == id) {
+            return Some(Target::Endpoint(system.info.id.clone(), Some(id.clone())));
+        }
+    }
+    None
+}
+
+/// Move unbound guest externals into `parent`, numbered after its own, with
+/// `is_same_as_id` groups shifted past the host's. Returns old id → new id.
+fn carry_unbound(
+    merged: &mut WorldModel,
+    parent: &Id,
+    externals: &HashMap<&Id, &ExternalEntity>,
+    bound: &HashMap<Id, Target>,
+) -> HashMap<Id, Id> {
+    let env = &merged.environment;
+    let same_as_base = env
+        .sources
+        .iter()
+        .chain(&env.sinks)
+        .chain(
+            merged
+                .systems
+                .iter()
+                .flat_map(|s| s.sources.iter().chain(&s.sinks)),
+        )
+        .filter_map(|e| e.is_same_as_id)
+        .map(|k| k + 1)
+        .max()
+        .unwrap_or(0);
+    let owner = merged
+        .systems
+        .iter_mut()
+        .find(|s| &s.info.id == parent)
+        .expect("`free_slot` found the parent");
+
+    let mut unbound: Vec<&&ExternalEntity> = externals
+        .iter()
+        .filter(|(id, _)| !bound.contains_key(**id))
+        .map(|(_, e)| e)
+        .collect();
+    unbound.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+
+    let mut carried = HashMap::new();
+    for entity in unbound {
+        let (list, ty) = match entity.ty {
+            ExternalEntityType::Source => (&mut owner.sources, IdType::Source),
+            ExternalEntityType::Sink => (&mut owner.sinks, IdType::Sink),
+        };
+        let n = list
+            .iter()
+            .filter_map(|e| e.info.id.indices.last())
+            .map(|i| i + 1)
+            .max()
+            .unwrap_or(0);
+        let mut indices = parent.indices.clone();
+        indices.push(n);
+        let mut e = (*entity).clone();
+        e.info.id = Id { ty, indices };
+        e.info.level = renumber::level_of(&e.info.id);
+        e.is_same_as_id = e.is_same_as_id.map(|k| k + same_as_base);
+        carried.insert(entity.info.id.clone(), e.info.id.clone());
+        list.push(e);
+    }
+    carried
+}
+
+/// A new boundary interface standing for `entity`: an import on the left for
+/// a source, an export on the right for a sink.
+fn port_for(entity: &ExternalEntity, id: Id, incoming: bool) -> Interface {
+    let level = renumber::level_of(&id);
+    Interface {
+        info: Info {
+            id,
+            level,
+            name: entity.info.name.clone(),
+            description: entity.info.description.clone(),
+        },
+        protocol: String::new(),
+        ty: if incoming {
+            InterfaceType::Import
+        } else {
+            InterfaceType::Export
+        },
+        exports_to: Vec::new(),
+        receives_from: Vec::new(),
+        angle: Some(if incoming { PI } else { 0.0 }),
+        paired_with: None,
+    }
+}
+
+/// Point the host interaction `flow` at the new subsystem in place of its
+/// old sink (`incoming`) or source, keeping the interface lists in step.
+fn reroute(merged: &mut WorldModel, flow: &Id, incoming: bool, end: Id, end_iface: Option<Id>) {
+    let ix = merged
+        .interactions
+        .iter_mut()
+        .find(|ix| &ix.info.id == flow)
+        .expect("resolved by `target`");
+    let (old, old_iface, far, far_iface) = if incoming {
+        let old = std::mem::replace(&mut ix.sink, end.clone());
+        let old_iface = std::mem::replace(&mut ix.sink_interface, end_iface.clone());
+        (
+            old,
+            old_iface,
+            ix.source.clone(),
+            ix.source_interface.clone(),
+        )
+    } else {
+        let old = std::mem::replace(&mut ix.source, end.clone());
+        let old_iface = std::mem::replace(&mut ix.source_interface, end_iface.clone());
+        (old, old_iface, ix.sink.clone(), ix.sink_interface.clone())
+    };
+
+    if let Some(iface) = old_iface.and_then(|i| interface_mut(&mut merged.systems, &i)) {
+        let list = if incoming {
+            &mut iface.receives_from
+        } else {
+            &mut iface.exports_to
+        };
+        list.retain(|i| *i != far);
+    }
+    if let Some(iface) = far_iface
+        .as_ref()
+        .and_then(|i| interface_mut(&mut merged.systems, i))
+    {
+        let list = if incoming {
+            &mut iface.exports_to
+        } else {
+            &mut iface.receives_from
+        };
+        list.retain(|i| *i != old);
+    }
+    link(merged, &far_iface, &end, !incoming);
+    link(merged, &end_iface, &far, incoming);
+}
+
+/// Record `other` in the interface `iface`'s `receives_from` (or `exports_to`).
+fn link(merged: &mut WorldModel, iface: &Option<Id>, other: &Id, receives: bool) {
+    let Some(iface) = iface
+        .as_ref()
+        .and_then(|i| interface_mut(&mut merged.systems, i))
+    else {
+        return;
+    };
+    let list = if receives {
+        &mut iface.receives_from
+    } else {
+        &mut iface.exports_to
+    };
+    if !list.contains(other) {
+        list.push(other.clone());
+    }
+}
+
+pub(crate) fn interface_mut<'m>(systems: &'m mut [System], id: &Id) -> Option<&'m mut Interface> {
+    systems
+        .iter_mut()
+        .flat_map(|s| s.boundary.interfaces.iter_mut())
+        .find(|i| &i.info.id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, id};
+    use crate::validate::{validate, Severity};
+
+    fn bind(external: &str, to: &str) -> Binding {
+        format!("{external}={to}").parse().unwrap()
+    }
+
+    fn errors(model: &WorldModel) -> Vec<String> {
+        validate(model)
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| format!("{}: {}", i.location, i.message))
+            .collect()
+    }
+
+    fn flow<'m>(model: &'m WorldModel, name: &str, sink: &Id) -> &'m Interaction {
+        model
+            .interactions
+            .iter()
+            .find(|ix| ix.info.name == name && (&ix.sink == sink || &ix.source == sink))
+            .unwrap_or_else(|| panic!("no flow '{name}' at {sink}"))
+    }
+
+    const CHAINS: [&str; 4] = ["bitcoin", "cosmos-hub", "ethereum", "solana"];
+
+    #[test]
+    fn every_chain_embeds_in_every_other_unbound() {
+        for host_name in CHAINS {
+            let host = example(host_name);
+            for guest_name in CHAINS.iter().filter(|g| **g != host_name) {
+                let guest = example(guest_name);
+                let c = embed(&host, &id("S0"), &guest, &[]).unwrap();
+                let m = &c.model;
+                let what = format!("{guest_name} in {host_name}");
+
+                assert_eq!(errors(m), Vec::<String>::new(), "{what}");
+                assert_eq!(m.systems.len(), host.systems.len() + guest.systems.len());
+                assert_eq!(
+                    m.interactions.len(),
+                    host.interactions.len() + guest.interactions.len()
+                );
+                let sub = m.systems.iter().find(|s| s.info.id == c.subsystem).unwrap();
+                assert_eq!(sub.parent, id("S0"));
+                assert_eq!(sub.info.level, 1);
+                let guest_root = guest.systems.iter().find(|s| s.info.level == 0).unwrap();
+                assert_eq!(sub.info.name, guest_root.info.name);
+
+                // The guest's environment now lives inside the host's root.
+                let root = m.systems.iter().find(|s| s.info.id == id("S0")).unwrap();
+                assert_eq!(
+                    root.sources.len(),
+                    guest.environment.sources.len(),
+                    "{what}"
+                );
+                assert_eq!(root.sinks.len(), guest.environment.sinks.len(), "{what}");
+                assert!(root.sources.iter().all(|e| e.info.level == 1));
+                assert_eq!(m.environment.sources.len(), host.environment.sources.len());
+            }
+        }
+    }
+
+    #[test]
+    fn bindings_connect_to_host_externals_and_reroute_host_flows() {
+        let host = example("bitcoin");
+        let guest = example("ethereum");
+        let c = embed(
+            &host,
+            &id("S0"),
+            &guest,
+            &[
+                // Users are the same people on both chains.
+                bind("Src-1.0", "Src-1.0"),
+                // Bitcoin's contributor flow now feeds the embedded chain.
+                bind("Src-1.2", "F-1.4"),
+            ],
+        )
+        .unwrap();
+        let m = &c.model;
+        assert_eq!(errors(m), Vec::<String>::new());
+        assert_eq!(c.subsystem, id("C0.54"));
+
+        let sub = m.systems.iter().find(|s| s.info.id == c.subsystem).unwrap();
+        let port = |ix: &Interaction| {
+            let i = ix.sink_interface.as_ref().unwrap();
+            sub.boundary
+                .interfaces
+                .iter()
+                .find(|p| &p.info.id == i)
+                .unwrap()
+        };
+
+        // Src-1.0 → S0 @ I0.52 became host Src-1.0 → C0.54 through the same interface.
+        let requests = m
+            .interactions
+            .iter()
+            .filter(|ix| ix.info.name == "Transaction Requests" && ix.sink == c.subsystem)
+            .collect::<Vec<_>>();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].source, id("Src-1.0"));
+        assert_eq!(port(requests[0]).receives_from, vec![id("Src-1.0")]);
+        assert_eq!(requests[0].info.level, -1);
+
+        // F-1.4 keeps its id and source but ends at the guest's port; the
+        // guest's own contributor flow is gone.
+        let contributors = flow(m, "Contributors", &c.subsystem);
+        assert_eq!(contributors.info.id, id("F-1.4"));
+        assert_eq!(contributors.source, id("Src-1.2"));
+
+        assert_eq!(
+            m.interactions.len(),
+            host.interactions.len() + guest.interactions.len() - 1
+        );
+        assert_eq!(port(contributors).receives_from, vec![id("Src-1.2")]);
+        let host_port = m.systems[0].boundary.interfaces.iter();
+        let host_port = host_port
+            .clone()
+            .find(|i| i.info.id == id("I0.51"))
+            .unwrap();
+        assert!(host_port.receives_from.is_empty());
+
+        // Only the unbound externals were carried in.
+        let root = m.systems.iter().find(|s| s.info.id == id("S0")).unwrap();
+        let carried: Vec<_> = root
+            .sources
+            .iter()
+            .chain(&root.sinks)
+            .map(|e| e.info.name.as_str())
+            .collect();
+        assert_eq!(carried, ["Internet Infrastructure", "Environment", "Users"]);
+    }
+
+    #[test]
+    fn flows_without_an_interface_get_a_port() {
+        let host = example("bitcoin");
+        let mut guest = example("solana");
+        let bandwidth = guest
+            .interactions
+            .iter_mut()
+            .find(|ix| ix.info.name == "Network Bandwidth")
+            .unwrap();
+        bandwidth.sink_interface = None;
+        let source = bandwidth.source.clone();
+        for iface in &mut guest.systems[0].boundary.interfaces {
+            iface.receives_from.retain(|i| *i != source);
+        }
+
+        let c = embed(
+            &host,
+            &id("S0"),
+            &guest,
+            &[bind(&source.to_string(), "Src-1.1")],
+        )
+        .unwrap();
+        let m = &c.model;
+        let ix = flow(m, "Network Bandwidth", &c.subsystem);
+        let port_id = ix.sink_interface.clone().unwrap();
+        let sub = m.systems.iter().find(|s| s.info.id == c.subsystem).unwrap();
+        let port = sub
+            .boundary
+            .interfaces
+            .iter()
+            .find(|i| i.info.id == port_id)
+            .unwrap();
+        assert_eq!(port.info.name, "Internet Infrastructure");
+        assert!(matches!(port.ty, InterfaceType::Import));
+        assert_eq!(port.receives_from, vec![id("Src-1.1")]);
+        assert_eq!(port.info.level, 2);
+        assert_eq!(errors(m), Vec::<String>::new());
+    }
+
+    #[test]
+    fn same_as_groups_do_not_collide_with_the_host() {
+        let host = example("bitcoin");
+        let c = embed(&host, &id("C0.1"), &example("ethereum"), &[]).unwrap();
+        let owner = c
+            .model
+            .systems
+            .iter()
+            .find(|s| s.info.id == id("C0.1"))
+            .unwrap();
+        let users: Vec<_> = owner
+            .sources
+            .iter()
+            .chain(&owner.sinks)
+            .filter(|e| e.info.name == "Users")
+            .collect();
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].is_same_as_id, Some(1));
+        assert_eq!(users[1].is_same_as_id, Some(1));
+        assert_eq!(users[0].info.id, id("Src0.1.0"));
+        assert_eq!(c.subsystem.indices.len(), 3);
+    }
+
+    #[test]
+    fn bad_bindings_are_rejected() {
+        let host = example("bitcoin");
+        let guest = example("ethereum");
+        let err = |parent: &str, bindings: &[Binding]| {
+            embed(&host, &id(parent), &guest, bindings).err().unwrap()
+        };
+        assert_eq!(err("C0.99", &[]), ComposeError::UnknownSystem(id("C0.99")));
+        assert_eq!(
+            err("S0", &[bind("C0.1", "Src-1.0")]),
+            ComposeError::NotAnExternal(id("C0.1"))
+        );
+        assert_eq!(
+            err("S0", &[bind("Src-1.0", "Src-1.9")]),
+            ComposeError::UnknownTarget(id("Src-1.9"))
+        );
+        assert_eq!(
+            err("S0", &[bind("Src-1.0", "Snk-1.1")]),
+            ComposeError::Mismatch {
+                external: id("Src-1.0"),
+                to: id("Snk-1.1")
+            }
+        );
+        assert_eq!(
+            err("S0", &[bind("Src-1.0", "Src-1.0"), bind("Src-1.0", "C0.1")]),
+            ComposeError::DuplicateBinding(id("Src-1.0"))
+        );
+        assert!("Src-1.0".parse::<Binding>().is_err());
+        assert!("Src-1.0=nonsense".parse::<Binding>().is_err());
+    }
+}
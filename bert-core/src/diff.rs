@@ -0,0 +1,875 @@
+//! Structural diff and three-way merge of [`WorldModel`]s, keyed by [`Id`].
+//!
+//! Text diffs of model JSON are dominated by re-serialized field order and
+//! transform noise. Here every system, interface, external entity and
+//! interaction is matched across models by its `Id` — so a renamed subsystem
+//! that kept its address is a modification, not a delete plus an add — and
+//! compared field by field. Layout-only fields (transforms, radii, interface
+//! angles, flow endpoint offsets, hidden entities) are classed as
+//! [`ChangeClass::Geometry`] so reviewers can filter them out.
+//!
+//! Elements are compared as flattened JSON: each leaf is addressed by its
+//! dotted path under the element (`info.name`, `transform.translation`).
+//! Child elements are compared separately — a system's fields never include
+//! its interfaces or local sources and sinks.
+
+use crate::*;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Which part of the 8-tuple an element belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    System,
+    Interface,
+    ExternalEntity,
+    Interaction,
+}
+
+impl std::fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElementKind::System => write!(f, "system"),
+            ElementKind::Interface => write!(f, "interface"),
+            ElementKind::ExternalEntity => write!(f, "external entity"),
+            ElementKind::Interaction => write!(f, "interaction"),
+        }
+    }
+}
+
+/// Whether a field carries model meaning or only canvas layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeClass {
+    Semantic,
+    Geometry,
+}
+
+/// One leaf that differs. `None` means the field is absent on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub class: ChangeClass,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementChange {
+    Added,
+    Removed,
+    Modified(Vec<FieldChange>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementDiff {
+    pub id: Id,
+    pub kind: ElementKind,
+    /// Name on the `after` side (or `before`, for removals).
+    pub name: String,
+    pub change: ElementChange,
+}
+
+impl ElementDiff {
+    /// True when every changed field is layout-only.
+    pub fn is_geometry_only(&self) -> bool {
+        match &self.change {
+            ElementChange::Modified(fields) => {
+                fields.iter().all(|f| f.class == ChangeClass::Geometry)
+            }
+            ElementChange::Added | ElementChange::Removed => false,
+        }
+    }
+}
+
+/// The result of [`diff`]: model-level field changes plus one entry per
+/// element that was added, removed or modified.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ModelDiff {
+    /// Changes to `version`, `mode`, the environment's info and `hidden_entities`.
+    pub model: Vec<FieldChange>,
+    pub elements: Vec<ElementDiff>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.model.is_empty() && self.elements.is_empty()
+    }
+
+    /// True if anything other than layout changed.
+    pub fn has_semantic_changes(&self) -> bool {
+        self.model.iter().any(|f| f.class == ChangeClass::Semantic)
+            || self.elements.iter().any(|e| !e.is_geometry_only())
+    }
+
+    /// Element diffs with geometry-only modifications dropped, and
+    /// geometry fields stripped from the remaining modifications.
+    pub fn semantic(&self) -> Vec<ElementDiff> {
+        self.elements
+            .iter()
+            .filter(|e| !e.is_geometry_only())
+            .map(|e| {
+                let mut e = e.clone();
+                if let ElementChange::Modified(fields) = &mut e.change {
+                    fields.retain(|f| f.class == ChangeClass::Semantic);
+                }
+                e
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |v: &Option<Value>| v.as_ref().map_or("∅".to_string(), Value::to_string);
+        for field in &self.model {
+            writeln!(
+                f,
+                "~ model {}: {} → {}",
+                field.path,
+                show(&field.before),
+                show(&field.after)
+            )?;
+        }
+        for e in &self.elements {
+            let id = serialize_id(&e.id);
+            match &e.change {
+                ElementChange::Added => writeln!(f, "+ {} {id} '{}'", e.kind, e.name)?,
+                ElementChange::Removed => writeln!(f, "- {} {id} '{}'", e.kind, e.name)?,
+                ElementChange::Modified(fields) => {
+                    writeln!(f, "~ {} {id} '{}'", e.kind, e.name)?;
+                    for field in fields {
+                        let tag = match field.class {
+                            ChangeClass::Semantic => "",
+                            ChangeClass::Geometry => " (geometry)",
+                        };
+                        writeln!(
+                            f,
+                            "    {}{tag}: {} → {}",
+                            field.path,
+                            show(&field.before),
+                            show(&field.after)
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare two models element by element.
+///
+/// Elements are reported in `after` order, followed by removals in `before`
+/// order, so the output is stable for a given pair of models.
+pub fn diff(before: &WorldModel, after: &WorldModel) -> ModelDiff {
+    let model = diff_fields(&model_fields(before), &model_fields(after));
+
+    let old = Elements::of(before);
+    let new = Elements::of(after);
+    let mut elements = Vec::new();
+    for (id, e) in new.iter() {
+        match old.get(id) {
+            None => elements.push(ElementDiff {
+                id: id.clone(),
+                kind: e.kind,
+                name: e.name.clone(),
+                change: ElementChange::Added,
+            }),
+            Some(o) => {
+                let fields = diff_fields(&o.fields, &e.fields);
+                if !fields.is_empty() {
+                    elements.push(ElementDiff {
+                        id: id.clone(),
+                        kind: e.kind,
+                        name: e.name.clone(),
+                        change: ElementChange::Modified(fields),
+                    });
+                }
+            }
+        }
+    }
+    for (id, o) in old.iter() {
+        if new.get(id).is_none() {
+            elements.push(ElementDiff {
+                id: id.clone(),
+                kind: o.kind,
+                name: o.name.clone(),
+                change: ElementChange::Removed,
+            });
+        }
+    }
+
+    ModelDiff { model, elements }
+}
+
+// ── Three-way merge ──────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both sides changed the same field to different values.
+    BothModified,
+    /// One side removed an element the other side modified.
+    ModifyDelete,
+    /// Both sides added an element under the same `Id` with differing fields.
+    AddAdd,
+}
+
+/// A field (or whole element) the merge could not decide. The merged model
+/// holds the `ours` value for field conflicts and the edited element for
+/// modify/delete; `base`/`ours`/`theirs` are `None` where absent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub kind: ConflictKind,
+    /// `None` for a model-level field.
+    pub id: Option<Id>,
+    pub element: Option<ElementKind>,
+    /// `None` when the conflict concerns the element's existence.
+    pub path: Option<String>,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+pub struct MergeResult {
+    pub model: WorldModel,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl MergeResult {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Three-way merge of two descendants of `base`.
+///
+/// Field-level: a field changed on one side only takes that side's value; a
+/// field changed identically on both sides is accepted; anything else is a
+/// [`Conflict`] resolved provisionally to `ours`. Element order follows
+/// `ours`, with elements only `theirs` added appended in their order.
+///
+/// Fails only if the merged fields do not deserialize back into a
+/// [`WorldModel`], which a conflict-free merge of valid models cannot cause.
+pub fn merge(
+    base: &WorldModel,
+    ours: &WorldModel,
+    theirs: &WorldModel,
+) -> Result<MergeResult, serde_json::Error> {
+    let mut conflicts = Vec::new();
+
+    let model = merge_fields(
+        &model_fields(base),
+        &model_fields(ours),
+        &model_fields(theirs),
+        |path, b, o, t| {
+            conflicts.push(Conflict {
+                kind: ConflictKind::BothModified,
+                id: None,
+                element: None,
+                path: Some(path),
+                base: b,
+                ours: o,
+                theirs: t,
+            })
+        },
+    );
+
+    let b = Elements::of(base);
+    let o = Elements::of(ours);
+    let t = Elements::of(theirs);
+
+    // Ours order, then theirs-only additions.
+    let mut order: Vec<&Id> = o.order.iter().collect();
+    let in_ours: HashSet<&Id> = o.order.iter().collect();
+    order.extend(t.order.iter().filter(|id| !in_ours.contains(id)));
+
+    let mut merged: Vec<(Id, Element)> = Vec::new();
+    for id in order {
+        let (be, oe, te) = (b.get(id), o.get(id), t.get(id));
+        let chosen = match (be, oe, te) {
+            // Added on one side only.
+            (None, Some(oe), None) | (None, None, Some(oe)) => Some(oe.clone()),
+            (Some(be), Some(oe), None) | (Some(be), None, Some(oe)) => {
+                // Removed on one side: honor it unless the other side edited.
+                if oe.fields == be.fields {
+                    None
+                } else {
+                    conflicts.push(Conflict {
+                        kind: ConflictKind::ModifyDelete,
+                        id: Some(id.clone()),
+                        element: Some(oe.kind),
+                        path: None,
+                        base: Some(unflatten(&be.fields)),
+                        ours: side_value(oe, te.is_none()),
+                        theirs: side_value(oe, te.is_some()),
+                    });
+                    Some(oe.clone())
+                }
+            }
+            (be, Some(oe), Some(te)) => {
+                let empty = BTreeMap::new();
+                let base_fields = be.map_or(&empty, |e| &e.fields);
+                let kind = if be.is_some() {
+                    ConflictKind::BothModified
+                } else {
+                    ConflictKind::AddAdd
+                };
+                let fields = merge_fields(base_fields, &oe.fields, &te.fields, |path, b, o, t| {
+                    conflicts.push(Conflict {
+                        kind,
+                        id: Some(id.clone()),
+                        element: Some(oe.kind),
+                        path: Some(path),
+                        base: b,
+                        ours: o,
+                        theirs: t,
+                    })
+                });
+                Some(Element {
+                    kind: oe.kind,
+                    owner: oe.owner.clone(),
+                    name: oe.name.clone(),
+                    fields,
+                })
+            }
+            (_, None, None) => None,
+        };
+        if let Some(e) = chosen {
+            merged.push((id.clone(), e));
+        }
+    }
+
+    // Removed on both sides: nothing to do. Removed on one side while the
+    // base is absent cannot happen — the id would not be in `order`.
+
+    let model = assemble(model, merged)?;
+    Ok(MergeResult { model, conflicts })
+}
+
+/// For a modify/delete conflict: the element's value on the side that kept
+/// it, `None` on the side that removed it.
+fn side_value(e: &Element, present: bool) -> Option<Value> {
+    present.then(|| unflatten(&e.fields))
+}
+
+// ── Element extraction ───────────────────────────────────────
+
+/// Dotted field path → leaf value.
+type Fields = BTreeMap<String, Value>;
+
+#[derive(Clone)]
+struct Element {
+    kind: ElementKind,
+    /// The system that holds this element (its boundary, or its local
+    /// sources/sinks); `None` for top-level systems lists, environment
+    /// externals, and interactions.
+    owner: Option<Id>,
+    name: String,
+    fields: Fields,
+}
+
+struct Elements {
+    order: Vec<Id>,
+    by_id: HashMap<Id, Element>,
+}
+
+impl Elements {
+    fn of(model: &WorldModel) -> Self {
+        let mut out = Elements {
+            order: Vec::new(),
+            by_id: HashMap::new(),
+        };
+        for ent in model
+            .environment
+            .sources
+            .iter()
+            .chain(&model.environment.sinks)
+        {
+            out.push(ElementKind::ExternalEntity, None, ent, ent);
+        }
+        for system in &model.systems {
+            let mut v = to_object(system);
+            v.remove("sources");
+            v.remove("sinks");
+            if let Some(boundary) = v.get_mut("boundary").and_then(Value::as_object_mut) {
+                boundary.remove("interfaces");
+            }
+            out.push_fields(
+                ElementKind::System,
+                None,
+                system.info(),
+                flatten(&Value::Object(v)),
+            );
+            let owner = Some(system.info.id.clone());
+            for iface in &system.boundary.interfaces {
+                out.push(ElementKind::Interface, owner.clone(), iface, iface);
+            }
+            for ent in system.sources.iter().chain(&system.sinks) {
+                out.push(ElementKind::ExternalEntity, owner.clone(), ent, ent);
+            }
+        }
+        for ix in &model.interactions {
+            out.push(ElementKind::Interaction, None, ix, ix);
+        }
+        out
+    }
+
+    fn push(
+        &mut self,
+        kind: ElementKind,
+        owner: Option<Id>,
+        element: &impl HasInfo,
+        value: &impl serde::Serialize,
+    ) {
+        let fields = flatten(&Value::Object(to_object(value)));
+        self.push_fields(kind, owner, element.info(), fields);
+    }
+
+    fn push_fields(&mut self, kind: ElementKind, owner: Option<Id>, info: &Info, fields: Fields) {
+        let id = info.id.clone();
+        let element = Element {
+            kind,
+            owner,
+            name: info.name.clone(),
+            fields,
+        };
+        // Duplicate ids are a validation error; the last one wins here.
+        if self.by_id.insert(id.clone(), element).is_none() {
+            self.order.push(id);
+        }
+    }
+
+    fn get(&self, id: &Id) -> Option<&Element> {
+        self.by_id.get(id)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&Id, &Element)> {
+        self.order.iter().map(|id| (id, &self.by_id[id]))
+    }
+}
+
+/// `version`, `mode`, the environment minus its externals, and `hidden_entities`.
+fn model_fields(model: &WorldModel) -> Fields {
+    let mut v = to_object(model);
+    v.remove("systems");
+    v.remove("interactions");
+    if let Some(env) = v.get_mut("environment").and_then(Value::as_object_mut) {
+        env.remove("sources");
+        env.remove("sinks");
+    }
+    flatten(&Value::Object(v))
+}
+
+/// Rebuild a model from merged model-level fields and elements.
+fn assemble(model: Fields, elements: Vec<(Id, Element)>) -> Result<WorldModel, serde_json::Error> {
+    let mut root = unflatten(&model);
+    let mut systems: Vec<(Id, Value)> = Vec::new();
+    let mut interactions = Vec::new();
+    let mut env_sources = Vec::new();
+    let mut env_sinks = Vec::new();
+    // Children are attached once all systems are known.
+    let mut children: Vec<(Id, ElementKind, Value)> = Vec::new();
+
+    for (id, e) in elements {
+        let value = unflatten(&e.fields);
+        match (e.kind, e.owner) {
+            (ElementKind::System, _) => systems.push((id, value)),
+            (ElementKind::Interaction, _) => interactions.push(value),
+            (kind, Some(owner)) => children.push((owner, kind, value)),
+            (ElementKind::ExternalEntity, None) => {
+                if id.ty == IdType::Sink {
+                    env_sinks.push(value)
+                } else {
+                    env_sources.push(value)
+                }
+            }
+            (ElementKind::Interface, None) => {}
+        }
+    }
+
+    for (_, system) in systems.iter_mut() {
+        let obj = system
+            .as_object_mut()
+            .expect("system fields form an object");
+        obj.insert("sources".into(), Value::Array(vec![]));
+        obj.insert("sinks".into(), Value::Array(vec![]));
+        if let Some(boundary) = obj.get_mut("boundary").and_then(Value::as_object_mut) {
+            boundary.insert("interfaces".into(), Value::Array(vec![]));
+        }
+    }
+    for (owner, kind, value) in children {
+        // An owner removed by the merge takes its children with it.
+        let Some((_, system)) = systems.iter_mut().find(|(id, _)| *id == owner) else {
+            continue;
+        };
+        let slot = match kind {
+            ElementKind::Interface => &mut system["boundary"]["interfaces"],
+            _ if value["type"] == "Sink" => &mut system["sinks"],
+            _ => &mut system["sources"],
+        };
+        if let Some(list) = slot.as_array_mut() {
+            list.push(value);
+        }
+    }
+
+    let env = &mut root["environment"];
+    env["sources"] = Value::Array(env_sources);
+    env["sinks"] = Value::Array(env_sinks);
+    root["systems"] = Value::Array(systems.into_iter().map(|(_, v)| v).collect());
+    root["interactions"] = Value::Array(interactions);
+    serde_json::from_value(root)
+}
+
+// ── Flattened field comparison ───────────────────────────────
+
+/// Top-level keys whose value is only canvas layout.
+const GEOMETRY_FIELDS: &[&str] = &[
+    "transform",
+    "radius",
+    "angle",
+    "endpoint_offset",
+    "hidden_entities",
+];
+
+/// Keys compared as a single value rather than recursed into: externally
+/// tagged enums, where merging the variants' inner fields would produce an
+/// object that is neither variant.
+const ATOMIC_FIELDS: &[&str] = &["complexity"];
+
+fn class_of(path: &str) -> ChangeClass {
+    let head = path.split('.').next().unwrap_or(path);
+    if GEOMETRY_FIELDS.contains(&head) {
+        ChangeClass::Geometry
+    } else {
+        ChangeClass::Semantic
+    }
+}
+
+fn to_object(value: &impl serde::Serialize) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Objects are recursed into (keys joined with `.`); arrays, scalars, empty
+/// objects and [`ATOMIC_FIELDS`] are leaves. Keys containing `.` are escaped
+/// as `\.` so [`unflatten`] can split paths unambiguously.
+fn flatten(value: &Value) -> Fields {
+    fn walk(prefix: &str, value: &Value, out: &mut Fields) {
+        match value {
+            Value::Object(map) if !map.is_empty() && !ATOMIC_FIELDS.contains(&prefix) => {
+                for (k, v) in map {
+                    let k = k.replace('\\', "\\\\").replace('.', "\\.");
+                    let path = if prefix.is_empty() {
+                        k
+                    } else {
+                        format!("{prefix}.{k}")
+                    };
+                    walk(&path, v, out);
+                }
+            }
+            _ => {
+                out.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+    let mut out = Fields::new();
+    walk("", value, &mut out);
+    out
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    parts.last_mut().unwrap().push(next);
+                }
+            }
+            '.' => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+    parts
+}
+
+fn unflatten(fields: &Fields) -> Value {
+    let mut root = Value::Object(Map::new());
+    for (path, leaf) in fields {
+        if path.is_empty() {
+            return leaf.clone();
+        }
+        let parts = split_path(path);
+        let mut node = &mut root;
+        for part in &parts[..parts.len() - 1] {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = node
+                .as_object_mut()
+                .unwrap()
+                .entry(part.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+        }
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        let last = parts.last().unwrap().clone();
+        let map = node.as_object_mut().unwrap();
+        // A nested object already claimed this key: the object wins.
+        if !map
+            .get(&last)
+            .is_some_and(|v| v.is_object() && !leaf.is_object())
+        {
+            map.insert(last, leaf.clone());
+        }
+    }
+    root
+}
+
+fn diff_fields(before: &Fields, after: &Fields) -> Vec<FieldChange> {
+    let paths: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    paths
+        .into_iter()
+        .filter(|p| before.get(*p) != after.get(*p))
+        .map(|p| FieldChange {
+            path: p.clone(),
+            before: before.get(p).cloned(),
+            after: after.get(p).cloned(),
+            class: class_of(p),
+        })
+        .collect()
+}
+
+fn merge_fields(
+    base: &Fields,
+    ours: &Fields,
+    theirs: &Fields,
+    mut conflict: impl FnMut(String, Option<Value>, Option<Value>, Option<Value>),
+) -> Fields {
+    let paths: std::collections::BTreeSet<&String> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+    let mut out = Fields::new();
+    for p in paths {
+        let (b, o, t) = (base.get(p), ours.get(p), theirs.get(p));
+        let pick = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            conflict(p.clone(), b.cloned(), o.cloned(), t.cloned());
+            o
+        };
+        if let Some(v) = pick {
+            out.insert(p.clone(), v.clone());
+        }
+    }
+    out
+}
+
+fn serialize_id(id: &Id) -> String {
+    match serde_json::to_value(id) {
+        Ok(Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, id};
+
+    fn system_mut<'a>(m: &'a mut WorldModel, s: &str) -> &'a mut System {
+        let target = id(s);
+        m.systems.iter_mut().find(|x| x.info.id == target).unwrap()
+    }
+
+    fn as_json(m: &WorldModel) -> Value {
+        serde_json::to_value(m).unwrap()
+    }
+
+    #[test]
+    fn identical_models_have_empty_diff() {
+        let m = example("bitcoin");
+        assert!(diff(&m, &m).is_empty());
+    }
+
+    #[test]
+    fn rename_with_same_id_is_a_modification() {
+        let before = example("bitcoin");
+        let mut after = before.clone();
+        system_mut(&mut after, "C0.1").info.name = "Proof of Work".to_string();
+
+        let d = diff(&before, &after);
+        assert_eq!(d.elements.len(), 1, "{d}");
+        let e = &d.elements[0];
+        assert_eq!(e.id, id("C0.1"));
+        assert_eq!(e.kind, ElementKind::System);
+        assert_eq!(e.name, "Proof of Work");
+        let ElementChange::Modified(fields) = &e.change else {
+            panic!("expected a modification, got {:?}", e.change);
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].path, "info.name");
+        assert_eq!(fields[0].before, Some(Value::from("Mining")));
+        assert_eq!(fields[0].class, ChangeClass::Semantic);
+    }
+
+    #[test]
+    fn geometry_changes_are_classed_separately() {
+        let before = example("bitcoin");
+        let mut after = before.clone();
+        let sys = system_mut(&mut after, "C0.2");
+        sys.radius += 10.0;
+        sys.transform = Some(Transform2d::default());
+        after.systems[0].boundary.interfaces[0].angle = Some(1.25);
+
+        let d = diff(&before, &after);
+        assert_eq!(d.elements.len(), 2, "{d}");
+        assert!(d.elements.iter().all(ElementDiff::is_geometry_only));
+        assert!(!d.has_semantic_changes());
+        assert!(d.semantic().is_empty());
+    }
+
+    #[test]
+    fn children_are_diffed_as_their_own_elements() {
+        let before = example("bitcoin");
+        let mut after = before.clone();
+        let removed = after.systems[0].boundary.interfaces.remove(1);
+        after.interactions[0].substance.sub_type = "Heat".to_string();
+
+        let d = diff(&before, &after);
+        assert!(d
+            .elements
+            .iter()
+            .any(|e| e.id == removed.info.id && e.change == ElementChange::Removed));
+        assert!(
+            !d.elements
+                .iter()
+                .any(|e| e.id == id("S0") && e.kind == ElementKind::System),
+            "removing an interface does not modify its system: {d}"
+        );
+        let ix = d
+            .elements
+            .iter()
+            .find(|e| e.kind == ElementKind::Interaction)
+            .unwrap();
+        let ElementChange::Modified(fields) = &ix.change else {
+            panic!()
+        };
+        assert_eq!(fields[0].path, "substance.sub_type");
+    }
+
+    #[test]
+    fn disjoint_edits_merge_cleanly() {
+        let base = example("bitcoin");
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        system_mut(&mut ours, "C0.1").info.description = "ours".to_string();
+        system_mut(&mut theirs, "C0.1").info.name = "theirs".to_string();
+        theirs.interactions.pop();
+
+        let merged = merge(&base, &ours, &theirs).unwrap();
+        assert!(merged.is_clean(), "{:?}", merged.conflicts);
+        let m = merged.model;
+        let c01 = m.systems.iter().find(|s| s.info.id == id("C0.1")).unwrap();
+        assert_eq!(c01.info.description, "ours");
+        assert_eq!(c01.info.name, "theirs");
+        assert_eq!(m.interactions.len(), base.interactions.len() - 1);
+        assert!(!crate::validate::validate(&m).has_errors());
+    }
+
+    #[test]
+    fn merge_of_unchanged_sides_reproduces_base() {
+        let base = example("bitcoin");
+        let merged = merge(&base, &base, &base).unwrap();
+        assert!(merged.is_clean());
+        assert_eq!(as_json(&merged.model), as_json(&base));
+    }
+
+    #[test]
+    fn merge_keeps_additions_from_both_sides() {
+        let base = example("bitcoin");
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        let mut a = ours.interactions[0].clone();
+        a.info.id = id("F-1.90");
+        ours.interactions.push(a);
+        let mut b = theirs.interactions[0].clone();
+        b.info.id = id("F-1.91");
+        theirs.interactions.push(b);
+
+        let merged = merge(&base, &ours, &theirs).unwrap();
+        assert!(merged.is_clean());
+        let ids: Vec<Id> = merged
+            .model
+            .interactions
+            .iter()
+            .map(|ix| ix.info.id.clone())
+            .collect();
+        assert!(ids.ends_with(&[id("F-1.90"), id("F-1.91")]), "{ids:?}");
+    }
+
+    #[test]
+    fn same_field_changed_differently_conflicts() {
+        let base = example("bitcoin");
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        system_mut(&mut ours, "C0.1").info.name = "A".to_string();
+        system_mut(&mut theirs, "C0.1").info.name = "B".to_string();
+
+        let merged = merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts.len(), 1);
+        let c = &merged.conflicts[0];
+        assert_eq!(c.kind, ConflictKind::BothModified);
+        assert_eq!(c.id, Some(id("C0.1")));
+        assert_eq!(c.path.as_deref(), Some("info.name"));
+        assert_eq!(c.ours, Some(Value::from("A")));
+        assert_eq!(c.theirs, Some(Value::from("B")));
+        let kept = merged
+            .model
+            .systems
+            .iter()
+            .find(|s| s.info.id == id("C0.1"))
+            .unwrap();
+        assert_eq!(
+            kept.info.name, "A",
+            "conflicts resolve provisionally to ours"
+        );
+    }
+
+    #[test]
+    fn modify_delete_conflicts_and_keeps_the_edit() {
+        let base = example("bitcoin");
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.interactions[0].unit = "MW".to_string();
+        theirs.interactions.remove(0);
+
+        let merged = merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts.len(), 1);
+        let c = &merged.conflicts[0];
+        assert_eq!(c.kind, ConflictKind::ModifyDelete);
+        assert!(c.ours.is_some() && c.theirs.is_none());
+        assert_eq!(merged.model.interactions[0].unit, "MW");
+    }
+
+    #[test]
+    fn complexity_is_merged_as_a_whole() {
+        let base = example("bitcoin");
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        system_mut(&mut ours, "C0.1").complexity = Complexity::Atomic;
+        system_mut(&mut theirs, "C0.1").complexity = Complexity::Multiset(3);
+
+        let merged = merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].path.as_deref(), Some("complexity"));
+    }
+}
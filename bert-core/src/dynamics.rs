@@ -0,0 +1,451 @@
+//! The dynamical face of a system — Mobus's transformation `T`, history `H`
+//! and time constant `Δt` — as typed values.
+//!
+//! Before file version 3 all three were free text. Each type here still reads
+//! that legacy shape, so an old model deserializes unchanged; the v2 → v3
+//! migration only rewrites the file into the typed form:
+//!
+//! - [`Timescale`] is a unit from the controlled [`TimeConstant`] vocabulary,
+//!   optionally scaled (`"Second"`, `"15 Minute"`), serialized as that string;
+//! - [`Transformation`] lists the substances a system takes in and gives out,
+//!   with an optional description (a legacy note becomes the description);
+//! - [`History`] is a log of [`HistoryEvent`]s, oldest first (a legacy note
+//!   becomes one undated event).
+
+use crate::*;
+use serde::de::{self, DeserializeOwned, Deserializer};
+use std::str::FromStr;
+
+/// Controlled vocabulary for a system's characteristic time scale (SL spec
+/// §1.3), mirrored by the `@values` of `attribute time_constant` in the
+/// TypeDB schema.
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Sequence)]
+pub enum TimeConstant {
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    Decade,
+    Century,
+    Epoch,
+}
+
+impl TimeConstant {
+    /// The whole vocabulary, shortest first.
+    pub fn all() -> impl Iterator<Item = Self> {
+        enum_iterator::all()
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Millisecond => "Millisecond",
+            Self::Second => "Second",
+            Self::Minute => "Minute",
+            Self::Hour => "Hour",
+            Self::Day => "Day",
+            Self::Week => "Week",
+            Self::Month => "Month",
+            Self::Year => "Year",
+            Self::Decade => "Decade",
+            Self::Century => "Century",
+            Self::Epoch => "Epoch",
+        }
+    }
+}
+
+impl fmt::Display for TimeConstant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TimeConstant {
+    type Err = DynamicsError;
+
+    /// Case-insensitive; a plural (`"Minutes"`) reads as its singular.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let singular = s
+            .strip_suffix('s')
+            .or_else(|| s.strip_suffix('S'))
+            .unwrap_or(s);
+        Self::all()
+            .find(|tc| {
+                tc.as_str().eq_ignore_ascii_case(s) || tc.as_str().eq_ignore_ascii_case(singular)
+            })
+            .ok_or_else(|| DynamicsError::UnknownTimeConstant(s.to_string()))
+    }
+}
+
+/// A system's time constant: how many of which [`TimeConstant`] it takes to
+/// respond. No magnitude means one unit.
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Timescale {
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    pub magnitude: Option<Decimal>,
+    pub unit: TimeConstant,
+}
+
+impl From<TimeConstant> for Timescale {
+    fn from(unit: TimeConstant) -> Self {
+        Self {
+            magnitude: None,
+            unit,
+        }
+    }
+}
+
+impl fmt::Display for Timescale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.magnitude {
+            Some(m) => write!(f, "{} {}", m.normalize(), self.unit),
+            None => write!(f, "{}", self.unit),
+        }
+    }
+}
+
+impl FromStr for Timescale {
+    type Err = DynamicsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some(unit), None, _) => Ok(unit.parse::<TimeConstant>()?.into()),
+            (Some(magnitude), Some(unit), None) => Ok(Self {
+                magnitude: Some(
+                    Decimal::from_str(magnitude)
+                        .map_err(|_| DynamicsError::BadMagnitude(magnitude.to_string()))?,
+                ),
+                unit: unit.parse()?,
+            }),
+            _ => Err(DynamicsError::UnknownTimeConstant(s.trim().to_string())),
+        }
+    }
+}
+
+impl Serialize for Timescale {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timescale {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        String::deserialize(d)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// What a system does with what it takes in: Mobus's `T`.
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Transformation {
+    /// Substances consumed. A blank `sub_type` stands for any of its type.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<Substance>,
+    /// Substances produced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<Substance>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl Transformation {
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty() && self.outputs.is_empty() && self.description.trim().is_empty()
+    }
+}
+
+impl fmt::Display for Transformation {
+    /// `"Electricity (Energy) → Heat (Energy): a kettle"`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |side: &[Substance]| {
+            side.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let typed = !self.inputs.is_empty() || !self.outputs.is_empty();
+        if typed {
+            write!(f, "{} → {}", list(&self.inputs), list(&self.outputs))?;
+        }
+        match (typed, self.description.trim()) {
+            (_, "") => Ok(()),
+            (true, description) => write!(f, ": {description}"),
+            (false, description) => f.write_str(description),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Transformation {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Legacy(String),
+            Typed {
+                #[serde(default)]
+                inputs: Vec<Substance>,
+                #[serde(default)]
+                outputs: Vec<Substance>,
+                #[serde(default)]
+                description: String,
+            },
+        }
+        Ok(match Repr::deserialize(d)? {
+            Repr::Legacy(description) => Self {
+                description,
+                ..Default::default()
+            },
+            Repr::Typed {
+                inputs,
+                outputs,
+                description,
+            } => Self {
+                inputs,
+                outputs,
+                description,
+            },
+        })
+    }
+}
+
+/// One entry of a system's [`History`].
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct HistoryEvent {
+    /// ISO 8601 date or date-time, as precise as is known (`"2009"`,
+    /// `"2009-01-03"`, `"2009-01-03T18:15:05Z"`). Blank when undated.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub timestamp: String,
+    pub event: String,
+}
+
+impl fmt::Display for HistoryEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.timestamp.trim() {
+            "" => f.write_str(&self.event),
+            at => write!(f, "{at}: {}", self.event),
+        }
+    }
+}
+
+/// What has happened to a system, oldest first: Mobus's `H`.
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct History(pub Vec<HistoryEvent>);
+
+impl History {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, HistoryEvent> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for History {
+    /// Events joined by `"; "`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, event) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{event}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for History {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Legacy(String),
+            Events(Vec<HistoryEvent>),
+        }
+        Ok(match Repr::deserialize(d)? {
+            Repr::Legacy(note) if note.trim().is_empty() => Self::default(),
+            Repr::Legacy(event) => Self(vec![HistoryEvent {
+                timestamp: String::new(),
+                event,
+            }]),
+            Repr::Events(events) => Self(events),
+        })
+    }
+}
+
+/// Whether `s` is an ISO 8601 calendar date, optionally with a time of day:
+/// `YYYY`, `YYYY-MM`, `YYYY-MM-DD`, then `Thh:mm`, `:ss`, `.fff`, and `Z` or
+/// `±hh:mm`. Ranges are checked field by field, not against the calendar.
+pub fn is_timestamp(s: &str) -> bool {
+    fn field(s: &str, len: usize, range: std::ops::RangeInclusive<u32>) -> bool {
+        s.len() == len
+            && s.bytes().all(|b| b.is_ascii_digit())
+            && s.parse().is_ok_and(|n| range.contains(&n))
+    }
+    fn time(s: &str) -> bool {
+        let (clock, zone) = match s.find(['Z', '+', '-']) {
+            Some(at) => s.split_at(at),
+            None => (s, ""),
+        };
+        let zone_ok = match zone {
+            "" | "Z" => true,
+            z => z[1..]
+                .split_once(':')
+                .is_some_and(|(h, m)| field(h, 2, 0..=23) && field(m, 2, 0..=59)),
+        };
+        let (clock, fraction) = clock.split_once('.').unwrap_or((clock, "0"));
+        let parts: Vec<&str> = clock.split(':').collect();
+        let clock_ok = match parts[..] {
+            [h, m] => field(h, 2, 0..=23) && field(m, 2, 0..=59),
+            [h, m, sec] => field(h, 2, 0..=23) && field(m, 2, 0..=59) && field(sec, 2, 0..=60),
+            _ => false,
+        };
+        zone_ok && clock_ok && !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    let (date, clock) = match s.split_once('T') {
+        Some((date, clock)) => (date, Some(clock)),
+        None => (s, None),
+    };
+    let parts: Vec<&str> = date.split('-').collect();
+    let date_ok = match parts[..] {
+        [y] => field(y, 4, 0..=9999),
+        [y, m] => field(y, 4, 0..=9999) && field(m, 2, 1..=12),
+        [y, m, d] => field(y, 4, 0..=9999) && field(m, 2, 1..=12) && field(d, 2, 1..=31),
+        _ => false,
+    };
+    // A time of day needs a full date to hang on.
+    date_ok && clock.is_none_or(|c| parts.len() == 3 && time(c))
+}
+
+/// `deserialize_with` for an optional dynamical slot: `null`, a missing key
+/// and a blank legacy string all read as `None`.
+pub(crate) fn blank_as_none<'de, D, T>(d: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    match Option::<serde_json::Value>::deserialize(d)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(v) => T::deserialize(v).map(Some).map_err(de::Error::custom),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicsError {
+    /// Not a [`TimeConstant`].
+    UnknownTimeConstant(String),
+    /// The number in front of a time constant is not a decimal.
+    BadMagnitude(String),
+}
+
+impl fmt::Display for DynamicsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTimeConstant(s) => {
+                let vocabulary: Vec<&str> = TimeConstant::all().map(TimeConstant::as_str).collect();
+                write!(
+                    f,
+                    "'{s}' is not a time constant; expected one of: {}",
+                    vocabulary.join(", ")
+                )
+            }
+            Self::BadMagnitude(s) => write!(f, "'{s}' is not a time constant magnitude"),
+        }
+    }
+}
+
+impl std::error::Error for DynamicsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timescales_round_trip_through_their_string_form() {
+        for (text, shown) in [
+            ("Second", "Second"),
+            ("second", "Second"),
+            ("15 Minute", "15 Minute"),
+            ("2.50 hours", "2.5 Hour"),
+        ] {
+            let ts: Timescale = text.parse().unwrap();
+            assert_eq!(ts.to_string(), shown);
+            assert_eq!(shown.parse::<Timescale>().unwrap(), ts);
+        }
+        assert_eq!(
+            "HorseYear".parse::<Timescale>(),
+            Err(DynamicsError::UnknownTimeConstant("HorseYear".into()))
+        );
+        assert_eq!(
+            "many Day".parse::<Timescale>(),
+            Err(DynamicsError::BadMagnitude("many".into()))
+        );
+        assert!("1 2 Day".parse::<Timescale>().is_err());
+    }
+
+    #[test]
+    fn legacy_strings_deserialize_into_typed_slots() {
+        let history: History = serde_json::from_str(r#""Founded in a garage""#).unwrap();
+        assert_eq!(history.0.len(), 1);
+        assert_eq!(history.0[0].event, "Founded in a garage");
+        assert!(serde_json::from_str::<History>(r#""  ""#)
+            .unwrap()
+            .is_empty());
+
+        let t: Transformation = serde_json::from_str(r#""Input -> Output""#).unwrap();
+        assert_eq!(t.description, "Input -> Output");
+        assert!(t.inputs.is_empty());
+
+        let t: Transformation = serde_json::from_str(
+            r#"{"inputs": [{"sub_type": "Electricity", "type": "Energy"}],
+                "outputs": [{"sub_type": "", "type": "Energy"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(t.to_string(), "Electricity (Energy) → Energy");
+        assert_eq!(
+            serde_json::to_value(&t).unwrap(),
+            serde_json::json!({
+                "inputs": [{"sub_type": "Electricity", "type": "Energy"}],
+                "outputs": [{"sub_type": "", "type": "Energy"}]
+            })
+        );
+    }
+
+    #[test]
+    fn timestamps_are_iso_8601_dates() {
+        for ok in [
+            "2009",
+            "2009-01",
+            "2009-01-03",
+            "2009-01-03T18:15",
+            "2009-01-03T18:15:05Z",
+            "2009-01-03T18:15:05.123+01:00",
+        ] {
+            assert!(is_timestamp(ok), "{ok}");
+        }
+        for bad in [
+            "",
+            "09",
+            "2009-13",
+            "2009-1-3",
+            "2009T18:15",
+            "2009-01-03T25:00",
+            "2009-01-03T18:15+1",
+            "January 2009",
+        ] {
+            assert!(!is_timestamp(bad), "{bad}");
+        }
+    }
+}
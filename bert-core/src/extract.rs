@@ -0,0 +1,472 @@
+//! Zooming in and out of the hierarchy: [`extract_subsystem`] promotes a
+//! subsystem to the System of Interest of a model of its own, and
+//! [`embed_subsystem`] puts such a model back under a host system.
+//!
+//! Extraction is Mobus's recursive decomposition run in reverse: what was the
+//! subsystem's context becomes its environment. Every interaction that crossed
+//! its boundary is kept, with the outside end replaced by a synthesized
+//! environment source or sink. Its `equivalence` names the host element it
+//! stands for — the interface the flow attached to, or else the system or
+//! external entity — and that name is what [`embed_subsystem`] resolves to
+//! reconnect the flow.
+
+use crate::compose::{self, interface_mut, Binding, ComposeError};
+use crate::renumber::{self, RenumberError};
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    /// The id names no system in the model.
+    UnknownSystem(Id),
+    /// An environment source or sink whose `equivalence` names no host element.
+    Unbound { external: Id, equivalence: String },
+    /// The model's ids or references are inconsistent.
+    Renumber(RenumberError),
+    /// The model could not be embedded in the host.
+    Compose(ComposeError),
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSystem(id) => write!(f, "'{id}' is not a system in this model"),
+            Self::Unbound {
+                external,
+                equivalence,
+            } => write!(
+                f,
+                "'{external}' has equivalence '{equivalence}', which names no host element"
+            ),
+            Self::Renumber(e) => write!(f, "{e}"),
+            Self::Compose(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+impl From<RenumberError> for ExtractError {
+    fn from(e: RenumberError) -> Self {
+        Self::Renumber(e)
+    }
+}
+
+impl From<ComposeError> for ExtractError {
+    fn from(e: ComposeError) -> Self {
+        Self::Compose(e)
+    }
+}
+
+/// A new model whose root `S0` is the system `id` of `model`.
+///
+/// Descendants are re-based under `S0` (numbered densely, in their original
+/// order) with levels recomputed. Each interaction that crossed the subsystem's boundary becomes
+/// an environment interaction — same name, substance, usability, amount, unit
+/// and parameters — from a synthesized source or to a synthesized sink.
+/// Externals standing for the same host element share an `is_same_as_id`.
+pub fn extract_subsystem(model: &WorldModel, id: &Id) -> Result<WorldModel, ExtractError> {
+    let target = model
+        .systems
+        .iter()
+        .find(|s| &s.info.id == id)
+        .ok_or_else(|| ExtractError::UnknownSystem(id.clone()))?;
+    let members = renumber::subtree_ids(model, target);
+    let env_id = model.environment.info.id.clone();
+
+    let mut systems: Vec<System> = model
+        .systems
+        .iter()
+        .filter(|s| members.contains(&s.info.id))
+        .cloned()
+        .collect();
+    let inside: HashSet<Id> = systems
+        .iter()
+        .flat_map(|s| {
+            std::iter::once(&s.info.id)
+                .chain(s.sources.iter().chain(&s.sinks).map(|e| &e.info.id))
+                .chain(s.boundary.interfaces.iter().map(|i| &i.info.id))
+        })
+        .cloned()
+        .collect();
+    for system in &mut systems {
+        if &system.info.id == id {
+            system.parent = env_id.clone();
+        }
+        if system
+            .boundary
+            .parent_interface
+            .as_ref()
+            .is_some_and(|i| !inside.contains(i))
+        {
+            system.boundary.parent_interface = None;
+        }
+        for iface in &mut system.boundary.interfaces {
+            iface.exports_to.retain(|i| inside.contains(i));
+            iface.receives_from.retain(|i| inside.contains(i));
+        }
+    }
+
+    let mut interactions: Vec<Interaction> = model
+        .interactions
+        .iter()
+        .filter(|ix| inside.contains(&ix.source) && inside.contains(&ix.sink))
+        .cloned()
+        .collect();
+    let used: HashSet<Id> = interactions.iter().map(|ix| ix.info.id.clone()).collect();
+    let mut env_flow_ids = (0..)
+        .map(|n| Id {
+            ty: IdType::Flow,
+            indices: vec![env_id.indices[0], n],
+        })
+        .filter(|id| !used.contains(id));
+
+    let mut sources = Vec::new();
+    let mut sinks = Vec::new();
+    for ix in &model.interactions {
+        let incoming = match (inside.contains(&ix.source), inside.contains(&ix.sink)) {
+            (false, true) => true,
+            (true, false) => false,
+            _ => continue,
+        };
+        let mut crossing = ix.clone();
+        // Temporary env-level id; `compact` numbers it below.
+        crossing.info.id = env_flow_ids.next().expect("unbounded");
+        let (outside, outside_iface, inside_iface) = if incoming {
+            (&ix.source, &ix.source_interface, &ix.sink_interface)
+        } else {
+            (&ix.sink, &ix.sink_interface, &ix.source_interface)
+        };
+        let list = if incoming { &mut sources } else { &mut sinks };
+        let external = stand_in(model, outside, outside_iface, incoming, list.len());
+        if let Some(iface) = inside_iface
+            .as_ref()
+            .and_then(|i| interface_mut(&mut systems, i))
+        {
+            if incoming {
+                iface.receives_from.push(external.info.id.clone());
+            } else {
+                iface.exports_to.push(external.info.id.clone());
+            }
+        }
+        if incoming {
+            crossing.source = external.info.id.clone();
+            crossing.source_interface = None;
+        } else {
+            crossing.sink = external.info.id.clone();
+            crossing.sink_interface = None;
+        }
+        list.push(external);
+        interactions.push(crossing);
+    }
+    link_same_as(&mut sources, &mut sinks);
+    layout_externals(&mut sources, -1.0, target.radius);
+    layout_externals(&mut sinks, 1.0, target.radius);
+
+    let (name, description) = describe(model, &target.parent).unwrap_or_default();
+    let extracted = WorldModel {
+        version: CURRENT_FILE_VERSION,
+        mode: model.mode,
+        environment: Environment {
+            info: Info {
+                id: env_id,
+                level: -1,
+                name: name.to_string(),
+                description: description.to_string(),
+            },
+            sources,
+            sinks,
+        },
+        systems,
+        interactions,
+        hidden_entities: model
+            .hidden_entities
+            .iter()
+            .filter(|i| inside.contains(i))
+            .cloned()
+            .collect(),
+        validation: model.validation.clone(),
+    };
+    Ok(canonicalize(&renumber::compact(&extracted)?.model))
+}
+
+/// Put the System of Interest of `soi` back into `host` as a subsystem of `parent`.
+///
+/// The inverse of [`extract_subsystem`]: the root takes the next free slot
+/// under `parent`, and every interaction with an environment source or sink is
+/// reconnected to the host element that external's `equivalence` names, through
+/// the same interfaces as before. Returns [`ExtractError::Unbound`] for an
+/// external whose equivalence does not resolve in `host`. An interface
+/// subsystem's link to its parent interface is not part of the extracted model
+/// and is not restored.
+pub fn embed_subsystem(
+    host: &WorldModel,
+    parent: &Id,
+    soi: &WorldModel,
+) -> Result<WorldModel, ExtractError> {
+    let env = &soi.environment;
+    let bindings = env
+        .sources
+        .iter()
+        .chain(&env.sinks)
+        .map(|e| {
+            e.equivalence
+                .parse()
+                .ok()
+                .filter(|to| compose::resolves(host, to))
+                .map(|to| Binding {
+                    external: e.info.id.clone(),
+                    to,
+                })
+                .ok_or_else(|| ExtractError::Unbound {
+                    external: e.info.id.clone(),
+                    equivalence: e.equivalence.clone(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(compose::attach(host, parent, soi, &bindings, false)?.model)
+}
+
+/// A synthesized environment source or sink standing for the host element
+/// `outside` (attached through `outside_iface`, if any).
+fn stand_in(
+    model: &WorldModel,
+    outside: &Id,
+    outside_iface: &Option<Id>,
+    is_source: bool,
+    n: usize,
+) -> ExternalEntity {
+    let (ty, id_ty) = if is_source {
+        (ExternalEntityType::Source, IdType::Source)
+    } else {
+        (ExternalEntityType::Sink, IdType::Sink)
+    };
+    let (name, description) = describe(model, outside).unwrap_or_default();
+    ExternalEntity {
+        info: Info {
+            id: Id {
+                ty: id_ty,
+                indices: vec![-1, n as i64],
+            },
+            level: -1,
+            name: name.to_string(),
+            description: description.to_string(),
+        },
+        ty,
+        transform: None,
+        equivalence: outside_iface.as_ref().unwrap_or(outside).to_string(),
+        model: String::new(),
+        is_same_as_id: None,
+    }
+}
+
+/// Give externals standing for the same host element a shared `is_same_as_id`.
+fn link_same_as(sources: &mut [ExternalEntity], sinks: &mut [ExternalEntity]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for e in sources.iter().chain(sinks.iter()) {
+        *counts
+            .entry(e.info.name.clone() + "\0" + &e.equivalence)
+            .or_default() += 1;
+    }
+    let mut groups: HashMap<String, usize> = HashMap::new();
+    for e in sources.iter_mut().chain(sinks.iter_mut()) {
+        let key = e.info.name.clone() + "\0" + &e.equivalence;
+        if counts[&key] > 1 {
+            let next = groups.len();
+            e.is_same_as_id = Some(*groups.entry(key).or_insert(next));
+        }
+    }
+}
+
+/// Stack externals in a column left (`side = -1`) or right (`side = 1`) of the root.
+pub(crate) fn layout_externals(externals: &mut [ExternalEntity], side: f32, radius: f32) {
+    let mid = (externals.len() as f32 - 1.0) / 2.0;
+    for (k, e) in externals.iter_mut().enumerate() {
+        e.transform = Some(Transform2d {
+            translation: Vec2::new(side * radius * 1.75, (k as f32 - mid) * radius * 0.6),
+            rotation: if side < 0.0 { PI } else { 0.0 },
+        });
+    }
+}
+
+/// Name and description of the system, external entity or interface `id`.
+fn describe<'m>(model: &'m WorldModel, id: &Id) -> Option<(&'m str, &'m str)> {
+    let env = &model.environment;
+    let info = std::iter::once(&env.info)
+        .chain(env.sources.iter().chain(&env.sinks).map(|e| &e.info))
+        .chain(model.systems.iter().flat_map(|s| {
+            std::iter::once(&s.info)
+                .chain(s.sources.iter().chain(&s.sinks).map(|e| &e.info))
+                .chain(s.boundary.interfaces.iter().map(|i| &i.info))
+        }))
+        .find(|info| &info.id == id)?;
+    Some((&info.name, &info.description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, examples, id};
+    use crate::validate::{validate, Severity};
+
+    fn errors(model: &WorldModel) -> Vec<String> {
+        validate(model)
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| format!("{}: {}", i.location, i.message))
+            .collect()
+    }
+
+    /// The model up to ids and layout: every system under its parent, every
+    /// interaction between its named endpoints.
+    fn signature(model: &WorldModel) -> Vec<String> {
+        let name = |id: &Id| describe(model, id).map_or("?", |(n, _)| n);
+        let port = |id: &Option<Id>| id.as_ref().map_or("", |i| name(i));
+        let mut sig: Vec<String> = model
+            .systems
+            .iter()
+            .map(|s| {
+                format!(
+                    "system {} < {} ({}, {} interfaces)",
+                    s.info.name,
+                    name(&s.parent),
+                    s.complexity,
+                    s.boundary.interfaces.len()
+                )
+            })
+            .chain(model.interactions.iter().map(|ix| {
+                format!(
+                    "flow {}: {}[{}] -> {}[{}] {:?} {} {} {}",
+                    ix.info.name,
+                    name(&ix.source),
+                    port(&ix.source_interface),
+                    name(&ix.sink),
+                    port(&ix.sink_interface),
+                    ix.substance.ty,
+                    ix.usability,
+                    ix.amount,
+                    ix.unit
+                )
+            }))
+            .collect();
+        sig.sort();
+        sig
+    }
+
+    #[test]
+    fn every_extracted_subsystem_validates() {
+        for (name, model) in examples() {
+            for system in &model.systems {
+                let soi = extract_subsystem(&model, &system.info.id).unwrap();
+                let root = &soi.systems[0];
+                assert_eq!(root.info.id, id("S0"), "{name} {}", system.info.id);
+                assert_eq!(root.info.level, 0);
+                assert_eq!(root.info.name, system.info.name);
+                assert_eq!(
+                    errors(&soi),
+                    Vec::<String>::new(),
+                    "{name}: extracting {}",
+                    system.info.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn crossing_interactions_become_environment_flows() {
+        let model = example("bitcoin");
+        let soi = extract_subsystem(&model, &id("C0.5")).unwrap();
+
+        // C0.5.x → C0.x, one level up.
+        assert_eq!(soi.systems.len(), 4);
+        assert!(soi.systems[1..].iter().all(|s| s.info.level == 1));
+
+        // F0.4: C0.5 → C0.2 is now S0 → a sink standing for C0.2.
+        let original = model
+            .interactions
+            .iter()
+            .find(|ix| ix.info.id == id("F0.4"))
+            .unwrap();
+        let crossing = soi
+            .interactions
+            .iter()
+            .find(|ix| ix.info.name == original.info.name)
+            .unwrap();
+        assert_eq!(crossing.source, id("S0"));
+        assert_eq!(crossing.info.level, -1);
+        assert_eq!(crossing.usability, original.usability);
+        assert_eq!(crossing.substance.ty, original.substance.ty);
+        assert_eq!(crossing.amount, original.amount);
+        assert_eq!(crossing.unit, original.unit);
+        let sink = soi
+            .environment
+            .sinks
+            .iter()
+            .find(|e| e.info.id == crossing.sink)
+            .unwrap();
+        assert_eq!(sink.equivalence, "C0.2");
+
+        // C0.1 both feeds and drains C0.5: its two stand-ins are the same entity.
+        let c01: Vec<_> = soi
+            .environment
+            .sources
+            .iter()
+            .chain(&soi.environment.sinks)
+            .filter(|e| e.equivalence == "C0.1")
+            .collect();
+        assert_eq!(c01.len(), 2);
+        assert!(c01[0].is_same_as_id.is_some());
+        assert_eq!(c01[0].is_same_as_id, c01[1].is_same_as_id);
+    }
+
+    #[test]
+    fn extract_then_embed_round_trips_every_subsystem() {
+        for (name, model) in examples() {
+            for system in model.systems.iter().filter(|s| s.info.level > 0) {
+                let soi = extract_subsystem(&model, &system.info.id).unwrap();
+
+                let gone = renumber::subtree_ids(&model, system);
+                let inside = |id: &Id| {
+                    gone.contains(id)
+                        || model.systems.iter().any(|s| {
+                            gone.contains(&s.info.id)
+                                && (s.sources.iter().chain(&s.sinks).any(|e| &e.info.id == id)
+                                    || s.boundary.interfaces.iter().any(|i| &i.info.id == id))
+                        })
+                };
+                let mut host = model.clone();
+                host.systems.retain(|s| !gone.contains(&s.info.id));
+                host.interactions
+                    .retain(|ix| !inside(&ix.source) && !inside(&ix.sink));
+
+                let back = embed_subsystem(&host, &system.parent, &soi).unwrap();
+                assert_eq!(
+                    signature(&back),
+                    signature(&model),
+                    "{name}: {}",
+                    system.info.id
+                );
+                assert_eq!(errors(&back), Vec::<String>::new(), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn embedding_rejects_unbound_externals_and_unknown_parents() {
+        let model = example("bitcoin");
+        let mut soi = extract_subsystem(&model, &id("C0.5")).unwrap();
+        assert_eq!(
+            embed_subsystem(&model, &id("C0.9"), &soi).err(),
+            Some(ExtractError::Compose(ComposeError::UnknownSystem(id(
+                "C0.9"
+            ))))
+        );
+        soi.environment.sources[0].equivalence = "C0.77".to_string();
+        assert!(matches!(
+            embed_subsystem(&model, &id("S0"), &soi),
+            Err(ExtractError::Unbound { .. })
+        ));
+    }
+}
@@ -0,0 +1,437 @@
+//! Feedback as a first-class object: the cycles of the kernel's dependency
+//! graph, each classified as reinforcing or balancing.
+//!
+//! [`Kernel::cycles`] enumerates every elementary cycle of `dep` — a chain of
+//! interactions that returns to where it started without revisiting a thing.
+//! Parallel interactions are distinct arrows, so an energy flow and a message
+//! flow between the same pair of systems close two different loops.
+//! [`WorldModel::feedback_loops`] names the cycles and reads each one's
+//! polarity off its flows:
+//!
+//! - a physical flow (energy, material) passes quantity along: a resource or
+//!   product pushes its sink the same way its source moves, a waste or
+//!   disruption pushes it the opposite way;
+//! - a message flow is a control signal: it reports its source's state so the
+//!   sink can counteract a deviation (Mobus's error feedback), which inverts the
+//!   sense — unless the message is itself a disruption, which inverts it back.
+//!
+//! A loop with an odd number of inverting links is balancing (it seeks a
+//! goal); an even number, including none, is reinforcing (it amplifies).
+//! Loops through the environment, which the editor draws between matched
+//! sources and sinks via `is_same_as_id`, are not kernel cycles and are not
+//! listed here.
+
+use crate::*;
+
+/// Whether a feedback loop amplifies or counteracts change.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    /// Even number of inverting links: change feeds more change.
+    Reinforcing,
+    /// Odd number of inverting links: change is counteracted.
+    Balancing,
+}
+
+impl fmt::Display for Polarity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reinforcing => write!(f, "Reinforcing"),
+            Self::Balancing => write!(f, "Balancing"),
+        }
+    }
+}
+
+/// One elementary cycle of a model's dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackLoop {
+    /// The things on the loop in flow order, starting from the earliest in
+    /// [`Kernel::things`]; `members[i]` is the source of `interactions[i]`.
+    pub members: Vec<Id>,
+    /// The interactions that close the loop.
+    pub interactions: Vec<Id>,
+    pub polarity: Polarity,
+}
+
+impl FeedbackLoop {
+    /// True if any link of the loop is a message flow.
+    pub fn is_controlled(&self, model: &WorldModel) -> bool {
+        model
+            .interactions
+            .iter()
+            .filter(|ix| self.interactions.contains(&ix.info.id))
+            .any(|ix| ix.substance.ty == SubstanceType::Message)
+    }
+}
+
+impl Kernel {
+    /// Every elementary cycle of `dep`, as indices into `dep` in flow order.
+    ///
+    /// Each cycle is reported once, starting at its earliest thing; self-loops
+    /// are cycles of length one. Arrows whose relata are not among `things`
+    /// are skipped. A dense graph can have exponentially many cycles: callers
+    /// that show them use [`Kernel::cycles_up_to`], and callers that only ask
+    /// whether there are any use [`Kernel::has_cycle`].
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        self.cycles_up_to(usize::MAX)
+    }
+
+    /// The first `limit` cycles of [`Kernel::cycles`], in the same order.
+    ///
+    /// Uses Johnson's blocking, so the time spent is linear in the size of the
+    /// graph per cycle found rather than in the number of paths explored.
+    pub fn cycles_up_to(&self, limit: usize) -> Vec<Vec<usize>> {
+        let out = self.arrows();
+        let n = out.len();
+        let mut back: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (s, arrows) in out.iter().enumerate() {
+            for &(t, _) in arrows {
+                back[t].push(s);
+            }
+        }
+
+        let mut cycles = Vec::new();
+        for start in 0..n {
+            if cycles.len() >= limit {
+                break;
+            }
+            // Only things after `start` that lead back to it can be on a cycle
+            // that `start` is the earliest member of.
+            let mut live = vec![false; n];
+            let mut stack = vec![start];
+            live[start] = true;
+            while let Some(node) = stack.pop() {
+                for &prev in &back[node] {
+                    if prev > start && !live[prev] {
+                        live[prev] = true;
+                        stack.push(prev);
+                    }
+                }
+            }
+            let mut walk = Walk {
+                out: &out,
+                live: &live,
+                start,
+                blocked: vec![false; n],
+                blocked_by: vec![Vec::new(); n],
+                path: Vec::new(),
+                limit,
+                cycles: &mut cycles,
+            };
+            walk.from(start);
+        }
+        cycles
+    }
+
+    /// True if `dep` has at least one cycle, in time linear in its size.
+    pub fn has_cycle(&self) -> bool {
+        // Peel off things with no remaining inflow; whatever is left lies on
+        // or downstream of a cycle.
+        let out = self.arrows();
+        let mut inflow = vec![0usize; out.len()];
+        for arrows in &out {
+            for &(t, _) in arrows {
+                inflow[t] += 1;
+            }
+        }
+        let mut ready: Vec<usize> = (0..out.len()).filter(|&i| inflow[i] == 0).collect();
+        let mut peeled = 0;
+        while let Some(node) = ready.pop() {
+            peeled += 1;
+            for &(t, _) in &out[node] {
+                inflow[t] -= 1;
+                if inflow[t] == 0 {
+                    ready.push(t);
+                }
+            }
+        }
+        peeled < out.len()
+    }
+
+    /// `dep` as adjacency lists over `things`: `(sink, arrow)` per source.
+    fn arrows(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut index: HashMap<&Id, usize> = HashMap::new();
+        for (i, thing) in self.things.iter().enumerate() {
+            index.entry(thing).or_insert(i);
+        }
+        let mut out: Vec<Vec<(usize, usize)>> = vec![Vec::new(); self.things.len()];
+        for (arrow, (source, sink)) in self.dep.iter().enumerate() {
+            if let (Some(&s), Some(&t)) = (index.get(source), index.get(sink)) {
+                out[s].push((t, arrow));
+            }
+        }
+        out
+    }
+}
+
+/// Depth-first search for the cycles through `start`, with Johnson's
+/// blocking: a thing that led nowhere stays blocked until one of the things
+/// it leads to joins a cycle.
+struct Walk<'a> {
+    out: &'a [Vec<(usize, usize)>],
+    live: &'a [bool],
+    start: usize,
+    blocked: Vec<bool>,
+    /// `blocked_by[w]`: blocked things to release when `w` is released.
+    blocked_by: Vec<Vec<usize>>,
+    path: Vec<usize>,
+    limit: usize,
+    cycles: &'a mut Vec<Vec<usize>>,
+}
+
+impl Walk<'_> {
+    /// Returns whether any cycle passes through `node` on the current path.
+    fn from(&mut self, node: usize) -> bool {
+        let out = self.out;
+        let mut found = false;
+        self.blocked[node] = true;
+        for &(next, arrow) in &out[node] {
+            if self.cycles.len() >= self.limit {
+                return true;
+            }
+            if next == self.start {
+                let mut cycle = self.path.clone();
+                cycle.push(arrow);
+                self.cycles.push(cycle);
+                found = true;
+            } else if next > self.start && self.live[next] && !self.blocked[next] {
+                self.path.push(arrow);
+                found |= self.from(next);
+                self.path.pop();
+            }
+        }
+        if found {
+            self.release(node);
+        } else {
+            for &(next, _) in &out[node] {
+                if next > self.start && self.live[next] && !self.blocked_by[next].contains(&node) {
+                    self.blocked_by[next].push(node);
+                }
+            }
+        }
+        found
+    }
+
+    fn release(&mut self, node: usize) {
+        let mut stack = vec![node];
+        while let Some(thing) = stack.pop() {
+            if self.blocked[thing] {
+                self.blocked[thing] = false;
+                stack.append(&mut self.blocked_by[thing]);
+            }
+        }
+    }
+}
+
+impl WorldModel {
+    /// The feedback loops of the model: every cycle of [`WorldModel::kernel`],
+    /// with its members, interactions and [`Polarity`].
+    pub fn feedback_loops(&self) -> Vec<FeedbackLoop> {
+        self.feedback_loops_up_to(usize::MAX)
+    }
+
+    /// The first `limit` of [`WorldModel::feedback_loops`].
+    pub fn feedback_loops_up_to(&self, limit: usize) -> Vec<FeedbackLoop> {
+        let kernel = self.kernel();
+        kernel
+            .cycles_up_to(limit)
+            .into_iter()
+            .map(|cycle| {
+                // `dep` is projected from `interactions` in order, one arrow each.
+                let links: Vec<&Interaction> = cycle
+                    .iter()
+                    .map(|&arrow| &self.interactions[arrow])
+                    .collect();
+                let inverting = links.iter().filter(|ix| inverts(ix)).count();
+                FeedbackLoop {
+                    members: cycle.iter().map(|&a| kernel.dep[a].0.clone()).collect(),
+                    interactions: links.iter().map(|ix| ix.info.id.clone()).collect(),
+                    polarity: if inverting % 2 == 1 {
+                        Polarity::Balancing
+                    } else {
+                        Polarity::Reinforcing
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether `ix` makes its sink move against its source.
+fn inverts(ix: &Interaction) -> bool {
+    let detrimental = matches!(
+        ix.usability,
+        InteractionUsability::Waste | InteractionUsability::Disruption
+    );
+    let signal = ix.substance.ty == SubstanceType::Message;
+    detrimental != signal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, examples, id};
+
+    fn ids(list: &[&str]) -> Vec<Id> {
+        list.iter().map(|s| id(s)).collect()
+    }
+
+    #[test]
+    fn cycles_are_elementary_and_reported_once() {
+        let (a, b, c) = (id("C0.0"), id("C0.1"), id("C0.2"));
+        let kernel = Kernel {
+            things: vec![a.clone(), b.clone(), c.clone()],
+            dep: vec![
+                (a.clone(), b.clone()), // 0
+                (b.clone(), c.clone()), // 1
+                (c.clone(), a.clone()), // 2
+                (b.clone(), a.clone()), // 3
+                (a.clone(), b.clone()), // 4: parallel to 0
+                (c.clone(), c.clone()), // 5: self-loop
+                (a, id("C0.9")),        // 6: dangling, skipped
+            ],
+        };
+        let mut cycles = kernel.cycles();
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![
+                vec![0, 1, 2],
+                vec![0, 3],
+                vec![4, 1, 2],
+                vec![4, 3],
+                vec![5]
+            ]
+        );
+    }
+
+    /// Every ordered pair of `n` things, each doubled by a parallel arrow.
+    fn dense(n: usize) -> Kernel {
+        let things: Vec<Id> = (0..n).map(|i| id(&format!("C0.{i}"))).collect();
+        let mut dep = Vec::new();
+        for a in &things {
+            for b in things.iter().filter(|b| *b != a) {
+                dep.push((a.clone(), b.clone()));
+                dep.push((a.clone(), b.clone()));
+            }
+        }
+        Kernel { things, dep }
+    }
+
+    #[test]
+    fn capped_cycles_are_a_prefix() {
+        let kernel = dense(4);
+        let all = kernel.cycles();
+        assert!(all.len() > 20);
+        for limit in [0, 1, 7, 20, all.len(), all.len() + 5] {
+            let capped = kernel.cycles_up_to(limit);
+            assert_eq!(capped, all[..limit.min(all.len())]);
+        }
+    }
+
+    #[test]
+    fn dense_kernels_stay_cheap() {
+        // Billions of elementary cycles; neither call may enumerate them.
+        let kernel = dense(14);
+        assert!(kernel.has_cycle());
+        assert_eq!(kernel.cycles_up_to(500).len(), 500);
+    }
+
+    #[test]
+    fn has_cycle_agrees_with_cycles() {
+        let (a, b, c) = (id("C0.0"), id("C0.1"), id("C0.2"));
+        let chain = Kernel {
+            things: vec![a.clone(), b.clone(), c.clone()],
+            dep: vec![
+                (a.clone(), b.clone()),
+                (b.clone(), c.clone()),
+                (a.clone(), c.clone()),
+                (c.clone(), id("C0.9")),
+            ],
+        };
+        assert!(!chain.has_cycle());
+        assert!(chain.cycles().is_empty());
+
+        let mut looped = chain.clone();
+        looped.dep.push((c.clone(), c));
+        assert!(looped.has_cycle());
+        let mut closed = chain;
+        closed.dep.push((id("C0.2"), a));
+        assert!(closed.has_cycle());
+    }
+
+    #[test]
+    fn bitcoin_loops_follow_their_flows() {
+        let model = example("bitcoin");
+        let loops = model.feedback_loops();
+        assert_eq!(loops.len(), 2, "{loops:#?}");
+
+        let by_id: HashMap<&Id, &Interaction> = model
+            .interactions
+            .iter()
+            .map(|ix| (&ix.info.id, ix))
+            .collect();
+        for l in &loops {
+            assert_eq!(l.members.len(), l.interactions.len());
+            for (i, ix) in l.interactions.iter().enumerate() {
+                let ix = by_id[ix];
+                assert_eq!(ix.source, l.members[i]);
+                assert_eq!(ix.sink, l.members[(i + 1) % l.members.len()]);
+            }
+            assert!(l.is_controlled(&model));
+        }
+
+        // Mining and consensus trade blocks for rules: two signals, reinforcing.
+        assert_eq!(loops[0].members, ids(&["C0.1", "C0.5"]));
+        assert_eq!(loops[0].interactions, ids(&["F0.2", "F0.3"]));
+        assert_eq!(loops[0].polarity, Polarity::Reinforcing);
+        // Mempool → mining → consensus → mempool: three signals, balancing.
+        assert_eq!(loops[1].members, ids(&["C0.1", "C0.5", "C0.2"]));
+        assert_eq!(loops[1].polarity, Polarity::Balancing);
+    }
+
+    #[test]
+    fn polarity_counts_inverting_links() {
+        let mut model = example("bitcoin");
+        let set = |model: &mut WorldModel, flow: &str, ty, usability| {
+            let ix = model
+                .interactions
+                .iter_mut()
+                .find(|ix| ix.info.id == id(flow))
+                .unwrap();
+            ix.substance.ty = ty;
+            ix.usability = usability;
+        };
+        let polarity = |model: &WorldModel| {
+            model
+                .feedback_loops()
+                .into_iter()
+                .find(|l| l.interactions == ids(&["F0.2", "F0.3"]))
+                .unwrap()
+                .polarity
+        };
+        use InteractionUsability::*;
+        use SubstanceType::*;
+
+        set(&mut model, "F0.2", Material, Product);
+        set(&mut model, "F0.3", Energy, Resource);
+        assert_eq!(polarity(&model), Polarity::Reinforcing);
+        set(&mut model, "F0.3", Energy, Waste);
+        assert_eq!(polarity(&model), Polarity::Balancing);
+        set(&mut model, "F0.3", Message, Resource);
+        assert_eq!(polarity(&model), Polarity::Balancing);
+        set(&mut model, "F0.3", Message, Disruption);
+        assert_eq!(polarity(&model), Polarity::Reinforcing);
+    }
+
+    #[test]
+    fn example_models_enter_cybernetic_mode_only_with_loops() {
+        for (name, model) in examples() {
+            let result = crate::validate::validate_mode(&model, Mode::Cybernetic);
+            assert_eq!(
+                result.has_errors(),
+                model.feedback_loops().is_empty(),
+                "{name}"
+            );
+        }
+    }
+}
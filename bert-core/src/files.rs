@@ -0,0 +1,105 @@
+//! Finding model files on disk.
+//!
+//! Every tool that takes a directory of models — `bert validate`,
+//! `bert-typedb batch` and `dump` — walks it with [`model_files`], so they
+//! all pick up the same files.
+
+use crate::rules::PROJECT_CONFIG_FILE;
+use std::path::{Path, PathBuf};
+
+/// Every `*.json` below `dir` except [`PROJECT_CONFIG_FILE`]s, in path
+/// order.
+///
+/// Hidden directories are skipped, and so are symlinked ones, which could
+/// lead back up the tree. Symlinked files are kept.
+pub fn model_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk(dir, &mut files)?;
+    Ok(files)
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for entry in entries {
+        let name = entry.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if std::fs::symlink_metadata(&entry)?.is_dir() {
+            if !name.starts_with('.') {
+                walk(&entry, files)?;
+            }
+        } else if name.ends_with(".json") && name != PROJECT_CONFIG_FILE && entry.is_file() {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh scratch directory for one test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bert-core-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn relative(dir: &Path) -> Vec<PathBuf> {
+        model_files(dir)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.strip_prefix(dir).unwrap().to_path_buf())
+            .collect()
+    }
+
+    #[test]
+    fn model_files_walk_in_path_order() {
+        let dir = scratch("models");
+        for sub in ["b", "a/deep", ".git"] {
+            std::fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        for file in [
+            "z.json",
+            "notes.txt",
+            PROJECT_CONFIG_FILE,
+            "b/m.json",
+            "a/deep/m.json",
+            "a/bert-rules.json",
+            ".git/hidden.json",
+        ] {
+            std::fs::write(dir.join(file), "{}").unwrap();
+        }
+
+        assert_eq!(
+            relative(&dir),
+            ["a/deep/m.json", "b/m.json", "z.json"].map(PathBuf::from)
+        );
+        assert!(model_files(&dir.join("missing")).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_not_followed() {
+        use std::os::unix::fs::symlink;
+
+        let dir = scratch("symlinks");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::write(dir.join("a/m.json"), "{}").unwrap();
+        symlink(&dir, dir.join("a/loop")).unwrap();
+        symlink(dir.join("a"), dir.join("b")).unwrap();
+        symlink(dir.join("a/m.json"), dir.join("linked.json")).unwrap();
+
+        assert_eq!(
+            relative(&dir),
+            ["a/m.json", "linked.json"].map(PathBuf::from)
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,377 @@
+//! Structured repairs for validation issues.
+//!
+//! A check whose suggestion is mechanical attaches a [`Fix`] to its
+//! [`ValidationIssue`]: the [`Edit`]s that make the rule hold, addressed by
+//! position the same way the issue's location is. Positions are only
+//! meaningful for the model the issue was found in, so apply a fix to that
+//! model, then re-validate before applying the next one — [`fix_all`] does
+//! exactly that.
+//!
+//! A fix is *safe* when it restores what the model already says elsewhere (the
+//! level an id implies, the type a `sinks` list implies) or drops a reference
+//! to nothing. Fixes that delete an element or re-address one are offered too,
+//! but only applied on request.
+
+use crate::renumber::element_ids;
+use crate::validate::{validate, ValidationIssue, ValidationResult};
+use crate::*;
+
+/// A repair for one validation issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What applying the fix does, e.g. "Set level to 1".
+    pub description: String,
+    /// Whether [`fix_all`] may apply this fix without asking.
+    pub safe: bool,
+    pub edits: Vec<Edit>,
+}
+
+/// One change to a [`WorldModel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Set `systems[system].info.level`.
+    SetLevel { system: usize, level: i32 },
+    /// Set an external entity's type to the one its list implies.
+    SetExternalType(ExternalRef),
+    /// Remove an external entity, and any cached or hidden-list reference to it.
+    RemoveExternal(ExternalRef),
+    /// Clear one interface reference of `interactions[interaction]`.
+    ClearInterfaceRef { interaction: usize, end: End },
+    /// Remove `interactions[interaction]`.
+    RemoveInteraction(usize),
+    /// Clear `paired_with` on `systems[system].boundary.interfaces[interface]`.
+    ClearPairing { system: usize, interface: usize },
+    /// Give an element a new id. References to the old id are left alone: they
+    /// keep naming the element that had it first.
+    Reassign { element: ElementRef, id: Id },
+}
+
+/// Position of a source or sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalRef {
+    /// Owning system, or `None` for the environment.
+    pub system: Option<usize>,
+    /// Whether the entity is in the `sinks` list rather than `sources`.
+    pub sink: bool,
+    pub index: usize,
+}
+
+/// Which end of an interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum End {
+    Source,
+    Sink,
+}
+
+/// Position of any element with an id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementRef {
+    System(usize),
+    External(ExternalRef),
+    Interface { system: usize, interface: usize },
+    Interaction(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixError {
+    /// An edit addresses an element the model doesn't have, so the fix was
+    /// computed for a different version of the model.
+    Stale(Edit),
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stale(edit) => write!(
+                f,
+                "fix no longer applies, the model changed since it was computed: {edit:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FixError {}
+
+impl Fix {
+    /// A fix [`fix_all`] may apply without asking.
+    pub fn safe(description: impl Into<String>, edits: Vec<Edit>) -> Self {
+        Self {
+            description: description.into(),
+            safe: true,
+            edits,
+        }
+    }
+
+    /// A fix that deletes or re-addresses an element, applied only on request.
+    pub fn risky(description: impl Into<String>, edits: Vec<Edit>) -> Self {
+        Self {
+            description: description.into(),
+            safe: false,
+            edits,
+        }
+    }
+
+    /// Apply every edit, or none of them if any is stale.
+    pub fn apply(&self, model: &mut WorldModel) -> Result<(), FixError> {
+        let mut fixed = model.clone();
+        for edit in &self.edits {
+            edit.apply(&mut fixed)
+                .ok_or_else(|| FixError::Stale(edit.clone()))?;
+        }
+        *model = fixed;
+        Ok(())
+    }
+}
+
+impl Edit {
+    fn apply(&self, model: &mut WorldModel) -> Option<()> {
+        match self {
+            Edit::SetLevel { system, level } => {
+                model.systems.get_mut(*system)?.info.level = *level;
+            }
+            Edit::SetExternalType(entity) => {
+                entity.get_mut(model)?.ty = if entity.sink {
+                    ExternalEntityType::Sink
+                } else {
+                    ExternalEntityType::Source
+                };
+            }
+            Edit::RemoveExternal(entity) => {
+                let list = entity.list_mut(model)?;
+                if entity.index >= list.len() {
+                    return None;
+                }
+                let id = list.remove(entity.index).info.id;
+                for iface in model
+                    .systems
+                    .iter_mut()
+                    .flat_map(|s| &mut s.boundary.interfaces)
+                {
+                    iface.exports_to.retain(|other| other != &id);
+                    iface.receives_from.retain(|other| other != &id);
+                }
+                model.hidden_entities.retain(|other| other != &id);
+            }
+            Edit::ClearInterfaceRef { interaction, end } => {
+                let ix = model.interactions.get_mut(*interaction)?;
+                match end {
+                    End::Source => ix.source_interface = None,
+                    End::Sink => ix.sink_interface = None,
+                }
+            }
+            Edit::RemoveInteraction(interaction) => {
+                if *interaction >= model.interactions.len() {
+                    return None;
+                }
+                let id = model.interactions.remove(*interaction).info.id;
+                model.hidden_entities.retain(|other| other != &id);
+            }
+            Edit::ClearPairing { system, interface } => {
+                model
+                    .systems
+                    .get_mut(*system)?
+                    .boundary
+                    .interfaces
+                    .get_mut(*interface)?
+                    .paired_with = None;
+            }
+            Edit::Reassign { element, id } => {
+                let info = match element {
+                    ElementRef::System(i) => &mut model.systems.get_mut(*i)?.info,
+                    ElementRef::External(entity) => &mut entity.get_mut(model)?.info,
+                    ElementRef::Interface { system, interface } => {
+                        &mut model
+                            .systems
+                            .get_mut(*system)?
+                            .boundary
+                            .interfaces
+                            .get_mut(*interface)?
+                            .info
+                    }
+                    ElementRef::Interaction(i) => &mut model.interactions.get_mut(*i)?.info,
+                };
+                info.id = id.clone();
+            }
+        }
+        Some(())
+    }
+}
+
+impl ExternalRef {
+    fn list_mut<'m>(&self, model: &'m mut WorldModel) -> Option<&'m mut Vec<ExternalEntity>> {
+        let (sources, sinks) = match self.system {
+            None => (&mut model.environment.sources, &mut model.environment.sinks),
+            Some(i) => {
+                let system = model.systems.get_mut(i)?;
+                (&mut system.sources, &mut system.sinks)
+            }
+        };
+        Some(if self.sink { sinks } else { sources })
+    }
+
+    fn get_mut<'m>(&self, model: &'m mut WorldModel) -> Option<&'m mut ExternalEntity> {
+        self.list_mut(model)?.get_mut(self.index)
+    }
+}
+
+/// An id no element of `model` has, next to `like`: same type and parent
+/// indices, with a last index past every sibling's.
+pub(crate) fn fresh_id(model: &WorldModel, like: &Id) -> Id {
+    let prefix = &like.indices[..like.indices.len().saturating_sub(1)];
+    let next = element_ids(model)
+        .into_iter()
+        .map(|(id, _)| &id.indices)
+        .filter(|i| i.len() == like.indices.len() && i.starts_with(prefix))
+        .filter_map(|i| i.last())
+        .max()
+        .map_or(0, |last| last + 1);
+    let mut indices = prefix.to_vec();
+    indices.push(next);
+    Id {
+        ty: like.ty,
+        indices,
+    }
+}
+
+/// The outcome of [`fix_all`].
+pub struct FixReport {
+    /// Fixes applied, in order.
+    pub applied: Vec<Fix>,
+    /// Validation of the fixed model.
+    pub result: ValidationResult,
+}
+
+/// Apply every safe fix [`validate`] offers, re-validating after each one.
+///
+/// Unsafe fixes are left on the remaining issues in [`FixReport::result`].
+pub fn fix_all(model: &mut WorldModel) -> FixReport {
+    let mut applied = Vec::new();
+    let mut result = validate(model);
+    // Every safe fix clears at least its own issue, so this bounds the loop
+    // even if a fix were to introduce a new issue.
+    let mut budget = result.issues.len();
+    while budget > 0 {
+        let Some(fix) = next_safe_fix(&result.issues) else {
+            break;
+        };
+        if fix.apply(model).is_err() {
+            break;
+        }
+        applied.push(fix);
+        result = validate(model);
+        budget -= 1;
+    }
+    FixReport { applied, result }
+}
+
+fn next_safe_fix(issues: &[ValidationIssue]) -> Option<Fix> {
+    issues
+        .iter()
+        .filter_map(|issue| issue.fix.as_ref())
+        .find(|fix| fix.safe)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+    use crate::test_support::example;
+    use crate::validate::Severity;
+
+    fn codes(result: &ValidationResult) -> Vec<&'static str> {
+        result.issues.iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn fix_all_repairs_safe_issues_and_leaves_the_rest() {
+        let clean = example("bitcoin");
+        let mut model = clean.clone();
+        model.systems[1].info.level = 7;
+        model.environment.sources[0].ty = ExternalEntityType::Sink;
+        model.interactions[0].source_interface = Some(Id {
+            ty: IdType::Interface,
+            indices: vec![0, 99],
+        });
+        model.interactions[1].info.id = model.interactions[2].info.id.clone();
+
+        let report = fix_all(&mut model);
+        assert_eq!(report.applied.len(), 3, "{:#?}", report.applied);
+        assert!(report.applied.iter().all(|f| f.safe));
+        assert_eq!(model.systems[1].info.level, clean.systems[1].info.level);
+        assert!(matches!(
+            model.environment.sources[0].ty,
+            ExternalEntityType::Source
+        ));
+        assert!(model.interactions[0].source_interface.is_none());
+
+        let remaining = report
+            .result
+            .issues
+            .iter()
+            .find(|i| i.code == rules::DUPLICATE_ID.code)
+            .expect("duplicate id is not fixed without asking");
+        let reassign = remaining.fix.clone().expect("duplicate id has a fix");
+        assert!(!reassign.safe);
+        reassign.apply(&mut model).unwrap();
+        assert_ne!(model.interactions[1].info.id, model.interactions[2].info.id);
+        assert!(!codes(&validate(&model)).contains(&rules::DUPLICATE_ID.code));
+    }
+
+    #[test]
+    fn orphans_and_dangling_endpoints_are_removed_only_on_request() {
+        let mut model = example("bitcoin");
+        let orphan = model.environment.sources[0].clone();
+        model.environment.sources.push(orphan);
+        let n = model.environment.sources.len();
+        model.environment.sources[n - 1].info.id =
+            fresh_id(&model, &model.environment.sources[0].info.id);
+        model.interactions[0].sink = Id {
+            ty: IdType::System,
+            indices: vec![0, 99],
+        };
+
+        let report = fix_all(&mut model);
+        assert!(report.applied.is_empty());
+        let mut errors: Vec<_> = report
+            .result
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .collect();
+        assert_eq!(
+            errors.iter().map(|i| i.code).collect::<Vec<_>>(),
+            vec![
+                rules::ORPHAN_SOURCE.code,
+                rules::ORPHAN_SINK.code,
+                rules::DANGLING_ENDPOINT.code
+            ],
+            "the interaction's old sink is orphaned too"
+        );
+
+        let (sinks, interactions) = (model.environment.sinks.len(), model.interactions.len());
+        for issue in errors.drain(..).rev() {
+            issue.fix.unwrap().apply(&mut model).unwrap();
+        }
+        assert_eq!(model.environment.sources.len(), n - 1);
+        assert_eq!(model.environment.sinks.len(), sinks - 1);
+        assert_eq!(model.interactions.len(), interactions - 1);
+    }
+
+    #[test]
+    fn stale_fixes_are_rejected_whole() {
+        let mut model = example("bitcoin");
+        let fix = Fix::safe(
+            "",
+            vec![
+                Edit::SetLevel {
+                    system: 0,
+                    level: 5,
+                },
+                Edit::RemoveInteraction(10_000),
+            ],
+        );
+        assert!(matches!(fix.apply(&mut model), Err(FixError::Stale(_))));
+        assert_ne!(model.systems[0].info.level, 5);
+    }
+}
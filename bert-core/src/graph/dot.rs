@@ -0,0 +1,109 @@
+//! Graphviz DOT.
+//!
+//! An opened system is a `cluster_` subgraph holding a node for the system
+//! itself and its parts; edges to that node are clipped at the cluster border
+//! (`compound=true` with `lhead`/`ltail`).
+
+use super::{color, hex, Element, Graph, Node};
+use crate::*;
+
+pub(super) fn write(graph: &Graph) -> String {
+    let mut out = String::new();
+    let title = graph
+        .model
+        .systems
+        .first()
+        .map_or(&graph.model.environment.info.name, |s| &s.info.name);
+    out.push_str(&format!("digraph {} {{\n", quote(title)));
+    out.push_str("    graph [rankdir=LR, compound=true, fontname=\"Helvetica\"];\n");
+    out.push_str("    node [shape=box, style=rounded, fontname=\"Helvetica\"];\n");
+    out.push_str("    edge [fontname=\"Helvetica\", fontsize=10];\n");
+    for node in graph.children(None) {
+        write_node(graph, node, 1, &mut out);
+    }
+    if !graph.edges.is_empty() {
+        out.push('\n');
+    }
+    for edge in &graph.edges {
+        let interaction = edge.interaction;
+        let mut attributes = vec![
+            format!("label={}", quote(&interaction.info.name)),
+            format!("color={}", quote(&hex(color(interaction.substance.ty)))),
+        ];
+        let style = match interaction.usability {
+            InteractionUsability::Resource => None,
+            InteractionUsability::Product => Some("bold"),
+            InteractionUsability::Waste => Some("dashed"),
+            InteractionUsability::Disruption => Some("dotted"),
+        };
+        attributes.extend(style.map(|style| format!("style={style}")));
+        if interaction.ty == InteractionType::Force {
+            attributes.push("arrowhead=empty".to_string());
+        }
+        if graph
+            .nodes
+            .iter()
+            .any(|n| n.id == edge.source && graph.is_cluster(n))
+        {
+            attributes.push(format!("ltail={}", quote(&cluster(edge.source))));
+        }
+        if graph
+            .nodes
+            .iter()
+            .any(|n| n.id == edge.target && graph.is_cluster(n))
+        {
+            attributes.push(format!("lhead={}", quote(&cluster(edge.target))));
+        }
+        out.push_str(&format!(
+            "    {} -> {} [{}];\n",
+            quote(&edge.source.to_string()),
+            quote(&edge.target.to_string()),
+            attributes.join(", ")
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn write_node(graph: &Graph, node: &Node, depth: usize, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    let id = quote(&node.id.to_string());
+    let label = quote(&node.name());
+    let shape = match node.element {
+        Element::System(_) if node.folded => "shape=box3d, style=\"\"",
+        Element::System(_) => "shape=box",
+        Element::External(external) => match external.ty {
+            ExternalEntityType::Source => "shape=invhouse, style=\"\"",
+            ExternalEntityType::Sink => "shape=house, style=\"\"",
+        },
+    };
+    if !graph.is_cluster(node) {
+        out.push_str(&format!("{indent}{id} [label={label}, {shape}];\n"));
+        return;
+    }
+    out.push_str(&format!(
+        "{indent}subgraph {} {{\n",
+        quote(&cluster(node.id))
+    ));
+    out.push_str(&format!("{indent}    label={label};\n"));
+    out.push_str(&format!("{indent}    style=rounded;\n"));
+    out.push_str(&format!("{indent}    {id} [shape=point, style=invis];\n"));
+    for child in graph.children(Some(node.id)) {
+        write_node(graph, child, depth + 1, out);
+    }
+    out.push_str(&format!("{indent}}}\n"));
+}
+
+fn cluster(id: &Id) -> String {
+    format!("cluster_{id}")
+}
+
+/// `s` as a DOT string.
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "");
+    format!("\"{escaped}\"")
+}
@@ -0,0 +1,107 @@
+//! GEXF 1.3, as read by Gephi.
+//!
+//! Nodes are listed flat with `pid` naming the system they are drawn in, so
+//! Gephi can group them; edges carry the substance color and the amount as
+//! weight.
+
+use super::{color, edge_attributes, node_attributes, xml, AttrType, Graph, EDGE_KEYS, NODE_KEYS};
+
+pub(super) fn write(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(concat!(
+        "<gexf xmlns=\"http://gexf.net/1.3\" ",
+        "xmlns:viz=\"http://gexf.net/1.3/viz\" version=\"1.3\">\n"
+    ));
+    out.push_str("  <meta>\n");
+    out.push_str("    <creator>BERT</creator>\n");
+    let title = graph
+        .model
+        .systems
+        .first()
+        .map_or(&graph.model.environment.info.name, |s| &s.info.name);
+    if !title.trim().is_empty() {
+        out.push_str(&format!("    <description>{}</description>\n", xml(title)));
+    }
+    out.push_str("  </meta>\n");
+    out.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+    for (class, keys) in [("node", &NODE_KEYS[..]), ("edge", &EDGE_KEYS[..])] {
+        out.push_str(&format!("    <attributes class=\"{class}\">\n"));
+        for (i, (name, ty)) in keys.iter().enumerate() {
+            out.push_str(&format!(
+                "      <attribute id=\"{i}\" title=\"{name}\" type=\"{}\"/>\n",
+                attr_type(*ty)
+            ));
+        }
+        out.push_str("    </attributes>\n");
+    }
+
+    out.push_str("    <nodes>\n");
+    for node in &graph.nodes {
+        let pid = node
+            .parent
+            .map(|parent| format!(" pid=\"{}\"", xml(&parent.to_string())))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "      <node id=\"{}\" label=\"{}\"{pid}>\n",
+            xml(&node.id.to_string()),
+            xml(&node.name())
+        ));
+        write_values(&NODE_KEYS, node_attributes(node), &mut out);
+        out.push_str("      </node>\n");
+    }
+    out.push_str("    </nodes>\n");
+
+    out.push_str("    <edges>\n");
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let interaction = edge.interaction;
+        let weight = if interaction.amount > rust_decimal::Decimal::ZERO {
+            format!(" weight=\"{}\"", interaction.amount)
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "      <edge id=\"{i}\" source=\"{}\" target=\"{}\" label=\"{}\"{weight}>\n",
+            xml(&edge.source.to_string()),
+            xml(&edge.target.to_string()),
+            xml(&interaction.info.name)
+        ));
+        write_values(&EDGE_KEYS, edge_attributes(edge), &mut out);
+        let (r, g, b) = color(interaction.substance.ty);
+        out.push_str(&format!(
+            "        <viz:color r=\"{r}\" g=\"{g}\" b=\"{b}\"/>\n"
+        ));
+        out.push_str("      </edge>\n");
+    }
+    out.push_str("    </edges>\n");
+    out.push_str("  </graph>\n");
+    out.push_str("</gexf>\n");
+    out
+}
+
+/// `<attvalues>` for `values`, referring to `keys` by position.
+fn write_values(keys: &[(&str, AttrType)], values: Vec<(&str, String)>, out: &mut String) {
+    if values.is_empty() {
+        return;
+    }
+    out.push_str("        <attvalues>\n");
+    for (key, value) in values {
+        let Some(i) = keys.iter().position(|(k, _)| *k == key) else {
+            continue;
+        };
+        out.push_str(&format!(
+            "          <attvalue for=\"{i}\" value=\"{}\"/>\n",
+            xml(&value)
+        ));
+    }
+    out.push_str("        </attvalues>\n");
+}
+
+fn attr_type(ty: AttrType) -> &'static str {
+    match ty {
+        AttrType::String => "string",
+        AttrType::Int => "integer",
+        AttrType::Double => "double",
+        AttrType::Boolean => "boolean",
+    }
+}
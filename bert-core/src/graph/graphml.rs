@@ -0,0 +1,77 @@
+//! GraphML, as read by yEd, Cytoscape and NetworkX.
+//!
+//! An opened system holds its parts in a nested `<graph>`; edges all sit in
+//! the top-level graph, which GraphML allows across nesting levels.
+
+use super::{edge_attributes, node_attributes, xml, AttrType, Graph, Node, EDGE_KEYS, NODE_KEYS};
+
+pub(super) fn write(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(concat!(
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" ",
+        "xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ",
+        "xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns ",
+        "http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n"
+    ));
+    for (domain, prefix, keys) in [("node", "n", &NODE_KEYS[..]), ("edge", "e", &EDGE_KEYS[..])] {
+        for (name, ty) in keys {
+            out.push_str(&format!(
+                "  <key id=\"{prefix}_{name}\" for=\"{domain}\" attr.name=\"{name}\" attr.type=\"{}\"/>\n",
+                attr_type(*ty)
+            ));
+        }
+    }
+    out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+    for node in graph.children(None) {
+        write_node(graph, node, 2, &mut out);
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{i}\" source=\"{}\" target=\"{}\">\n",
+            xml(&edge.source.to_string()),
+            xml(&edge.target.to_string())
+        ));
+        for (key, value) in edge_attributes(edge) {
+            out.push_str(&format!(
+                "      <data key=\"e_{key}\">{}</data>\n",
+                xml(&value)
+            ));
+        }
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n");
+    out.push_str("</graphml>\n");
+    out
+}
+
+fn write_node(graph: &Graph, node: &Node, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let id = xml(&node.id.to_string());
+    out.push_str(&format!("{indent}<node id=\"{id}\">\n"));
+    for (key, value) in node_attributes(node) {
+        out.push_str(&format!(
+            "{indent}  <data key=\"n_{key}\">{}</data>\n",
+            xml(&value)
+        ));
+    }
+    if graph.is_cluster(node) {
+        out.push_str(&format!(
+            "{indent}  <graph id=\"{id}:\" edgedefault=\"directed\">\n"
+        ));
+        for child in graph.children(Some(node.id)) {
+            write_node(graph, child, depth + 2, out);
+        }
+        out.push_str(&format!("{indent}  </graph>\n"));
+    }
+    out.push_str(&format!("{indent}</node>\n"));
+}
+
+fn attr_type(ty: AttrType) -> &'static str {
+    match ty {
+        AttrType::String => "string",
+        AttrType::Int => "int",
+        AttrType::Double => "double",
+        AttrType::Boolean => "boolean",
+    }
+}
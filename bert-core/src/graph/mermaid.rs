@@ -0,0 +1,80 @@
+//! Mermaid flowcharts.
+//!
+//! An opened system is a subgraph, which edges can end on directly. Edge
+//! colors and line styles go in one `linkStyle` line per edge, by position.
+//! Node ids are BERT ids with every character other than an ASCII letter or
+//! digit replaced by `_` (`C0.1` is `C0_1`).
+
+use super::{color, hex, Element, Graph, Node};
+use crate::*;
+
+pub(super) fn write(graph: &Graph) -> String {
+    let mut out = String::from("flowchart LR\n");
+    for node in graph.children(None) {
+        write_node(graph, node, 1, &mut out);
+    }
+    for edge in &graph.edges {
+        let interaction = edge.interaction;
+        let arrow = match interaction.ty {
+            InteractionType::Force => "--o",
+            InteractionType::Flow => "-->",
+        };
+        let label = match interaction.info.name.trim() {
+            "" => String::new(),
+            name => format!("|{}|", quote(name)),
+        };
+        out.push_str(&format!(
+            "    {} {arrow}{label} {}\n",
+            node_id(edge.source),
+            node_id(edge.target)
+        ));
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let interaction = edge.interaction;
+        let stroke = hex(color(interaction.substance.ty));
+        let line = match interaction.usability {
+            InteractionUsability::Resource => "stroke-width:2px",
+            InteractionUsability::Product => "stroke-width:4px",
+            InteractionUsability::Waste => "stroke-width:2px,stroke-dasharray:6 4",
+            InteractionUsability::Disruption => "stroke-width:2px,stroke-dasharray:2 3",
+        };
+        out.push_str(&format!("    linkStyle {i} stroke:{stroke},{line}\n"));
+    }
+    out
+}
+
+fn write_node(graph: &Graph, node: &Node, depth: usize, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    let id = node_id(node.id);
+    let label = quote(&node.name());
+    if graph.is_cluster(node) {
+        out.push_str(&format!("{indent}subgraph {id}[{label}]\n"));
+        for child in graph.children(Some(node.id)) {
+            write_node(graph, child, depth + 1, out);
+        }
+        out.push_str(&format!("{indent}end\n"));
+        return;
+    }
+    let shape = match node.element {
+        Element::System(_) => format!("[{label}]"),
+        Element::External(external) => match external.ty {
+            ExternalEntityType::Source => format!("[/{label}/]"),
+            ExternalEntityType::Sink => format!("[\\{label}\\]"),
+        },
+    };
+    out.push_str(&format!("{indent}{id}{shape}\n"));
+}
+
+/// Mermaid ids are words, so `C0.1` becomes `C0_1`.
+fn node_id(id: &Id) -> String {
+    id.to_string()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// `s` as a quoted Mermaid label, with `"` as an entity.
+fn quote(s: &str) -> String {
+    let escaped = s.replace('"', "#quot;").replace(['\n', '\r'], " ");
+    format!("\"{escaped}\"")
+}
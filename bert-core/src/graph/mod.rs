@@ -0,0 +1,560 @@
+//! A model's structure as a graph for drawing and analysis tools: Graphviz
+//! DOT and Mermaid for documents, GraphML for yEd and GEXF for Gephi.
+//!
+//! Nodes are systems and sources/sinks, edges are interactions. `depth` says
+//! how far the system tree is opened: `Some(0)` shows the system of interest
+//! alone in its environment, `Some(1)` adds its subsystems and their sources
+//! and sinks, and so on; `None` opens every level. A system at the limit
+//! stands for everything inside it: flows to its internals attach to it, and
+//! flows entirely within it are left out.
+//!
+//! DOT and Mermaid draw each opened system as a cluster around its parts,
+//! color edges by [`SubstanceType`] as the editor does and style them by
+//! [`InteractionUsability`]: products bold, wastes dashed, disruptions dotted.
+//! GraphML and GEXF carry every field of the elements as attributes, with
+//! interfaces, transformations, history, agents and parameters as JSON, and
+//! nest nodes in their system (GraphML subgraphs, GEXF `pid`).
+//!
+//! `bert export` and the editor's Export menu write these files.
+
+mod dot;
+mod gexf;
+mod graphml;
+mod mermaid;
+
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// A graph file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    GraphMl,
+    Gexf,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 4] = [Self::Dot, Self::Mermaid, Self::GraphMl, Self::Gexf];
+
+    /// The usual file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Mermaid => "mmd",
+            Self::GraphMl => "graphml",
+            Self::Gexf => "gexf",
+        }
+    }
+
+    /// The MIME type to serve or download the file as.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Dot => "text/vnd.graphviz",
+            Self::Mermaid => "text/plain",
+            Self::GraphMl | Self::Gexf => "application/xml",
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dot => write!(f, "DOT"),
+            Self::Mermaid => write!(f, "Mermaid"),
+            Self::GraphMl => write!(f, "GraphML"),
+            Self::Gexf => write!(f, "GEXF"),
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    /// A format by its name or extension, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| {
+                format.extension().eq_ignore_ascii_case(s)
+                    || format.to_string().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| format!("'{s}' is not a graph format (dot, mermaid, graphml, gexf)"))
+    }
+}
+
+/// Write `model` as a graph in `format`, opened down to `depth` levels below
+/// the system of interest, or all of them.
+pub fn export(model: &WorldModel, format: GraphFormat, depth: Option<usize>) -> String {
+    let graph = Graph::new(model, depth);
+    match format {
+        GraphFormat::Dot => dot::write(&graph),
+        GraphFormat::Mermaid => mermaid::write(&graph),
+        GraphFormat::GraphMl => graphml::write(&graph),
+        GraphFormat::Gexf => gexf::write(&graph),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Element<'m> {
+    System(&'m System),
+    External(&'m ExternalEntity),
+}
+
+struct Node<'m> {
+    id: &'m Id,
+    element: Element<'m>,
+    /// The opened system the node is drawn in; `None` for the environment.
+    parent: Option<&'m Id>,
+    /// Whether the system has parts that are folded into it.
+    folded: bool,
+}
+
+impl Node<'_> {
+    /// The element's name, or its id when it has none.
+    fn name(&self) -> String {
+        let info = match self.element {
+            Element::System(system) => &system.info,
+            Element::External(external) => &external.info,
+        };
+        match info.name.trim() {
+            "" => info.id.to_string(),
+            name => name.to_string(),
+        }
+    }
+}
+
+struct Edge<'m> {
+    interaction: &'m Interaction,
+    source: &'m Id,
+    target: &'m Id,
+}
+
+struct Graph<'m> {
+    model: &'m WorldModel,
+    /// Environment sources and sinks, then each system followed by its own
+    /// sources and sinks and its subsystems, depth first.
+    nodes: Vec<Node<'m>>,
+    edges: Vec<Edge<'m>>,
+}
+
+impl<'m> Graph<'m> {
+    fn new(model: &'m WorldModel, depth: Option<usize>) -> Self {
+        let ids: HashSet<&Id> = model.systems.iter().map(|s| &s.info.id).collect();
+        let mut children: HashMap<&Id, Vec<&System>> = HashMap::new();
+        let mut roots = Vec::new();
+        for system in &model.systems {
+            if system.parent != system.info.id && ids.contains(&system.parent) {
+                children.entry(&system.parent).or_default().push(system);
+            } else {
+                roots.push(system);
+            }
+        }
+
+        let mut nodes = Vec::new();
+        // The node each element is drawn as: itself, or the folded system
+        // it is inside.
+        let mut shown: HashMap<&Id, &Id> = HashMap::new();
+        let environment = &model.environment;
+        for external in environment.sources.iter().chain(&environment.sinks) {
+            shown.insert(&external.info.id, &external.info.id);
+            nodes.push(Node {
+                id: &external.info.id,
+                element: Element::External(external),
+                parent: None,
+                folded: false,
+            });
+        }
+
+        let mut stack: Vec<(&System, Option<&Id>, usize)> =
+            roots.into_iter().rev().map(|s| (s, None, 0)).collect();
+        while let Some((system, parent, level)) = stack.pop() {
+            let id = &system.info.id;
+            if shown.contains_key(id) {
+                continue;
+            }
+            let opened = depth.is_none_or(|depth| level < depth);
+            let has_parts =
+                children.contains_key(id) || !system.sources.is_empty() || !system.sinks.is_empty();
+            shown.insert(id, id);
+            nodes.push(Node {
+                id,
+                element: Element::System(system),
+                parent,
+                folded: !opened && has_parts,
+            });
+            if !opened {
+                fold(system, &children, &mut shown);
+                continue;
+            }
+            for external in system.sources.iter().chain(&system.sinks) {
+                shown.entry(&external.info.id).or_insert(&external.info.id);
+                nodes.push(Node {
+                    id: &external.info.id,
+                    element: Element::External(external),
+                    parent: Some(id),
+                    folded: false,
+                });
+            }
+            for child in children.get(id).into_iter().flatten().rev() {
+                stack.push((child, Some(id), level + 1));
+            }
+        }
+
+        let edges = model
+            .interactions
+            .iter()
+            .filter_map(|interaction| {
+                let source = *shown.get(&interaction.source)?;
+                let target = *shown.get(&interaction.sink)?;
+                let within = source == target && interaction.source != interaction.sink;
+                (!within).then_some(Edge {
+                    interaction,
+                    source,
+                    target,
+                })
+            })
+            .collect();
+
+        Self {
+            model,
+            nodes,
+            edges,
+        }
+    }
+
+    /// The nodes drawn directly in `parent`, or in the environment.
+    fn children<'g>(&'g self, parent: Option<&'g Id>) -> impl Iterator<Item = &'g Node<'m>> + 'g {
+        self.nodes.iter().filter(move |n| n.parent == parent)
+    }
+
+    /// Whether the node is a system drawn around other nodes.
+    fn is_cluster(&self, node: &Node) -> bool {
+        matches!(node.element, Element::System(_)) && self.children(Some(node.id)).next().is_some()
+    }
+}
+
+/// Draw everything inside `system` as `system`.
+fn fold<'m>(
+    system: &'m System,
+    children: &HashMap<&'m Id, Vec<&'m System>>,
+    shown: &mut HashMap<&'m Id, &'m Id>,
+) {
+    let into = &system.info.id;
+    let mut stack = vec![system];
+    while let Some(system) = stack.pop() {
+        for external in system.sources.iter().chain(&system.sinks) {
+            shown.entry(&external.info.id).or_insert(into);
+        }
+        for child in children.get(&system.info.id).into_iter().flatten() {
+            if !shown.contains_key(&child.info.id) {
+                shown.insert(&child.info.id, into);
+                stack.push(child);
+            }
+        }
+    }
+}
+
+/// Edge color of each substance type, as the editor draws flows.
+fn color(ty: SubstanceType) -> (u8, u8, u8) {
+    match ty {
+        SubstanceType::Energy => (181, 27, 27),
+        SubstanceType::Material => (128, 128, 128),
+        SubstanceType::Message => (191, 191, 191),
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// The value type of an attribute.
+#[derive(Clone, Copy)]
+enum AttrType {
+    String,
+    Int,
+    Double,
+    Boolean,
+}
+
+/// The attributes GraphML and GEXF declare for nodes, in order.
+const NODE_KEYS: [(&str, AttrType); 24] = [
+    ("kind", AttrType::String),
+    ("name", AttrType::String),
+    ("description", AttrType::String),
+    ("level", AttrType::Int),
+    ("parent", AttrType::String),
+    ("folded", AttrType::Boolean),
+    ("complexity", AttrType::String),
+    ("adaptable", AttrType::Boolean),
+    ("evolveable", AttrType::Boolean),
+    ("multiset_count", AttrType::Int),
+    ("archetype", AttrType::String),
+    ("time_constant", AttrType::String),
+    ("member_autonomy", AttrType::Double),
+    ("equivalence", AttrType::String),
+    ("boundary_name", AttrType::String),
+    ("boundary_description", AttrType::String),
+    ("porosity", AttrType::Double),
+    ("perceptive_fuzziness", AttrType::Double),
+    ("parent_interface", AttrType::String),
+    ("interfaces", AttrType::String),
+    ("transformation", AttrType::String),
+    ("history", AttrType::String),
+    ("agent", AttrType::String),
+    ("model", AttrType::String),
+];
+
+/// The attributes GraphML and GEXF declare for edges, in order.
+const EDGE_KEYS: [(&str, AttrType); 14] = [
+    ("name", AttrType::String),
+    ("description", AttrType::String),
+    ("interaction_type", AttrType::String),
+    ("substance_type", AttrType::String),
+    ("substance_sub_type", AttrType::String),
+    ("usability", AttrType::String),
+    ("amount", AttrType::Double),
+    ("unit", AttrType::String),
+    ("source_element", AttrType::String),
+    ("source_interface", AttrType::String),
+    ("sink_element", AttrType::String),
+    ("sink_interface", AttrType::String),
+    ("parameters", AttrType::String),
+    ("smart_parameters", AttrType::String),
+];
+
+fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// A node's values for [`NODE_KEYS`]; fields without a value are left out.
+/// Interfaces, transformations, histories and agents are JSON, as in the
+/// model file.
+fn node_attributes(node: &Node) -> Vec<(&'static str, String)> {
+    let mut values = Vec::new();
+    let mut text = |key, value: &str| {
+        if !value.trim().is_empty() {
+            values.push((key, value.to_string()));
+        }
+    };
+    match node.element {
+        Element::System(system) => {
+            text("kind", "system");
+            text("name", &system.info.name);
+            text("description", &system.info.description);
+            text("equivalence", &system.equivalence);
+            text("boundary_name", &system.boundary.info.name);
+            text("boundary_description", &system.boundary.info.description);
+            values.push(("level", system.info.level.to_string()));
+            values.push(("folded", node.folded.to_string()));
+            values.push(("complexity", system.complexity.to_string()));
+            match system.complexity {
+                Complexity::Complex {
+                    adaptable,
+                    evolveable,
+                } => {
+                    values.push(("adaptable", adaptable.to_string()));
+                    values.push(("evolveable", evolveable.to_string()));
+                }
+                Complexity::Atomic => {}
+                Complexity::Multiset(count) => values.push(("multiset_count", count.to_string())),
+            }
+            if let Some(archetype) = system.archetype {
+                values.push(("archetype", archetype.to_string()));
+            }
+            if let Some(time_constant) = &system.time_constant {
+                values.push(("time_constant", time_constant.to_string()));
+            }
+            values.push(("member_autonomy", system.member_autonomy.to_string()));
+            values.push(("porosity", system.boundary.porosity.to_string()));
+            values.push((
+                "perceptive_fuzziness",
+                system.boundary.perceptive_fuzziness.to_string(),
+            ));
+            if let Some(interface) = &system.boundary.parent_interface {
+                values.push(("parent_interface", interface.to_string()));
+            }
+            if !system.boundary.interfaces.is_empty() {
+                values.push(("interfaces", json(&system.boundary.interfaces)));
+            }
+            if let Some(transformation) = &system.transformation {
+                values.push(("transformation", json(transformation)));
+            }
+            if !system.history.is_empty() {
+                values.push(("history", json(&system.history)));
+            }
+            if let Some(agent) = &system.agent {
+                values.push(("agent", json(agent)));
+            }
+        }
+        Element::External(external) => {
+            let kind = match external.ty {
+                ExternalEntityType::Source => "source",
+                ExternalEntityType::Sink => "sink",
+            };
+            text("kind", kind);
+            text("name", &external.info.name);
+            text("description", &external.info.description);
+            text("equivalence", &external.equivalence);
+            text("model", &external.model);
+            values.push(("level", external.info.level.to_string()));
+        }
+    }
+    if let Some(parent) = node.parent {
+        values.push(("parent", parent.to_string()));
+    }
+    values.sort_by_key(|(key, _)| NODE_KEYS.iter().position(|(k, _)| k == key));
+    values
+}
+
+/// An edge's values for [`EDGE_KEYS`]; fields without a value are left out.
+/// `source_element`/`sink_element` name the interaction's own ends, which
+/// differ from the edge's when they are folded into a system.
+fn edge_attributes(edge: &Edge) -> Vec<(&'static str, String)> {
+    let interaction = edge.interaction;
+    let mut values = Vec::new();
+    let mut text = |key, value: &str| {
+        if !value.trim().is_empty() {
+            values.push((key, value.to_string()));
+        }
+    };
+    text("name", &interaction.info.name);
+    text("description", &interaction.info.description);
+    text("interaction_type", &interaction.ty.to_string());
+    text("substance_type", &interaction.substance.ty.to_string());
+    text("substance_sub_type", &interaction.substance.sub_type);
+    text("usability", &interaction.usability.to_string());
+    text("amount", &interaction.amount.to_string());
+    text("unit", &interaction.unit);
+    text("source_element", &interaction.source.to_string());
+    text("sink_element", &interaction.sink.to_string());
+    if let Some(interface) = &interaction.source_interface {
+        values.push(("source_interface", interface.to_string()));
+    }
+    if let Some(interface) = &interaction.sink_interface {
+        values.push(("sink_interface", interface.to_string()));
+    }
+    if !interaction.parameters.is_empty() {
+        values.push(("parameters", json(&interaction.parameters)));
+    }
+    if !interaction.smart_parameters.is_empty() {
+        values.push(("smart_parameters", json(&interaction.smart_parameters)));
+    }
+    values.sort_by_key(|(key, _)| EDGE_KEYS.iter().position(|(k, _)| k == key));
+    values
+}
+
+/// `s` as XML character data or attribute value. Characters XML 1.0 cannot
+/// hold are dropped.
+fn xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c < ' ' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::example;
+
+    fn systems(graph: &Graph) -> usize {
+        graph
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.element, Element::System(_)))
+            .count()
+    }
+
+    #[test]
+    fn depth_folds_the_tree() {
+        let model = example("bitcoin");
+
+        let full = Graph::new(&model, None);
+        assert_eq!(systems(&full), model.systems.len());
+        assert_eq!(full.edges.len(), model.interactions.len());
+        assert!(full.nodes.iter().all(|n| !n.folded));
+
+        let top = Graph::new(&model, Some(0));
+        assert_eq!(systems(&top), 1);
+        assert!(top.nodes.iter().all(|n| n.parent.is_none()));
+        let root = &model.systems[0].info.id;
+        assert!(top
+            .edges
+            .iter()
+            .all(|e| e.source == root || e.target == root));
+        assert!(top.edges.len() < full.edges.len());
+
+        let one = Graph::new(&model, Some(1));
+        let subsystems = model.systems.iter().filter(|s| s.info.level == 1).count();
+        assert_eq!(systems(&one), 1 + subsystems);
+        assert!(one
+            .nodes
+            .iter()
+            .filter(|n| n.parent.is_some())
+            .all(|n| n.parent == Some(root)));
+        // Flows deeper down attach to the level-1 system they are inside.
+        let shown: HashSet<&Id> = one.nodes.iter().map(|n| n.id).collect();
+        assert!(one
+            .edges
+            .iter()
+            .all(|e| shown.contains(e.source) && shown.contains(e.target)));
+    }
+
+    #[test]
+    fn formats_draw_clusters_and_styles() {
+        let model = example("bitcoin");
+
+        let dot = export(&model, GraphFormat::Dot, None);
+        assert!(dot.starts_with("digraph \"Bitcoin\" {"));
+        assert!(dot.contains("subgraph \"cluster_S0\" {"));
+        assert!(dot.contains("color=\"#b51b1b\""));
+        assert!(dot.contains("style=dashed"));
+        let shallow = export(&model, GraphFormat::Dot, Some(0));
+        assert!(!shallow.contains("subgraph"));
+
+        let mermaid = export(&model, GraphFormat::Mermaid, None);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("subgraph S0[\"Bitcoin\"]"));
+        let links = Graph::new(&model, None).edges.len();
+        assert_eq!(mermaid.matches("linkStyle ").count(), links);
+
+        let graphml = export(&model, GraphFormat::GraphMl, None);
+        assert!(graphml.contains("<graph id=\"S0:\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("attr.name=\"usability\""));
+        assert_eq!(graphml.matches("<edge ").count(), links);
+
+        let gexf = export(&model, GraphFormat::Gexf, None);
+        assert!(gexf.contains("<gexf xmlns=\"http://gexf.net/1.3\""));
+        assert!(gexf.contains("pid=\"S0\""));
+        assert_eq!(gexf.matches("<edge ").count(), links);
+        for xml in [&graphml, &gexf] {
+            assert_eq!(
+                xml.matches("<node ").count(),
+                xml.matches("</node>").count()
+            );
+        }
+    }
+
+    #[test]
+    fn formats_parse_by_extension() {
+        assert_eq!("GraphML".parse(), Ok(GraphFormat::GraphMl));
+        assert_eq!("mmd".parse(), Ok(GraphFormat::Mermaid));
+        assert_eq!("mermaid".parse(), Ok(GraphFormat::Mermaid));
+        assert_eq!(
+            "svg".parse::<GraphFormat>(),
+            Err("'svg' is not a graph format (dot, mermaid, graphml, gexf)".to_string())
+        );
+    }
+}
@@ -9,8 +9,33 @@
 //! The `reflect` feature gates `bevy_reflect::Reflect` derives for the app,
 //! whose ECS components embed kernel types ([`Complexity`], [`AgentModel`]).
 
+pub mod canonical;
+pub mod compose;
+pub mod diff;
+pub mod dynamics;
+pub mod extract;
+pub mod feedback;
+pub mod files;
+pub mod fix;
+pub mod graph;
+pub mod migrate;
+pub mod renumber;
+pub mod rules;
+pub mod sl;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod units;
 pub mod validate;
 
+pub use canonical::{canonicalize, to_canonical_json};
+pub use dynamics::{History, HistoryEvent, TimeConstant, Timescale, Transformation};
+pub use extract::{embed_subsystem, extract_subsystem};
+pub use feedback::{FeedbackLoop, Polarity};
+pub use fix::{fix_all, Fix};
+pub use graph::GraphFormat;
+pub use rules::{Rule, RuleConfig};
+pub use units::{Dimension, Unit, UnitError};
+
 use enum_iterator::Sequence;
 use std::fmt::Formatter;
 use uuid::Uuid;
@@ -497,7 +522,7 @@ impl SmartParameter {
 // ## Version Management
 //
 // The data format uses semantic versioning to handle schema evolution:
-// - Version increments trigger data migration logic
+// - Version increments trigger data migration logic ([`migrate`])
 // - Backward compatibility is maintained where possible
 // - Breaking changes are clearly documented
 //
@@ -524,12 +549,16 @@ use std::fmt;
 /// # Version History
 ///
 /// - **Version 1**: Initial data format with basic system modeling support
+/// - **Version 2**: Defaulted fields normalized to the shape the saver writes
+/// - **Version 3**: Typed dynamical face (time constant, transformation, history)
 ///
 /// # Usage
 ///
 /// Always use this constant when creating new [`WorldModel`] instances to ensure
-/// compatibility with the current format expectations.
-pub const CURRENT_FILE_VERSION: u32 = 1;
+/// compatibility with the current format expectations. Older files are upgraded
+/// by [`migrate`]; bumping this constant requires a matching step in
+/// [`migrate::STEPS`].
+pub const CURRENT_FILE_VERSION: u32 = 3;
 
 /// Root container representing a complete BERT system model for serialization.
 ///
@@ -555,7 +584,7 @@ pub const CURRENT_FILE_VERSION: u32 = 1;
 /// # Versioning Strategy
 ///
 /// The version field enables format evolution while maintaining backward compatibility:
-/// - Version mismatches trigger migration logic during deserialization
+/// - Older versions are upgraded by [`migrate::from_slice`] before deserialization
 /// - New versions can add optional fields with serde defaults
 /// - Breaking changes require version increment and migration code
 ///
@@ -595,7 +624,7 @@ pub struct WorldModel {
     /// Schema version for format compatibility and migration support.
     ///
     /// Must match [`CURRENT_FILE_VERSION`] for newly created models.
-    /// Older versions trigger migration logic during deserialization.
+    /// Older versions are upgraded by [`migrate`] when loaded through it.
     pub version: u32,
 
     /// Authoring mode along the kernel ladder (Core/Structural/Operational/Full).
@@ -632,6 +661,12 @@ pub struct WorldModel {
     /// allowing users to maintain their preferred level of detail.
     #[serde(default)]
     pub hidden_entities: Vec<Id>,
+
+    /// Rules this model suppresses or escalates; see [`rules`].
+    ///
+    /// Omitted when empty, so models without overrides serialize unchanged.
+    #[serde(default, skip_serializing_if = "RuleConfig::is_empty")]
+    pub validation: RuleConfig,
 }
 
 /// A rung on the kernel ladder: how much structure an author has committed to.
@@ -642,9 +677,9 @@ pub struct WorldModel {
 /// and `Operational` (Mobus) each impose their own precondition independently —
 /// neither inherits the other's. They share only `Core`'s on-ness. `Full` extends
 /// `Operational` with the dynamical face. See [`validate::validate_mode`].
-/// `Cybernetic` is deliberately absent: feedback-as-first-class is still an open
-/// question (no faithful finite acyclic comparison yet), so it is not a buildable
-/// mode here. `Full` is the default and richest view.
+/// `Cybernetic` is an engineering mode rather than a proven lens (feedback has no
+/// faithful finite acyclic comparison yet): it makes the cycles `Operational`
+/// forbids the object of study. `Full` is the default view.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Mode {
     /// Klir's (things, dependency) — the kernel itself. Precondition: on-ness only.
@@ -655,6 +690,9 @@ pub enum Mode {
     Operational,
     /// The dynamical face populated (transformation, history, time constant). Extends `Operational`.
     Full,
+    /// Feedback as a first-class cycle, each loop reinforcing or balancing (see
+    /// [`feedback`]). Precondition: at least one cycle in the dependency graph.
+    Cybernetic,
 }
 
 /// The K ≅ 2 kernel projected out of a [`WorldModel`]: a system *is* a morphism,
@@ -812,11 +850,26 @@ pub struct Id {
     pub indices: Vec<i64>,
 }
 
-impl Serialize for Id {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+/// Hierarchy-first ordering: by `indices`, then by type. Sorting any id list
+/// this way puts every parent before its children (`S0` < `C0.1` < `C0.1.0` <
+/// `C0.2`) and environment entities (`-1`) before the system tree.
+impl Ord for Id {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.indices
+            .cmp(&other.indices)
+            .then_with(|| self.ty.cmp(&other.ty))
+    }
+}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The serialized form, e.g. `C0.1` or `Src-1.0`.
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut str_value = serde_json::to_string(&self.ty).expect("This shouldn't fail");
         str_value = str_value[1..str_value.len() - 1].to_string();
 
@@ -829,7 +882,16 @@ impl Serialize for Id {
                 .join("."),
         );
 
-        serializer.serialize_str(&str_value)
+        f.write_str(&str_value)
+    }
+}
+
+impl Serialize for Id {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
     }
 }
 
@@ -872,6 +934,15 @@ impl<'de> Deserialize<'de> for Id {
     }
 }
 
+/// Parses the serialized form, e.g. `"C0.1".parse::<Id>()`.
+impl std::str::FromStr for Id {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IdVisitor.visit_str(s)
+    }
+}
+
 /// Enumeration of all entity types that can be uniquely identified in the data model.
 ///
 /// `IdType` provides type safety for the hierarchical ID system by ensuring that
@@ -908,7 +979,7 @@ impl<'de> Deserialize<'de> for Id {
 ///
 /// - [`Id`]: The hierarchical identifier structure that uses these types
 /// - [`HasInfo`]: Trait for entities that contain typed ID information
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum IdType {
     /// Root system or primary system entity.
     ///
@@ -1064,10 +1135,26 @@ pub struct System {
     pub radius: f32,
     pub transform: Option<Transform2d>,
     pub equivalence: String,
-    pub history: String,
-    pub transformation: String,
+    /// What has happened to the system, oldest first. Reads a legacy
+    /// free-text note as one undated event.
+    #[serde(default, skip_serializing_if = "History::is_empty")]
+    pub history: History,
+    /// The substances the system turns into others. Reads a legacy free-text
+    /// note as the description.
+    #[serde(
+        default,
+        deserialize_with = "dynamics::blank_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub transformation: Option<Transformation>,
     pub member_autonomy: f32,
-    pub time_constant: String,
+    /// The system's characteristic response time, e.g. `"Second"` or `"15 Minute"`.
+    #[serde(
+        default,
+        deserialize_with = "dynamics::blank_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub time_constant: Option<Timescale>,
     /// HCGS archetype classification (Governance, Economy, Agent).
     /// None = Unspecified (backward compatible with older models).
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1096,7 +1183,11 @@ pub struct AgentModel {
     pub primitives: Vec<ProcessPrimitive>,
 
     /// Domain-agnostic cognitive parameters (e.g., "fee_threshold": 50.0)
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub cognitive_params: HashMap<String, f64>,
 
     /// Process behavior configurations with flexible parameters
@@ -1104,7 +1195,11 @@ pub struct AgentModel {
     pub process_configs: Vec<ProcessAssignment>,
 
     /// Initial state for agent instantiation as arbitrary JSON values
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub initial_state: HashMap<String, serde_json::Value>,
 
@@ -1117,6 +1212,17 @@ fn default_agency_capacity() -> f32 {
     0.5
 }
 
+/// Serialize a `HashMap` in key order so saved files don't depend on hash seeds.
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    map.iter()
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
 impl Default for AgentModel {
     fn default() -> Self {
         Self {
@@ -1164,7 +1270,7 @@ pub enum ProcessPrimitive {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ProcessAssignment {
     pub name: String,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub params: HashMap<String, serde_json::Value>,
 }
@@ -1174,10 +1280,10 @@ pub struct ProcessAssignment {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct NetworkConfig {
     pub topology: String,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub connection_params: HashMap<String, serde_json::Value>,
-    #[serde(default)]
+    #[serde(default, serialize_with = "serialize_sorted")]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub interaction_rules: HashMap<String, serde_json::Value>,
 }
@@ -1195,6 +1301,27 @@ pub struct Boundary {
     pub parent_interface: Option<Id>,
 }
 
+impl Boundary {
+    /// The bidirectional ports of this boundary: each Import/Export pair linked
+    /// through [`Interface::paired_with`], once, import side first. Links that
+    /// do not resolve to a mutual Import/Export pair on this boundary are
+    /// skipped (the validator reports them).
+    pub fn port_pairs(&self) -> Vec<(&Interface, &Interface)> {
+        self.interfaces
+            .iter()
+            .filter(|i| matches!(i.ty, InterfaceType::Import))
+            .filter_map(|import| {
+                let export = self
+                    .interfaces
+                    .iter()
+                    .find(|e| Some(&e.info.id) == import.paired_with.as_ref())?;
+                let mutual = export.paired_with.as_ref() == Some(&import.info.id);
+                (mutual && matches!(export.ty, InterfaceType::Export)).then_some((import, export))
+            })
+            .collect()
+    }
+}
+
 /// Interface of a system
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Interface {
@@ -1210,6 +1337,11 @@ pub struct Interface {
     pub receives_from: Vec<Id>,
     /// Rotation in radians.
     pub angle: Option<f32>,
+    /// The other half of a bidirectional port: an interface on the same
+    /// boundary, of the opposite type, with the same protocol, that links back
+    /// to this one. A socket or a market is one such Import/Export pair.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired_with: Option<Id>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy)]
@@ -1218,7 +1350,8 @@ pub enum InterfaceType {
     Export,
     /// Interface contains only incoming interactions
     Import,
-    /// Interface contains both incoming and outgoing interactions. This is not implemented yet.
+    /// Interface contains both incoming and outgoing interactions. Superseded by an
+    /// Import/Export pair linked through [`Interface::paired_with`].
     Hybrid,
 }
 
@@ -1292,13 +1425,24 @@ pub struct EndpointOffset {
     pub end_angle: Option<f32>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Substance {
     pub sub_type: String,
     #[serde(rename = "type")]
     pub ty: SubstanceType,
 }
 
+impl fmt::Display for Substance {
+    /// `"Electricity (Energy)"`, or just the type when `sub_type` is blank.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.sub_type.trim() {
+            "" => write!(f, "{}", self.ty),
+            sub_type => write!(f, "{sub_type} ({})", self.ty),
+        }
+    }
+}
+
 /// Defines the complexity classification of system entities based on their internal structure.
 ///
 /// The `Complexity` enum implements the System Language framework's approach to
@@ -1644,6 +1788,7 @@ pub struct Transform2d {
 /// The following data model types implement `HasInfo`:
 /// - [`System`]: System entities at all hierarchical levels
 /// - [`Boundary`]: System boundary definitions
+/// - [`Interface`]: Boundary interfaces
 /// - [`Environment`]: Environmental context container
 /// - [`ExternalEntity`]: External sources and sinks
 /// - [`Interaction`]: Flow connections between entities
@@ -1688,7 +1833,14 @@ macro_rules! impl_has_info {
     }
 }
 
-impl_has_info!(System, Boundary, Environment, ExternalEntity, Interaction);
+impl_has_info!(
+    System,
+    Boundary,
+    Interface,
+    Environment,
+    ExternalEntity,
+    Interaction
+);
 
 /// Trait for entities that can contain external sources and sinks.
 ///
@@ -1847,6 +1999,37 @@ macro_rules! impl_has_sources_and_sinks {
 
 impl_has_sources_and_sinks!(System, Environment);
 
+impl core::fmt::Display for InteractionUsability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InteractionUsability::Resource => write!(f, "Resource"),
+            InteractionUsability::Disruption => write!(f, "Disruption"),
+            InteractionUsability::Product => write!(f, "Product"),
+            InteractionUsability::Waste => write!(f, "Waste"),
+        }
+    }
+}
+
+impl std::fmt::Display for SubstanceType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubstanceType::Energy => write!(f, "Energy"),
+            SubstanceType::Material => write!(f, "Material"),
+            SubstanceType::Message => write!(f, "Message"),
+        }
+    }
+}
+
+impl std::fmt::Display for AgentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentKind::Reactive => write!(f, "Reactive"),
+            AgentKind::Anticipatory => write!(f, "Anticipatory"),
+            AgentKind::Intentional => write!(f, "Intentional"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1884,34 +2067,3 @@ mod tests {
         assert!(result.is_ok());
     }
 }
-
-impl core::fmt::Display for InteractionUsability {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            InteractionUsability::Resource => write!(f, "Resource"),
-            InteractionUsability::Disruption => write!(f, "Disruption"),
-            InteractionUsability::Product => write!(f, "Product"),
-            InteractionUsability::Waste => write!(f, "Waste"),
-        }
-    }
-}
-
-impl std::fmt::Display for SubstanceType {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SubstanceType::Energy => write!(f, "Energy"),
-            SubstanceType::Material => write!(f, "Material"),
-            SubstanceType::Message => write!(f, "Message"),
-        }
-    }
-}
-
-impl std::fmt::Display for AgentKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AgentKind::Reactive => write!(f, "Reactive"),
-            AgentKind::Anticipatory => write!(f, "Anticipatory"),
-            AgentKind::Intentional => write!(f, "Intentional"),
-        }
-    }
-}
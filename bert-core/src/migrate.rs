@@ -0,0 +1,620 @@
+//! Schema migration for serialized `WorldModel` files.
+//!
+//! Migrations operate on raw [`serde_json::Value`], not on typed models: a step
+//! must be able to read shapes the current structs no longer accept. Each
+//! [`MigrationStep`] upgrades exactly one version (`from` → `from + 1`) and
+//! records what it touched, so [`migrate`] can chain them from any supported
+//! version up to [`CURRENT_FILE_VERSION`] and hand back a [`MigrationReport`].
+//!
+//! Adding a schema change means: bump [`CURRENT_FILE_VERSION`], append a step
+//! to [`STEPS`], and extend the golden tests below. Never edit a shipped step —
+//! files in the wild were upgraded by it.
+
+use crate::{Timescale, WorldModel, CURRENT_FILE_VERSION};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// One upgrade in the chain: rewrites a version-`from` document into version `from + 1`.
+pub struct MigrationStep {
+    /// Version this step accepts. The step produces `from + 1`.
+    pub from: u32,
+    /// One-line summary of the schema change, shown in reports.
+    pub description: &'static str,
+    /// Rewrites the document in place, pushing one entry per change made.
+    /// Must not touch `version` — [`migrate`] stamps it after the step runs.
+    pub apply: fn(&mut Value, &mut Vec<Change>),
+}
+
+/// A single edit made by a step, addressed by JSON pointer into the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// RFC 6901 pointer to the edited value (e.g. `/systems/3/archetype`).
+    pub pointer: String,
+    pub description: String,
+}
+
+impl Change {
+    fn new(pointer: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// What one step did to a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepReport {
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    pub changes: Vec<Change>,
+}
+
+/// What [`migrate`] did end to end. Empty `steps` means the file was already current.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub original_version: u32,
+    pub final_version: u32,
+    pub steps: Vec<StepReport>,
+}
+
+impl MigrationReport {
+    /// True when the document was already at [`CURRENT_FILE_VERSION`].
+    pub fn is_noop(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Total number of edits across all steps.
+    pub fn change_count(&self) -> usize {
+        self.steps.iter().map(|s| s.changes.len()).sum()
+    }
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The document is not a JSON object.
+    NotAnObject,
+    /// `version` is absent or not a non-negative integer.
+    MissingVersion,
+    /// The file was written by a newer BERT than this one.
+    FromFuture { found: u32, supported: u32 },
+    /// No registered step upgrades from this version.
+    NoStep { from: u32 },
+    /// The bytes are not JSON, or the migrated document does not fit [`WorldModel`].
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnObject => write!(f, "model file must be a JSON object"),
+            Self::MissingVersion => write!(f, "model file has no integer 'version' field"),
+            Self::FromFuture { found, supported } => write!(
+                f,
+                "model version {found} is newer than this BERT supports ({supported})"
+            ),
+            Self::NoStep { from } => write!(f, "no migration registered from version {from}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<serde_json::Error> for MigrationError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// The registry, in version order. `STEPS[i].from == i + 1`.
+pub const STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        description: "materialize serde defaults and drop legacy nulls",
+        apply: v1_to_v2,
+    },
+    MigrationStep {
+        from: 2,
+        description: "type the dynamical face: time constant, transformation, history",
+        apply: v2_to_v3,
+    },
+];
+
+/// Upgrade `doc` in place to [`CURRENT_FILE_VERSION`].
+///
+/// A document already at the current version is left untouched and yields a
+/// no-op report. On error the document may be partially migrated; callers that
+/// need the original should clone first.
+pub fn migrate(doc: &mut Value) -> Result<MigrationReport, MigrationError> {
+    let original_version = read_version(doc)?;
+    if original_version > CURRENT_FILE_VERSION {
+        return Err(MigrationError::FromFuture {
+            found: original_version,
+            supported: CURRENT_FILE_VERSION,
+        });
+    }
+
+    let mut version = original_version;
+    let mut steps = Vec::new();
+    while version < CURRENT_FILE_VERSION {
+        let step = STEPS
+            .iter()
+            .find(|s| s.from == version)
+            .ok_or(MigrationError::NoStep { from: version })?;
+        let mut changes = Vec::new();
+        (step.apply)(doc, &mut changes);
+        version += 1;
+        doc["version"] = Value::from(version);
+        steps.push(StepReport {
+            from: step.from,
+            to: version,
+            description: step.description,
+            changes,
+        });
+    }
+
+    Ok(MigrationReport {
+        original_version,
+        final_version: version,
+        steps,
+    })
+}
+
+/// Migrate a parsed document and deserialize it into a current [`WorldModel`].
+pub fn from_value(mut doc: Value) -> Result<(WorldModel, MigrationReport), MigrationError> {
+    let report = migrate(&mut doc)?;
+    let model = serde_json::from_value(doc)?;
+    Ok((model, report))
+}
+
+/// Parse, migrate and deserialize a model file. The entry point for every loader.
+pub fn from_slice(bytes: &[u8]) -> Result<(WorldModel, MigrationReport), MigrationError> {
+    from_value(serde_json::from_slice(bytes)?)
+}
+
+fn read_version(doc: &Value) -> Result<u32, MigrationError> {
+    let obj = doc.as_object().ok_or(MigrationError::NotAnObject)?;
+    obj.get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or(MigrationError::MissingVersion)
+}
+
+// ── Step helpers ─────────────────────────────────────────────
+
+/// Visit every object in `parent[key]` (an array), with its JSON pointer.
+fn for_each_in(
+    parent: &mut Value,
+    key: &str,
+    pointer: &str,
+    mut f: impl FnMut(&mut Map<String, Value>, &str),
+) {
+    if let Some(items) = parent.get_mut(key).and_then(Value::as_array_mut) {
+        for (i, item) in items.iter_mut().enumerate() {
+            if let Some(obj) = item.as_object_mut() {
+                f(obj, &format!("{pointer}/{key}/{i}"));
+            }
+        }
+    }
+}
+
+fn insert_default(
+    obj: &mut Map<String, Value>,
+    key: &str,
+    value: Value,
+    pointer: &str,
+    changes: &mut Vec<Change>,
+) {
+    if !obj.contains_key(key) {
+        changes.push(Change::new(
+            format!("{pointer}/{key}"),
+            format!("added default {value}"),
+        ));
+        obj.insert(key.to_string(), value);
+    }
+}
+
+fn remove_if(
+    obj: &mut Map<String, Value>,
+    key: &str,
+    pred: impl Fn(&Value) -> bool,
+    pointer: &str,
+    changes: &mut Vec<Change>,
+) {
+    if obj.get(key).is_some_and(pred) {
+        let old = obj.remove(key).unwrap_or_default();
+        changes.push(Change::new(
+            format!("{pointer}/{key}"),
+            format!("removed redundant {old}"),
+        ));
+    }
+}
+
+// ── v1 → v2 ──────────────────────────────────────────────────
+
+/// v1 grew fields one release at a time behind `#[serde(default)]`, so v1
+/// files disagree on which keys they carry. v2 is the shape the saver writes:
+/// always-serialized defaults present, skip-if-empty fields absent rather than
+/// `null`/`[]`. The typed model is identical before and after.
+fn v1_to_v2(doc: &mut Value, changes: &mut Vec<Change>) {
+    if let Some(root) = doc.as_object_mut() {
+        insert_default(root, "hidden_entities", Value::Array(vec![]), "", changes);
+        remove_if(root, "mode", Value::is_null, "", changes);
+    }
+
+    if let Some(env) = doc.get_mut("environment").and_then(Value::as_object_mut) {
+        externals_v1_to_v2(env, "/environment", changes);
+    }
+
+    for_each_in(doc, "systems", "", |sys, ptr| {
+        remove_if(sys, "archetype", Value::is_null, ptr, changes);
+        remove_if(sys, "agent", Value::is_null, ptr, changes);
+        externals_v1_to_v2(sys, ptr, changes);
+    });
+
+    for_each_in(doc, "interactions", "", |ix, ptr| {
+        remove_if(ix, "endpoint_offset", Value::is_null, ptr, changes);
+        remove_if(
+            ix,
+            "smart_parameters",
+            |v| v.as_array().is_some_and(Vec::is_empty),
+            ptr,
+            changes,
+        );
+        if let Some(params) = ix.get_mut("parameters").and_then(Value::as_array_mut) {
+            for (i, param) in params.iter_mut().enumerate() {
+                if let Some(param) = param.as_object_mut() {
+                    let p = format!("{ptr}/parameters/{i}");
+                    insert_default(param, "unit", Value::String(String::new()), &p, changes);
+                }
+            }
+        }
+    });
+}
+
+fn externals_v1_to_v2(
+    container: &mut Map<String, Value>,
+    pointer: &str,
+    changes: &mut Vec<Change>,
+) {
+    for key in ["sources", "sinks"] {
+        if let Some(items) = container.get_mut(key).and_then(Value::as_array_mut) {
+            for (i, item) in items.iter_mut().enumerate() {
+                if let Some(ent) = item.as_object_mut() {
+                    let p = format!("{pointer}/{key}/{i}");
+                    insert_default(ent, "is_same_as_id", Value::Null, &p, changes);
+                }
+            }
+        }
+    }
+}
+
+// ── v2 → v3 ──────────────────────────────────────────────────
+
+/// v2 kept `time_constant`, `transformation` and `history` as free text. v3
+/// writes a normalized [`Timescale`] string, a transformation object and an
+/// event list, and omits empty slots. A legacy note becomes the transformation's
+/// description or one undated history event. A time constant outside the
+/// vocabulary cannot be typed; its text is kept as an undated history event so
+/// the next save does not lose it, and the report says so.
+fn v2_to_v3(doc: &mut Value, changes: &mut Vec<Change>) {
+    let blank = |v: &Value| v.is_null() || v.as_str().is_some_and(|s| s.trim().is_empty());
+    for_each_in(doc, "systems", "", |sys, ptr| {
+        for key in ["time_constant", "transformation", "history"] {
+            remove_if(sys, key, blank, ptr, changes);
+        }
+        if let Some(Value::String(text)) = sys.get("transformation").cloned() {
+            changes.push(Change::new(
+                format!("{ptr}/transformation"),
+                "note became the transformation's description",
+            ));
+            sys.insert(
+                "transformation".into(),
+                serde_json::json!({ "description": text }),
+            );
+        }
+        if let Some(Value::String(text)) = sys.get("history").cloned() {
+            changes.push(Change::new(
+                format!("{ptr}/history"),
+                "note became an undated history event",
+            ));
+            sys.insert("history".into(), serde_json::json!([{ "event": text }]));
+        }
+        if let Some(Value::String(text)) = sys.get("time_constant").cloned() {
+            let pointer = format!("{ptr}/time_constant");
+            match text.parse::<Timescale>() {
+                Ok(ts) if ts.to_string() != text => {
+                    changes.push(Change::new(
+                        pointer,
+                        format!("normalized '{text}' to '{ts}'"),
+                    ));
+                    sys.insert("time_constant".into(), Value::String(ts.to_string()));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    changes.push(Change::new(
+                        pointer,
+                        format!("{e}; kept as an undated history event"),
+                    ));
+                    sys.remove("time_constant");
+                    let event = serde_json::json!({ "event": format!("Time constant: {text}") });
+                    match sys.get_mut("history").and_then(Value::as_array_mut) {
+                        Some(events) => events.push(event),
+                        None => {
+                            sys.insert("history".into(), Value::Array(vec![event]));
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    /// Every WorldModel-shaped JSON under `assets/models/` (spec files and
+    /// other non-model JSON lack an `environment` key and are skipped).
+    fn model_files() -> Vec<PathBuf> {
+        fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path, out);
+                } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                    out.push(path);
+                }
+            }
+        }
+        let root = PathBuf::from(format!("{}/../assets/models", env!("CARGO_MANIFEST_DIR")));
+        let mut files = Vec::new();
+        walk(&root, &mut files);
+        files.retain(|p| {
+            let v: Value = serde_json::from_slice(&std::fs::read(p).unwrap()).unwrap();
+            v.get("environment").is_some()
+        });
+        files.sort();
+        assert!(!files.is_empty(), "no model files found under {root:?}");
+        files
+    }
+
+    /// The typed model as a value, with `version` masked so the pre- and
+    /// post-migration models compare on content alone.
+    fn typed_content(doc: &Value) -> Value {
+        let model: WorldModel = serde_json::from_value(doc.clone()).unwrap();
+        let mut v = serde_json::to_value(&model).unwrap();
+        v["version"] = Value::Null;
+        v
+    }
+
+    #[test]
+    fn registry_is_contiguous_and_reaches_current() {
+        for (i, step) in STEPS.iter().enumerate() {
+            assert_eq!(step.from, i as u32 + 1, "STEPS[{i}] is out of order");
+        }
+        assert_eq!(STEPS.len() as u32 + 1, CURRENT_FILE_VERSION);
+    }
+
+    #[test]
+    fn every_bundled_model_upgrades_losslessly() {
+        for path in model_files() {
+            let name = path.display();
+            let original: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            let mut migrated = original.clone();
+            let report = migrate(&mut migrated).unwrap_or_else(|e| panic!("{name}: {e}"));
+
+            assert_eq!(report.final_version, CURRENT_FILE_VERSION, "{name}");
+            assert_eq!(migrated["version"], CURRENT_FILE_VERSION, "{name}");
+            assert_eq!(
+                typed_content(&original),
+                typed_content(&migrated),
+                "{name}: migration changed model content"
+            );
+            let (model, _) = from_value(original).unwrap();
+            assert!(
+                !crate::validate::validate(&model).has_errors(),
+                "{name}: migrated model has validation errors"
+            );
+        }
+    }
+
+    #[test]
+    fn migrated_output_is_a_fixed_point() {
+        for path in model_files() {
+            let mut doc: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+            migrate(&mut doc).unwrap();
+            let once = doc.clone();
+            let report = migrate(&mut doc).unwrap();
+            assert!(report.is_noop(), "{}: second pass did work", path.display());
+            assert_eq!(once, doc);
+        }
+    }
+
+    fn legacy_v1() -> Value {
+        serde_json::json!({
+            "version": 1,
+            "mode": null,
+            "environment": {
+                "info": { "id": "E-1", "level": -1, "name": "", "description": "" },
+                "sources": [{
+                    "info": { "id": "Src-1.0", "level": -1, "name": "Sun", "description": "" },
+                    "type": "Source", "transform": null, "equivalence": "", "model": ""
+                }],
+                "sinks": []
+            },
+            "systems": [{
+                "info": { "id": "S0", "level": 0, "name": "Plant", "description": "" },
+                "sources": [], "sinks": [],
+                "parent": "E-1",
+                "complexity": "Atomic",
+                "boundary": {
+                    "info": { "id": "B0", "level": 0, "name": "", "description": "" },
+                    "porosity": 0.0, "perceptive_fuzziness": 0.0,
+                    "interfaces": [], "parent_interface": null
+                },
+                "radius": 100.0, "transform": null,
+                "equivalence": "", "history": "", "transformation": "",
+                "member_autonomy": 1.0, "time_constant": "Second",
+                "archetype": null, "agent": null
+            }],
+            "interactions": [{
+                "info": { "id": "F-1.0", "level": -1, "name": "Light", "description": "" },
+                "substance": { "sub_type": "", "type": "Energy" },
+                "type": "Flow", "usability": "Resource",
+                "source": "Src-1.0", "source_interface": null,
+                "sink": "S0", "sink_interface": null,
+                "amount": "1", "unit": "W",
+                "parameters": [{ "name": "Wavelength", "value": "550" }],
+                "smart_parameters": [],
+                "endpoint_offset": null
+            }]
+        })
+    }
+
+    #[test]
+    fn v1_legacy_shape_is_normalized_and_reported() {
+        let original = legacy_v1();
+        let mut doc = original.clone();
+        let report = migrate(&mut doc).unwrap();
+
+        assert_eq!(report.original_version, 1);
+        assert_eq!(report.steps.len(), 2);
+        let pointers: Vec<&str> = report.steps[0]
+            .changes
+            .iter()
+            .map(|c| c.pointer.as_str())
+            .collect();
+        for expected in [
+            "/hidden_entities",
+            "/mode",
+            "/environment/sources/0/is_same_as_id",
+            "/systems/0/archetype",
+            "/systems/0/agent",
+            "/interactions/0/endpoint_offset",
+            "/interactions/0/smart_parameters",
+            "/interactions/0/parameters/0/unit",
+        ] {
+            assert!(
+                pointers.contains(&expected),
+                "missing {expected}: {pointers:?}"
+            );
+        }
+        assert_eq!(report.steps[0].changes.len(), 8);
+        // v3 drops the blank dynamical slots.
+        assert_eq!(report.change_count(), 10);
+        assert_eq!(typed_content(&original), typed_content(&doc));
+
+        // The result is exactly what the saver writes.
+        let (model, _) = from_value(doc.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&model).unwrap(), doc);
+    }
+
+    #[test]
+    fn v2_dynamical_notes_become_typed() {
+        let mut original = legacy_v1();
+        migrate_to(&mut original, 2);
+        let sys = &mut original["systems"][0];
+        sys["history"] = Value::from("Built in 1998");
+        sys["transformation"] = Value::from("Light into sugar");
+        sys["time_constant"] = Value::from(" days ");
+
+        let mut doc = original.clone();
+        let report = migrate(&mut doc).unwrap();
+        assert_eq!(report.steps.len(), 1);
+        assert_eq!(report.change_count(), 3);
+        let sys = &doc["systems"][0];
+        assert_eq!(
+            sys["history"],
+            serde_json::json!([{ "event": "Built in 1998" }])
+        );
+        assert_eq!(
+            sys["transformation"],
+            serde_json::json!({ "description": "Light into sugar" })
+        );
+        assert_eq!(sys["time_constant"], "Day");
+        assert_eq!(typed_content(&original), typed_content(&doc));
+        let (model, _) = from_value(doc.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&model).unwrap(), doc);
+    }
+
+    #[test]
+    fn v2_time_constant_outside_the_vocabulary_is_kept() {
+        let mut original = legacy_v1();
+        migrate_to(&mut original, 2);
+        original["systems"][0]["time_constant"] = Value::from("about two HorseYears");
+
+        // It cannot be typed, so it moves into the history; the report says so.
+        let mut doc = original.clone();
+        let report = migrate(&mut doc).unwrap();
+        let moved = report.steps[0]
+            .changes
+            .iter()
+            .find(|c| c.pointer == "/systems/0/time_constant")
+            .unwrap();
+        assert!(moved.description.contains("HorseYears"), "{moved:?}");
+        let sys = &doc["systems"][0];
+        assert!(sys.get("time_constant").is_none());
+        assert_eq!(
+            sys["history"],
+            serde_json::json!([{ "event": "Time constant: about two HorseYears" }])
+        );
+
+        // Alongside a legacy history note, it comes after it.
+        let mut doc = original;
+        doc["systems"][0]["history"] = Value::from("Built in 1998");
+        migrate(&mut doc).unwrap();
+        assert_eq!(
+            doc["systems"][0]["history"],
+            serde_json::json!([
+                { "event": "Built in 1998" },
+                { "event": "Time constant: about two HorseYears" }
+            ])
+        );
+
+        // And it survives a save and a reload.
+        let (model, _) = from_value(doc.clone()).unwrap();
+        let saved = serde_json::to_value(&model).unwrap();
+        assert_eq!(saved, doc);
+        let reloaded = from_value(saved).unwrap().0;
+        assert!(reloaded.systems[0].history.0[1]
+            .event
+            .contains("HorseYears"));
+    }
+
+    /// Run the registered steps on `doc` up to `version` only.
+    fn migrate_to(doc: &mut Value, version: u32) {
+        let mut changes = Vec::new();
+        for step in STEPS.iter().filter(|s| s.from < version) {
+            (step.apply)(doc, &mut changes);
+        }
+        doc["version"] = Value::from(version);
+    }
+
+    #[test]
+    fn future_version_is_rejected() {
+        let mut doc = legacy_v1();
+        doc["version"] = Value::from(CURRENT_FILE_VERSION + 1);
+        assert!(matches!(
+            migrate(&mut doc),
+            Err(MigrationError::FromFuture { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_version_is_rejected() {
+        let mut doc = legacy_v1();
+        doc.as_object_mut().unwrap().remove("version");
+        assert!(matches!(
+            migrate(&mut doc),
+            Err(MigrationError::MissingVersion)
+        ));
+        assert!(matches!(
+            migrate(&mut Value::Array(vec![])),
+            Err(MigrationError::NotAnObject)
+        ));
+    }
+}
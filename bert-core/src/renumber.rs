@@ -0,0 +1,758 @@
+//! Dense renumbering and re-parenting of [`Id`]s with every reference rewritten.
+//!
+//! An `Id` is an address: `C0.3.1` is subsystem 1 of subsystem 3 of the root.
+//! Deleting elements leaves gaps in those addresses, and moving a subsystem to
+//! another parent changes the address of everything beneath it. [`compact`]
+//! renumbers a whole model densely; [`reparent`] moves one subtree under a new
+//! parent. Both rewrite every reference — interaction endpoints and interfaces,
+//! `exports_to`/`receives_from`, `Interface.paired_with`,
+//! `Boundary.parent_interface`, `System.parent`, `hidden_entities` — and
+//! recompute `Info.level`, so a model that passed [`validate::validate`] still
+//! does.
+//!
+//! The numbering follows the editor's save path:
+//!
+//! - a boundary carries its system's indices (`B0.1` for `C0.1`);
+//! - a system's subsystems and interfaces share one index space, because an
+//!   interface subsystem (the processor attached through `parent_interface`)
+//!   takes its interface's index: `I0.3` ↔ `C0.3`;
+//! - sources and sinks are numbered separately under their owner (`Src-1.n` in
+//!   the environment, `Src0.1.n` inside `C0.1`);
+//! - interactions are numbered under the system whose parts they connect, read
+//!   from the prefix of their current id (`F-1.n` for the environment).
+//!
+//! Relative order is kept: an element never moves ahead of a sibling that had a
+//! lower index.
+
+use crate::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A renumbered model and the ids that changed, old → new.
+pub struct Renumbered {
+    pub model: WorldModel,
+    pub renamed: BTreeMap<Id, Id>,
+}
+
+impl Renumbered {
+    /// True when every id kept its address.
+    pub fn is_noop(&self) -> bool {
+        self.renamed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenumberError {
+    /// Two elements share this id, so references to it are ambiguous.
+    DuplicateId(Id),
+    /// A structural reference resolves to no element.
+    Dangling { location: String, id: Id },
+    /// No chain of `parent` links connects this system to the environment.
+    Detached(Id),
+    /// The id names no system in the model.
+    UnknownSystem(Id),
+    /// Only subsystems can be moved; the root system is anchored to the environment.
+    NotASubsystem(Id),
+    /// The new parent lies inside the subtree being moved.
+    IntoOwnSubtree { subtree: Id, new_parent: Id },
+}
+
+impl fmt::Display for RenumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateId(id) => write!(f, "duplicate id '{id}'"),
+            Self::Dangling { location, id } => {
+                write!(f, "{location}: '{id}' does not resolve to any element")
+            }
+            Self::Detached(id) => write!(f, "system '{id}' is not connected to the environment"),
+            Self::UnknownSystem(id) => write!(f, "'{id}' is not a system in this model"),
+            Self::NotASubsystem(id) => write!(f, "'{id}' is the root system and cannot be moved"),
+            Self::IntoOwnSubtree {
+                subtree,
+                new_parent,
+            } => write!(
+                f,
+                "cannot move '{subtree}' under its own descendant '{new_parent}'"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RenumberError {}
+
+/// Renumber every id densely from 0, keeping each element under its parent.
+///
+/// Compacting a compact model is a no-op.
+pub fn compact(model: &WorldModel) -> Result<Renumbered, RenumberError> {
+    check_references(model)?;
+    let tree = Tree::new(model);
+    let env = &model.environment;
+
+    let mut map = HashMap::new();
+    map.insert(env.info.id.clone(), env.info.id.clone());
+    number_externals(&env.sources, &env.info.id.indices, &mut map);
+    number_externals(&env.sinks, &env.info.id.indices, &mut map);
+    for (n, root) in tree.children_of(&env.info.id).iter().enumerate() {
+        let id = Id {
+            ty: IdType::System,
+            indices: vec![n as i64],
+        };
+        tree.number_system(root, id, &mut map);
+    }
+    if let Some(detached) = model.systems.iter().find(|s| !map.contains_key(&s.info.id)) {
+        return Err(RenumberError::Detached(detached.info.id.clone()));
+    }
+    tree.number_interactions(model.interactions.iter(), &mut map);
+
+    Ok(apply(model, map))
+}
+
+/// Move the subsystem `subtree` and everything beneath it under `new_parent`.
+///
+/// The subtree takes the next free index under its new parent and is numbered
+/// densely within; ids outside it keep their addresses. A moved interface
+/// subsystem is detached from its interface, which stays with the old parent.
+pub fn reparent(
+    model: &WorldModel,
+    subtree: &Id,
+    new_parent: &Id,
+) -> Result<Renumbered, RenumberError> {
+    check_references(model)?;
+    let tree = Tree::new(model);
+    let find = |id: &Id| {
+        model
+            .systems
+            .iter()
+            .find(|s| &s.info.id == id)
+            .ok_or_else(|| RenumberError::UnknownSystem(id.clone()))
+    };
+    let root = find(subtree)?;
+    let target = find(new_parent)?;
+    if root.parent == model.environment.info.id {
+        return Err(RenumberError::NotASubsystem(subtree.clone()));
+    }
+
+    let members = tree.subtree(root);
+    if members.contains(new_parent) {
+        return Err(RenumberError::IntoOwnSubtree {
+            subtree: subtree.clone(),
+            new_parent: new_parent.clone(),
+        });
+    }
+    if &root.parent == new_parent {
+        return Ok(apply(model, identity(model)));
+    }
+
+    let mut map = identity(model);
+    let new_id = Id {
+        ty: root.info.id.ty,
+        indices: tree.free_slot(target),
+    };
+    tree.number_system(root, new_id, &mut map);
+    tree.number_interactions(
+        model
+            .interactions
+            .iter()
+            .filter(|ix| members.contains(tree.container(ix))),
+        &mut map,
+    );
+
+    // Re-link before rewriting: old ids still name the elements here.
+    let owned_interfaces: HashSet<&Id> = model
+        .systems
+        .iter()
+        .filter(|s| members.contains(&s.info.id))
+        .flat_map(|s| s.boundary.interfaces.iter().map(|i| &i.info.id))
+        .collect();
+    let mut moved = model.clone();
+    for system in &mut moved.systems {
+        if !members.contains(&system.info.id) {
+            continue;
+        }
+        if &system.info.id == subtree {
+            system.parent = new_parent.clone();
+        }
+        if system
+            .boundary
+            .parent_interface
+            .as_ref()
+            .is_some_and(|i| !owned_interfaces.contains(i))
+        {
+            system.boundary.parent_interface = None;
+        }
+    }
+
+    Ok(apply(&moved, map))
+}
+
+/// `root` and the ids of every system beneath it.
+pub(crate) fn subtree_ids(model: &WorldModel, root: &System) -> HashSet<Id> {
+    Tree::new(model)
+        .subtree(root)
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+/// The address a new subsystem of `parent` takes, or `None` if `parent` is not a system.
+pub(crate) fn free_slot(model: &WorldModel, parent: &Id) -> Option<Vec<i64>> {
+    let system = model.systems.iter().find(|s| &s.info.id == parent)?;
+    Some(Tree::new(model).free_slot(system))
+}
+
+/// A fresh id for a new interaction between `source` and `sink`, numbered
+/// after the interactions already in their nearest common owner.
+pub(crate) fn next_interaction_id(model: &WorldModel, source: &Id, sink: &Id) -> Id {
+    let tree = Tree::new(model);
+    let container = &tree.common_owner(source, sink).indices;
+    let next = model
+        .interactions
+        .iter()
+        .map(|ix| &ix.info.id.indices)
+        .filter(|i| i.len() == container.len() + 1 && i.starts_with(container))
+        .map(|i| i[container.len()] + 1)
+        .max()
+        .unwrap_or(0);
+    let mut indices = container.clone();
+    indices.push(next);
+    Id {
+        ty: IdType::Flow,
+        indices,
+    }
+}
+
+/// The structural level an id implies: `-1` in the environment, otherwise
+/// its depth below the root system.
+pub(crate) fn level_of(id: &Id) -> i32 {
+    match id.indices.first() {
+        Some(i) if *i < 0 => -1,
+        _ => id.indices.len() as i32 - 1,
+    }
+}
+
+fn last_index(id: &Id) -> i64 {
+    id.indices.last().copied().unwrap_or(0)
+}
+
+fn child_id(ty: IdType, parent: &[i64], n: usize) -> Id {
+    let mut indices = parent.to_vec();
+    indices.push(n as i64);
+    Id { ty, indices }
+}
+
+/// Sources and sinks are numbered in their list, sorted by current id.
+fn number_externals(entities: &[ExternalEntity], owner: &[i64], map: &mut HashMap<Id, Id>) {
+    let mut sorted: Vec<&Id> = entities.iter().map(|e| &e.info.id).collect();
+    sorted.sort();
+    for (n, id) in sorted.into_iter().enumerate() {
+        map.insert(id.clone(), child_id(id.ty, owner, n));
+    }
+}
+
+fn identity(model: &WorldModel) -> HashMap<Id, Id> {
+    element_ids(model)
+        .into_iter()
+        .map(|(id, _)| (id.clone(), id.clone()))
+        .collect()
+}
+
+/// Every element id with its location, in document order.
+pub(crate) fn element_ids(model: &WorldModel) -> Vec<(&Id, String)> {
+    let env = &model.environment;
+    let mut ids = vec![(&env.info.id, "environment.info.id".to_string())];
+    for (i, e) in env.sources.iter().enumerate() {
+        ids.push((&e.info.id, format!("environment.sources[{i}].info.id")));
+    }
+    for (i, e) in env.sinks.iter().enumerate() {
+        ids.push((&e.info.id, format!("environment.sinks[{i}].info.id")));
+    }
+    for (i, system) in model.systems.iter().enumerate() {
+        ids.push((&system.info.id, format!("systems[{i}].info.id")));
+        ids.push((
+            &system.boundary.info.id,
+            format!("systems[{i}].boundary.info.id"),
+        ));
+        for (j, e) in system.sources.iter().enumerate() {
+            ids.push((&e.info.id, format!("systems[{i}].sources[{j}].info.id")));
+        }
+        for (j, e) in system.sinks.iter().enumerate() {
+            ids.push((&e.info.id, format!("systems[{i}].sinks[{j}].info.id")));
+        }
+        for (j, iface) in system.boundary.interfaces.iter().enumerate() {
+            ids.push((
+                &iface.info.id,
+                format!("systems[{i}].boundary.interfaces[{j}].info.id"),
+            ));
+        }
+    }
+    for (i, ix) in model.interactions.iter().enumerate() {
+        ids.push((&ix.info.id, format!("interactions[{i}].info.id")));
+    }
+    ids
+}
+
+/// Renumbering is only well-defined when every id names one element and every
+/// structural reference resolves. `exports_to`, `receives_from` and
+/// `hidden_entities` are caches and view state; stale entries there are dropped
+/// by [`apply`] instead.
+fn check_references(model: &WorldModel) -> Result<(), RenumberError> {
+    let mut known = HashSet::new();
+    for (id, _) in element_ids(model) {
+        if !known.insert(id) {
+            return Err(RenumberError::DuplicateId(id.clone()));
+        }
+    }
+
+    let mut refs: Vec<(&Id, String)> = Vec::new();
+    for (i, system) in model.systems.iter().enumerate() {
+        refs.push((&system.parent, format!("systems[{i}].parent")));
+        if let Some(id) = &system.boundary.parent_interface {
+            refs.push((id, format!("systems[{i}].boundary.parent_interface")));
+        }
+        for (j, iface) in system.boundary.interfaces.iter().enumerate() {
+            if let Some(id) = &iface.paired_with {
+                refs.push((
+                    id,
+                    format!("systems[{i}].boundary.interfaces[{j}].paired_with"),
+                ));
+            }
+        }
+    }
+    for (i, ix) in model.interactions.iter().enumerate() {
+        refs.push((&ix.source, format!("interactions[{i}].source")));
+        refs.push((&ix.sink, format!("interactions[{i}].sink")));
+        if let Some(id) = &ix.source_interface {
+            refs.push((id, format!("interactions[{i}].source_interface")));
+        }
+        if let Some(id) = &ix.sink_interface {
+            refs.push((id, format!("interactions[{i}].sink_interface")));
+        }
+    }
+    match refs.into_iter().find(|(id, _)| !known.contains(id)) {
+        Some((id, location)) => Err(RenumberError::Dangling {
+            location,
+            id: id.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The system hierarchy as the `parent` links describe it.
+struct Tree<'a> {
+    model: &'a WorldModel,
+    /// Subsystems of each system (and root systems of the environment), by id.
+    children: HashMap<&'a Id, Vec<&'a System>>,
+    /// Systems and the environment by their indices, to resolve interaction prefixes.
+    by_indices: HashMap<&'a [i64], &'a Id>,
+    /// The containing system (or environment) of every system, external entity and interface.
+    owner: HashMap<&'a Id, &'a Id>,
+}
+
+impl<'a> Tree<'a> {
+    fn new(model: &'a WorldModel) -> Self {
+        let env = &model.environment;
+        let mut children: HashMap<&Id, Vec<&System>> = HashMap::new();
+        let mut by_indices = HashMap::new();
+        let mut owner = HashMap::new();
+
+        by_indices.insert(env.info.id.indices.as_slice(), &env.info.id);
+        for e in env.sources.iter().chain(&env.sinks) {
+            owner.insert(&e.info.id, &env.info.id);
+        }
+        for system in &model.systems {
+            children.entry(&system.parent).or_default().push(system);
+            by_indices.insert(system.info.id.indices.as_slice(), &system.info.id);
+            owner.insert(&system.info.id, &system.parent);
+            for e in system.sources.iter().chain(&system.sinks) {
+                owner.insert(&e.info.id, &system.info.id);
+            }
+            for iface in &system.boundary.interfaces {
+                owner.insert(&iface.info.id, &system.info.id);
+            }
+        }
+        for list in children.values_mut() {
+            list.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+        }
+
+        Self {
+            model,
+            children,
+            by_indices,
+            owner,
+        }
+    }
+
+    fn children_of(&self, id: &Id) -> &[&'a System] {
+        self.children.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// `root` and every system beneath it.
+    fn subtree(&self, root: &'a System) -> HashSet<&'a Id> {
+        let mut members = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(system) = stack.pop() {
+            if members.insert(&system.info.id) {
+                stack.extend(self.children_of(&system.info.id));
+            }
+        }
+        members
+    }
+
+    /// The indices of the next free child slot under `parent`: past every
+    /// subsystem and interface it already has.
+    fn free_slot(&self, parent: &System) -> Vec<i64> {
+        let slot = self
+            .children_of(&parent.info.id)
+            .iter()
+            .map(|s| last_index(&s.info.id))
+            .chain(
+                parent
+                    .boundary
+                    .interfaces
+                    .iter()
+                    .map(|i| last_index(&i.info.id)),
+            )
+            .max()
+            .map_or(0, |max| max + 1);
+        let mut indices = parent.info.id.indices.clone();
+        indices.push(slot);
+        indices
+    }
+
+    /// Give `system` the id `new_id`, then number its boundary, external
+    /// entities, interfaces and subsystems beneath it.
+    fn number_system(&self, system: &System, new_id: Id, map: &mut HashMap<Id, Id>) {
+        let indices = new_id.indices.clone();
+        map.insert(
+            system.boundary.info.id.clone(),
+            Id {
+                ty: IdType::Boundary,
+                indices: indices.clone(),
+            },
+        );
+        number_externals(&system.sources, &indices, map);
+        number_externals(&system.sinks, &indices, map);
+
+        enum Slot<'s> {
+            Subsystem(&'s System),
+            Interface(&'s Interface, Option<&'s System>),
+        }
+        let children = self.children_of(&system.info.id);
+        let mut claimed = HashSet::new();
+        let mut slots = Vec::new();
+        for iface in &system.boundary.interfaces {
+            let processor = children.iter().copied().find(|c| {
+                c.boundary.parent_interface.as_ref() == Some(&iface.info.id)
+                    && !claimed.contains(&c.info.id)
+            });
+            if let Some(p) = processor {
+                claimed.insert(&p.info.id);
+            }
+            slots.push((&iface.info.id, 1, Slot::Interface(iface, processor)));
+        }
+        for child in children.iter().filter(|c| !claimed.contains(&c.info.id)) {
+            slots.push((&child.info.id, 0, Slot::Subsystem(child)));
+        }
+        slots.sort_by(|(a, rank_a, _), (b, rank_b, _)| {
+            (last_index(a), rank_a, a).cmp(&(last_index(b), rank_b, b))
+        });
+
+        for (n, (_, _, slot)) in slots.into_iter().enumerate() {
+            match slot {
+                Slot::Interface(iface, processor) => {
+                    map.insert(
+                        iface.info.id.clone(),
+                        child_id(IdType::Interface, &indices, n),
+                    );
+                    if let Some(p) = processor {
+                        self.number_system(p, child_id(p.info.id.ty, &indices, n), map);
+                    }
+                }
+                Slot::Subsystem(child) => {
+                    self.number_system(child, child_id(child.info.id.ty, &indices, n), map);
+                }
+            }
+        }
+
+        map.insert(system.info.id.clone(), new_id);
+    }
+
+    /// The system (or environment) an interaction belongs to: the one its id
+    /// prefix names, falling back to the nearest common owner of its endpoints.
+    fn container(&self, ix: &Interaction) -> &'a Id {
+        let indices = &ix.info.id.indices;
+        let prefix = &indices[..indices.len().saturating_sub(1)];
+        match self.by_indices.get(prefix) {
+            Some(id) => id,
+            None => self.common_owner(&ix.source, &ix.sink),
+        }
+    }
+
+    /// The nearest system (or the environment) that owns both `a` and `b`.
+    fn common_owner(&self, a: &Id, b: &Id) -> &'a Id {
+        let to = self.owners(b);
+        self.owners(a)
+            .into_iter()
+            .find(|o| to.contains(o))
+            .unwrap_or(&self.model.environment.info.id)
+    }
+
+    /// The chain of owners above `id`, nearest first.
+    fn owners(&self, id: &Id) -> Vec<&'a Id> {
+        let mut chain = Vec::new();
+        let mut current = id;
+        while let Some(&owner) = self.owner.get(current) {
+            if chain.contains(&owner) {
+                break;
+            }
+            chain.push(owner);
+            current = owner;
+        }
+        chain
+    }
+
+    /// Number `interactions` densely under the new address of their container.
+    /// Every container must already be in `map`.
+    fn number_interactions(
+        &self,
+        interactions: impl Iterator<Item = &'a Interaction>,
+        map: &mut HashMap<Id, Id>,
+    ) {
+        let mut by_container: BTreeMap<Vec<i64>, Vec<&Id>> = BTreeMap::new();
+        for ix in interactions {
+            let container = &map[self.container(ix)];
+            by_container
+                .entry(container.indices.clone())
+                .or_default()
+                .push(&ix.info.id);
+        }
+        for (container, mut ids) in by_container {
+            ids.sort();
+            for (n, id) in ids.into_iter().enumerate() {
+                map.insert(id.clone(), child_id(IdType::Flow, &container, n));
+            }
+        }
+    }
+}
+
+/// Rewrite every id and reference in `model` through `map` and recompute levels.
+fn apply(model: &WorldModel, map: HashMap<Id, Id>) -> Renumbered {
+    let mut m = model.clone();
+    let re = |id: &mut Id| {
+        if let Some(new) = map.get(id) {
+            *id = new.clone();
+        }
+    };
+    let re_info = |info: &mut Info| {
+        re(&mut info.id);
+        info.level = level_of(&info.id);
+    };
+    let re_list = |ids: &mut Vec<Id>| {
+        ids.retain(|id| map.contains_key(id));
+        ids.iter_mut().for_each(re);
+    };
+
+    re_info(&mut m.environment.info);
+    for e in m
+        .environment
+        .sources
+        .iter_mut()
+        .chain(&mut m.environment.sinks)
+    {
+        re_info(&mut e.info);
+    }
+    for system in &mut m.systems {
+        re_info(&mut system.info);
+        re(&mut system.parent);
+        re_info(&mut system.boundary.info);
+        system.boundary.parent_interface.as_mut().map(re);
+        for e in system.sources.iter_mut().chain(&mut system.sinks) {
+            re_info(&mut e.info);
+        }
+        for iface in &mut system.boundary.interfaces {
+            re_info(&mut iface.info);
+            re_list(&mut iface.exports_to);
+            re_list(&mut iface.receives_from);
+            iface.paired_with.as_mut().map(re);
+        }
+    }
+    for ix in &mut m.interactions {
+        re_info(&mut ix.info);
+        re(&mut ix.source);
+        re(&mut ix.sink);
+        ix.source_interface.as_mut().map(re);
+        ix.sink_interface.as_mut().map(re);
+    }
+    re_list(&mut m.hidden_entities);
+
+    let renamed = map.into_iter().filter(|(old, new)| old != new).collect();
+    Renumbered { model: m, renamed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{example, examples, id};
+    use crate::validate::{validate, Severity};
+
+    fn errors(model: &WorldModel) -> Vec<String> {
+        validate(model)
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| format!("{}: {}", i.location, i.message))
+            .collect()
+    }
+
+    fn system<'m>(model: &'m WorldModel, s: &str) -> &'m System {
+        model.systems.iter().find(|x| x.info.id == id(s)).unwrap()
+    }
+
+    /// Drop `root`, its descendants and every interaction touching them.
+    fn delete_subtree(model: &mut WorldModel, root: &str) {
+        let gone = subtree_ids(model, system(model, root));
+        model.systems.retain(|s| !gone.contains(&s.info.id));
+        model
+            .interactions
+            .retain(|ix| !gone.contains(&ix.source) && !gone.contains(&ix.sink));
+    }
+
+    #[test]
+    fn compacting_every_example_keeps_it_valid_and_is_idempotent() {
+        for (name, model) in examples() {
+            let compacted = compact(&model).unwrap();
+            assert_eq!(errors(&compacted.model), Vec::<String>::new(), "{name}");
+
+            // The kernel is the same graph under the new names.
+            let rename = |i: &Id| compacted.renamed.get(i).unwrap_or(i).clone();
+            let expected: Vec<_> = model
+                .kernel()
+                .dep
+                .iter()
+                .map(|(a, b)| (rename(a), rename(b)))
+                .collect();
+            assert_eq!(compacted.model.kernel().dep, expected, "{name}");
+
+            assert!(compact(&compacted.model).unwrap().is_noop(), "{name}");
+        }
+    }
+
+    #[test]
+    fn interfaces_and_subsystems_share_one_dense_index_space() {
+        let model = example("bitcoin");
+        let r = compact(&model).unwrap();
+        // S0 children by old index: I0.0 C0.1 C0.2 I0.3 C0.4 C0.5 I0.51 I0.52 I0.53.
+        for (old, new) in [
+            ("I0.3", "I0.3"),
+            ("I0.51", "I0.6"),
+            ("C0.51", "C0.6"),
+            ("C0.53", "C0.8"),
+            ("B0.53", "B0.8"),
+        ] {
+            assert_eq!(r.renamed.get(&id(old)).unwrap_or(&id(old)), &id(new));
+        }
+        let processor = system(&r.model, "C0.6");
+        assert_eq!(processor.boundary.parent_interface, Some(id("I0.6")));
+        assert!(r
+            .model
+            .interactions
+            .iter()
+            .any(|ix| ix.source == id("Src-1.2") && ix.sink_interface == Some(id("I0.6"))));
+    }
+
+    #[test]
+    fn compacting_closes_gaps_left_by_deletion() {
+        let mut model = example("bitcoin");
+        delete_subtree(&mut model, "C0.4");
+        let r = compact(&model).unwrap();
+        assert_eq!(errors(&r.model), Vec::<String>::new());
+
+        assert_eq!(r.renamed[&id("C0.5")], id("C0.4"));
+        assert_eq!(r.renamed[&id("C0.5.2")], id("C0.4.2"));
+        let moved = system(&r.model, "C0.4.2");
+        assert_eq!(moved.parent, id("C0.4"));
+        assert_eq!(moved.boundary.info.id, id("B0.4.2"));
+
+        // Interactions are renumbered densely too, in their old order.
+        let mut flows: Vec<_> = r
+            .model
+            .interactions
+            .iter()
+            .filter(|ix| ix.info.id.indices[0] == 0)
+            .map(|ix| ix.info.id.to_string())
+            .collect();
+        flows.sort();
+        assert_eq!(
+            flows,
+            ["F0.0", "F0.1", "F0.2", "F0.3", "F0.4", "F0.5", "F0.6"]
+        );
+    }
+
+    #[test]
+    fn reparenting_moves_the_subtree_and_rewrites_references() {
+        let model = example("bitcoin");
+        let r = reparent(&model, &id("C0.1"), &id("C0.2")).unwrap();
+        assert_eq!(errors(&r.model), Vec::<String>::new());
+
+        // C0.2 already has C0.2.0 and C0.2.1.
+        let moved = system(&r.model, "C0.2.2");
+        assert_eq!(moved.parent, id("C0.2"));
+        assert_eq!(moved.info.level, 2);
+        assert_eq!(moved.boundary.info.id, id("B0.2.2"));
+        let grandchild = system(&r.model, "C0.2.2.1");
+        assert_eq!(grandchild.parent, id("C0.2.2"));
+        assert_eq!(grandchild.info.level, 3);
+
+        // Only the subtree changed address.
+        assert!(r
+            .renamed
+            .keys()
+            .all(|old| old.indices.starts_with(&[0, 1]) && old.ty != IdType::Interface));
+        assert!(r
+            .model
+            .interactions
+            .iter()
+            .any(|ix| ix.source == id("C0.2") && ix.sink == id("C0.2.2")));
+        assert!(r
+            .model
+            .interactions
+            .iter()
+            .any(|ix| ix.source == id("C0.2.2.0") && ix.sink == id("C0.2.2.1")));
+    }
+
+    #[test]
+    fn reparenting_an_interface_subsystem_detaches_it() {
+        let model = example("bitcoin");
+        let r = reparent(&model, &id("C0.51"), &id("C0.4")).unwrap();
+        let moved = system(&r.model, "C0.4.3");
+        assert_eq!(moved.boundary.parent_interface, None);
+        assert_eq!(errors(&r.model), Vec::<String>::new());
+    }
+
+    #[test]
+    fn invalid_moves_and_dangling_references_are_rejected() {
+        let model = example("bitcoin");
+        assert_eq!(
+            reparent(&model, &id("S0"), &id("C0.1")).err(),
+            Some(RenumberError::NotASubsystem(id("S0")))
+        );
+        assert!(matches!(
+            reparent(&model, &id("C0.1"), &id("C0.1.0")),
+            Err(RenumberError::IntoOwnSubtree { .. })
+        ));
+        assert_eq!(
+            reparent(&model, &id("C0.9"), &id("S0")).err(),
+            Some(RenumberError::UnknownSystem(id("C0.9")))
+        );
+        assert!(reparent(&model, &id("C0.1"), &id("S0")).unwrap().is_noop());
+
+        let mut broken = model.clone();
+        broken.interactions[0].sink = id("Snk-1.7");
+        assert_eq!(
+            compact(&broken).err(),
+            Some(RenumberError::Dangling {
+                location: "interactions[0].sink".to_string(),
+                id: id("Snk-1.7"),
+            })
+        );
+    }
+}
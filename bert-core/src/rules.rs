@@ -0,0 +1,307 @@
+//! Stable codes for every validation rule, and per-model or per-project
+//! overrides of their severity.
+//!
+//! Each check in [`validate`](crate::validate) reports under one [`Rule`]. A
+//! code is `BERT-<layer>-<nnn>`, where the layer follows the validation layers
+//! of `docs/bert-schema-reference.md`:
+//!
+//! | Layer | Concern |
+//! |---|---|
+//! | `L1` | Shape and values of a parsed model |
+//! | `L2` | Referential integrity: ids resolve, nothing dangles |
+//! | `L3` | Required fields, checked on raw JSON before parsing |
+//! | `L4` | Processor boundary-tracing |
+//! | `L5` | Quantities: units and conservation |
+//! | `M`  | Preconditions of a [`Mode`](crate::Mode) |
+//!
+//! Codes are never reused or renumbered: a retired rule keeps its code.
+//!
+//! A [`RuleConfig`] suppresses or escalates rules, either from the model's own
+//! `validation` field or from a [`PROJECT_CONFIG_FILE`] next to it or in any
+//! parent directory. A pattern names one code (`BERT-L4-002`) or a whole layer
+//! (`BERT-L4`).
+
+use crate::validate::{Severity, ValidationIssue};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// One validation rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// Stable code, e.g. `BERT-L2-001`.
+    pub code: &'static str,
+    /// Short kebab-case name, e.g. `orphan-source`.
+    pub name: &'static str,
+    /// Severity unless a [`RuleConfig`] escalates or suppresses it.
+    pub severity: Severity,
+    pub description: &'static str,
+}
+
+macro_rules! rules {
+    ($($ident:ident = $code:literal, $name:literal, $severity:ident, $description:literal;)*) => {
+        $(
+            #[doc = $description]
+            pub const $ident: Rule = Rule {
+                code: $code,
+                name: $name,
+                severity: Severity::$severity,
+                description: $description,
+            };
+        )*
+
+        /// Every rule, in code order.
+        pub const RULES: &[Rule] = &[$($ident),*];
+    };
+}
+
+rules! {
+    PARSE_ERROR = "BERT-L1-001", "parse-error", Error,
+        "The file is not valid BERT JSON.";
+    ENVIRONMENT_ID = "BERT-L1-002", "environment-id", Warning,
+        "The environment's id is E-1.";
+    SOURCE_SINK_TYPE = "BERT-L1-003", "source-sink-type", Warning,
+        "Entities in a sources array are Sources, and in a sinks array Sinks.";
+    LEVEL_CONSISTENCY = "BERT-L1-004", "level-consistency", Warning,
+        "A system's level is the number of its id indices minus one.";
+    VERSION = "BERT-L1-005", "version", Warning,
+        "The model is at the current file version.";
+    UNKNOWN_RULE = "BERT-L1-006", "unknown-rule", Warning,
+        "Every pattern in a validation config names a known rule or layer.";
+    ORPHAN_SOURCE = "BERT-L2-001", "orphan-source", Error,
+        "Every source is the source of some interaction.";
+    ORPHAN_SINK = "BERT-L2-002", "orphan-sink", Error,
+        "Every sink is the sink of some interaction.";
+    DANGLING_ENDPOINT = "BERT-L2-003", "dangling-endpoint", Error,
+        "An interaction's source and sink resolve to known entities.";
+    DANGLING_INTERFACE = "BERT-L2-004", "dangling-interface", Error,
+        "An interaction's source and sink interfaces resolve to known interfaces.";
+    DANGLING_PARENT = "BERT-L2-005", "dangling-parent", Error,
+        "A system's parent resolves to the environment or a known system.";
+    DUPLICATE_ID = "BERT-L2-006", "duplicate-id", Error,
+        "Every entity has a unique id.";
+    ORPHAN_INTERFACE = "BERT-L2-007", "orphan-interface", Warning,
+        "Every interface routes a flow or has an interface processor.";
+    PORT_PAIR = "BERT-L2-008", "port-pair", Error,
+        "A paired interface names an interface of the opposite direction on the same boundary that names it back.";
+    PORT_PROTOCOL = "BERT-L2-009", "port-protocol", Warning,
+        "Both halves of a bidirectional port use the same protocol.";
+    DANGLING_LINK = "BERT-L2-010", "dangling-link", Error,
+        "An interface's exports_to and receives_from name known systems or external entities, and a boundary's parent_interface a known interface.";
+    MISSING_FIELD = "BERT-L3-001", "missing-field", Error,
+        "Every required field is present.";
+    NOT_AN_OBJECT = "BERT-L3-002", "not-an-object", Error,
+        "Every model element is a JSON object.";
+    IDLE_PROCESSOR = "BERT-L4-001", "idle-processor", Warning,
+        "An interface processor is the source or sink of some flow.";
+    UNPROCESSED_INTERFACE = "BERT-L4-002", "unprocessed-interface", Warning,
+        "Every interface of the system of interest has an interface processor.";
+    INTERFACE_UNITS = "BERT-L5-001", "interface-units", Warning,
+        "Flows through one interface have units of one dimension.";
+    UNBALANCED_FLOWS = "BERT-L5-002", "unbalanced-flows", Warning,
+        "A system that is not a store gives out what it takes in.";
+    ROLL_UP = "BERT-L5-003", "roll-up", Warning,
+        "Subsystem flows across a boundary add up to the system's own.";
+    NO_BOND = "BERT-M-001", "no-bond", Error,
+        "Structural: at least one interaction bonds two distinct systems.";
+    SELF_LOOP = "BERT-M-002", "self-loop", Error,
+        "Operational and Full: no interaction has the same source and sink.";
+    NO_FEEDBACK = "BERT-M-003", "no-feedback", Error,
+        "Cybernetic: some chain of interactions forms a loop.";
+    EMPTY_DYNAMICAL_FACE = "BERT-M-004", "empty-dynamical-face", Warning,
+        "Full: some system has a transformation, history or time constant.";
+    TIME_CONSTANT = "BERT-M-005", "time-constant", Warning,
+        "Full: a time constant's magnitude is positive.";
+    TRANSFORMATION_FLOW = "BERT-M-006", "transformation-flow", Warning,
+        "Full: a transformation's inputs and outputs flow into and out of its system.";
+    HISTORY_EVENT = "BERT-M-007", "history-event", Warning,
+        "Full: history events are described, dated in ISO 8601 and in order.";
+}
+
+/// The rule with the given code.
+pub fn rule(code: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.code == code)
+}
+
+/// Whether `pattern` is `code` itself or a prefix of it ending at a `-`.
+fn covers(pattern: &str, code: &str) -> bool {
+    code.strip_prefix(pattern)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+/// Name of the project-wide validation config, looked up by
+/// [`RuleConfig::discover`].
+pub const PROJECT_CONFIG_FILE: &str = "bert-rules.json";
+
+/// Per-model or per-project overrides of rule severities.
+///
+/// ```json
+/// { "suppress": ["BERT-L4"], "escalate": ["BERT-L2-007"] }
+/// ```
+///
+/// Suppression wins over escalation.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuleConfig {
+    /// Rules whose issues are dropped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppress: Vec<String>,
+    /// Rules whose warnings become errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub escalate: Vec<String>,
+}
+
+impl RuleConfig {
+    pub fn is_empty(&self) -> bool {
+        self.suppress.is_empty() && self.escalate.is_empty()
+    }
+
+    pub fn is_suppressed(&self, code: &str) -> bool {
+        self.suppress.iter().any(|p| covers(p, code))
+    }
+
+    pub fn is_escalated(&self, code: &str) -> bool {
+        self.escalate.iter().any(|p| covers(p, code))
+    }
+
+    /// Add `other`'s patterns to these, e.g. a model's own config on top of
+    /// its project's.
+    pub fn merge(&mut self, other: &RuleConfig) {
+        for (mine, theirs) in [
+            (&mut self.suppress, &other.suppress),
+            (&mut self.escalate, &other.escalate),
+        ] {
+            for pattern in theirs {
+                if !mine.contains(pattern) {
+                    mine.push(pattern.clone());
+                }
+            }
+        }
+    }
+
+    /// Drop suppressed issues and raise escalated ones to errors.
+    pub fn apply(&self, issues: &mut Vec<ValidationIssue>) {
+        issues.retain(|issue| !self.is_suppressed(issue.code));
+        for issue in issues.iter_mut() {
+            if self.is_escalated(issue.code) {
+                issue.severity = Severity::Error;
+            }
+        }
+    }
+
+    /// Patterns that cover no rule, as `(field, index, pattern)`.
+    pub fn unknown_patterns(&self) -> Vec<(&'static str, usize, &str)> {
+        [("suppress", &self.suppress), ("escalate", &self.escalate)]
+            .into_iter()
+            .flat_map(|(field, patterns)| {
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| !RULES.iter().any(|rule| covers(p, rule.code)))
+                    .map(move |(i, p)| (field, i, p.as_str()))
+            })
+            .collect()
+    }
+
+    /// Find the nearest [`PROJECT_CONFIG_FILE`] in `dir` or one of its
+    /// ancestors, and parse it.
+    pub fn discover(dir: &Path) -> Result<Option<(PathBuf, RuleConfig)>, RuleConfigError> {
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(PROJECT_CONFIG_FILE);
+            if path.is_file() {
+                let bytes = std::fs::read(&path)?;
+                let config = serde_json::from_slice(&bytes)?;
+                return Ok(Some((path, config)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+pub enum RuleConfigError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for RuleConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot read {PROJECT_CONFIG_FILE}: {e}"),
+            Self::Json(e) => write!(f, "invalid {PROJECT_CONFIG_FILE}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RuleConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for RuleConfigError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for RuleConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_well_formed() {
+        for (i, rule) in RULES.iter().enumerate() {
+            let parts: Vec<&str> = rule.code.split('-').collect();
+            assert_eq!(parts.len(), 3, "{}", rule.code);
+            assert_eq!(parts[0], "BERT");
+            assert!(
+                ["L1", "L2", "L3", "L4", "L5", "M"].contains(&parts[1]),
+                "{}",
+                rule.code
+            );
+            assert_eq!(parts[2].len(), 3, "{}", rule.code);
+            assert!(
+                RULES[..i]
+                    .iter()
+                    .all(|r| r.code < rule.code && r.name != rule.name),
+                "{} is out of order or duplicated",
+                rule.code
+            );
+        }
+        assert_eq!(rule("BERT-L2-001"), Some(&ORPHAN_SOURCE));
+        assert_eq!(rule("BERT-L2-0"), None);
+    }
+
+    #[test]
+    fn patterns_cover_codes_or_layers() {
+        let config: RuleConfig = serde_json::from_str(
+            r#"{"suppress": ["BERT-L4", "BERT-L2-001"], "escalate": ["BERT-L2-00", "BERT-X"]}"#,
+        )
+        .unwrap();
+        assert!(config.is_suppressed("BERT-L4-002"));
+        assert!(config.is_suppressed("BERT-L2-001"));
+        assert!(!config.is_suppressed("BERT-L2-002"));
+        assert!(!config.is_escalated("BERT-L2-007"), "no partial numbers");
+        assert_eq!(
+            config.unknown_patterns(),
+            vec![("escalate", 0, "BERT-L2-00"), ("escalate", 1, "BERT-X")]
+        );
+
+        let mut merged = RuleConfig {
+            suppress: vec!["BERT-L4".into()],
+            escalate: vec![],
+        };
+        merged.merge(&config);
+        assert_eq!(merged.suppress, vec!["BERT-L4", "BERT-L2-001"]);
+        assert_eq!(merged.escalate.len(), 2);
+    }
+}
@@ -0,0 +1,185 @@
+//! Tokens of the textual notation.
+
+use super::ParseError;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Tok {
+    /// A keyword or an element's identifier; which one depends on position.
+    Ident(String),
+    Str(String),
+    /// Kept as written, so every numeric type parses it exactly.
+    Num(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Comma,
+    Colon,
+    Dot,
+    Arrow,
+}
+
+impl Tok {
+    pub(super) fn describe(&self) -> String {
+        match self {
+            Tok::Ident(s) => format!("'{s}'"),
+            Tok::Str(s) => format!("string {s:?}"),
+            Tok::Num(s) => format!("number {s}"),
+            Tok::LBrace => "'{'".to_string(),
+            Tok::RBrace => "'}'".to_string(),
+            Tok::LBracket => "'['".to_string(),
+            Tok::RBracket => "']'".to_string(),
+            Tok::Comma => "','".to_string(),
+            Tok::Colon => "':'".to_string(),
+            Tok::Dot => "'.'".to_string(),
+            Tok::Arrow => "'->'".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Token {
+    pub tok: Tok,
+    pub span: Range<usize>,
+}
+
+pub(super) fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+pub(super) fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+pub(super) fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        let single = |tok| Some((tok, start + 1));
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '#' => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+                continue;
+            }
+            '{' => single(Tok::LBrace),
+            '}' => single(Tok::RBrace),
+            '[' => single(Tok::LBracket),
+            ']' => single(Tok::RBracket),
+            ',' => single(Tok::Comma),
+            ':' => single(Tok::Colon),
+            '.' => single(Tok::Dot),
+            _ => None,
+        };
+        if let Some((tok, end)) = token {
+            chars.next();
+            tokens.push(Token {
+                tok,
+                span: start..end,
+            });
+            continue;
+        }
+
+        let (tok, end) = if source[start..].starts_with("->") {
+            chars.next();
+            chars.next();
+            (Tok::Arrow, start + 2)
+        } else if c == '"' {
+            lex_string(source, start, &mut chars)?
+        } else if c.is_ascii_digit() || c == '-' {
+            lex_number(source, start, &mut chars)?
+        } else if is_ident_start(c) {
+            let mut end = start;
+            while let Some((i, c)) = chars.next_if(|&(_, c)| is_ident_char(c)) {
+                end = i + c.len_utf8();
+            }
+            (Tok::Ident(source[start..end].to_string()), end)
+        } else {
+            return Err(ParseError::new(
+                source,
+                start..start + c.len_utf8(),
+                format!("unexpected character {c:?}"),
+            ));
+        };
+        tokens.push(Token {
+            tok,
+            span: start..end,
+        });
+    }
+    Ok(tokens)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::CharIndices<'a>>;
+
+fn lex_string(
+    source: &str,
+    start: usize,
+    chars: &mut Chars<'_>,
+) -> Result<(Tok, usize), ParseError> {
+    chars.next();
+    let mut value = String::new();
+    loop {
+        let Some((i, c)) = chars.next() else {
+            return Err(ParseError::new(
+                source,
+                start..source.len(),
+                "unterminated string",
+            ));
+        };
+        match c {
+            '"' => return Ok((Tok::Str(value), i + 1)),
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, '"')) => '"',
+                    Some((_, '\\')) => '\\',
+                    Some((_, 'n')) => '\n',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'r')) => '\r',
+                    Some((j, other)) => {
+                        return Err(ParseError::new(
+                            source,
+                            i..j + other.len_utf8(),
+                            format!("unknown escape '\\{other}'"),
+                        ))
+                    }
+                    None => continue,
+                };
+                value.push(escaped);
+            }
+            c => value.push(c),
+        }
+    }
+}
+
+fn lex_number(
+    source: &str,
+    start: usize,
+    chars: &mut Chars<'_>,
+) -> Result<(Tok, usize), ParseError> {
+    let mut end = start;
+    while let Some((i, c)) =
+        chars.next_if(|&(i, c)| c.is_ascii_alphanumeric() || c == '.' || (c == '-' && i == start))
+    {
+        end = i + c.len_utf8();
+    }
+    let text = &source[start..end];
+    let unsigned = text.strip_prefix('-').unwrap_or(text);
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let valid = match unsigned.split_once('.') {
+        Some((whole, frac)) => digits(whole) && digits(frac),
+        None => digits(unsigned),
+    };
+    if !valid {
+        return Err(ParseError::new(
+            source,
+            start..end,
+            format!("'{text}' is not a number"),
+        ));
+    }
+    Ok((Tok::Num(text.to_string()), end))
+}
@@ -0,0 +1,293 @@
+//! A textual notation for [`WorldModel`]s: [`parse`] reads it, [`print`]
+//! writes it.
+//!
+//! The notation covers the 8-tuple without the positional ids of the JSON
+//! form. Every element gets an identifier of its own choosing, and references
+//! (flow endpoints, `on`, `paired`) go through those identifiers; the parser
+//! generates the ids, levels, `exports_to`/`receives_from` and a simple layout.
+//!
+//! ```text
+//! environment "Town" {
+//!     description "Where the bakery sells its bread"
+//!     mode Operational
+//!
+//!     source mill "Mill"
+//!     sink customers "Customers"
+//!
+//!     system bakery "Bakery" {
+//!         complexity complex adaptable
+//!         time_constant "1 Day"
+//!         boundary "Shop walls" { porosity 0.2 }
+//!
+//!         import flour_door "Loading door" { protocol "Pallet" }
+//!         export counter "Counter"
+//!
+//!         system oven "Oven" {
+//!             complexity atomic
+//!         }
+//!         system intake "Intake" on flour_door
+//!     }
+//!
+//!     flow flour "Flour": mill -> flour_door {
+//!         substance Material "Wheat flour"
+//!         usability Resource
+//!         amount 200 "kg/day"
+//!     }
+//!     flow bread "Bread": counter -> customers {
+//!         substance Material
+//!         usability Product
+//!         amount 300 "loaves/day"
+//!     }
+//! }
+//! ```
+//!
+//! - Declarations: `source`/`sink` (environment or system level), `system`
+//!   (exactly one in the environment, any number nested), `import`/`export`/
+//!   `hybrid` interfaces, and `flow`/`force` interactions in the environment.
+//!   Each is a keyword, an identifier, a name and an optional `{ ... }` body of
+//!   properties, one per line in any order.
+//! - `system x "X" on i` makes `x` the interface subsystem of interface `i`.
+//! - A flow endpoint is an identifier: a system, a source or sink, or an
+//!   interface, which stands for its system entered through it. `s.i` spells
+//!   out both.
+//! - System bodies also take `transformation { input Energy "sub" output ... }`,
+//!   `history { event "2009-01-03" "Genesis" }` and an `agent Kind { ... }`.
+//! - `#` starts a comment.
+//!
+//! Layout (positions, radii, interface angles, flow endpoint offsets) and view
+//! state (`hidden_entities`) are not part of the notation. The printer drops
+//! them and the parser lays elements out afresh, so a model survives the round
+//! trip up to renumbering and layout: `print(parse(print(m)))` equals
+//! `print(m)`.
+
+mod lex;
+mod parse;
+mod print;
+
+use crate::WorldModel;
+use std::fmt;
+use std::ops::Range;
+
+/// Read a model written in the notation.
+pub fn parse(source: &str) -> Result<WorldModel, ParseError> {
+    parse::parse(source)
+}
+
+/// Write `model` in the notation. Any model prints; references that resolve
+/// to nothing are written as their raw id in quotes, which [`parse`] rejects.
+pub fn print(model: &WorldModel) -> String {
+    print::print(model)
+}
+
+/// Where and why [`parse`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Byte range of the offending text.
+    pub span: Range<usize>,
+    /// 1-based line of the start of `span`.
+    pub line: usize,
+    /// 1-based column of the start of `span`, in characters.
+    pub column: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(source: &str, span: Range<usize>, message: impl Into<String>) -> Self {
+        let before = &source[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        Self {
+            message: message.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            span,
+        }
+    }
+
+    /// The error with the offending line of `source` and a caret under the span.
+    pub fn render(&self, source: &str) -> String {
+        let line_start = source[..self.span.start].rfind('\n').map_or(0, |n| n + 1);
+        let line_end = source[self.span.start..]
+            .find('\n')
+            .map_or(source.len(), |n| self.span.start + n);
+        let text = &source[line_start..line_end];
+        let width = source[self.span.start..self.span.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = self.line.to_string().len();
+        format!(
+            "{self}\n{:gutter$} |\n{} | {text}\n{:gutter$} | {}{}",
+            "",
+            self.line,
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{examples, id};
+    use crate::validate::{validate, Severity};
+
+    fn errors(model: &WorldModel) -> Vec<String> {
+        validate(model)
+            .issues
+            .into_iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| format!("{}: {}", i.code, i.message))
+            .collect()
+    }
+
+    /// The example from the module docs.
+    const TOWN: &str = r#"
+environment "Town" {
+    mode Operational
+    source mill "Mill"
+    sink customers "Customers"
+
+    system bakery "Bakery" {
+        boundary "Shop walls" { porosity 0.2 }
+        import flour_door "Loading door" { protocol "Pallet" }
+        export counter "Counter"
+        system oven "Oven" { complexity atomic }
+        system intake "Intake" on flour_door
+    }
+
+    flow flour "Flour": mill -> flour_door {
+        substance Material "Wheat flour"
+        usability Resource
+        amount 200 "kg/day"
+    }
+    flow bread "Bread": bakery.counter -> customers {
+        substance Material
+        usability Product
+    }
+}
+"#;
+
+    fn parse_err(source: &str) -> ParseError {
+        match parse(source) {
+            Ok(_) => panic!("parsed:\n{source}"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn ids_are_generated_from_the_declarations() {
+        let model = parse(TOWN).unwrap();
+        assert_eq!(errors(&model), Vec::<String>::new());
+
+        let ids: Vec<_> = model
+            .systems
+            .iter()
+            .map(|s| s.info.id.to_string())
+            .collect();
+        assert_eq!(ids, ["S0", "C0.2", "C0.0"]);
+        let bakery = &model.systems[0];
+        let interfaces: Vec<_> = bakery
+            .boundary
+            .interfaces
+            .iter()
+            .map(|i| i.info.id.to_string())
+            .collect();
+        assert_eq!(interfaces, ["I0.0", "I0.1"]);
+        assert_eq!(model.systems[2].boundary.parent_interface, Some(id("I0.0")));
+        assert_eq!(model.systems[2].info.level, 1);
+        assert_eq!(bakery.boundary.porosity, 0.2);
+
+        let flour = &model.interactions[0];
+        assert_eq!(flour.info.id, id("F-1.0"));
+        assert_eq!(
+            (&flour.source, &flour.sink, &flour.sink_interface),
+            (&id("Src-1.0"), &id("S0"), &Some(id("I0.0")))
+        );
+        assert_eq!(flour.amount.to_string(), "200");
+        assert_eq!(flour.unit, "kg/day");
+        assert_eq!(model.interactions[1].info.id, id("F-1.1"));
+        assert_eq!(bakery.boundary.interfaces[0].receives_from, [id("Src-1.0")]);
+        assert_eq!(bakery.boundary.interfaces[1].exports_to, [id("Snk-1.0")]);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let e = parse_err(&TOWN.replace("-> customers", "-> customer"));
+        assert_eq!(e.message, "'customer' is not declared");
+        assert_eq!((e.line, e.column), (20, 43));
+        assert_eq!(
+            &TOWN.replace("-> customers", "-> customer")[e.span],
+            "customer"
+        );
+
+        let e = parse_err(&TOWN.replace("system oven", "system counter"));
+        assert_eq!(e.message, "'counter' is already declared");
+        let e = parse_err(&TOWN.replace("on flour_door", "on mill"));
+        assert_eq!(e.message, "'mill' is not an interface of 'bakery'");
+        let e = parse_err(&TOWN.replace("usability Product", ""));
+        assert_eq!(e.message, "'bread' has no usability");
+        let e = parse_err(&TOWN.replace("porosity 0.2", "porosity 0.2.1"));
+        assert_eq!(e.message, "'0.2.1' is not a number");
+        let e = parse_err(&TOWN.replace("mode Operational", "mode Loose"));
+        assert_eq!(e.message, "'Loose' is not a mode");
+        let e = parse_err(&TOWN.replace("{ complexity atomic }", "{ colour red }"));
+        assert_eq!(e.message, "'colour' is not a property of a system");
+
+        let source = "environment \"E\" {\n    system s \"S\" {\n";
+        let e = parse_err(source);
+        assert_eq!(
+            e.to_string(),
+            "3:1: expected a property or '}', found the end of the input"
+        );
+
+        let source = "environment \"E\" {\n  system s \"S\" { complexity multiset x }\n}";
+        let e = parse_err(source);
+        assert_eq!(
+            e.render(source),
+            "2:38: expected a count, found 'x'\n  |\n2 |   system s \"S\" { complexity multiset x }\n  |                                      ^"
+        );
+    }
+
+    #[test]
+    fn examples_round_trip() {
+        for (name, model) in examples() {
+            let text = print(&model);
+            let parsed = parse(&text).unwrap_or_else(|e| panic!("{name}: {}", e.render(&text)));
+            assert_eq!(print(&parsed), text, "{name}");
+            assert_eq!(errors(&parsed), Vec::<String>::new(), "{name}");
+
+            let interfaces = |m: &WorldModel| {
+                m.systems
+                    .iter()
+                    .map(|s| s.boundary.interfaces.len())
+                    .sum::<usize>()
+            };
+            assert_eq!(parsed.systems.len(), model.systems.len(), "{name}");
+            assert_eq!(interfaces(&parsed), interfaces(&model), "{name}");
+            assert_eq!(
+                parsed.interactions.len(),
+                model.interactions.len(),
+                "{name}"
+            );
+            assert_eq!(
+                parsed.environment.sources.len(),
+                model.environment.sources.len(),
+                "{name}"
+            );
+            assert_eq!(
+                parsed.environment.sinks.len(),
+                model.environment.sinks.len(),
+                "{name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,995 @@
+//! Tokens → declarations → a [`WorldModel`].
+//!
+//! The parser reads declarations as written, references unresolved; the
+//! builder then numbers every element, resolves references through the
+//! identifiers and lays the model out.
+
+use super::lex::{tokenize, Tok, Token};
+use super::ParseError;
+use crate::extract::layout_externals;
+use crate::renumber::{level_of, next_interaction_id};
+use crate::*;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Radius of the root system, as the editor draws it.
+const ROOT_RADIUS: f32 = 300.0;
+
+pub(super) fn parse(source: &str) -> Result<WorldModel, ParseError> {
+    let tokens = tokenize(source)?;
+    let document = Parser {
+        source,
+        tokens,
+        pos: 0,
+    }
+    .document()?;
+    Builder::build(source, document)
+}
+
+/// An identifier as written.
+#[derive(Clone, Debug)]
+struct Ident {
+    name: String,
+    span: Range<usize>,
+}
+
+struct Document {
+    environment: Environment,
+    name_span: Range<usize>,
+    mode: Option<Mode>,
+    validation: RuleConfig,
+    sources: Vec<ExternalDecl>,
+    sinks: Vec<ExternalDecl>,
+    systems: Vec<SystemDecl>,
+    flows: Vec<FlowDecl>,
+}
+
+struct ExternalDecl {
+    ident: Ident,
+    entity: ExternalEntity,
+}
+
+struct InterfaceDecl {
+    ident: Ident,
+    interface: Interface,
+    paired: Option<Ident>,
+}
+
+struct SystemDecl {
+    ident: Ident,
+    system: System,
+    on: Option<Ident>,
+    interfaces: Vec<InterfaceDecl>,
+    sources: Vec<ExternalDecl>,
+    sinks: Vec<ExternalDecl>,
+    subsystems: Vec<SystemDecl>,
+}
+
+struct FlowDecl {
+    ident: Ident,
+    interaction: Interaction,
+    source: Endpoint,
+    sink: Endpoint,
+}
+
+/// `element` or `element.interface`.
+struct Endpoint {
+    element: Ident,
+    interface: Option<Ident>,
+}
+
+/// Info of an element not yet numbered.
+fn unnumbered(ty: IdType, name: String) -> Info {
+    Info {
+        id: Id {
+            ty,
+            indices: Vec::new(),
+        },
+        level: 0,
+        name,
+        description: String::new(),
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    /// Span of the next token, or an empty one at the end of the input.
+    fn span(&self) -> Range<usize> {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.source.len()..self.source.len(), |t| t.span.clone())
+    }
+
+    fn error(&self, span: Range<usize>, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.source, span, message)
+    }
+
+    fn expected(&self, what: &str) -> ParseError {
+        let found = self
+            .peek()
+            .map_or("the end of the input".to_string(), Tok::describe);
+        self.error(self.span(), format!("expected {what}, found {found}"))
+    }
+
+    fn unknown(&self, keyword: &Ident, context: &str) -> ParseError {
+        self.error(
+            keyword.span.clone(),
+            format!("'{}' is not a property of {context}", keyword.name),
+        )
+    }
+
+    fn eat(&mut self, tok: &Tok) -> bool {
+        let found = self.peek() == Some(tok);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.eat(&Tok::Ident(keyword.to_string()))
+    }
+
+    fn expect(&mut self, tok: Tok) -> Result<(), ParseError> {
+        if self.eat(&tok) {
+            Ok(())
+        } else {
+            Err(self.expected(&tok.describe()))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> Result<Ident, ParseError> {
+        match self.peek() {
+            Some(Tok::Ident(name)) => {
+                let ident = Ident {
+                    name: name.clone(),
+                    span: self.span(),
+                };
+                self.pos += 1;
+                Ok(ident)
+            }
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// A string, if one comes next.
+    fn optional_string(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Tok::Str(s)) => {
+                let s = s.clone();
+                self.pos += 1;
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    fn string(&mut self, what: &str) -> Result<String, ParseError> {
+        self.optional_string().ok_or_else(|| self.expected(what))
+    }
+
+    fn number<T: FromStr>(&mut self, what: &str) -> Result<T, ParseError> {
+        let span = self.span();
+        match self.peek() {
+            Some(Tok::Num(text)) => {
+                let value = text
+                    .parse()
+                    .map_err(|_| self.error(span, format!("'{text}' is not {what}")))?;
+                self.pos += 1;
+                Ok(value)
+            }
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// A string read through `T`'s `FromStr`.
+    fn parsed<T: FromStr>(&mut self, what: &str) -> Result<T, ParseError>
+    where
+        T::Err: fmt::Display,
+    {
+        let span = self.span();
+        let text = self.string(what)?;
+        text.parse().map_err(|e| self.error(span, format!("{e}")))
+    }
+
+    /// A unit variant of `T`, written as its serialized name.
+    fn variant<T: DeserializeOwned>(&mut self, what: &str) -> Result<T, ParseError> {
+        let ident = self.ident(what)?;
+        serde_json::from_value(serde_json::Value::String(ident.name.clone()))
+            .map_err(|_| self.error(ident.span, format!("'{}' is not {what}", ident.name)))
+    }
+
+    /// `[Variant, ...]`.
+    fn list<T: DeserializeOwned>(&mut self, what: &str) -> Result<Vec<T>, ParseError> {
+        self.expect(Tok::LBracket)?;
+        let mut items = Vec::new();
+        while !self.eat(&Tok::RBracket) {
+            items.push(self.variant(what)?);
+            if !self.eat(&Tok::Comma) {
+                self.expect(Tok::RBracket)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// A JSON value, written as a string.
+    fn json<T: DeserializeOwned>(&mut self) -> Result<T, ParseError> {
+        let span = self.span();
+        let text = self.string("a JSON string")?;
+        serde_json::from_str(&text).map_err(|e| self.error(span, format!("invalid JSON: {e}")))
+    }
+
+    /// `{ property* }` if a body comes next. `property` is called with each
+    /// property's keyword and reads the rest of it.
+    fn body(
+        &mut self,
+        mut property: impl FnMut(&mut Self, Ident) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        if !self.eat(&Tok::LBrace) {
+            return Ok(());
+        }
+        while !self.eat(&Tok::RBrace) {
+            let keyword = self.ident("a property or '}'")?;
+            property(self, keyword)?;
+        }
+        Ok(())
+    }
+
+    fn document(mut self) -> Result<Document, ParseError> {
+        if !self.eat_keyword("environment") {
+            return Err(self.expected("'environment'"));
+        }
+        let name_span = self.span();
+        let mut document = Document {
+            environment: Environment {
+                info: unnumbered(IdType::Environment, self.string("a name")?),
+                sources: Vec::new(),
+                sinks: Vec::new(),
+            },
+            name_span,
+            mode: None,
+            validation: RuleConfig::default(),
+            sources: Vec::new(),
+            sinks: Vec::new(),
+            systems: Vec::new(),
+            flows: Vec::new(),
+        };
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "description" => {
+                    document.environment.info.description = p.string("a description")?
+                }
+                "mode" => document.mode = Some(p.variant("a mode")?),
+                "validation" => p.body(|p, keyword| {
+                    let patterns = match keyword.name.as_str() {
+                        "suppress" => &mut document.validation.suppress,
+                        "escalate" => &mut document.validation.escalate,
+                        _ => return Err(p.unknown(&keyword, "'validation'")),
+                    };
+                    patterns.push(p.string("a rule code")?);
+                    Ok(())
+                })?,
+                "source" => document
+                    .sources
+                    .push(p.external(ExternalEntityType::Source)?),
+                "sink" => document.sinks.push(p.external(ExternalEntityType::Sink)?),
+                "system" => document.systems.push(p.system()?),
+                "flow" => document.flows.push(p.flow(InteractionType::Flow)?),
+                "force" => document.flows.push(p.flow(InteractionType::Force)?),
+                _ => return Err(p.unknown(&keyword, "the environment")),
+            }
+            Ok(())
+        })?;
+        if self.peek().is_some() {
+            return Err(self.expected("the end of the input"));
+        }
+        Ok(document)
+    }
+
+    fn external(&mut self, ty: ExternalEntityType) -> Result<ExternalDecl, ParseError> {
+        let (id_ty, context) = match ty {
+            ExternalEntityType::Source => (IdType::Source, "a source"),
+            ExternalEntityType::Sink => (IdType::Sink, "a sink"),
+        };
+        let ident = self.ident("an identifier")?;
+        let mut entity = ExternalEntity {
+            info: unnumbered(id_ty, self.string("a name")?),
+            ty,
+            transform: None,
+            equivalence: String::new(),
+            model: String::new(),
+            is_same_as_id: None,
+        };
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "description" => entity.info.description = p.string("a description")?,
+                "equivalence" => entity.equivalence = p.string("an equivalence")?,
+                "model" => entity.model = p.string("a model")?,
+                "same_as" => entity.is_same_as_id = Some(p.number("an index")?),
+                _ => return Err(p.unknown(&keyword, context)),
+            }
+            Ok(())
+        })?;
+        Ok(ExternalDecl { ident, entity })
+    }
+
+    fn system(&mut self) -> Result<SystemDecl, ParseError> {
+        let ident = self.ident("an identifier")?;
+        let name = self.string("a name")?;
+        let on = if self.eat_keyword("on") {
+            Some(self.ident("an interface")?)
+        } else {
+            None
+        };
+        let mut decl = SystemDecl {
+            ident,
+            system: System {
+                info: unnumbered(IdType::Subsystem, name),
+                sources: Vec::new(),
+                sinks: Vec::new(),
+                parent: Id {
+                    ty: IdType::Environment,
+                    indices: Vec::new(),
+                },
+                complexity: Complexity::default(),
+                boundary: Boundary {
+                    info: unnumbered(IdType::Boundary, String::new()),
+                    porosity: 0.0,
+                    perceptive_fuzziness: 0.0,
+                    interfaces: Vec::new(),
+                    parent_interface: None,
+                },
+                radius: 0.0,
+                transform: None,
+                equivalence: String::new(),
+                history: History::default(),
+                transformation: None,
+                member_autonomy: 1.0,
+                time_constant: None,
+                archetype: None,
+                agent: None,
+            },
+            on,
+            interfaces: Vec::new(),
+            sources: Vec::new(),
+            sinks: Vec::new(),
+            subsystems: Vec::new(),
+        };
+        self.body(|p, keyword| {
+            let system = &mut decl.system;
+            match keyword.name.as_str() {
+                "description" => system.info.description = p.string("a description")?,
+                "equivalence" => system.equivalence = p.string("an equivalence")?,
+                "complexity" => system.complexity = p.complexity()?,
+                "archetype" => system.archetype = Some(p.variant("an archetype")?),
+                "time_constant" => system.time_constant = Some(p.parsed("a time constant")?),
+                "member_autonomy" => system.member_autonomy = p.number("a number")?,
+                "boundary" => p.boundary(&mut system.boundary)?,
+                "transformation" => system.transformation = Some(p.transformation()?),
+                "history" => system.history = p.history()?,
+                "agent" => system.agent = Some(p.agent()?),
+                "import" => decl.interfaces.push(p.interface(InterfaceType::Import)?),
+                "export" => decl.interfaces.push(p.interface(InterfaceType::Export)?),
+                "hybrid" => decl.interfaces.push(p.interface(InterfaceType::Hybrid)?),
+                "source" => decl.sources.push(p.external(ExternalEntityType::Source)?),
+                "sink" => decl.sinks.push(p.external(ExternalEntityType::Sink)?),
+                "system" => decl.subsystems.push(p.system()?),
+                _ => return Err(p.unknown(&keyword, "a system")),
+            }
+            Ok(())
+        })?;
+        Ok(decl)
+    }
+
+    /// `atomic`, `multiset N` or `complex [adaptable] [evolveable]`.
+    fn complexity(&mut self) -> Result<Complexity, ParseError> {
+        let kind = self.ident("a complexity")?;
+        match kind.name.as_str() {
+            "atomic" => Ok(Complexity::Atomic),
+            "multiset" => Ok(Complexity::Multiset(self.number("a count")?)),
+            "complex" => Ok(Complexity::Complex {
+                adaptable: self.eat_keyword("adaptable"),
+                evolveable: self.eat_keyword("evolveable"),
+            }),
+            _ => Err(self.error(
+                kind.span,
+                format!(
+                    "'{}' is not a complexity; expected 'complex', 'atomic' or 'multiset'",
+                    kind.name
+                ),
+            )),
+        }
+    }
+
+    fn boundary(&mut self, boundary: &mut Boundary) -> Result<(), ParseError> {
+        boundary.info.name = self.string("a name")?;
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "description" => boundary.info.description = p.string("a description")?,
+                "porosity" => boundary.porosity = p.number("a number")?,
+                "fuzziness" => boundary.perceptive_fuzziness = p.number("a number")?,
+                _ => return Err(p.unknown(&keyword, "a boundary")),
+            }
+            Ok(())
+        })
+    }
+
+    fn interface(&mut self, ty: InterfaceType) -> Result<InterfaceDecl, ParseError> {
+        let ident = self.ident("an identifier")?;
+        let mut decl = InterfaceDecl {
+            ident,
+            interface: Interface {
+                info: unnumbered(IdType::Interface, self.string("a name")?),
+                protocol: String::new(),
+                ty,
+                exports_to: Vec::new(),
+                receives_from: Vec::new(),
+                angle: None,
+                paired_with: None,
+            },
+            paired: None,
+        };
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "description" => decl.interface.info.description = p.string("a description")?,
+                "protocol" => decl.interface.protocol = p.string("a protocol")?,
+                "paired" => decl.paired = Some(p.ident("an interface")?),
+                _ => return Err(p.unknown(&keyword, "an interface")),
+            }
+            Ok(())
+        })?;
+        Ok(decl)
+    }
+
+    /// `Type ["sub type"]`.
+    fn substance(&mut self) -> Result<Substance, ParseError> {
+        Ok(Substance {
+            ty: self.variant("a substance type")?,
+            sub_type: self.optional_string().unwrap_or_default(),
+        })
+    }
+
+    fn transformation(&mut self) -> Result<Transformation, ParseError> {
+        let mut transformation = Transformation::default();
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "input" => transformation.inputs.push(p.substance()?),
+                "output" => transformation.outputs.push(p.substance()?),
+                "description" => transformation.description = p.string("a description")?,
+                _ => return Err(p.unknown(&keyword, "a transformation")),
+            }
+            Ok(())
+        })?;
+        Ok(transformation)
+    }
+
+    /// `event ["timestamp"] "what happened"`, oldest first.
+    fn history(&mut self) -> Result<History, ParseError> {
+        let mut events = Vec::new();
+        self.body(|p, keyword| {
+            if keyword.name != "event" {
+                return Err(p.unknown(&keyword, "a history"));
+            }
+            let first = p.string("an event")?;
+            events.push(match p.optional_string() {
+                Some(event) => HistoryEvent {
+                    timestamp: first,
+                    event,
+                },
+                None => HistoryEvent {
+                    timestamp: String::new(),
+                    event: first,
+                },
+            });
+            Ok(())
+        })?;
+        Ok(History(events))
+    }
+
+    fn agent(&mut self) -> Result<AgentModel, ParseError> {
+        let mut agent = AgentModel {
+            kind: self.variant("an agent kind")?,
+            ..Default::default()
+        };
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "capacity" => agent.agency_capacity = p.number("a number")?,
+                "primitives" => agent.primitives = p.list("a process primitive")?,
+                "cognitive" => {
+                    let name = p.string("a parameter name")?;
+                    agent.cognitive_params.insert(name, p.number("a number")?);
+                }
+                "process" => {
+                    let name = p.string("a process name")?;
+                    let mut params = HashMap::new();
+                    p.body(|p, keyword| {
+                        if keyword.name != "param" {
+                            return Err(p.unknown(&keyword, "a process"));
+                        }
+                        let name = p.string("a parameter name")?;
+                        params.insert(name, p.json()?);
+                        Ok(())
+                    })?;
+                    agent
+                        .process_configs
+                        .push(ProcessAssignment { name, params });
+                }
+                "state" => {
+                    let key = p.string("a key")?;
+                    agent.initial_state.insert(key, p.json()?);
+                }
+                "network" => {
+                    let mut network = NetworkConfig {
+                        topology: p.string("a topology")?,
+                        connection_params: HashMap::new(),
+                        interaction_rules: HashMap::new(),
+                    };
+                    p.body(|p, keyword| {
+                        let entries = match keyword.name.as_str() {
+                            "connection" => &mut network.connection_params,
+                            "rule" => &mut network.interaction_rules,
+                            _ => return Err(p.unknown(&keyword, "a network")),
+                        };
+                        let key = p.string("a key")?;
+                        entries.insert(key, p.json()?);
+                        Ok(())
+                    })?;
+                    agent.network_config = Some(network);
+                }
+                _ => return Err(p.unknown(&keyword, "an agent")),
+            }
+            Ok(())
+        })?;
+        Ok(agent)
+    }
+
+    /// `ident "Name": endpoint -> endpoint { ... }`.
+    fn flow(&mut self, ty: InteractionType) -> Result<FlowDecl, ParseError> {
+        let ident = self.ident("an identifier")?;
+        let name = self.string("a name")?;
+        self.expect(Tok::Colon)?;
+        let source = self.endpoint()?;
+        self.expect(Tok::Arrow)?;
+        let sink = self.endpoint()?;
+        let unresolved = Id {
+            ty: IdType::System,
+            indices: Vec::new(),
+        };
+        let mut interaction = Interaction {
+            info: unnumbered(IdType::Flow, name),
+            substance: Substance {
+                sub_type: String::new(),
+                ty: SubstanceType::default(),
+            },
+            ty,
+            usability: InteractionUsability::Resource,
+            source: unresolved.clone(),
+            source_interface: None,
+            sink: unresolved,
+            sink_interface: None,
+            amount: Decimal::ONE,
+            unit: String::new(),
+            parameters: Vec::new(),
+            smart_parameters: Vec::new(),
+            endpoint_offset: None,
+        };
+        let mut usability = None;
+        self.body(|p, keyword| {
+            match keyword.name.as_str() {
+                "description" => interaction.info.description = p.string("a description")?,
+                "substance" => interaction.substance = p.substance()?,
+                "usability" => usability = Some(p.variant("a usability")?),
+                "amount" => {
+                    interaction.amount = p.number("a number")?;
+                    interaction.unit = p.optional_string().unwrap_or_default();
+                }
+                "param" => {
+                    let name = p.string("a parameter name")?;
+                    let value = p.string("a value")?;
+                    interaction.parameters.push(Parameter {
+                        name,
+                        value,
+                        unit: p.optional_string().unwrap_or_default(),
+                        ..Default::default()
+                    });
+                }
+                "smart_param" => {
+                    let name = p.string("a parameter name")?;
+                    interaction
+                        .smart_parameters
+                        .push(SmartParameter::new(name, p.json()?));
+                }
+                _ => return Err(p.unknown(&keyword, "a flow")),
+            }
+            Ok(())
+        })?;
+        interaction.usability = usability.ok_or_else(|| {
+            self.error(
+                ident.span.clone(),
+                format!("'{}' has no usability", ident.name),
+            )
+        })?;
+        Ok(FlowDecl {
+            ident,
+            interaction,
+            source,
+            sink,
+        })
+    }
+
+    fn endpoint(&mut self) -> Result<Endpoint, ParseError> {
+        let element = self.ident("an endpoint")?;
+        let interface = if self.eat(&Tok::Dot) {
+            Some(self.ident("an interface")?)
+        } else {
+            None
+        };
+        Ok(Endpoint { element, interface })
+    }
+}
+
+/// What an identifier names.
+struct Element {
+    id: Id,
+    /// The system an interface is on.
+    owner: Option<Id>,
+}
+
+/// Numbers the declarations and resolves their references.
+struct Builder<'a> {
+    source: &'a str,
+    model: WorldModel,
+    elements: HashMap<String, Element>,
+}
+
+fn child(ty: IdType, parent: &Id, n: usize) -> Id {
+    let mut indices = parent.indices.clone();
+    indices.push(n as i64);
+    Id { ty, indices }
+}
+
+/// Offset of the `k`-th of `n` items spread `step` apart around a centre.
+fn spread(k: usize, n: usize, step: f32) -> f32 {
+    (k as f32 - (n as f32 - 1.0) / 2.0) * step
+}
+
+impl<'a> Builder<'a> {
+    fn build(source: &'a str, document: Document) -> Result<WorldModel, ParseError> {
+        let Document {
+            mut environment,
+            name_span,
+            mode,
+            validation,
+            sources,
+            sinks,
+            systems,
+            flows,
+        } = document;
+        let env_id = Id {
+            ty: IdType::Environment,
+            indices: vec![-1],
+        };
+        environment.info.id = env_id.clone();
+        environment.info.level = -1;
+        let mut builder = Builder {
+            source,
+            model: WorldModel {
+                version: CURRENT_FILE_VERSION,
+                mode,
+                environment,
+                systems: Vec::new(),
+                interactions: Vec::new(),
+                hidden_entities: Vec::new(),
+                validation,
+            },
+            elements: HashMap::new(),
+        };
+
+        builder.model.environment.sources = builder.externals(sources, &env_id, ROOT_RADIUS)?;
+        builder.model.environment.sinks = builder.externals(sinks, &env_id, ROOT_RADIUS)?;
+
+        let mut systems = systems.into_iter();
+        let root = systems
+            .next()
+            .ok_or_else(|| builder.error(name_span, "the environment has no system"))?;
+        if let Some(extra) = systems.next() {
+            return Err(builder.error(
+                extra.ident.span,
+                "the environment holds one system; nest the others inside it",
+            ));
+        }
+        let root_id = Id {
+            ty: IdType::System,
+            indices: vec![0],
+        };
+        builder.system(
+            root,
+            root_id,
+            env_id,
+            ROOT_RADIUS,
+            Transform2d::default(),
+            None,
+        )?;
+
+        for flow in flows {
+            let (source, source_interface) = builder.endpoint(&flow.source)?;
+            let (sink, sink_interface) = builder.endpoint(&flow.sink)?;
+            let id = next_interaction_id(&builder.model, &source, &sink);
+            builder.declare(&flow.ident, &id, None)?;
+            let mut interaction = flow.interaction;
+            interaction.info.level = level_of(&id);
+            interaction.info.id = id;
+            interaction.source = source;
+            interaction.source_interface = source_interface;
+            interaction.sink = sink;
+            interaction.sink_interface = sink_interface;
+            builder.model.interactions.push(interaction);
+        }
+        builder.link_interfaces();
+        Ok(builder.model)
+    }
+
+    fn error(&self, span: Range<usize>, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.source, span, message)
+    }
+
+    fn declare(&mut self, ident: &Ident, id: &Id, owner: Option<&Id>) -> Result<(), ParseError> {
+        if self.elements.contains_key(&ident.name) {
+            return Err(self.error(
+                ident.span.clone(),
+                format!("'{}' is already declared", ident.name),
+            ));
+        }
+        self.elements.insert(
+            ident.name.clone(),
+            Element {
+                id: id.clone(),
+                owner: owner.cloned(),
+            },
+        );
+        Ok(())
+    }
+
+    fn resolve(&self, ident: &Ident) -> Result<&Element, ParseError> {
+        self.elements.get(&ident.name).ok_or_else(|| {
+            self.error(
+                ident.span.clone(),
+                format!("'{}' is not declared", ident.name),
+            )
+        })
+    }
+
+    /// The interface `ident` names, which must be on the boundary of `system`.
+    fn interface_of(&self, ident: &Ident, system: &Ident, id: &Id) -> Result<Id, ParseError> {
+        let element = self.resolve(ident)?;
+        if element.owner.as_ref() != Some(id) {
+            return Err(self.error(
+                ident.span.clone(),
+                format!("'{}' is not an interface of '{}'", ident.name, system.name),
+            ));
+        }
+        Ok(element.id.clone())
+    }
+
+    fn externals(
+        &mut self,
+        decls: Vec<ExternalDecl>,
+        owner: &Id,
+        radius: f32,
+    ) -> Result<Vec<ExternalEntity>, ParseError> {
+        let mut externals = Vec::new();
+        for (k, ExternalDecl { ident, mut entity }) in decls.into_iter().enumerate() {
+            let id = child(entity.info.id.ty, owner, k);
+            self.declare(&ident, &id, None)?;
+            entity.info.level = level_of(&id);
+            entity.info.id = id;
+            externals.push(entity);
+        }
+        let side = match externals.first().map(|e| e.ty) {
+            Some(ExternalEntityType::Source) => -1.0,
+            _ => 1.0,
+        };
+        layout_externals(&mut externals, side, radius);
+        Ok(externals)
+    }
+
+    /// Number `decl` as `id` and its contents beneath it, then its subsystems.
+    ///
+    /// Interfaces take the first indices of the system; plain subsystems the
+    /// ones after, and an interface subsystem the index of its interface.
+    fn system(
+        &mut self,
+        decl: SystemDecl,
+        id: Id,
+        parent: Id,
+        radius: f32,
+        transform: Transform2d,
+        parent_interface: Option<Id>,
+    ) -> Result<(), ParseError> {
+        let SystemDecl {
+            ident,
+            mut system,
+            on: _,
+            interfaces,
+            sources,
+            sinks,
+            subsystems,
+        } = decl;
+        self.declare(&ident, &id, None)?;
+        let level = level_of(&id);
+        system.info.id = id.clone();
+        system.info.level = level;
+        system.parent = parent;
+        system.radius = radius;
+        system.transform = Some(transform);
+        system.boundary.info.id = Id {
+            ty: IdType::Boundary,
+            indices: id.indices.clone(),
+        };
+        system.boundary.info.level = level;
+        system.boundary.parent_interface = parent_interface;
+
+        // Imports face left, exports right, hybrids up.
+        let side = |ty: InterfaceType| match ty {
+            InterfaceType::Import => 0,
+            InterfaceType::Export => 1,
+            InterfaceType::Hybrid => 2,
+        };
+        let centre = [PI, 0.0, PI / 2.0];
+        let mut totals = [0; 3];
+        for decl in &interfaces {
+            totals[side(decl.interface.ty)] += 1;
+        }
+        let mut placed = [0; 3];
+        let mut partners = Vec::new();
+        for (k, decl) in interfaces.into_iter().enumerate() {
+            let InterfaceDecl {
+                ident,
+                mut interface,
+                paired,
+            } = decl;
+            let interface_id = child(IdType::Interface, &id, k);
+            self.declare(&ident, &interface_id, Some(&id))?;
+            interface.info.level = level_of(&interface_id);
+            interface.info.id = interface_id;
+            let s = side(interface.ty);
+            interface.angle = Some(centre[s] + spread(placed[s], totals[s], 0.3));
+            placed[s] += 1;
+            partners.push(paired);
+            system.boundary.interfaces.push(interface);
+        }
+        for (interface, paired) in system.boundary.interfaces.iter_mut().zip(partners) {
+            if let Some(paired) = paired {
+                interface.paired_with = Some(self.interface_of(&paired, &ident, &id)?);
+            }
+        }
+        let angles: HashMap<Id, f32> = system
+            .boundary
+            .interfaces
+            .iter()
+            .map(|i| (i.info.id.clone(), i.angle.unwrap_or(0.0)))
+            .collect();
+
+        system.sources = self.externals(sources, &id, radius)?;
+        system.sinks = self.externals(sinks, &id, radius)?;
+        let mut next = system.boundary.interfaces.len();
+        self.model.systems.push(system);
+
+        let ring = subsystems.iter().filter(|s| s.on.is_none()).count();
+        let mut on_ring = 0;
+        let mut attached = HashSet::new();
+        for subsystem in subsystems {
+            let (index, sub_radius, sub_transform, sub_interface) = match &subsystem.on {
+                Some(on) => {
+                    let interface = self.interface_of(on, &ident, &id)?;
+                    if !attached.insert(interface.clone()) {
+                        return Err(self.error(
+                            on.span.clone(),
+                            format!("'{}' already has a subsystem", on.name),
+                        ));
+                    }
+                    let transform = Transform2d {
+                        translation: Vec2::ZERO,
+                        rotation: angles[&interface],
+                    };
+                    let index = interface.indices.last().copied().unwrap_or(0) as usize;
+                    (index, radius * 0.04, transform, Some(interface))
+                }
+                None => {
+                    let distance = if ring == 1 { 0.0 } else { radius * 0.5 };
+                    let angle = 2.0 * PI * on_ring as f32 / ring as f32;
+                    on_ring += 1;
+                    next += 1;
+                    let transform = Transform2d {
+                        translation: Vec2::from_angle(angle) * distance,
+                        rotation: 0.0,
+                    };
+                    (next - 1, radius * 0.14, transform, None)
+                }
+            };
+            let sub_id = child(IdType::Subsystem, &id, index);
+            self.system(
+                subsystem,
+                sub_id,
+                id.clone(),
+                sub_radius,
+                sub_transform,
+                sub_interface,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The element and interface a flow endpoint names.
+    fn endpoint(&self, endpoint: &Endpoint) -> Result<(Id, Option<Id>), ParseError> {
+        let Endpoint { element, interface } = endpoint;
+        let resolved = self.resolve(element)?;
+        let is_system = matches!(resolved.id.ty, IdType::System | IdType::Subsystem);
+        match (interface, resolved.id.ty, &resolved.owner) {
+            (Some(interface), _, _) if is_system => Ok((
+                resolved.id.clone(),
+                Some(self.interface_of(interface, element, &resolved.id)?),
+            )),
+            (Some(_), _, _) => Err(self.error(
+                element.span.clone(),
+                format!("'{}' is not a system", element.name),
+            )),
+            (None, IdType::Interface, Some(owner)) => {
+                Ok((owner.clone(), Some(resolved.id.clone())))
+            }
+            (None, IdType::Flow, _) => Err(self.error(
+                element.span.clone(),
+                format!("'{}' is a flow, not an endpoint", element.name),
+            )),
+            (None, _, _) => Ok((resolved.id.clone(), None)),
+        }
+    }
+
+    /// Fill in each interface's `exports_to` and `receives_from` from the
+    /// flows through it.
+    fn link_interfaces(&mut self) {
+        let mut links = Vec::new();
+        for ix in &self.model.interactions {
+            if let Some(interface) = &ix.source_interface {
+                links.push((interface.clone(), ix.sink.clone(), true));
+            }
+            if let Some(interface) = &ix.sink_interface {
+                links.push((interface.clone(), ix.source.clone(), false));
+            }
+        }
+        for interface in self
+            .model
+            .systems
+            .iter_mut()
+            .flat_map(|s| &mut s.boundary.interfaces)
+        {
+            for (at, other, outgoing) in &links {
+                if at != &interface.info.id {
+                    continue;
+                }
+                let list = if *outgoing {
+                    &mut interface.exports_to
+                } else {
+                    &mut interface.receives_from
+                };
+                if !list.contains(other) {
+                    list.push(other.clone());
+                }
+            }
+        }
+    }
+}
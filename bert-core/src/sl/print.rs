@@ -0,0 +1,521 @@
+//! A [`WorldModel`] → the notation.
+//!
+//! Identifiers come from element names, made unique in the order the elements
+//! are printed, so printing a parsed model gives back the same text.
+
+use crate::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub(super) fn print(model: &WorldModel) -> String {
+    let forest = forest(model);
+    let mut printer = Printer {
+        model,
+        idents: HashMap::new(),
+        taken: HashSet::new(),
+        declared: HashSet::new(),
+        owners: HashMap::new(),
+        out: String::new(),
+        indent: 0,
+    };
+    printer.name_all(&forest);
+    printer.document(&forest);
+    printer.out
+}
+
+/// A system and the systems that name it as their parent.
+struct Tree {
+    index: usize,
+    subsystems: Vec<Tree>,
+}
+
+/// The system hierarchy, as indices into `model.systems`. Systems whose parent
+/// is not a system are roots; so is the first system of any parent cycle, so
+/// every system is printed exactly once.
+fn forest(model: &WorldModel) -> Vec<Tree> {
+    fn grow(model: &WorldModel, index: usize, visited: &mut [bool]) -> Tree {
+        visited[index] = true;
+        let id = &model.systems[index].info.id;
+        let mut subsystems = Vec::new();
+        for (j, system) in model.systems.iter().enumerate() {
+            if !visited[j] && &system.parent == id {
+                subsystems.push(grow(model, j, visited));
+            }
+        }
+        Tree { index, subsystems }
+    }
+
+    let mut visited = vec![false; model.systems.len()];
+    let is_system = |id: &Id| model.systems.iter().any(|s| &s.info.id == id);
+    let mut trees = Vec::new();
+    for (i, system) in model.systems.iter().enumerate() {
+        if !visited[i] && !is_system(&system.parent) {
+            trees.push(grow(model, i, &mut visited));
+        }
+    }
+    for i in 0..model.systems.len() {
+        if !visited[i] {
+            trees.push(grow(model, i, &mut visited));
+        }
+    }
+    trees
+}
+
+struct Printer<'m> {
+    model: &'m WorldModel,
+    /// The identifier references to each id use.
+    idents: HashMap<&'m Id, String>,
+    taken: HashSet<String>,
+    /// Ids whose declaration has been printed; a duplicate gets a fresh identifier.
+    declared: HashSet<&'m Id>,
+    /// The system each interface is on.
+    owners: HashMap<&'m Id, &'m Id>,
+    out: String,
+    indent: usize,
+}
+
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_string()
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The serialized name of a unit variant.
+fn variant<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn substance(substance: &Substance) -> String {
+    match substance.sub_type.as_str() {
+        "" => variant(&substance.ty),
+        sub_type => format!("{} {}", variant(&substance.ty), quote(sub_type)),
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> String {
+    quote(&serde_json::to_string(value).unwrap_or_default())
+}
+
+fn sorted<V>(map: &HashMap<String, V>) -> BTreeMap<&String, &V> {
+    map.iter().collect()
+}
+
+impl<'m> Printer<'m> {
+    /// A new identifier for an element called `name`.
+    fn fresh(&mut self, name: &str, fallback: &str) -> String {
+        let slug = slug(name);
+        let base = if slug.is_empty() {
+            fallback.to_string()
+        } else if slug.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("{fallback}_{slug}")
+        } else {
+            slug
+        };
+        let mut ident = base.clone();
+        let mut n = 2;
+        while !self.taken.insert(ident.clone()) {
+            ident = format!("{base}_{n}");
+            n += 1;
+        }
+        ident
+    }
+
+    fn name(&mut self, info: &'m Info, fallback: &str) {
+        if !self.idents.contains_key(&info.id) {
+            let ident = self.fresh(&info.name, fallback);
+            self.idents.insert(&info.id, ident);
+        }
+    }
+
+    /// Name every element, in the order [`Printer::document`] prints them.
+    fn name_all(&mut self, forest: &[Tree]) {
+        let model = self.model;
+        for e in &model.environment.sources {
+            self.name(&e.info, "source");
+        }
+        for e in &model.environment.sinks {
+            self.name(&e.info, "sink");
+        }
+        for tree in forest {
+            self.name_tree(tree);
+        }
+        for ix in &model.interactions {
+            self.name(&ix.info, "flow");
+        }
+    }
+
+    fn name_tree(&mut self, tree: &Tree) {
+        let system = &self.model.systems[tree.index];
+        self.name(&system.info, "system");
+        for interface in &system.boundary.interfaces {
+            self.name(&interface.info, "interface");
+            self.owners
+                .entry(&interface.info.id)
+                .or_insert(&system.info.id);
+        }
+        for e in &system.sources {
+            self.name(&e.info, "source");
+        }
+        for e in &system.sinks {
+            self.name(&e.info, "sink");
+        }
+        for subsystem in &tree.subsystems {
+            self.name_tree(subsystem);
+        }
+    }
+
+    /// The identifier to declare the element `info` under.
+    fn declare(&mut self, info: &'m Info, fallback: &str) -> String {
+        match self.idents.get(&info.id) {
+            Some(ident) if self.declared.insert(&info.id) => ident.clone(),
+            _ => self.fresh(&info.name, fallback),
+        }
+    }
+
+    fn reference(&self, id: &Id) -> String {
+        self.idents
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| quote(&id.to_string()))
+    }
+
+    fn endpoint(&self, element: &Id, interface: Option<&Id>) -> String {
+        match interface {
+            Some(interface) if self.owners.get(interface) == Some(&element) => {
+                self.reference(interface)
+            }
+            Some(interface) => format!("{}.{}", self.reference(element), self.reference(interface)),
+            None => self.reference(element),
+        }
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(text.as_ref());
+        self.out.push('\n');
+    }
+
+    /// A blank line, unless at the start of a block.
+    fn gap(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("{\n") && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// `header { ... }`, or just `header` if `body` prints nothing.
+    fn block(&mut self, header: &str, body: impl FnOnce(&mut Self)) {
+        let start = self.out.len();
+        self.line(format!("{header} {{"));
+        let opened = self.out.len();
+        self.indent += 1;
+        body(self);
+        self.indent -= 1;
+        if self.out.len() == opened {
+            self.out.truncate(start);
+            self.line(header);
+        } else {
+            self.line("}");
+        }
+    }
+
+    /// `keyword "value"`, unless the value is empty.
+    fn text(&mut self, keyword: &str, value: &str) {
+        if !value.is_empty() {
+            self.line(format!("{keyword} {}", quote(value)));
+        }
+    }
+
+    fn document(&mut self, forest: &[Tree]) {
+        let model = self.model;
+        let env = &model.environment;
+        self.block(&format!("environment {}", quote(&env.info.name)), |p| {
+            p.text("description", &env.info.description);
+            if let Some(mode) = model.mode {
+                p.line(format!("mode {}", variant(&mode)));
+            }
+            if !model.validation.is_empty() {
+                p.block("validation", |p| {
+                    for pattern in &model.validation.suppress {
+                        p.text("suppress", pattern);
+                    }
+                    for pattern in &model.validation.escalate {
+                        p.text("escalate", pattern);
+                    }
+                });
+            }
+            if !env.sources.is_empty() || !env.sinks.is_empty() {
+                p.gap();
+            }
+            for e in &env.sources {
+                p.external("source", e);
+            }
+            for e in &env.sinks {
+                p.external("sink", e);
+            }
+            for tree in forest {
+                p.gap();
+                p.system(tree);
+            }
+            for ix in &model.interactions {
+                p.gap();
+                p.interaction(ix);
+            }
+        });
+    }
+
+    fn external(&mut self, keyword: &str, e: &'m ExternalEntity) {
+        let header = format!(
+            "{keyword} {} {}",
+            self.declare(&e.info, keyword),
+            quote(&e.info.name)
+        );
+        self.block(&header, |p| {
+            p.text("description", &e.info.description);
+            p.text("equivalence", &e.equivalence);
+            p.text("model", &e.model);
+            if let Some(index) = e.is_same_as_id {
+                p.line(format!("same_as {index}"));
+            }
+        });
+    }
+
+    fn system(&mut self, tree: &Tree) {
+        let model = self.model;
+        let system = &model.systems[tree.index];
+        let mut header = format!(
+            "system {} {}",
+            self.declare(&system.info, "system"),
+            quote(&system.info.name)
+        );
+        if let Some(interface) = &system.boundary.parent_interface {
+            header.push_str(" on ");
+            header.push_str(&self.reference(interface));
+        }
+        self.block(&header, |p| {
+            p.text("description", &system.info.description);
+            p.text("equivalence", &system.equivalence);
+            match system.complexity {
+                Complexity::Complex {
+                    adaptable: false,
+                    evolveable: false,
+                } => {}
+                Complexity::Complex {
+                    adaptable,
+                    evolveable,
+                } => {
+                    let mut line = "complexity complex".to_string();
+                    if adaptable {
+                        line.push_str(" adaptable");
+                    }
+                    if evolveable {
+                        line.push_str(" evolveable");
+                    }
+                    p.line(line);
+                }
+                Complexity::Atomic => p.line("complexity atomic"),
+                Complexity::Multiset(n) => p.line(format!("complexity multiset {n}")),
+            }
+            if let Some(archetype) = system.archetype {
+                p.line(format!("archetype {}", variant(&archetype)));
+            }
+            if let Some(time_constant) = &system.time_constant {
+                p.text("time_constant", &time_constant.to_string());
+            }
+            if system.member_autonomy != 1.0 {
+                p.line(format!("member_autonomy {}", system.member_autonomy));
+            }
+            p.boundary(&system.boundary);
+            if let Some(transformation) = &system.transformation {
+                p.block("transformation", |p| {
+                    for input in &transformation.inputs {
+                        p.line(format!("input {}", substance(input)));
+                    }
+                    for output in &transformation.outputs {
+                        p.line(format!("output {}", substance(output)));
+                    }
+                    p.text("description", &transformation.description);
+                });
+            }
+            if !system.history.is_empty() {
+                p.block("history", |p| {
+                    for event in system.history.iter() {
+                        match event.timestamp.as_str() {
+                            "" => p.text("event", &event.event),
+                            at => p.line(format!("event {} {}", quote(at), quote(&event.event))),
+                        }
+                    }
+                });
+            }
+            if let Some(agent) = &system.agent {
+                p.agent(agent);
+            }
+
+            if !system.boundary.interfaces.is_empty() {
+                p.gap();
+            }
+            for interface in &system.boundary.interfaces {
+                p.interface(interface);
+            }
+            if !system.sources.is_empty() || !system.sinks.is_empty() {
+                p.gap();
+            }
+            for e in &system.sources {
+                p.external("source", e);
+            }
+            for e in &system.sinks {
+                p.external("sink", e);
+            }
+            for subsystem in &tree.subsystems {
+                p.gap();
+                p.system(subsystem);
+            }
+        });
+    }
+
+    fn boundary(&mut self, boundary: &Boundary) {
+        let Boundary {
+            info,
+            porosity,
+            perceptive_fuzziness,
+            ..
+        } = boundary;
+        if info.name.is_empty()
+            && info.description.is_empty()
+            && *porosity == 0.0
+            && *perceptive_fuzziness == 0.0
+        {
+            return;
+        }
+        self.block(&format!("boundary {}", quote(&info.name)), |p| {
+            p.text("description", &info.description);
+            if *porosity != 0.0 {
+                p.line(format!("porosity {porosity}"));
+            }
+            if *perceptive_fuzziness != 0.0 {
+                p.line(format!("fuzziness {perceptive_fuzziness}"));
+            }
+        });
+    }
+
+    fn agent(&mut self, agent: &AgentModel) {
+        self.block(&format!("agent {}", variant(&agent.kind)), |p| {
+            p.line(format!("capacity {}", agent.agency_capacity));
+            if !agent.primitives.is_empty() {
+                let primitives: Vec<_> = agent.primitives.iter().map(variant).collect();
+                p.line(format!("primitives [{}]", primitives.join(", ")));
+            }
+            for (name, value) in sorted(&agent.cognitive_params) {
+                p.line(format!("cognitive {} {value}", quote(name)));
+            }
+            for process in &agent.process_configs {
+                p.block(&format!("process {}", quote(&process.name)), |p| {
+                    for (name, value) in sorted(&process.params) {
+                        p.line(format!("param {} {}", quote(name), json(value)));
+                    }
+                });
+            }
+            for (key, value) in sorted(&agent.initial_state) {
+                p.line(format!("state {} {}", quote(key), json(value)));
+            }
+            if let Some(network) = &agent.network_config {
+                p.block(&format!("network {}", quote(&network.topology)), |p| {
+                    for (key, value) in sorted(&network.connection_params) {
+                        p.line(format!("connection {} {}", quote(key), json(value)));
+                    }
+                    for (key, value) in sorted(&network.interaction_rules) {
+                        p.line(format!("rule {} {}", quote(key), json(value)));
+                    }
+                });
+            }
+        });
+    }
+
+    fn interface(&mut self, interface: &'m Interface) {
+        let keyword = match interface.ty {
+            InterfaceType::Import => "import",
+            InterfaceType::Export => "export",
+            InterfaceType::Hybrid => "hybrid",
+        };
+        let header = format!(
+            "{keyword} {} {}",
+            self.declare(&interface.info, "interface"),
+            quote(&interface.info.name)
+        );
+        self.block(&header, |p| {
+            p.text("description", &interface.info.description);
+            p.text("protocol", &interface.protocol);
+            if let Some(paired) = &interface.paired_with {
+                p.line(format!("paired {}", p.reference(paired)));
+            }
+        });
+    }
+
+    fn interaction(&mut self, ix: &'m Interaction) {
+        let keyword = match ix.ty {
+            InteractionType::Flow => "flow",
+            InteractionType::Force => "force",
+        };
+        let header = format!(
+            "{keyword} {} {}: {} -> {}",
+            self.declare(&ix.info, "flow"),
+            quote(&ix.info.name),
+            self.endpoint(&ix.source, ix.source_interface.as_ref()),
+            self.endpoint(&ix.sink, ix.sink_interface.as_ref()),
+        );
+        self.block(&header, |p| {
+            p.text("description", &ix.info.description);
+            p.line(format!("substance {}", substance(&ix.substance)));
+            p.line(format!("usability {}", variant(&ix.usability)));
+            match ix.unit.as_str() {
+                "" => p.line(format!("amount {}", ix.amount)),
+                unit => p.line(format!("amount {} {}", ix.amount, quote(unit))),
+            }
+            for parameter in &ix.parameters {
+                let mut line = format!(
+                    "param {} {}",
+                    quote(&parameter.name),
+                    quote(&parameter.value)
+                );
+                if !parameter.unit.is_empty() {
+                    line.push(' ');
+                    line.push_str(&quote(&parameter.unit));
+                }
+                p.line(line);
+            }
+            for parameter in &ix.smart_parameters {
+                p.line(format!(
+                    "smart_param {} {}",
+                    quote(&parameter.name),
+                    json(&parameter.value)
+                ));
+            }
+        });
+    }
+}
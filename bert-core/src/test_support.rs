@@ -0,0 +1,39 @@
+//! Example models and ids shared by the unit tests.
+
+use crate::{Id, WorldModel};
+
+/// `assets/models/examples/{name}.json`, migrated to the current version.
+pub(crate) fn example(name: &str) -> WorldModel {
+    let path = format!(
+        "{}/../assets/models/examples/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let bytes = std::fs::read(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    crate::migrate::from_slice(&bytes)
+        .unwrap_or_else(|e| panic!("{path}: {e}"))
+        .0
+}
+
+/// Every example model with its name, in name order.
+pub(crate) fn examples() -> Vec<(String, WorldModel)> {
+    let dir = format!("{}/../assets/models/examples", env!("CARGO_MANIFEST_DIR"));
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|e| {
+            let name = e.unwrap().file_name().into_string().unwrap();
+            name.strip_suffix(".json").map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let model = example(&name);
+            (name, model)
+        })
+        .collect()
+}
+
+pub(crate) fn id(s: &str) -> Id {
+    s.parse().unwrap()
+}
@@ -0,0 +1,716 @@
+//! Units of measure: parsing, normalization, conversion and dimensional
+//! analysis for the free-text unit strings on flows and parameters.
+//!
+//! A unit string is a product of factors, each an optionally prefixed atom
+//! with an optional exponent: `kWh`, `kg/s`, `m^2`, `USD per year`,
+//! `people/yr`, `Mbps`. Atoms cover SI and the domains BERT models live in —
+//! energy, mass, currency, data, people — and are matched leniently: exact
+//! symbols first, then names and plurals (`watts`, `tons`), then any casing
+//! (`kwh`, `Twh`). Parsing yields a [`Unit`] carrying its [`Dimension`], its
+//! factor to SI, and a canonical [`Unit::symbol`].
+//!
+//! Currencies share one dimension but have no fixed rate between them, so
+//! `USD` and `EUR` are dimensionally compatible yet not convertible.
+
+use crate::*;
+use std::str::FromStr;
+
+/// Base quantities, in the order of a [`Dimension`]'s exponents.
+const BASES: [&str; 8] = ["L", "M", "T", "I", "Θ", "$", "bit", "pop"];
+
+/// Exponents of the base quantities: length, mass, time, electric current,
+/// temperature, currency, information, people.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dimension([i8; 8]);
+
+impl Dimension {
+    pub const NONE: Self = Self([0; 8]);
+    pub const LENGTH: Self = Self([1, 0, 0, 0, 0, 0, 0, 0]);
+    pub const MASS: Self = Self([0, 1, 0, 0, 0, 0, 0, 0]);
+    pub const TIME: Self = Self([0, 0, 1, 0, 0, 0, 0, 0]);
+    pub const CURRENT: Self = Self([0, 0, 0, 1, 0, 0, 0, 0]);
+    pub const TEMPERATURE: Self = Self([0, 0, 0, 0, 1, 0, 0, 0]);
+    pub const CURRENCY: Self = Self([0, 0, 0, 0, 0, 1, 0, 0]);
+    pub const INFORMATION: Self = Self([0, 0, 0, 0, 0, 0, 1, 0]);
+    pub const PEOPLE: Self = Self([0, 0, 0, 0, 0, 0, 0, 1]);
+    pub const ENERGY: Self = Self([2, 1, -2, 0, 0, 0, 0, 0]);
+    pub const POWER: Self = Self([2, 1, -3, 0, 0, 0, 0, 0]);
+
+    /// `None` if an exponent leaves the `i8` range.
+    fn pow(self, exponent: i8) -> Option<Self> {
+        let mut out = self.0;
+        for e in &mut out {
+            *e = e.checked_mul(exponent)?;
+        }
+        Some(Self(out))
+    }
+
+    /// `None` if an exponent leaves the `i8` range.
+    fn mul(self, other: Self) -> Option<Self> {
+        let mut out = self.0;
+        for (e, o) in out.iter_mut().zip(other.0) {
+            *e = e.checked_add(o)?;
+        }
+        Some(Self(out))
+    }
+
+    /// The everyday name of the dimension, if it has one.
+    pub fn name(&self) -> Option<&'static str> {
+        let per_time = |d: Self| d.mul(FREQUENCY).expect("named dimensions are small");
+        let names = [
+            (Self::NONE, "dimensionless"),
+            (Self::LENGTH, "length"),
+            (AREA, "area"),
+            (VOLUME, "volume"),
+            (Self::MASS, "mass"),
+            (Self::TIME, "time"),
+            (Self::CURRENT, "electric current"),
+            (Self::TEMPERATURE, "temperature"),
+            (Self::CURRENCY, "currency"),
+            (Self::INFORMATION, "information"),
+            (Self::PEOPLE, "people"),
+            (Self::ENERGY, "energy"),
+            (Self::POWER, "power"),
+            (VOLTAGE, "voltage"),
+            (PRESSURE, "pressure"),
+            (FREQUENCY, "frequency"),
+            (per_time(Self::LENGTH), "speed"),
+            (per_time(VOLUME), "volume flow"),
+            (per_time(Self::MASS), "mass flow"),
+            (per_time(Self::CURRENCY), "currency flow"),
+            (DATA_RATE, "data rate"),
+            (per_time(Self::PEOPLE), "people flow"),
+        ];
+        names.iter().find(|(d, _)| d == self).map(|(_, n)| *n)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.name() {
+            return write!(f, "{name}");
+        }
+        let mut first = true;
+        for (base, &e) in BASES.iter().zip(&self.0) {
+            if e == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, "·")?;
+            }
+            first = false;
+            write!(f, "{base}")?;
+            if e != 1 {
+                write!(f, "^{e}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A parsed unit of measure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit {
+    symbol: String,
+    /// Multiplier to the SI (or base-domain) unit of the same dimension.
+    factor: f64,
+    /// Added after scaling; non-zero only for a bare °C or °F.
+    offset: f64,
+    dimension: Dimension,
+    /// The currency, for units that involve one.
+    currency: Option<&'static str>,
+}
+
+impl Unit {
+    /// Parse a unit string; see the module docs for what is accepted.
+    pub fn parse(input: &str) -> Result<Self, UnitError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(UnitError::Empty);
+        }
+
+        let mut factors: Vec<(Factor, i8)> = Vec::new();
+        let mut divide = false;
+        for token in tokens(input) {
+            match token {
+                "/" => divide = true,
+                term => {
+                    let (factor, mut exponent) = parse_term(term)?;
+                    if divide {
+                        exponent = exponent
+                            .checked_neg()
+                            .ok_or_else(|| UnitError::BadExponent(term.to_string()))?;
+                    }
+                    factors.push((factor, exponent));
+                    divide = false;
+                }
+            }
+        }
+        if factors.is_empty() {
+            return Err(UnitError::Unknown(input.to_string()));
+        }
+
+        let mut unit = Unit {
+            symbol: String::new(),
+            factor: 1.0,
+            offset: 0.0,
+            dimension: Dimension::NONE,
+            currency: None,
+        };
+        for (factor, exponent) in &factors {
+            unit.factor *= (factor.prefix * factor.atom.factor).powi(i32::from(*exponent));
+            unit.dimension = factor
+                .atom
+                .dimension
+                .pow(*exponent)
+                .and_then(|d| unit.dimension.mul(d))
+                .ok_or_else(|| UnitError::BadExponent(input.to_string()))?;
+            if let Some(code) = factor.atom.currency {
+                match unit.currency {
+                    Some(other) if other != code => {
+                        return Err(UnitError::NotConvertible {
+                            from: other.to_string(),
+                            to: code.to_string(),
+                        })
+                    }
+                    _ => unit.currency = Some(code),
+                }
+            }
+        }
+        // An offset scale only means a point on that scale when it stands
+        // alone; inside a compound (°C/s) it is a difference.
+        if let [(factor, 1)] = &factors[..] {
+            unit.offset = factor.atom.offset;
+        }
+        unit.symbol = symbol(&factors);
+        Ok(unit)
+    }
+
+    /// The canonical spelling: `kwh` and `kilowatt-hours` both give `kWh`.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// True if both units measure the same kind of quantity.
+    pub fn is_compatible(&self, other: &Unit) -> bool {
+        self.dimension == other.dimension
+    }
+
+    /// Express `value` in `self` as a value in `to`.
+    pub fn convert(&self, value: f64, to: &Unit) -> Result<f64, UnitError> {
+        if !self.is_compatible(to) {
+            return Err(UnitError::Incompatible {
+                from: self.symbol.clone(),
+                to: to.symbol.clone(),
+                dimensions: [self.dimension, to.dimension],
+            });
+        }
+        if self.currency != to.currency {
+            return Err(UnitError::NotConvertible {
+                from: self.symbol.clone(),
+                to: to.symbol.clone(),
+            });
+        }
+        Ok((value * self.factor + self.offset - to.offset) / to.factor)
+    }
+}
+
+impl FromStr for Unit {
+    type Err = UnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitError {
+    /// The unit string is blank.
+    Empty,
+    /// A factor is not a known unit.
+    Unknown(String),
+    /// An exponent is not a small integer.
+    BadExponent(String),
+    /// The units measure different kinds of quantity.
+    Incompatible {
+        from: String,
+        to: String,
+        dimensions: [Dimension; 2],
+    },
+    /// The units are compatible but have no fixed rate (two currencies).
+    NotConvertible { from: String, to: String },
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no unit given"),
+            Self::Unknown(unit) => write!(f, "unknown unit '{unit}'"),
+            Self::BadExponent(term) => write!(f, "bad exponent in '{term}'"),
+            Self::Incompatible {
+                from,
+                to,
+                dimensions: [a, b],
+            } => write!(f, "'{from}' ({a}) cannot be converted to '{to}' ({b})"),
+            Self::NotConvertible { from, to } => {
+                write!(f, "no fixed exchange rate between {from} and {to}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+/// One entry of the unit table.
+struct Atom {
+    /// Canonical symbol.
+    symbol: &'static str,
+    /// Other spellings, matched in any case.
+    names: &'static [&'static str],
+    factor: f64,
+    offset: f64,
+    dimension: Dimension,
+    /// Whether SI prefixes apply (`kWh`, `Mbps`).
+    prefixable: bool,
+    currency: Option<&'static str>,
+}
+
+/// An atom that takes no prefix.
+const fn atom(
+    symbol: &'static str,
+    names: &'static [&'static str],
+    factor: f64,
+    dimension: Dimension,
+) -> Atom {
+    Atom {
+        symbol,
+        names,
+        factor,
+        offset: 0.0,
+        dimension,
+        prefixable: false,
+        currency: None,
+    }
+}
+
+/// An atom that takes SI prefixes.
+const fn si(
+    symbol: &'static str,
+    names: &'static [&'static str],
+    factor: f64,
+    dimension: Dimension,
+) -> Atom {
+    Atom {
+        prefixable: true,
+        ..atom(symbol, names, factor, dimension)
+    }
+}
+
+const fn money(symbol: &'static str, names: &'static [&'static str]) -> Atom {
+    Atom {
+        currency: Some(symbol),
+        ..si(symbol, names, 1.0, Dimension::CURRENCY)
+    }
+}
+
+/// A temperature scale whose zero sits `offset` kelvin above absolute zero.
+const fn scale(
+    symbol: &'static str,
+    names: &'static [&'static str],
+    factor: f64,
+    offset: f64,
+) -> Atom {
+    Atom {
+        offset,
+        ..atom(symbol, names, factor, Dimension::TEMPERATURE)
+    }
+}
+
+const NONE: Dimension = Dimension::NONE;
+const ENERGY: Dimension = Dimension::ENERGY;
+const POWER: Dimension = Dimension::POWER;
+const MASS: Dimension = Dimension::MASS;
+const LENGTH: Dimension = Dimension::LENGTH;
+const AREA: Dimension = Dimension([2, 0, 0, 0, 0, 0, 0, 0]);
+const VOLUME: Dimension = Dimension([3, 0, 0, 0, 0, 0, 0, 0]);
+const TIME: Dimension = Dimension::TIME;
+const FREQUENCY: Dimension = Dimension([0, 0, -1, 0, 0, 0, 0, 0]);
+const PRESSURE: Dimension = Dimension([-1, 1, -2, 0, 0, 0, 0, 0]);
+const CURRENT: Dimension = Dimension::CURRENT;
+const VOLTAGE: Dimension = Dimension([2, 1, -3, -1, 0, 0, 0, 0]);
+const INFORMATION: Dimension = Dimension::INFORMATION;
+const DATA_RATE: Dimension = Dimension([0, 0, -1, 0, 0, 0, 1, 0]);
+const PEOPLE: Dimension = Dimension::PEOPLE;
+
+/// Mean Gregorian year, in seconds.
+const YEAR: f64 = 31_556_952.0;
+
+const ATOMS: &[Atom] = &[
+    // Energy and power
+    si("J", &["joule", "joules"], 1.0, ENERGY),
+    si(
+        "Wh",
+        &["watt-hour", "watt-hours", "watthour"],
+        3_600.0,
+        ENERGY,
+    ),
+    si("cal", &["calorie", "calories"], 4.184, ENERGY),
+    atom("BTU", &["btus"], 1_055.055_852_62, ENERGY),
+    si(
+        "eV",
+        &["electronvolt", "electronvolts"],
+        1.602_176_634e-19,
+        ENERGY,
+    ),
+    si("W", &["watt", "watts"], 1.0, POWER),
+    atom("hp", &["horsepower"], 745.699_871_582_270_2, POWER),
+    // Mass
+    si("g", &["gram", "grams", "gramme", "grammes"], 1e-3, MASS),
+    si("t", &["tonne", "tonnes", "ton", "tons"], 1e3, MASS),
+    atom("lb", &["lbs", "pound", "pounds"], 0.453_592_37, MASS),
+    atom("oz", &["ounce", "ounces"], 0.028_349_523_125, MASS),
+    // Length, area, volume
+    si("m", &["meter", "meters", "metre", "metres"], 1.0, LENGTH),
+    atom("mi", &["mile", "miles"], 1_609.344, LENGTH),
+    atom("ft", &["foot", "feet"], 0.3048, LENGTH),
+    atom("ha", &["hectare", "hectares"], 1e4, AREA),
+    si(
+        "L",
+        &["l", "liter", "liters", "litre", "litres"],
+        1e-3,
+        VOLUME,
+    ),
+    atom("gal", &["gallon", "gallons"], 3.785_411_784e-3, VOLUME),
+    atom("bbl", &["barrel", "barrels"], 0.158_987_294_928, VOLUME),
+    // Time
+    si("s", &["sec", "secs", "second", "seconds"], 1.0, TIME),
+    atom("min", &["mins", "minute", "minutes"], 60.0, TIME),
+    atom("h", &["hr", "hrs", "hour", "hours"], 3_600.0, TIME),
+    atom("d", &["day", "days"], 86_400.0, TIME),
+    atom("wk", &["week", "weeks"], 604_800.0, TIME),
+    atom("month", &["months", "mo"], YEAR / 12.0, TIME),
+    atom("yr", &["y", "year", "years", "annum", "a"], YEAR, TIME),
+    si("Hz", &["hertz"], 1.0, FREQUENCY),
+    // Temperature
+    scale("K", &["kelvin"], 1.0, 0.0),
+    scale("°C", &["degc", "celsius"], 1.0, 273.15),
+    scale("°F", &["degf", "fahrenheit"], 5.0 / 9.0, 459.67 * 5.0 / 9.0),
+    // Pressure and electricity
+    si("Pa", &["pascal", "pascals"], 1.0, PRESSURE),
+    si("bar", &["bars"], 1e5, PRESSURE),
+    atom("atm", &["atmosphere", "atmospheres"], 101_325.0, PRESSURE),
+    atom("psi", &[], 6_894.757_293_168, PRESSURE),
+    si("A", &["amp", "amps", "ampere", "amperes"], 1.0, CURRENT),
+    si("V", &["volt", "volts"], 1.0, VOLTAGE),
+    // Information
+    si("bit", &["bits", "b"], 1.0, INFORMATION),
+    si("B", &["byte", "bytes"], 8.0, INFORMATION),
+    si("bit/s", &["bps"], 1.0, DATA_RATE),
+    // People
+    atom(
+        "people",
+        &["person", "persons", "capita", "pax"],
+        1.0,
+        PEOPLE,
+    ),
+    // Currency
+    money("USD", &["$", "dollar", "dollars", "us$"]),
+    money("EUR", &["€", "euro", "euros"]),
+    money("GBP", &["£", "sterling"]),
+    money("JPY", &["¥", "yen"]),
+    money("CNY", &["yuan", "rmb"]),
+    money("BTC", &["bitcoin", "bitcoins"]),
+    money("ETH", &["ether"]),
+    // Dimensionless
+    atom("%", &["percent", "pct"], 0.01, NONE),
+    atom("ppm", &[], 1e-6, NONE),
+];
+
+/// SI and binary prefixes, longest first so `Ki` wins over `K`-anything.
+const PREFIXES: &[(&str, f64)] = &[
+    ("Ki", 1_024.0),
+    ("Mi", 1_048_576.0),
+    ("Gi", 1_073_741_824.0),
+    ("Ti", 1_099_511_627_776.0),
+    ("kilo", 1e3),
+    ("mega", 1e6),
+    ("giga", 1e9),
+    ("tera", 1e12),
+    ("milli", 1e-3),
+    ("micro", 1e-6),
+    ("E", 1e18),
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("c", 1e-2),
+    ("m", 1e-3),
+    ("µ", 1e-6),
+    ("u", 1e-6),
+    ("n", 1e-9),
+];
+
+struct Factor {
+    prefix: f64,
+    prefix_symbol: &'static str,
+    atom: &'static Atom,
+}
+
+/// Split into terms and `/` markers; `per`, `*`, `·`, `×` and spaces separate.
+fn tokens(input: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    for word in input.split_whitespace() {
+        if word.eq_ignore_ascii_case("per") {
+            out.push("/");
+            continue;
+        }
+        // Named data rates are atoms even though they contain a slash.
+        if find_atom(word).is_some() || prefixed(word).is_some() {
+            out.push(word);
+            continue;
+        }
+        let mut rest = word;
+        while !rest.is_empty() {
+            let end = rest.find(['/', '*', '·', '×']).unwrap_or(rest.len());
+            if end > 0 {
+                out.push(&rest[..end]);
+            }
+            if let Some(sep) = rest[end..].chars().next() {
+                if sep == '/' {
+                    out.push("/");
+                }
+                rest = &rest[end + sep.len_utf8()..];
+            } else {
+                rest = "";
+            }
+        }
+    }
+    out
+}
+
+fn parse_term(term: &str) -> Result<(Factor, i8), UnitError> {
+    let (base, exponent) = split_exponent(term)?;
+    if let Some(atom) = find_atom(base) {
+        let factor = Factor {
+            prefix: 1.0,
+            prefix_symbol: "",
+            atom,
+        };
+        return Ok((factor, exponent));
+    }
+    prefixed(base)
+        .map(|factor| (factor, exponent))
+        .ok_or_else(|| UnitError::Unknown(term.to_string()))
+}
+
+fn split_exponent(term: &str) -> Result<(&str, i8), UnitError> {
+    if let Some((base, exponent)) = term.split_once('^') {
+        let exponent = exponent
+            .parse()
+            .map_err(|_| UnitError::BadExponent(term.to_string()))?;
+        return Ok((base, exponent));
+    }
+    const SUPERSCRIPTS: [(char, i8); 3] = [('¹', 1), ('²', 2), ('³', 3)];
+    for (sup, exponent) in SUPERSCRIPTS {
+        if let Some(base) = term.strip_suffix(sup) {
+            return Ok(match base.strip_suffix('⁻') {
+                Some(base) => (base, -exponent),
+                None => (base, exponent),
+            });
+        }
+    }
+    // `m2`, `s-1`: a trailing integer on an alphabetic unit.
+    let digits = term.trim_end_matches(|c: char| c.is_ascii_digit() || c == '-');
+    if digits.len() < term.len() && digits.chars().all(char::is_alphabetic) && !digits.is_empty() {
+        if let Ok(exponent) = term[digits.len()..].parse() {
+            return Ok((digits, exponent));
+        }
+    }
+    Ok((term, 1))
+}
+
+fn find_atom(s: &str) -> Option<&'static Atom> {
+    ATOMS.iter().find(|a| a.symbol == s).or_else(|| {
+        ATOMS.iter().find(|a| {
+            a.symbol.eq_ignore_ascii_case(s) || a.names.iter().any(|n| n.eq_ignore_ascii_case(s))
+        })
+    })
+}
+
+fn prefixed(s: &str) -> Option<Factor> {
+    PREFIXES.iter().find_map(|&(prefix, scale)| {
+        let rest = s.strip_prefix(prefix)?.trim_start_matches('-');
+        let atom = find_atom(rest).filter(|a| a.prefixable)?;
+        let prefix_symbol = match prefix {
+            "kilo" => "k",
+            "mega" => "M",
+            "giga" => "G",
+            "tera" => "T",
+            "milli" => "m",
+            "micro" | "u" => "µ",
+            p => p,
+        };
+        Some(Factor {
+            prefix: scale,
+            prefix_symbol,
+            atom,
+        })
+    })
+}
+
+/// `num·num/den/den`, exponents as `^n`.
+fn symbol(factors: &[(Factor, i8)]) -> String {
+    let term = |f: &Factor, e: u8| {
+        let mut s = format!("{}{}", f.prefix_symbol, f.atom.symbol);
+        if e != 1 {
+            s.push_str(&format!("^{e}"));
+        }
+        s
+    };
+    let numerator: Vec<String> = factors
+        .iter()
+        .filter(|(_, e)| *e > 0)
+        .map(|(f, e)| term(f, e.unsigned_abs()))
+        .collect();
+    let mut out = if numerator.is_empty() {
+        "1".to_string()
+    } else {
+        numerator.join("·")
+    };
+    for (f, e) in factors.iter().filter(|(_, e)| *e < 0) {
+        out.push('/');
+        out.push_str(&term(f, e.unsigned_abs()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(s: &str) -> Unit {
+        Unit::parse(s).unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    #[test]
+    fn spellings_normalize_to_one_symbol() {
+        for (input, canonical) in [
+            ("kWh", "kWh"),
+            ("kwh", "kWh"),
+            ("kilowatt-hours", "kWh"),
+            ("Twh", "TWh"),
+            ("watts", "W"),
+            ("tons", "t"),
+            ("kg/s", "kg/s"),
+            ("bits/s", "bit/s"),
+            ("Mbps", "Mbit/s"),
+            ("USD per year", "USD/yr"),
+            ("$/month", "USD/month"),
+            ("people/yr", "people/yr"),
+            ("m^2", "m^2"),
+            ("m²", "m^2"),
+            ("s-1", "1/s"),
+            ("/d", "1/d"),
+            ("°C", "°C"),
+            ("kg·m^2/s^2", "kg·m^2/s^2"),
+        ] {
+            assert_eq!(unit(input).symbol(), canonical, "{input}");
+        }
+    }
+
+    #[test]
+    fn dimensions_are_named_and_compared() {
+        assert_eq!(unit("kWh").dimension(), Dimension::ENERGY);
+        assert_eq!(unit("kg·m^2/s^2").dimension(), Dimension::ENERGY);
+        assert_eq!(unit("kW").dimension().to_string(), "power");
+        assert_eq!(unit("tons/yr").dimension().to_string(), "mass flow");
+        assert_eq!(unit("Mbps").dimension().to_string(), "data rate");
+        assert_eq!(unit("people/d").dimension().to_string(), "people flow");
+        assert_eq!(unit("USD/kg").dimension().to_string(), "M^-1·$");
+        assert!(unit("J").is_compatible(&unit("kWh")));
+        assert!(!unit("J").is_compatible(&unit("W")));
+        assert!(unit("USD").is_compatible(&unit("EUR")));
+    }
+
+    #[test]
+    fn compatible_units_convert() {
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * b.abs().max(1.0);
+        assert!(close(unit("kWh").convert(1.0, &unit("J")).unwrap(), 3.6e6));
+        assert!(close(unit("TWh").convert(1.0, &unit("GWh")).unwrap(), 1e3));
+        assert!(close(unit("t").convert(2.0, &unit("kg")).unwrap(), 2e3));
+        assert!(close(unit("MB").convert(1.0, &unit("Mbit")).unwrap(), 8.0));
+        assert!(close(unit("kg/s").convert(1.0, &unit("t/h")).unwrap(), 3.6));
+        assert!(close(
+            unit("°C").convert(100.0, &unit("°F")).unwrap(),
+            212.0
+        ));
+        assert!(close(unit("K").convert(0.0, &unit("°C")).unwrap(), -273.15));
+        assert!(close(
+            unit("USD/month").convert(1.0, &unit("$/yr")).unwrap(),
+            12.0
+        ));
+
+        assert_eq!(
+            unit("kWh")
+                .convert(1.0, &unit("kg"))
+                .unwrap_err()
+                .to_string(),
+            "'kWh' (energy) cannot be converted to 'kg' (mass)"
+        );
+        assert_eq!(
+            unit("USD").convert(1.0, &unit("EUR")),
+            Err(UnitError::NotConvertible {
+                from: "USD".into(),
+                to: "EUR".into()
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_and_blank_units_are_errors() {
+        assert_eq!(Unit::parse("  "), Err(UnitError::Empty));
+        assert_eq!(
+            Unit::parse("Block"),
+            Err(UnitError::Unknown("Block".into()))
+        );
+        assert_eq!(
+            Unit::parse("kg/furlong"),
+            Err(UnitError::Unknown("furlong".into()))
+        );
+        assert_eq!(
+            Unit::parse("m^x"),
+            Err(UnitError::BadExponent("m^x".into()))
+        );
+    }
+
+    #[test]
+    fn exponent_overflow_is_an_error() {
+        for (input, term) in [
+            ("J^100", "J^100"),
+            ("kWh^64", "kWh^64"),
+            ("m/s^-128", "s^-128"),
+            ("m^127 m", "m^127 m"),
+            ("kg2 kg127", "kg2 kg127"),
+        ] {
+            assert_eq!(
+                Unit::parse(input),
+                Err(UnitError::BadExponent(term.into())),
+                "{input}"
+            );
+        }
+        // The extremes themselves are representable.
+        assert_eq!(unit("s^-128").symbol(), "1/s^128");
+        assert_eq!(unit("m^127").dimension().to_string(), "L^127");
+    }
+}
@@ -2,10 +2,12 @@
 //! Siblings: `general-systems-reasoner/core/src/constraints.rs` (generation-time, spec `Value`),
 //! `bert/tools/bert-typedb/src/validate.rs` (pre-transpile). See the bert-dev skill "Validators".
 
+use crate::fix::{fresh_id, Edit, ElementRef, End, ExternalRef, Fix};
 use crate::*;
+use rust_decimal::prelude::ToPrimitive;
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Severity {
     Error,
     Warning,
@@ -13,37 +15,77 @@ pub enum Severity {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidationIssue {
+    /// Code of the [`Rule`](rules::Rule) this issue breaks, e.g. `BERT-L2-001`.
+    pub code: &'static str,
     pub severity: Severity,
     pub location: String,
     pub message: String,
     pub suggestion: Option<String>,
+    /// A structured repair, for rules whose suggestion is mechanical.
+    pub fix: Option<Fix>,
 }
 
 impl ValidationIssue {
-    fn error(
+    /// An issue under `rule`, at the rule's default severity.
+    pub fn new(
+        rule: &rules::Rule,
         location: impl Into<String>,
         message: impl Into<String>,
         suggestion: Option<&str>,
     ) -> Self {
         Self {
-            severity: Severity::Error,
+            code: rule.code,
+            severity: rule.severity,
             location: location.into(),
             message: message.into(),
             suggestion: suggestion.map(|s| s.to_string()),
+            fix: None,
         }
     }
 
-    fn warning(
-        location: impl Into<String>,
-        message: impl Into<String>,
-        suggestion: Option<&str>,
-    ) -> Self {
-        Self {
-            severity: Severity::Warning,
-            location: location.into(),
-            message: message.into(),
-            suggestion: suggestion.map(|s| s.to_string()),
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// [`location`](Self::location) as an RFC 6901 JSON pointer into the
+    /// model file, e.g. `/systems/3/boundary/interfaces/1`.
+    ///
+    /// Entity names quoted into a location are dropped, and locations that are
+    /// not a path (`root`, `mode/Cybernetic`) point at the whole document.
+    pub fn json_pointer(&self) -> String {
+        let location = self.location.as_str();
+        if location == "root" || location.starts_with("mode/") {
+            return String::new();
         }
+
+        let mut pointer = String::new();
+        let mut rest = location;
+        while !rest.is_empty() {
+            if let Some(quoted) = rest.strip_prefix(" '") {
+                // A name runs to the first quote that ends the location or
+                // is followed by the next segment.
+                let end = quoted
+                    .match_indices('\'')
+                    .map(|(k, _)| k)
+                    .find(|&k| matches!(quoted.as_bytes().get(k + 1), None | Some(b'.')))
+                    .unwrap_or(quoted.len().saturating_sub(1));
+                rest = quoted.get(end + 1..).unwrap_or("");
+            } else if let Some(tail) = rest.strip_prefix('.') {
+                rest = tail;
+            } else if let Some(tail) = rest.strip_prefix('[') {
+                let end = tail.find(']').unwrap_or(tail.len());
+                pointer.push('/');
+                pointer.push_str(&tail[..end]);
+                rest = tail.get(end + 1..).unwrap_or("");
+            } else {
+                let end = rest.find(['.', '[', ' ']).unwrap_or(rest.len()).max(1);
+                pointer.push('/');
+                pointer.push_str(&rest[..end].replace('~', "~0").replace('/', "~1"));
+                rest = &rest[end..];
+            }
+        }
+        pointer
     }
 }
 
@@ -63,9 +105,21 @@ impl ValidationResult {
     pub fn is_clean(&self) -> bool {
         self.issues.is_empty()
     }
+
+    /// Apply further overrides, e.g. a project's [`RuleConfig`](rules::RuleConfig).
+    pub fn apply(&mut self, config: &rules::RuleConfig) {
+        config.apply(&mut self.issues);
+    }
 }
 
+/// Check the rules every model must satisfy, whatever its mode, then apply the
+/// model's own [`WorldModel::validation`] overrides.
 pub fn validate(model: &WorldModel) -> ValidationResult {
+    validate_with(model, &model.validation)
+}
+
+/// [`validate`] with `config` in place of the model's own overrides.
+pub fn validate_with(model: &WorldModel, config: &rules::RuleConfig) -> ValidationResult {
     let mut issues = Vec::new();
 
     let known_ids = collect_known_ids(model);
@@ -77,6 +131,7 @@ pub fn validate(model: &WorldModel) -> ValidationResult {
     check_interface_references(model, &interface_ids, &mut issues);
     check_orphan_interfaces(model, &mut issues);
     check_parent_references(model, &known_ids, &mut issues);
+    check_interface_links(model, &interface_ids, &mut issues);
     check_duplicate_ids(model, &mut issues);
 
     check_environment_id(model, &mut issues);
@@ -85,7 +140,11 @@ pub fn validate(model: &WorldModel) -> ValidationResult {
     check_level_consistency(model, &mut issues);
     check_processor_flows(model, &mut issues);
     check_s0_interface_processors(model, &mut issues);
+    check_interface_units(model, &mut issues);
+    check_interface_pairs(model, &mut issues);
+    check_rule_config(config, &mut issues);
 
+    config.apply(&mut issues);
     ValidationResult { issues }
 }
 
@@ -98,7 +157,10 @@ pub fn validate(model: &WorldModel) -> ValidationResult {
 /// parallel lenses, not a tower: `Structural` needs a bond (Bunge) and
 /// `Operational` needs irreflexivity (Mobus), but neither inherits the other —
 /// they share only `Core`'s on-ness. `Full` extends `Operational` with a
-/// dynamical-face check that, since `Full` is the default view, only warns.
+/// dynamical-face check and [`validate_conservation`], which, since `Full` is
+/// the default view, only warn.
+/// `Cybernetic` is the converse of `Operational`: it needs a feedback loop, and
+/// a self-loop counts as one.
 ///
 /// Universal structural errors (dangling refs, orphans, duplicates) are caught
 /// first by [`validate`] and surface in every mode — they are defects, not
@@ -117,9 +179,12 @@ pub fn validate_mode(model: &WorldModel, target: Mode) -> ValidationResult {
         Mode::Full => {
             check_self_loops(model, issues);
             check_dynamical_face(model, issues);
+            issues.extend(validate_conservation(model, CONSERVATION_TOLERANCE).issues);
         }
+        Mode::Cybernetic => check_feedback(model, issues),
     }
 
+    result.apply(&model.validation);
     result
 }
 
@@ -146,7 +211,8 @@ fn check_bond(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
         .iter()
         .any(|ix| is_system(&ix.source) && is_system(&ix.sink) && ix.source != ix.sink);
     if !bonded {
-        issues.push(ValidationIssue::error(
+        issues.push(ValidationIssue::new(
+            &rules::NO_BOND,
             "mode/Structural",
             "Bunge Def 1.1: a system requires at least one bond between distinct \
              components; an unbonded collection is an aggregate",
@@ -159,41 +225,169 @@ fn check_bond(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
 fn check_self_loops(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
     for (i, ix) in model.interactions.iter().enumerate() {
         if ix.source == ix.sink {
-            issues.push(ValidationIssue::error(
+            issues.push(ValidationIssue::new(
+                &rules::SELF_LOOP,
                 format!("interactions[{i}]"),
                 format!(
                     "Mobus §4.3: flow edges require k ≠ o; '{}' has the same endpoint as \
                      source and sink, and self-dependency is not representable in the 8-tuple",
                     ix.info.name
                 ),
-                Some("Remove the self-loop; feedback as a first-class cycle is Cybernetic mode (not yet available)"),
+                Some("Remove the self-loop, or author in Cybernetic mode, where feedback is a first-class cycle"),
             ));
         }
     }
 }
 
+/// Cybernetic precondition: the dependency graph has at least one cycle.
+fn check_feedback(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
+    if !model.kernel().has_cycle() {
+        issues.push(ValidationIssue::new(
+            &rules::NO_FEEDBACK,
+            "mode/Cybernetic",
+            "Cybernetic mode treats feedback as a first-class cycle, but no chain of \
+             interactions returns to where it started",
+            Some("Close a loop, e.g. a message flow from the regulated system back to its controller, or author in Operational mode"),
+        ));
+    }
+}
+
 /// Full check: warn *once* when the model engages the dynamical face nowhere.
-/// Full is the default view, so an empty face informs rather than blocks (the
-/// slots stay stringly-typed in v2.0 — typing them is deferred). A model-level
-/// check, not per-system: a single populated system means the model is using
-/// Full, so a per-leaf warning would only add noise. Any non-empty slot counts.
+/// Full is the default view, so an empty face informs rather than blocks. A
+/// model-level check, not per-system: a single populated system means the
+/// model is using Full, so a per-leaf warning would only add noise. Any
+/// populated slot counts.
+///
+/// Populated slots are then checked for content, one warning per problem:
+/// a time constant must be positive, a transformation's inputs and outputs
+/// must match substances that actually flow into and out of the system, and
+/// history events must be dated, described, and in order.
 fn check_dynamical_face(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
     if model.systems.is_empty() {
         return;
     }
-    let any_face = model.systems.iter().any(|s| {
-        !s.transformation.trim().is_empty()
-            || !s.history.trim().is_empty()
-            || !s.time_constant.trim().is_empty()
-    });
+    let any_face = model
+        .systems
+        .iter()
+        .any(|s| s.transformation.is_some() || !s.history.is_empty() || s.time_constant.is_some());
     if !any_face {
-        issues.push(ValidationIssue::warning(
+        issues.push(ValidationIssue::new(
+            &rules::EMPTY_DYNAMICAL_FACE,
             "mode/Full",
             "Full mode shows the dynamical face, but no system has a transformation, \
              history, or time constant",
             Some("Populate the dynamical slots, or view this model in Operational mode"),
         ));
     }
+
+    for (i, system) in model.systems.iter().enumerate() {
+        let name = &system.info.name;
+        if let Some(magnitude) = system.time_constant.and_then(|tc| tc.magnitude) {
+            if magnitude <= Decimal::ZERO {
+                issues.push(ValidationIssue::new(
+                    &rules::TIME_CONSTANT,
+                    format!("systems[{i}].time_constant"),
+                    format!("The time constant of '{name}' must be positive, not {magnitude}"),
+                    Some("Drop the magnitude to mean one unit"),
+                ));
+            }
+        }
+        if let Some(transformation) = &system.transformation {
+            check_transformation(model, i, transformation, issues);
+        }
+        check_history(i, system, issues);
+    }
+}
+
+fn check_transformation(
+    model: &WorldModel,
+    i: usize,
+    transformation: &Transformation,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let system = &model.systems[i];
+    let carries = |ix: &Interaction, s: &Substance| {
+        ix.substance.ty == s.ty
+            && (s.sub_type.trim().is_empty()
+                || ix
+                    .substance
+                    .sub_type
+                    .trim()
+                    .eq_ignore_ascii_case(s.sub_type.trim()))
+    };
+    for (side, list, direction) in [
+        ("inputs", &transformation.inputs, "into"),
+        ("outputs", &transformation.outputs, "out of"),
+    ] {
+        for (j, substance) in list.iter().enumerate() {
+            let flows = model.interactions.iter().any(|ix| {
+                let end = if side == "inputs" {
+                    &ix.sink
+                } else {
+                    &ix.source
+                };
+                *end == system.info.id && carries(ix, substance)
+            });
+            if !flows {
+                issues.push(ValidationIssue::new(
+                    &rules::TRANSFORMATION_FLOW,
+                    format!("systems[{i}].transformation.{side}[{j}]"),
+                    format!(
+                        "'{}' transforms {substance}, but no such flow goes {direction} it",
+                        system.info.name
+                    ),
+                    Some("Add the flow, or correct the transformation"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_history(i: usize, system: &System, issues: &mut Vec<ValidationIssue>) {
+    let name = &system.info.name;
+    let mut latest: Option<&str> = None;
+    for (j, event) in system.history.iter().enumerate() {
+        let location = format!("systems[{i}].history[{j}]");
+        let at = event.timestamp.trim();
+        if event.event.trim().is_empty() {
+            issues.push(ValidationIssue::new(
+                &rules::HISTORY_EVENT,
+                &location,
+                format!("A history event of '{name}' says nothing happened"),
+                Some("Describe the event, or remove it"),
+            ));
+        }
+        if at.is_empty() {
+            issues.push(ValidationIssue::new(
+                &rules::HISTORY_EVENT,
+                &location,
+                format!("History event '{}' of '{name}' is undated", event.event),
+                Some("Give it an ISO 8601 timestamp, e.g. 2009-01-03"),
+            ));
+        } else if !dynamics::is_timestamp(at) {
+            issues.push(ValidationIssue::new(
+                &rules::HISTORY_EVENT,
+                &location,
+                format!("'{at}' in the history of '{name}' is not an ISO 8601 date"),
+                Some("Write dates as YYYY, YYYY-MM or YYYY-MM-DD, optionally with a time"),
+            ));
+        } else {
+            // Same-precision ISO dates sort as strings; a coarser date sorts
+            // before the finer dates it contains, which reads as "no later".
+            if latest.is_some_and(|prev| at < prev && !prev.starts_with(at)) {
+                issues.push(ValidationIssue::new(
+                    &rules::HISTORY_EVENT,
+                    &location,
+                    format!(
+                        "The history of '{name}' goes back in time: {at} follows {}",
+                        latest.unwrap_or_default()
+                    ),
+                    Some("List history events oldest first"),
+                ));
+            }
+            latest = Some(at);
+        }
+    }
 }
 
 /// Classify a model as open or closed *with respect to mass*, returning a short
@@ -238,6 +432,239 @@ pub fn classify_openness(model: &WorldModel) -> String {
     }
 }
 
+/// Overrides must name rules that exist, or a typo silently suppresses nothing.
+fn check_rule_config(config: &rules::RuleConfig, issues: &mut Vec<ValidationIssue>) {
+    for (field, i, pattern) in config.unknown_patterns() {
+        issues.push(ValidationIssue::new(
+            &rules::UNKNOWN_RULE,
+            format!("validation.{field}[{i}]"),
+            format!("'{pattern}' is not a rule code or layer"),
+            Some("Use a code such as BERT-L2-007 or a layer such as BERT-L4; see bert_core::rules::RULES"),
+        ));
+    }
+}
+
+/// Relative tolerance [`validate_mode`] uses for [`validate_conservation`]:
+/// inflow and outflow may differ by 1% of the larger of the two.
+pub const CONSERVATION_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+/// Check that declared flow amounts balance — the static counterpart of the
+/// conservation bert-compose proves by simulation.
+///
+/// Only Energy/Material flows count (as in [`classify_openness`], information
+/// is not conserved), and amounts are only compared within one substance type
+/// and in convertible [`Unit`]s — `kWh` and `TWh` meet, `kWh` and `kg` or an
+/// unparsed unit and anything else do not. Two checks, both warnings:
+///
+/// - **Balance**: every system takes in what it gives out. An interface
+///   processor is credited with the parent's flows through its interface.
+///   Stores — systems whose agent has the `Buffering` primitive — accumulate
+///   and are exempt.
+/// - **Roll-up**: the flows a system's subsystems exchange with the outside
+///   add up to the flows the system itself declares, for every substance the
+///   subsystems carry.
+///
+/// Two totals agree if they differ by at most `tolerance` times the larger.
+pub fn validate_conservation(model: &WorldModel, tolerance: Decimal) -> ValidationResult {
+    let mut issues = Vec::new();
+    let conserved: Vec<&Interaction> = model
+        .interactions
+        .iter()
+        .filter(|ix| ix.ty == InteractionType::Flow && ix.substance.ty != SubstanceType::Message)
+        .collect();
+
+    for (i, system) in model.systems.iter().enumerate() {
+        let id = &system.info.id;
+        if !is_store(system) {
+            let mut ledger = Ledger::default();
+            for ix in &conserved {
+                if &ix.sink == id {
+                    ledger.post(ix, Side::In);
+                }
+                if &ix.source == id {
+                    ledger.post(ix, Side::Out);
+                }
+            }
+            credit_parent_interface(system, &conserved, &mut ledger);
+
+            for balance in &ledger.0 {
+                if !agrees(balance.inflow, balance.outflow, tolerance) {
+                    issues.push(ValidationIssue::new(
+                        &rules::UNBALANCED_FLOWS,
+                        format!("systems[{i}]"),
+                        format!(
+                            "'{}' does not conserve {}: {} in, {} out",
+                            system.info.name,
+                            balance.quantity(),
+                            balance.inflow,
+                            balance.outflow
+                        ),
+                        Some("Add the missing flow (e.g. waste heat to a sink), correct the amounts, or give the system the Buffering primitive if it is a store"),
+                    ));
+                }
+            }
+        }
+
+        let children: Vec<&System> = model.systems.iter().filter(|s| &s.parent == id).collect();
+        if children.is_empty() {
+            continue;
+        }
+        let is_child = |other: &Id| children.iter().any(|c| &c.info.id == other);
+        let mut declared = Ledger::default();
+        let mut rolled_up = Ledger::default();
+        for ix in &conserved {
+            if &ix.sink == id {
+                declared.post(ix, Side::In);
+            }
+            if &ix.source == id {
+                declared.post(ix, Side::Out);
+            }
+            if is_child(&ix.sink) && !is_child(&ix.source) && &ix.source != id {
+                rolled_up.post(ix, Side::In);
+            }
+            if is_child(&ix.source) && !is_child(&ix.sink) && &ix.sink != id {
+                rolled_up.post(ix, Side::Out);
+            }
+        }
+        for child in &children {
+            credit_parent_interface(child, &conserved, &mut rolled_up);
+        }
+
+        for balance in &rolled_up.0 {
+            let (inflow, outflow) = declared.totals(balance);
+            for (verb, inner, outer) in [
+                ("take in", balance.inflow, inflow),
+                ("give out", balance.outflow, outflow),
+            ] {
+                if !agrees(inner, outer, tolerance) {
+                    issues.push(ValidationIssue::new(
+                        &rules::ROLL_UP,
+                        format!("systems[{i}]"),
+                        format!(
+                            "The subsystems of '{name}' {verb} {inner} {} across its boundary, but '{name}' declares {outer}",
+                            balance.quantity(),
+                            name = system.info.name,
+                        ),
+                        Some("Route boundary flows through interface processors, or make the subsystem flows add up to the system's own"),
+                    ));
+                }
+            }
+        }
+    }
+
+    model.validation.apply(&mut issues);
+    ValidationResult { issues }
+}
+
+/// A store accumulates what flows in, so its flows need not balance.
+fn is_store(system: &System) -> bool {
+    system
+        .agent
+        .as_ref()
+        .is_some_and(|agent| agent.primitives.contains(&ProcessPrimitive::Buffering))
+}
+
+/// Post the parent's flows through `system`'s parent interface, if it is an
+/// interface processor: what enters the parent there enters the processor.
+fn credit_parent_interface(system: &System, conserved: &[&Interaction], ledger: &mut Ledger) {
+    let Some(interface) = &system.boundary.parent_interface else {
+        return;
+    };
+    for ix in conserved {
+        if ix.sink == system.parent && ix.sink_interface.as_ref() == Some(interface) {
+            ledger.post(ix, Side::In);
+        }
+        if ix.source == system.parent && ix.source_interface.as_ref() == Some(interface) {
+            ledger.post(ix, Side::Out);
+        }
+    }
+}
+
+fn agrees(a: Decimal, b: Decimal, tolerance: Decimal) -> bool {
+    (a - b).abs() <= tolerance * a.max(b)
+}
+
+enum Side {
+    In,
+    Out,
+}
+
+/// Inflow and outflow totals of one quantity (substance type and unit).
+struct Balance {
+    substance: SubstanceType,
+    /// The unit as first spelled; compatible units are converted into it.
+    unit: String,
+    parsed: Option<Unit>,
+    inflow: Decimal,
+    outflow: Decimal,
+}
+
+impl Balance {
+    fn quantity(&self) -> String {
+        if self.unit.trim().is_empty() {
+            format!("{} (no unit)", self.substance)
+        } else {
+            format!("{} [{}]", self.substance, self.unit)
+        }
+    }
+
+    /// `amount` of `unit` in this balance's unit, if the two are convertible.
+    fn express(&self, amount: Decimal, unit: &str) -> Option<Decimal> {
+        if unit == self.unit {
+            return Some(amount);
+        }
+        let from = Unit::parse(unit).ok()?;
+        let value = from.convert(amount.to_f64()?, self.parsed.as_ref()?).ok()?;
+        Decimal::from_f64_retain(value).map(|d| d.round_dp(9).normalize())
+    }
+}
+
+/// Balances in order of first appearance, so reports follow the model.
+#[derive(Default)]
+struct Ledger(Vec<Balance>);
+
+impl Ledger {
+    fn post(&mut self, ix: &Interaction, side: Side) {
+        let found = self.0.iter().enumerate().find_map(|(k, b)| {
+            if b.substance != ix.substance.ty {
+                return None;
+            }
+            b.express(ix.amount, &ix.unit).map(|amount| (k, amount))
+        });
+        let (balance, amount) = match found {
+            Some((k, amount)) => (&mut self.0[k], amount),
+            None => {
+                self.0.push(Balance {
+                    substance: ix.substance.ty,
+                    unit: ix.unit.clone(),
+                    parsed: Unit::parse(&ix.unit).ok(),
+                    inflow: Decimal::ZERO,
+                    outflow: Decimal::ZERO,
+                });
+                (self.0.last_mut().unwrap(), ix.amount)
+            }
+        };
+        match side {
+            Side::In => balance.inflow += amount,
+            Side::Out => balance.outflow += amount,
+        }
+    }
+
+    /// This ledger's totals for the quantity of `like`, in `like`'s unit.
+    fn totals(&self, like: &Balance) -> (Decimal, Decimal) {
+        self.0
+            .iter()
+            .filter(|b| b.substance == like.substance)
+            .find_map(|b| {
+                Some((
+                    like.express(b.inflow, &b.unit)?,
+                    like.express(b.outflow, &b.unit)?,
+                ))
+            })
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO))
+    }
+}
+
 fn serialize_id(id: &Id) -> String {
     serde_json::to_string(id)
         .ok()
@@ -291,22 +718,34 @@ fn check_orphan_sources(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
         .map(|ix| serialize_id(&ix.source))
         .collect();
 
-    let mut check_sources = |sources: &[ExternalEntity], loc_prefix: &str| {
+    let mut check_sources = |sources: &[ExternalEntity], owner: Option<usize>| {
         for (i, src) in sources.iter().enumerate() {
             let id_str = serialize_id(&src.info.id);
             if !referenced_sources.contains(&id_str) {
-                issues.push(ValidationIssue::error(
-                    format!("{loc_prefix}.sources[{i}]"),
-                    format!("orphan source '{id_str}' is not referenced by any interaction"),
-                    Some("Add an interaction with this source, or remove it"),
-                ));
+                let entity = ExternalRef {
+                    system: owner,
+                    sink: false,
+                    index: i,
+                };
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::ORPHAN_SOURCE,
+                        format!("{}.sources[{i}]", owner_location(owner)),
+                        format!("orphan source '{id_str}' is not referenced by any interaction"),
+                        Some("Add an interaction with this source, or remove it"),
+                    )
+                    .with_fix(Fix::risky(
+                        format!("Remove source '{}'", src.info.name),
+                        vec![Edit::RemoveExternal(entity)],
+                    )),
+                );
             }
         }
     };
 
-    check_sources(&model.environment.sources, "environment");
+    check_sources(&model.environment.sources, None);
     for (i, system) in model.systems.iter().enumerate() {
-        check_sources(&system.sources, &format!("systems[{i}]"));
+        check_sources(&system.sources, Some(i));
     }
 }
 
@@ -318,22 +757,42 @@ fn check_orphan_sinks(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
         .map(|ix| serialize_id(&ix.sink))
         .collect();
 
-    let mut check_sinks = |sinks: &[ExternalEntity], loc_prefix: &str| {
+    let mut check_sinks = |sinks: &[ExternalEntity], owner: Option<usize>| {
         for (i, snk) in sinks.iter().enumerate() {
             let id_str = serialize_id(&snk.info.id);
             if !referenced_sinks.contains(&id_str) {
-                issues.push(ValidationIssue::error(
-                    format!("{loc_prefix}.sinks[{i}]"),
-                    format!("orphan sink '{id_str}' is not referenced by any interaction"),
-                    Some("Add an interaction with this sink, or remove it"),
-                ));
+                let entity = ExternalRef {
+                    system: owner,
+                    sink: true,
+                    index: i,
+                };
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::ORPHAN_SINK,
+                        format!("{}.sinks[{i}]", owner_location(owner)),
+                        format!("orphan sink '{id_str}' is not referenced by any interaction"),
+                        Some("Add an interaction with this sink, or remove it"),
+                    )
+                    .with_fix(Fix::risky(
+                        format!("Remove sink '{}'", snk.info.name),
+                        vec![Edit::RemoveExternal(entity)],
+                    )),
+                );
             }
         }
     };
 
-    check_sinks(&model.environment.sinks, "environment");
+    check_sinks(&model.environment.sinks, None);
     for (i, system) in model.systems.iter().enumerate() {
-        check_sinks(&system.sinks, &format!("systems[{i}]"));
+        check_sinks(&system.sinks, Some(i));
+    }
+}
+
+/// Location of the environment, or of the system that owns an external entity.
+fn owner_location(owner: Option<usize>) -> String {
+    match owner {
+        None => "environment".to_string(),
+        Some(i) => format!("systems[{i}]"),
     }
 }
 
@@ -345,23 +804,38 @@ fn check_interaction_references(
     for (i, ix) in model.interactions.iter().enumerate() {
         let src = serialize_id(&ix.source);
         if !known.contains(&src) {
-            issues.push(ValidationIssue::error(
-                format!("interactions[{i}].source"),
-                format!("source '{src}' does not resolve to any known entity"),
-                Some("Check the source ID matches an existing system, source, or sink"),
-            ));
+            issues.push(
+                ValidationIssue::new(
+                    &rules::DANGLING_ENDPOINT,
+                    format!("interactions[{i}].source"),
+                    format!("source '{src}' does not resolve to any known entity"),
+                    Some("Check the source ID matches an existing system, source, or sink"),
+                )
+                .with_fix(remove_interaction(i, ix)),
+            );
         }
         let snk = serialize_id(&ix.sink);
         if !known.contains(&snk) {
-            issues.push(ValidationIssue::error(
-                format!("interactions[{i}].sink"),
-                format!("sink '{snk}' does not resolve to any known entity"),
-                Some("Check the sink ID matches an existing system, source, or sink"),
-            ));
+            issues.push(
+                ValidationIssue::new(
+                    &rules::DANGLING_ENDPOINT,
+                    format!("interactions[{i}].sink"),
+                    format!("sink '{snk}' does not resolve to any known entity"),
+                    Some("Check the sink ID matches an existing system, source, or sink"),
+                )
+                .with_fix(remove_interaction(i, ix)),
+            );
         }
     }
 }
 
+fn remove_interaction(i: usize, ix: &Interaction) -> Fix {
+    Fix::risky(
+        format!("Remove interaction '{}'", ix.info.name),
+        vec![Edit::RemoveInteraction(i)],
+    )
+}
+
 fn check_interface_references(
     model: &WorldModel,
     interfaces: &HashSet<String>,
@@ -371,21 +845,45 @@ fn check_interface_references(
         if let Some(ref src_iface) = ix.source_interface {
             let id_str = serialize_id(src_iface);
             if !interfaces.contains(&id_str) {
-                issues.push(ValidationIssue::error(
-                    format!("interactions[{i}].source_interface"),
-                    format!("source_interface '{id_str}' does not resolve to any known interface"),
-                    Some("Check the interface ID exists on the source system's boundary"),
-                ));
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::DANGLING_INTERFACE,
+                        format!("interactions[{i}].source_interface"),
+                        format!(
+                            "source_interface '{id_str}' does not resolve to any known interface"
+                        ),
+                        Some("Check the interface ID exists on the source system's boundary"),
+                    )
+                    .with_fix(Fix::safe(
+                        "Clear the source interface",
+                        vec![Edit::ClearInterfaceRef {
+                            interaction: i,
+                            end: End::Source,
+                        }],
+                    )),
+                );
             }
         }
         if let Some(ref snk_iface) = ix.sink_interface {
             let id_str = serialize_id(snk_iface);
             if !interfaces.contains(&id_str) {
-                issues.push(ValidationIssue::error(
-                    format!("interactions[{i}].sink_interface"),
-                    format!("sink_interface '{id_str}' does not resolve to any known interface"),
-                    Some("Check the interface ID exists on the sink system's boundary"),
-                ));
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::DANGLING_INTERFACE,
+                        format!("interactions[{i}].sink_interface"),
+                        format!(
+                            "sink_interface '{id_str}' does not resolve to any known interface"
+                        ),
+                        Some("Check the interface ID exists on the sink system's boundary"),
+                    )
+                    .with_fix(Fix::safe(
+                        "Clear the sink interface",
+                        vec![Edit::ClearInterfaceRef {
+                            interaction: i,
+                            end: End::Sink,
+                        }],
+                    )),
+                );
             }
         }
     }
@@ -413,7 +911,8 @@ fn check_orphan_interfaces(model: &WorldModel, issues: &mut Vec<ValidationIssue>
         for (j, iface) in system.boundary.interfaces.iter().enumerate() {
             let id_str = serialize_id(&iface.info.id);
             if !referenced.contains(&id_str) {
-                issues.push(ValidationIssue::warning(
+                issues.push(ValidationIssue::new(
+                    &rules::ORPHAN_INTERFACE,
                     format!("systems[{i}].boundary.interfaces[{j}]"),
                     format!("interface '{id_str}' has no flow routing and no attached processor"),
                     Some("Add a flow using this interface, attach an interface processor, or remove it if unused"),
@@ -431,7 +930,8 @@ fn check_parent_references(
     for (i, system) in model.systems.iter().enumerate() {
         let parent = serialize_id(&system.parent);
         if !known.contains(&parent) {
-            issues.push(ValidationIssue::error(
+            issues.push(ValidationIssue::new(
+                &rules::DANGLING_PARENT,
                 format!("systems[{i}].parent"),
                 format!("parent '{parent}' does not resolve to any known entity"),
                 Some("Parent must be 'E-1' (environment) or an existing system ID"),
@@ -440,66 +940,146 @@ fn check_parent_references(
     }
 }
 
+fn check_interface_links(
+    model: &WorldModel,
+    interfaces: &HashSet<String>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    // A link's counterpart is a system or an external entity; an interface
+    // or the environment node is not a valid target.
+    let mut counterparts: HashSet<String> = HashSet::new();
+    for system in &model.systems {
+        counterparts.insert(serialize_id(&system.info.id));
+        for ext in system.sources.iter().chain(&system.sinks) {
+            counterparts.insert(serialize_id(&ext.info.id));
+        }
+    }
+    for ext in model
+        .environment
+        .sources
+        .iter()
+        .chain(&model.environment.sinks)
+    {
+        counterparts.insert(serialize_id(&ext.info.id));
+    }
+
+    for (i, system) in model.systems.iter().enumerate() {
+        if let Some(ref parent_iface) = system.boundary.parent_interface {
+            let id_str = serialize_id(parent_iface);
+            if !interfaces.contains(&id_str) {
+                issues.push(ValidationIssue::new(
+                    &rules::DANGLING_LINK,
+                    format!("systems[{i}].boundary.parent_interface"),
+                    format!("parent_interface '{id_str}' does not resolve to any known interface"),
+                    Some(
+                        "Point parent_interface at an interface of the parent system, or clear it",
+                    ),
+                ));
+            }
+        }
+        for (j, iface) in system.boundary.interfaces.iter().enumerate() {
+            for (field, ids) in [
+                ("exports_to", &iface.exports_to),
+                ("receives_from", &iface.receives_from),
+            ] {
+                for (k, id) in ids.iter().enumerate() {
+                    let id_str = serialize_id(id);
+                    if !counterparts.contains(&id_str) {
+                        issues.push(ValidationIssue::new(
+                            &rules::DANGLING_LINK,
+                            format!("systems[{i}].boundary.interfaces[{j}].{field}[{k}]"),
+                            format!(
+                                "{field} '{id_str}' on interface '{}' does not resolve to any known system or external entity",
+                                iface.info.name
+                            ),
+                            Some("Link interfaces only to existing systems, sources or sinks"),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn check_duplicate_ids(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
     let mut seen: HashMap<String, String> = HashMap::new();
 
-    let mut record = |id_str: String, location: String, issues: &mut Vec<ValidationIssue>| {
+    let mut record = |id: &Id, element: ElementRef, location: String| {
+        let id_str = serialize_id(id);
         if let Some(prior) = seen.insert(id_str.clone(), location.clone()) {
-            issues.push(ValidationIssue::error(
-                &location,
-                format!("duplicate ID '{id_str}' (first seen at {prior})"),
-                Some("Each entity must have a unique ID"),
-            ));
+            let fresh = fresh_id(model, id);
+            issues.push(
+                ValidationIssue::new(
+                    &rules::DUPLICATE_ID,
+                    &location,
+                    format!("duplicate ID '{id_str}' (first seen at {prior})"),
+                    Some("Each entity must have a unique ID"),
+                )
+                .with_fix(Fix::risky(
+                    format!("Give this element the new ID '{}'", serialize_id(&fresh)),
+                    vec![Edit::Reassign { element, id: fresh }],
+                )),
+            );
         }
     };
+    let external = |system, sink, index| {
+        ElementRef::External(ExternalRef {
+            system,
+            sink,
+            index,
+        })
+    };
 
     for (i, system) in model.systems.iter().enumerate() {
         record(
-            serialize_id(&system.info.id),
+            &system.info.id,
+            ElementRef::System(i),
             format!("systems[{i}].info.id"),
-            issues,
         );
         for (j, src) in system.sources.iter().enumerate() {
             record(
-                serialize_id(&src.info.id),
+                &src.info.id,
+                external(Some(i), false, j),
                 format!("systems[{i}].sources[{j}].info.id"),
-                issues,
             );
         }
         for (j, snk) in system.sinks.iter().enumerate() {
             record(
-                serialize_id(&snk.info.id),
+                &snk.info.id,
+                external(Some(i), true, j),
                 format!("systems[{i}].sinks[{j}].info.id"),
-                issues,
             );
         }
         for (j, iface) in system.boundary.interfaces.iter().enumerate() {
             record(
-                serialize_id(&iface.info.id),
+                &iface.info.id,
+                ElementRef::Interface {
+                    system: i,
+                    interface: j,
+                },
                 format!("systems[{i}].boundary.interfaces[{j}].info.id"),
-                issues,
             );
         }
     }
     for (i, src) in model.environment.sources.iter().enumerate() {
         record(
-            serialize_id(&src.info.id),
+            &src.info.id,
+            external(None, false, i),
             format!("environment.sources[{i}].info.id"),
-            issues,
         );
     }
     for (i, snk) in model.environment.sinks.iter().enumerate() {
         record(
-            serialize_id(&snk.info.id),
+            &snk.info.id,
+            external(None, true, i),
             format!("environment.sinks[{i}].info.id"),
-            issues,
         );
     }
     for (i, ix) in model.interactions.iter().enumerate() {
         record(
-            serialize_id(&ix.info.id),
+            &ix.info.id,
+            ElementRef::Interaction(i),
             format!("interactions[{i}].info.id"),
-            issues,
         );
     }
 }
@@ -507,7 +1087,8 @@ fn check_duplicate_ids(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
 fn check_environment_id(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
     let env_id = serialize_id(&model.environment.info.id);
     if env_id != "E-1" {
-        issues.push(ValidationIssue::warning(
+        issues.push(ValidationIssue::new(
+            &rules::ENVIRONMENT_ID,
             "environment.info.id",
             format!("environment ID is '{env_id}', expected 'E-1'"),
             Some("The environment entity should always have ID 'E-1'"),
@@ -518,24 +1099,47 @@ fn check_environment_id(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
 fn check_source_sink_type_consistency(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
     let check = |sources: &[ExternalEntity],
                  sinks: &[ExternalEntity],
-                 loc_prefix: &str,
+                 owner: Option<usize>,
                  issues: &mut Vec<ValidationIssue>| {
+        let loc_prefix = owner_location(owner);
         for (i, src) in sources.iter().enumerate() {
             if !matches!(src.ty, ExternalEntityType::Source) {
-                issues.push(ValidationIssue::warning(
-                    format!("{loc_prefix}.sources[{i}].type"),
-                    "entity in sources array has type 'Sink'".to_string(),
-                    Some("Entities in the sources array should have type 'Source'"),
-                ));
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::SOURCE_SINK_TYPE,
+                        format!("{loc_prefix}.sources[{i}].type"),
+                        "entity in sources array has type 'Sink'".to_string(),
+                        Some("Entities in the sources array should have type 'Source'"),
+                    )
+                    .with_fix(Fix::safe(
+                        "Set type to 'Source'",
+                        vec![Edit::SetExternalType(ExternalRef {
+                            system: owner,
+                            sink: false,
+                            index: i,
+                        })],
+                    )),
+                );
             }
         }
         for (i, snk) in sinks.iter().enumerate() {
             if !matches!(snk.ty, ExternalEntityType::Sink) {
-                issues.push(ValidationIssue::warning(
-                    format!("{loc_prefix}.sinks[{i}].type"),
-                    "entity in sinks array has type 'Source'".to_string(),
-                    Some("Entities in the sinks array should have type 'Sink'"),
-                ));
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::SOURCE_SINK_TYPE,
+                        format!("{loc_prefix}.sinks[{i}].type"),
+                        "entity in sinks array has type 'Source'".to_string(),
+                        Some("Entities in the sinks array should have type 'Sink'"),
+                    )
+                    .with_fix(Fix::safe(
+                        "Set type to 'Sink'",
+                        vec![Edit::SetExternalType(ExternalRef {
+                            system: owner,
+                            sink: true,
+                            index: i,
+                        })],
+                    )),
+                );
             }
         }
     };
@@ -543,28 +1147,34 @@ fn check_source_sink_type_consistency(model: &WorldModel, issues: &mut Vec<Valid
     check(
         &model.environment.sources,
         &model.environment.sinks,
-        "environment",
+        None,
         issues,
     );
     for (i, system) in model.systems.iter().enumerate() {
-        check(
-            &system.sources,
-            &system.sinks,
-            &format!("systems[{i}]"),
-            issues,
-        );
+        check(&system.sources, &system.sinks, Some(i), issues);
     }
 }
 
 fn check_version(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
-    if model.version != CURRENT_FILE_VERSION {
-        issues.push(ValidationIssue::warning(
+    if model.version < CURRENT_FILE_VERSION {
+        issues.push(ValidationIssue::new(
+            &rules::VERSION,
             "version",
             format!(
                 "model version is {}, current is {CURRENT_FILE_VERSION}",
                 model.version
             ),
-            Some("This model may have been created with a different version of BERT"),
+            Some("Load the file through bert_core::migrate to upgrade it"),
+        ));
+    } else if model.version > CURRENT_FILE_VERSION {
+        issues.push(ValidationIssue::new(
+            &rules::VERSION,
+            "version",
+            format!(
+                "model version is {}, current is {CURRENT_FILE_VERSION}",
+                model.version
+            ),
+            Some("This model may have been created with a newer version of BERT"),
         ));
     }
 }
@@ -573,16 +1183,26 @@ fn check_level_consistency(model: &WorldModel, issues: &mut Vec<ValidationIssue>
     for (i, system) in model.systems.iter().enumerate() {
         let expected = (system.info.id.indices.len() as i32) - 1;
         if system.info.level != expected {
-            issues.push(ValidationIssue::warning(
-                format!("systems[{i}].info.level"),
-                format!(
-                    "level is {} but ID '{}' implies level {}",
-                    system.info.level,
-                    serialize_id(&system.info.id),
-                    expected
-                ),
-                Some("Level should equal the number of ID indices minus one"),
-            ));
+            issues.push(
+                ValidationIssue::new(
+                    &rules::LEVEL_CONSISTENCY,
+                    format!("systems[{i}].info.level"),
+                    format!(
+                        "level is {} but ID '{}' implies level {}",
+                        system.info.level,
+                        serialize_id(&system.info.id),
+                        expected
+                    ),
+                    Some("Level should equal the number of ID indices minus one"),
+                )
+                .with_fix(Fix::safe(
+                    format!("Set level to {expected}"),
+                    vec![Edit::SetLevel {
+                        system: i,
+                        level: expected,
+                    }],
+                )),
+            );
         }
     }
 }
@@ -599,10 +1219,7 @@ const SYSTEM_FIELDS: &[&str] = &[
     "boundary",
     "radius",
     "equivalence",
-    "history",
-    "transformation",
     "member_autonomy",
-    "time_constant",
 ];
 const INFO_FIELDS: &[&str] = &["id", "level", "name", "description"];
 const BOUNDARY_FIELDS: &[&str] = &["info", "porosity", "perceptive_fuzziness", "interfaces"];
@@ -630,7 +1247,8 @@ fn check_required_fields(
     if let Some(map) = obj.as_object() {
         for &field in required {
             if !map.contains_key(field) {
-                issues.push(ValidationIssue::error(
+                issues.push(ValidationIssue::new(
+                    &rules::MISSING_FIELD,
                     location,
                     format!("Missing required field '{field}'"),
                     Some(&format!("Add the '{field}' field to {location}")),
@@ -638,7 +1256,8 @@ fn check_required_fields(
             }
         }
     } else {
-        issues.push(ValidationIssue::error(
+        issues.push(ValidationIssue::new(
+            &rules::NOT_AN_OBJECT,
             location,
             format!("Expected an object, found {}", json_type_name(obj)),
             None,
@@ -756,7 +1375,8 @@ fn check_processor_flows(model: &WorldModel, issues: &mut Vec<ValidationIssue>)
             .iter()
             .any(|f| serialize_id(&f.sink) == sys_id);
         if !is_source && !is_sink {
-            issues.push(ValidationIssue::warning(
+            issues.push(ValidationIssue::new(
+                &rules::IDLE_PROCESSOR,
                 format!("systems.{name}", name = system.info.name),
                 format!(
                     "Processor '{}' has parent_interface but no connecting flows",
@@ -789,7 +1409,8 @@ fn check_s0_interface_processors(model: &WorldModel, issues: &mut Vec<Validation
     for (j, iface) in s0.boundary.interfaces.iter().enumerate() {
         let id_str = serialize_id(&iface.info.id);
         if !claimed.contains(&id_str) {
-            issues.push(ValidationIssue::warning(
+            issues.push(ValidationIssue::new(
+                &rules::UNPROCESSED_INTERFACE,
                 format!("systems[{s0_idx}].boundary.interfaces[{j}]"),
                 format!(
                     "Interface '{}' has no processor — external flows won't trace to internal subsystems",
@@ -801,6 +1422,127 @@ fn check_s0_interface_processors(model: &WorldModel, issues: &mut Vec<Validation
     }
 }
 
+// ── L5: Dimensional analysis ──────────────────────────────────
+
+/// Flows through one interface must measure the same kind of quantity. Units
+/// that do not parse are skipped: free-form units stay legal.
+fn check_interface_units(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
+    for (i, system) in model.systems.iter().enumerate() {
+        for (j, iface) in system.boundary.interfaces.iter().enumerate() {
+            let id = Some(&iface.info.id);
+            let mut seen: Vec<Unit> = Vec::new();
+            for ix in &model.interactions {
+                if ix.source_interface.as_ref() != id && ix.sink_interface.as_ref() != id {
+                    continue;
+                }
+                let Ok(unit) = Unit::parse(&ix.unit) else {
+                    continue;
+                };
+                if let Some(other) = seen.iter().find(|u| !u.is_compatible(&unit)) {
+                    issues.push(ValidationIssue::new(
+                        &rules::INTERFACE_UNITS,
+                        format!("systems[{i}].boundary.interfaces[{j}]"),
+                        format!(
+                            "Flows meeting at interface '{}' have incompatible units: '{other}' ({}) and '{unit}' ({})",
+                            iface.info.name,
+                            other.dimension(),
+                            unit.dimension()
+                        ),
+                        Some("Give every flow through an interface units of one dimension, or split the interface"),
+                    ));
+                    break;
+                }
+                seen.push(unit);
+            }
+        }
+    }
+}
+
+fn unpair(system: usize, interface: usize) -> Fix {
+    Fix::safe(
+        "Unpair this interface",
+        vec![Edit::ClearPairing { system, interface }],
+    )
+}
+
+/// A bidirectional port is one Import and one Export on the same boundary,
+/// each naming the other through `paired_with`. Differing protocols only warn:
+/// the two halves may legitimately speak different dialects of one exchange.
+fn check_interface_pairs(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
+    for (i, system) in model.systems.iter().enumerate() {
+        let interfaces = &system.boundary.interfaces;
+        for (j, iface) in interfaces.iter().enumerate() {
+            let Some(partner_id) = iface.paired_with.as_ref() else {
+                continue;
+            };
+            let location = format!("systems[{i}].boundary.interfaces[{j}].paired_with");
+            let partner_str = serialize_id(partner_id);
+
+            let Some(partner) = interfaces.iter().find(|p| &p.info.id == partner_id) else {
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::PORT_PAIR,
+                        location,
+                        format!(
+                            "interface '{}' is paired with '{partner_str}', which is not an interface on the same boundary",
+                            iface.info.name
+                        ),
+                        Some("Pair interfaces only with another interface of the same system"),
+                    )
+                    .with_fix(unpair(i, j)),
+                );
+                continue;
+            };
+            if partner.paired_with.as_ref() != Some(&iface.info.id) {
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::PORT_PAIR,
+                        location,
+                        format!(
+                            "interface '{}' is paired with '{}', which is not paired back",
+                            iface.info.name, partner.info.name
+                        ),
+                        Some("Set paired_with on both halves of the port"),
+                    )
+                    .with_fix(unpair(i, j)),
+                );
+                continue;
+            }
+            if !matches!(
+                (iface.ty, partner.ty),
+                (InterfaceType::Import, InterfaceType::Export)
+                    | (InterfaceType::Export, InterfaceType::Import)
+            ) {
+                issues.push(
+                    ValidationIssue::new(
+                        &rules::PORT_PAIR,
+                        location,
+                        format!(
+                            "paired interfaces '{}' and '{}' must be one Import and one Export",
+                            iface.info.name, partner.info.name
+                        ),
+                        Some("Make one half of the port an Import and the other an Export"),
+                    )
+                    .with_fix(unpair(i, j)),
+                );
+                continue;
+            }
+            // Report protocol mismatch once per pair, from the import side.
+            if matches!(iface.ty, InterfaceType::Import) && iface.protocol != partner.protocol {
+                issues.push(ValidationIssue::new(
+                    &rules::PORT_PROTOCOL,
+                    location,
+                    format!(
+                        "paired interfaces '{}' and '{}' use different protocols: '{}' and '{}'",
+                        iface.info.name, partner.info.name, iface.protocol, partner.protocol
+                    ),
+                    Some("Give both halves of a bidirectional port the same protocol"),
+                ));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -811,7 +1553,9 @@ mod tests {
             env!("CARGO_MANIFEST_DIR")
         );
         let bytes = std::fs::read(&path).unwrap_or_else(|_| panic!("should read {path}"));
-        serde_json::from_slice(&bytes).unwrap_or_else(|_| panic!("should parse {name}"))
+        crate::migrate::from_slice(&bytes)
+            .unwrap_or_else(|_| panic!("should parse {name}"))
+            .0
     }
 
     #[test]
@@ -887,15 +1631,16 @@ mod tests {
                 radius: 100.0,
                 transform: None,
                 equivalence: String::new(),
-                history: String::new(),
-                transformation: String::new(),
+                history: History::default(),
+                transformation: None,
                 member_autonomy: 1.0,
-                time_constant: "Second".to_string(),
+                time_constant: Some(TimeConstant::Second.into()),
                 archetype: None,
                 agent: None,
             }],
             interactions: vec![],
             hidden_entities: vec![],
+            validation: Default::default(),
         }
     }
 
@@ -1112,6 +1857,7 @@ mod tests {
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(0.0),
+            paired_with: None,
         });
         model.systems.push(System {
             info: Info {
@@ -1142,10 +1888,10 @@ mod tests {
             radius: 50.0,
             transform: None,
             equivalence: String::new(),
-            history: String::new(),
-            transformation: String::new(),
+            history: History::default(),
+            transformation: None,
             member_autonomy: 1.0,
-            time_constant: "Second".to_string(),
+            time_constant: Some(TimeConstant::Second.into()),
             archetype: None,
             agent: None,
         });
@@ -1175,6 +1921,7 @@ mod tests {
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(0.0),
+            paired_with: None,
         });
         let result = validate(&model);
         assert!(!result.has_errors());
@@ -1218,6 +1965,7 @@ mod tests {
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(0.0),
+            paired_with: None,
         });
         // Add a flow referencing the interface so check_orphan_interfaces doesn't fire
         model.environment.sources.push(ExternalEntity {
@@ -1295,6 +2043,7 @@ mod tests {
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(0.0),
+            paired_with: None,
         });
         // Add a processor subsystem claiming this interface
         model.systems.push(System {
@@ -1329,10 +2078,10 @@ mod tests {
             radius: 12.0,
             transform: None,
             equivalence: String::new(),
-            history: String::new(),
-            transformation: String::new(),
+            history: History::default(),
+            transformation: None,
             member_autonomy: 1.0,
-            time_constant: "Second".to_string(),
+            time_constant: Some(TimeConstant::Second.into()),
             archetype: None,
             agent: None,
         });
@@ -1437,10 +2186,10 @@ mod tests {
             radius: 100.0,
             transform: None,
             equivalence: String::new(),
-            history: String::new(),
-            transformation: String::new(),
+            history: History::default(),
+            transformation: None,
             member_autonomy: 1.0,
-            time_constant: String::new(),
+            time_constant: None,
             archetype: None,
             agent: None,
         }
@@ -1546,6 +2295,7 @@ mod tests {
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(0.0),
+            paired_with: None,
         };
         m.systems[1]
             .boundary
@@ -1597,6 +2347,29 @@ mod tests {
         assert!(!validate_mode(&m, Mode::Operational).has_errors());
     }
 
+    #[test]
+    fn cybernetic_needs_a_loop_and_admits_self_loops() {
+        let mut m = two_component_model();
+        m.interactions
+            .push(flow(0, "bond", sys_id(vec![0, 0]), sys_id(vec![0, 1])));
+        let r = validate_mode(&m, Mode::Cybernetic);
+        assert!(
+            r.issues.iter().any(|i| i.location == "mode/Cybernetic"),
+            "an acyclic model cannot enter Cybernetic: {:#?}",
+            r.issues
+        );
+
+        m.interactions
+            .push(flow(1, "report", sys_id(vec![0, 1]), sys_id(vec![0, 0])));
+        assert!(!validate_mode(&m, Mode::Cybernetic).has_errors());
+
+        m.interactions.pop();
+        m.interactions
+            .push(flow(1, "loop", sys_id(vec![0, 1]), sys_id(vec![0, 1])));
+        assert!(!validate_mode(&m, Mode::Cybernetic).has_errors());
+        assert!(validate_mode(&m, Mode::Operational).has_errors());
+    }
+
     #[test]
     fn absent_mode_is_full_and_byte_stable() {
         let m = minimal_model();
@@ -1643,7 +2416,7 @@ mod tests {
     fn empty_dynamical_face_warns_in_full_mode() {
         // Clear S0's default time_constant so no system carries a dynamical face.
         let mut m = two_component_model();
-        m.systems[0].time_constant = String::new();
+        m.systems[0].time_constant = None;
         let r = validate_mode(&m, Mode::Full);
         assert!(
             !r.has_errors(),
@@ -1662,10 +2435,56 @@ mod tests {
             r.issues
         );
         // And the warning is gone once any system has a face.
-        m.systems[0].time_constant = "Second".to_string();
+        m.systems[0].time_constant = Some(TimeConstant::Second.into());
         assert!(!validate_mode(&m, Mode::Full).has_warnings());
     }
 
+    #[test]
+    fn dynamical_face_content_is_checked_in_full_mode() {
+        let mut m = two_component_model();
+        let (a, b) = (m.systems[1].info.id.clone(), m.systems[2].info.id.clone());
+        m.interactions.push(flow(0, "ore", a.clone(), b));
+        let full = |m: &WorldModel| -> Vec<String> {
+            let r = validate_mode(m, Mode::Full);
+            assert!(!r.has_errors(), "{:#?}", r.issues);
+            r.issues.into_iter().map(|i| i.location).collect()
+        };
+        assert!(full(&m).is_empty());
+
+        let material = |sub_type: &str| Substance {
+            sub_type: sub_type.to_string(),
+            ty: SubstanceType::Material,
+        };
+        m.systems[1].transformation = Some(Transformation {
+            inputs: vec![material("")],
+            outputs: vec![material(""), material("Slag")],
+            description: String::new(),
+        });
+        m.systems[0].time_constant = Some("-2 Day".parse().unwrap());
+        m.systems[2].history = serde_json::from_value(serde_json::json!([
+            {"timestamp": "2020-05", "event": "Commissioned"},
+            {"timestamp": "2019", "event": "Planned"},
+            {"timestamp": "last spring", "event": "Refitted"},
+            {"event": "Sold"},
+        ]))
+        .unwrap();
+        assert_eq!(
+            full(&m),
+            [
+                "systems[0].time_constant",
+                "systems[1].transformation.inputs[0]",
+                "systems[1].transformation.outputs[1]",
+                "systems[2].history[1]",
+                "systems[2].history[2]",
+                "systems[2].history[3]",
+            ]
+        );
+
+        // A coarser date that contains the previous one is not out of order.
+        m.systems[2].history.0[1].timestamp = "2020".to_string();
+        assert!(!full(&m).contains(&"systems[2].history[1]".to_string()));
+    }
+
     // ---- Kernel projection round trip (bert#88 Part 3) -------------------
 
     #[test]
@@ -1741,10 +2560,320 @@ mod tests {
         m.interactions
             .push(flow(0, "bond", sys_id(vec![0, 0]), sys_id(vec![0, 1])));
         let before = m.kernel();
-        for mode in [Mode::Core, Mode::Structural, Mode::Operational, Mode::Full] {
+        for mode in [
+            Mode::Core,
+            Mode::Structural,
+            Mode::Operational,
+            Mode::Full,
+            Mode::Cybernetic,
+        ] {
             let _ = validate_mode(&m, mode);
         }
         let after = m.kernel();
         assert_eq!(before, after, "mode views must not mutate the kernel");
     }
+
+    fn conservation_warnings(model: &WorldModel) -> Vec<String> {
+        validate_conservation(model, CONSERVATION_TOLERANCE)
+            .issues
+            .into_iter()
+            .map(|i| {
+                assert_eq!(i.severity, Severity::Warning);
+                format!("{}: {}", i.location, i.message)
+            })
+            .collect()
+    }
+
+    fn interaction_mut<'a>(model: &'a mut WorldModel, id: &str) -> &'a mut Interaction {
+        let id: Id = id.parse().unwrap();
+        model
+            .interactions
+            .iter_mut()
+            .find(|ix| ix.info.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn conservation_compares_like_quantities_within_tolerance() {
+        let mut m = load_example_model("bitcoin.json");
+        // Electricity comes in as TWh, heat leaves without a unit: neither balances.
+        assert_eq!(
+            conservation_warnings(&m),
+            vec![
+                "systems[0]: 'Bitcoin' does not conserve Energy (no unit): 0 in, 1 out",
+                "systems[0]: 'Bitcoin' does not conserve Energy [Twh]: 1 in, 0 out",
+            ]
+        );
+        assert!(validate_mode(&m, Mode::Full).issues.len() > validate(&m).issues.len());
+
+        interaction_mut(&mut m, "F-1.0").unit = "Twh".to_string();
+        assert!(conservation_warnings(&m).is_empty());
+
+        interaction_mut(&mut m, "F-1.0").amount = Decimal::new(995, 3);
+        assert!(conservation_warnings(&m).is_empty(), "within 1%");
+
+        interaction_mut(&mut m, "F-1.0").amount = Decimal::new(9, 1);
+        assert_eq!(
+            conservation_warnings(&m),
+            vec!["systems[0]: 'Bitcoin' does not conserve Energy [Twh]: 1 in, 0.9 out"]
+        );
+    }
+
+    #[test]
+    fn stores_and_messages_are_exempt_from_balance() {
+        let mut m = load_example_model("bitcoin.json");
+        m.systems[0].agent = Some(AgentModel {
+            primitives: vec![ProcessPrimitive::Buffering],
+            ..Default::default()
+        });
+        assert!(conservation_warnings(&m).is_empty());
+
+        // Messages are never conserved, whatever their amounts.
+        let mut m = load_example_model("llm.json");
+        for ix in &mut m.interactions {
+            ix.amount = Decimal::new(7, 0);
+        }
+        assert!(conservation_warnings(&m).is_empty());
+    }
+
+    #[test]
+    fn subsystem_flows_roll_up_to_the_parent_boundary() {
+        let mut m = load_example_model("bitcoin.json");
+        interaction_mut(&mut m, "F-1.0").unit = "Twh".to_string();
+
+        // Route the electricity into an interface processor: it now takes it
+        // in on the parent's behalf and must pass it on, and the heat the
+        // system gives out has to come from some subsystem too.
+        let processor: Id = "C0.51".parse().unwrap();
+        let p = m
+            .systems
+            .iter()
+            .position(|s| s.info.id == processor)
+            .unwrap();
+        let interface = m.systems[p].boundary.parent_interface.clone();
+        interaction_mut(&mut m, "F-1.3").sink_interface = interface;
+        assert_eq!(
+            conservation_warnings(&m),
+            vec![
+                "systems[0]: The subsystems of 'Bitcoin' give out 0 Energy [Twh] across its \
+                 boundary, but 'Bitcoin' declares 1"
+                    .to_string(),
+                format!(
+                    "systems[{p}]: 'GitHub Interface' does not conserve Energy [Twh]: 1 in, 0 out"
+                ),
+            ]
+        );
+
+        // Dumping heat straight into the environment satisfies the processor,
+        // but only if it gives out no more than the system declares.
+        let mut dump = interaction_mut(&mut m, "F-1.0").clone();
+        dump.info.id = "F0.99".parse().unwrap();
+        dump.source = processor;
+        dump.source_interface = None;
+        m.interactions.push(dump.clone());
+        assert!(conservation_warnings(&m).is_empty());
+        m.interactions.pop();
+        dump.amount = Decimal::new(3, 0);
+        m.interactions.push(dump);
+        assert_eq!(
+            conservation_warnings(&m),
+            vec![
+                "systems[0]: The subsystems of 'Bitcoin' give out 3 Energy [Twh] across its \
+                 boundary, but 'Bitcoin' declares 1"
+                    .to_string(),
+                format!(
+                    "systems[{p}]: 'GitHub Interface' does not conserve Energy [Twh]: 1 in, 3 out"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn conservation_converts_between_compatible_units() {
+        let mut m = load_example_model("bitcoin.json");
+        let heat = interaction_mut(&mut m, "F-1.0");
+        heat.unit = "GWh".to_string();
+        heat.amount = Decimal::new(1_000, 0);
+        assert!(conservation_warnings(&m).is_empty());
+
+        interaction_mut(&mut m, "F-1.0").amount = Decimal::new(500, 0);
+        assert_eq!(
+            conservation_warnings(&m),
+            vec!["systems[0]: 'Bitcoin' does not conserve Energy [GWh]: 1000 in, 500 out"]
+        );
+    }
+
+    #[test]
+    fn incompatible_units_at_one_interface_warn() {
+        let mut m = load_example_model("bitcoin.json");
+        let unit_warnings = |m: &WorldModel| -> Vec<String> {
+            validate(m)
+                .issues
+                .into_iter()
+                .filter(|i| i.message.contains("incompatible units"))
+                .map(|i| format!("{}: {}", i.location, i.message))
+                .collect()
+        };
+        assert!(unit_warnings(&m).is_empty());
+
+        // A second inflow through the electricity interface, in kilograms.
+        let mut ore = interaction_mut(&mut m, "F-1.3").clone();
+        ore.info.id = "F-1.9".parse().unwrap();
+        ore.unit = "kg".to_string();
+        m.interactions.push(ore.clone());
+        let interface = m.systems[0]
+            .boundary
+            .interfaces
+            .iter()
+            .position(|i| Some(&i.info.id) == ore.sink_interface.as_ref())
+            .unwrap();
+        assert_eq!(
+            unit_warnings(&m),
+            vec![format!(
+                "systems[0].boundary.interfaces[{interface}]: Flows meeting at interface \
+                 'Transformers' have incompatible units: 'TWh' (energy) and 'kg' (mass)"
+            )]
+        );
+
+        // Units the parser does not know are left alone, and so are
+        // exponents out of range.
+        for unit in ["Block", "J^100", "m/s^-128", "kg2 kg127"] {
+            m.interactions.last_mut().unwrap().unit = unit.to_string();
+            assert!(unit_warnings(&m).is_empty(), "{unit}");
+        }
+    }
+
+    #[test]
+    fn paired_interfaces_must_be_mutual_and_opposite() {
+        let mut m = two_component_model();
+        let mk_iface = |idx: i64, ty: InterfaceType, partner: i64| Interface {
+            info: Info {
+                id: Id {
+                    ty: IdType::Interface,
+                    indices: vec![0, 0, idx],
+                },
+                level: 2,
+                name: format!("I{idx}"),
+                description: String::new(),
+            },
+            protocol: "TCP".to_string(),
+            ty,
+            exports_to: vec![],
+            receives_from: vec![],
+            angle: Some(0.0),
+            paired_with: Some(Id {
+                ty: IdType::Interface,
+                indices: vec![0, 0, partner],
+            }),
+        };
+        m.systems[1].boundary.interfaces = vec![
+            mk_iface(0, InterfaceType::Import, 1),
+            mk_iface(1, InterfaceType::Export, 0),
+        ];
+        let pair_issues = |m: &WorldModel| -> Vec<String> {
+            validate(m)
+                .issues
+                .into_iter()
+                .filter(|i| i.location.ends_with("paired_with"))
+                .map(|i| format!("{:?} {}: {}", i.severity, i.location, i.message))
+                .collect()
+        };
+        assert!(pair_issues(&m).is_empty(), "got: {:#?}", pair_issues(&m));
+        assert_eq!(m.systems[1].boundary.port_pairs().len(), 1);
+
+        m.systems[1].boundary.interfaces[1].protocol = "UDP".to_string();
+        assert_eq!(
+            pair_issues(&m),
+            vec![
+                "Warning systems[1].boundary.interfaces[0].paired_with: paired interfaces 'I0' \
+                 and 'I1' use different protocols: 'TCP' and 'UDP'"
+            ]
+        );
+
+        m.systems[1].boundary.interfaces[1].ty = InterfaceType::Import;
+        assert_eq!(pair_issues(&m).len(), 2, "both halves are flagged");
+        assert!(m.systems[1].boundary.port_pairs().is_empty());
+
+        m.systems[1].boundary.interfaces[1] = mk_iface(1, InterfaceType::Export, 7);
+        assert_eq!(
+            pair_issues(&m),
+            vec![
+                "Error systems[1].boundary.interfaces[0].paired_with: interface 'I0' is paired \
+                 with 'I1', which is not paired back",
+                "Error systems[1].boundary.interfaces[1].paired_with: interface 'I1' is paired \
+                 with 'I0.0.7', which is not an interface on the same boundary",
+            ]
+        );
+    }
+
+    #[test]
+    fn interface_links_must_resolve() {
+        let mut m = two_component_model();
+        let iface_id = Id {
+            ty: IdType::Interface,
+            indices: vec![0, 0, 0],
+        };
+        m.systems[1].boundary.interfaces.push(Interface {
+            info: Info {
+                id: iface_id.clone(),
+                level: 2,
+                name: "Out".to_string(),
+                description: String::new(),
+            },
+            protocol: String::new(),
+            ty: InterfaceType::Export,
+            exports_to: vec![sys_id(vec![0, 1])],
+            receives_from: vec![],
+            angle: Some(0.0),
+            paired_with: None,
+        });
+        m.systems[2].boundary.parent_interface = Some(iface_id.clone());
+        let link_issues = |m: &WorldModel| -> Vec<String> {
+            validate(m)
+                .issues
+                .into_iter()
+                .filter(|i| i.code == rules::DANGLING_LINK.code)
+                .map(|i| format!("{}: {}", i.location, i.message))
+                .collect()
+        };
+        assert!(link_issues(&m).is_empty(), "got: {:#?}", link_issues(&m));
+
+        let iface = &mut m.systems[1].boundary.interfaces[0];
+        iface.exports_to.push(sys_id(vec![0, 9]));
+        iface.receives_from.push(iface_id);
+        m.systems[2].boundary.parent_interface = Some(Id {
+            ty: IdType::Interface,
+            indices: vec![0, 0, 5],
+        });
+        assert_eq!(
+            link_issues(&m),
+            vec![
+                "systems[1].boundary.interfaces[0].exports_to[1]: exports_to 'C0.9' on \
+                 interface 'Out' does not resolve to any known system or external entity",
+                "systems[1].boundary.interfaces[0].receives_from[0]: receives_from 'I0.0.0' on \
+                 interface 'Out' does not resolve to any known system or external entity",
+                "systems[2].boundary.parent_interface: parent_interface 'I0.0.5' does not \
+                 resolve to any known interface",
+            ]
+        );
+    }
+
+    #[test]
+    fn locations_map_to_json_pointers() {
+        let pointer = |location: &str| {
+            ValidationIssue::new(&rules::MISSING_FIELD, location, "", None).json_pointer()
+        };
+        assert_eq!(pointer("root"), "");
+        assert_eq!(pointer("mode/Cybernetic"), "");
+        assert_eq!(pointer("version"), "/version");
+        assert_eq!(
+            pointer("systems[3].boundary.interfaces[1].paired_with"),
+            "/systems/3/boundary/interfaces/1/paired_with"
+        );
+        assert_eq!(
+            pointer("systems[0] 'Farmer's Market. Inc'.boundary.interfaces[2] 'In'"),
+            "/systems/0/boundary/interfaces/2"
+        );
+        assert_eq!(pointer("validation.suppress[0]"), "/validation/suppress/0");
+    }
 }
@@ -59,7 +59,7 @@ impl SystemBundle {
         name: &str,
         description: &str,
         equivalence: &str,
-        time_unit: &str,
+        time_constant: Option<Timescale>,
         archetype: HcgsArchetype,
         agent: Option<crate::bevy_app::data_model::AgentModel>,
     ) -> Self {
@@ -78,10 +78,10 @@ impl SystemBundle {
                 complexity,
                 membership: 1.0,
                 equivalence: equivalence.to_string(),
-                transformation: "".to_string(),
-                history: "".to_string(),
+                transformation: None,
+                history: History::default(),
                 boundary,
-                time_unit: time_unit.to_string(),
+                time_constant,
                 archetype,
                 agent,
             },
@@ -104,6 +104,18 @@ impl SystemBundle {
             initial_position: InitialPosition::new(position),
         }
     }
+
+    /// Carries over a loaded system's transformation and history, which a
+    /// newly drawn system starts without.
+    pub fn with_dynamics(
+        mut self,
+        transformation: Option<Transformation>,
+        history: History,
+    ) -> Self {
+        self.system.transformation = transformation;
+        self.system.history = history;
+        self
+    }
 }
 
 #[derive(Bundle)]
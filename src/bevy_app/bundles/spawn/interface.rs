@@ -121,8 +121,28 @@ pub fn spawn_interface_only(
             NestingLevel::new(nesting_level),
         ))
         .observe(
-            |on: On<DragPosition>, mut writer: MessageWriter<InterfaceDrag>| {
-                writer.write(InterfaceDrag::from_on(&on));
+            |on: On<DragPosition>,
+             mut writer: MessageWriter<InterfaceDrag>,
+             paired_query: Query<&PairedInterface>,
+             transform_query: Query<&Transform>| {
+                let drag = InterfaceDrag::from_on(&on);
+
+                // Drag the other half of a port along, keeping its angular offset on the boundary.
+                if let Ok(paired) = paired_query.get(drag.target) {
+                    if let (Ok(target), Ok(partner)) = (
+                        transform_query.get(drag.target),
+                        transform_query.get(paired.partner),
+                    ) {
+                        let offset = partner.translation.truncate().to_angle()
+                            - target.translation.truncate().to_angle();
+                        writer.write(InterfaceDrag {
+                            target: paired.partner,
+                            position: Vec2::from_angle(offset).rotate(drag.position),
+                        });
+                    }
+                }
+
+                writer.write(drag);
             },
         )
         .id();
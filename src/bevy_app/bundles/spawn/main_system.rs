@@ -1,7 +1,8 @@
 use crate::bevy_app::bundles::spawn::spatial_interaction::spawn_system_with_spatial_regions;
 use crate::bevy_app::bundles::SystemBundle;
 use crate::bevy_app::components::{
-    HcgsArchetype, NestingLevel, Subsystem, SystemBoundary, SystemEnvironment,
+    HcgsArchetype, History, NestingLevel, Subsystem, SystemBoundary, SystemEnvironment, Timescale,
+    Transformation,
 };
 use crate::bevy_app::constants::MAIN_SYSTEM_RADIUS;
 use crate::bevy_app::data_model::Complexity;
@@ -22,7 +23,9 @@ pub fn spawn_main_system(
     name: &str,
     description: &str,
     equivalence: &str,
-    time_unit: &str,
+    time_constant: Option<Timescale>,
+    transformation: Option<Transformation>,
+    history: History,
     environment_name: &str,
     environment_description: &str,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -42,10 +45,11 @@ pub fn spawn_main_system(
                 name,
                 description,
                 equivalence,
-                time_unit,
+                time_constant,
                 HcgsArchetype::default(),
                 None,
-            ),
+            )
+            .with_dynamics(transformation, history),
             SystemEnvironment {
                 name: environment_name.to_string(),
                 description: environment_description.to_string(),
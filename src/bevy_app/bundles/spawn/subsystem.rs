@@ -198,7 +198,7 @@ fn spawn_subsystem_common(
                     name,
                     description,
                     "",
-                    "Second",
+                    Some(TimeConstant::Second.into()),
                     HcgsArchetype::default(),
                     None, // agent: new subsystems from UI have no agent config
                 ),
@@ -146,10 +146,10 @@ impl std::fmt::Display for SystemElement {
 ///     complexity: Complexity::Atomic,
 ///     membership: 1.0,
 ///     equivalence: "Processing Unit".to_string(),
-///     transformation: "Input -> Process -> Output".to_string(),
-///     history: "Created for manufacturing analysis".to_string(),
+///     transformation: None,
+///     history: History::default(),
 ///     boundary: SystemBoundary::default(),
-///     time_unit: "seconds".to_string(),
+///     time_constant: Some(TimeConstant::Second.into()),
 /// };
 /// ```
 ///
@@ -185,17 +185,17 @@ pub struct System {
     /// between systems with comparable functions or structures.
     pub equivalence: String,
 
-    /// Description of the system's transformation process or function.
+    /// The system's transformation process or function.
     ///
-    /// Captures the primary purpose or behavioral pattern of the system,
-    /// describing how it processes inputs to produce outputs.
-    pub transformation: String,
+    /// Lists the substances the system takes in and the ones it gives out,
+    /// with an optional description of how it turns one into the other.
+    pub transformation: Option<Transformation>,
 
     /// Historical context and evolution of the system.
     ///
-    /// Records the system's development over time, including past states,
-    /// changes, and contextual information relevant to its current configuration.
-    pub history: String,
+    /// Records the system's development over time as dated events, oldest
+    /// first.
+    pub history: History,
 
     /// Boundary definition specifying interaction capabilities.
     ///
@@ -203,11 +203,11 @@ pub struct System {
     /// types of interactions are possible across the system boundary.
     pub boundary: SystemBoundary,
 
-    /// Temporal unit for system operations and measurements.
+    /// Characteristic time scale of the system.
     ///
     /// Specifies the time scale at which the system operates, enabling
     /// proper temporal modeling and analysis of system behaviors.
-    pub time_unit: String,
+    pub time_constant: Option<Timescale>,
 
     /// HCGS archetype classification (Mobus 2022).
     ///
@@ -301,6 +301,17 @@ pub struct Interface {
     pub protocol: String,
 }
 
+/// Links the two halves of a bidirectional port: an Import and an Export interface on the same
+/// boundary that are drawn, dragged and saved as one. Both halves carry this component, each
+/// pointing at the other.
+#[derive(Copy, Clone, Debug, Component, Reflect, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct PairedInterface {
+    pub partner: Entity,
+    /// Which half of the port this interface is.
+    pub direction: InterfaceType,
+}
+
 /// Marks a Subsystem as having Interface behavior (I ⊆ C per Mobus 8-tuple).
 ///
 /// Phase 3A: Enables Interface ↔ Subsystem flows by treating interfaces as special subsystems.
@@ -448,7 +459,8 @@ pub struct Flow {
 // in the bert-core kernel crate. Re-exported here so existing paths
 // (`components::SubstanceType`, glob imports) keep working unchanged.
 pub use bert_core::{
-    HcgsArchetype, InteractionType, InteractionUsability, Parameter, SubstanceType,
+    HcgsArchetype, History, InteractionType, InteractionUsability, Parameter, SubstanceType,
+    TimeConstant, Timescale, Transformation,
 };
 
 /// Bevy-coupled view helpers for the kernel's [`SubstanceType`] — inherent
@@ -255,6 +255,7 @@ mod tests {
             systems: vec![],
             interactions: vec![],
             hidden_entities: vec![],
+            validation: Default::default(),
         };
 
         let complexity = calculate_simonian_complexity(&world_model);
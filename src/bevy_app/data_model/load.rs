@@ -2,7 +2,9 @@ use crate::bevy_app::bundles::{
     spawn_external_entity_only, spawn_interaction_only, spawn_interface_only,
     spawn_is_same_as_id_counter, spawn_main_system, SystemBundle,
 };
-use crate::bevy_app::components::{FlowEndpointHandle, FlowEndpointOffset, OriginalId};
+use crate::bevy_app::components::{
+    FlowEndpointHandle, FlowEndpointOffset, OriginalId, PairedInterface,
+};
 use crate::bevy_app::constants::{EXTERNAL_ENTITY_Z, INTERFACE_Z, SUBSYSTEM_Z};
 use crate::bevy_app::data_model::System;
 use crate::bevy_app::data_model::*;
@@ -15,8 +17,17 @@ use rust_decimal_macros::dec;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-fn load_from_bytes(bytes: &[u8]) -> Result<WorldModel, serde_json::Error> {
-    serde_json::from_slice(bytes)
+fn load_from_bytes(bytes: &[u8]) -> Result<WorldModel, migrate::MigrationError> {
+    let (model, report) = migrate::from_slice(bytes)?;
+    if !report.is_noop() {
+        info!(
+            "load_world: Migrated model from version {} to {} ({} changes)",
+            report.original_version,
+            report.final_version,
+            report.change_count()
+        );
+    }
+    Ok(model)
 }
 
 /// Context for bookkeeping while we traverse the data model and spawn the entities and components.
@@ -102,6 +113,7 @@ pub fn load_world(
         spawn_is_same_as_id_counter(&world_model, &mut is_same_as_id_counter);
 
         ctx.hidden_entities = world_model.hidden_entities.clone();
+        commands.insert_resource(ModelValidationConfig(world_model.validation.clone()));
 
         // then spawn everything
 
@@ -115,6 +127,8 @@ pub fn load_world(
             &mut fixed_system_element_geometries,
         );
 
+        pair_loaded_interfaces(&mut commands, &ctx, &world_model);
+
         make_systems_parent_child_hierarchy(&mut commands, &mut ctx, &world_model);
 
         spawn_external_entities(
@@ -302,7 +316,9 @@ fn spawn_systems_interfaces_and_external_entities(
                 &system.info.name,
                 &system.info.description,
                 &system.equivalence,
-                &system.time_constant,
+                system.time_constant,
+                system.transformation.clone(),
+                system.history.clone(),
                 &world_model.environment.info.name,
                 &world_model.environment.info.description,
                 meshes,
@@ -434,6 +450,26 @@ fn spawn_external_entities<S: HasSourcesAndSinks + HasInfo>(
     }
 }
 
+/// Link both halves of every bidirectional port. Only mutual Import/Export pairs are restored;
+/// anything else is reported by validation and loads as two independent interfaces.
+fn pair_loaded_interfaces(commands: &mut Commands, ctx: &Context, world_model: &WorldModel) {
+    for system in &world_model.systems {
+        for (import, export) in system.boundary.port_pairs() {
+            let import_entity = ctx.id_to_entity[&import.info.id];
+            let export_entity = ctx.id_to_entity[&export.info.id];
+
+            commands.entity(import_entity).insert(PairedInterface {
+                partner: export_entity,
+                direction: crate::bevy_app::components::InterfaceType::Import,
+            });
+            commands.entity(export_entity).insert(PairedInterface {
+                partner: import_entity,
+                direction: crate::bevy_app::components::InterfaceType::Export,
+            });
+        }
+    }
+}
+
 /// Go through all the systems create parent-child relationships between them. If we find a first
 /// level interface subsystem we add it as a child of the interface. Otherwise it's added as a
 /// child to its parent system.
@@ -601,11 +637,12 @@ fn spawn_loaded_subsystem(
             &system.info.name,
             &system.info.description,
             &system.equivalence,
-            &system.time_constant,
+            system.time_constant,
             // Convert Option<HcgsArchetype> to HcgsArchetype (None = Unspecified)
             system.archetype.unwrap_or_default(),
             system.agent.clone(),
-        ),
+        )
+        .with_dynamics(system.transformation.clone(), system.history.clone()),
     ));
 
     // Preserve original ID for stable serialization across save/load cycles
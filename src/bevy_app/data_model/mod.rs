@@ -9,6 +9,7 @@ pub mod complexity_calculator;
 pub mod load;
 pub mod save;
 
+pub use bert_core::migrate;
 pub use bert_core::validate;
 pub use bert_core::*;
 // Explicit re-exports win over glob imports: these kernel names collide with
@@ -2,7 +2,7 @@ use crate::bevy_app::components::*;
 use crate::bevy_app::data_model::Interaction;
 use crate::bevy_app::data_model::System;
 use crate::bevy_app::data_model::*;
-use crate::bevy_app::resources::CurrentFile;
+use crate::bevy_app::resources::{CurrentFile, ModelValidationConfig, SerializedIds};
 use crate::events::SaveSuccessEvent;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
@@ -180,8 +180,7 @@ pub fn save_world(
                     invoke::<()>(
                         "save_with_dialog",
                         &Args {
-                            data: serde_json::to_string_pretty(&model)
-                                .expect("This shouldn't fail"),
+                            data: to_canonical_json(&model),
                             path: suggested_name,
                         },
                     )
@@ -195,8 +194,7 @@ pub fn save_world(
                     invoke::<()>(
                         "save_to_file",
                         &Args {
-                            data: serde_json::to_string_pretty(&model)
-                                .expect("This shouldn't fail"),
+                            data: to_canonical_json(&model),
                             path: file_path.clone(),
                         },
                     )
@@ -223,11 +221,7 @@ pub fn save_world(
             .unwrap_or_else(|| "untitled.json".to_string());
 
         let array = Array::new();
-        let uint8_array = Uint8Array::from(
-            serde_json::to_string_pretty(&world_model)
-                .expect("This shouldn't fail")
-                .as_bytes(),
-        );
+        let uint8_array = Uint8Array::from(to_canonical_json(&world_model).as_bytes());
         array.push(&uint8_array);
 
         let blob = Blob::new_with_str_sequence(&array).unwrap();
@@ -299,7 +293,7 @@ pub fn save_world_as(
                 invoke::<()>(
                     "save_with_dialog",
                     &Args {
-                        data: serde_json::to_string_pretty(&model).expect("This shouldn't fail"),
+                        data: to_canonical_json(&model),
                         path: suggested_name,
                     },
                 )
@@ -345,6 +339,9 @@ pub fn serialize_world(
     )>,
     hidden_query: Query<Entity, With<Hidden>>,
     original_id_query: Query<&OriginalId>,
+    paired_interface_query: Query<(Entity, &PairedInterface)>,
+    validation_config: Res<ModelValidationConfig>,
+    mut serialized_ids: ResMut<SerializedIds>,
 ) -> WorldModel {
     let (system_entity, system_component, environment) = main_system_info_query
         .single()
@@ -491,29 +488,17 @@ pub fn serialize_world(
         }
     }
 
-    // Sort systems by ID for deterministic output order
     let mut systems: Vec<_> = entity_to_system.into_values().collect();
-    systems.sort_by(|a, b| a.info.id.indices.cmp(&b.info.id.indices));
+    link_paired_interfaces(&mut systems, &ctx, &paired_interface_query);
 
-    // Sort interfaces, sources, and sinks within each system for deterministic output
-    for system in &mut systems {
-        system
-            .boundary
-            .interfaces
-            .sort_by(|a, b| a.info.id.indices.cmp(&b.info.id.indices));
-        system
-            .sources
-            .sort_by(|a, b| a.info.id.indices.cmp(&b.info.id.indices));
-        system
-            .sinks
-            .sort_by(|a, b| a.info.id.indices.cmp(&b.info.id.indices));
-    }
-
-    // Sort interactions by ID for deterministic output order
-    ctx.interactions
-        .sort_by(|a, b| a.info.id.indices.cmp(&b.info.id.indices));
+    **serialized_ids = ctx
+        .entity_to_id
+        .iter()
+        .map(|(entity, id)| (id.clone(), *entity))
+        .collect();
 
-    WorldModel {
+    // Canonical order and precision so that re-saving an unchanged model is a no-op in git.
+    canonicalize(&WorldModel {
         version: CURRENT_FILE_VERSION,
         // Absent ≡ Full: the save format stays byte-stable, no `mode` key emitted.
         mode: None,
@@ -521,6 +506,36 @@ pub fn serialize_world(
         interactions: ctx.interactions,
         hidden_entities,
         environment,
+        validation: validation_config.0.clone(),
+    })
+}
+
+/// Record each bidirectional port on both of its halves. A half without flows takes its direction
+/// from the pairing, since [`register_all_system_interfaces`] would otherwise default it to Import.
+fn link_paired_interfaces(
+    systems: &mut [System],
+    ctx: &Context,
+    paired_interface_query: &Query<(Entity, &PairedInterface)>,
+) {
+    for (interface_entity, paired) in paired_interface_query {
+        let (Some(id), Some(partner_id)) = (
+            ctx.entity_to_id.get(&interface_entity),
+            ctx.entity_to_id.get(&paired.partner),
+        ) else {
+            continue;
+        };
+        let Some(interface) = systems
+            .iter_mut()
+            .flat_map(|system| system.boundary.interfaces.iter_mut())
+            .find(|interface| &interface.info.id == id)
+        else {
+            continue;
+        };
+
+        interface.paired_with = Some(partner_id.clone());
+        if interface.exports_to.is_empty() && interface.receives_from.is_empty() {
+            interface.ty = paired.direction.into();
+        }
     }
 }
 
@@ -935,6 +950,7 @@ fn register_all_system_interfaces(
                 exports_to: vec![],
                 receives_from: vec![],
                 angle: Some(interface_transform.right().truncate().to_angle()),
+                paired_with: None,
             });
     }
 }
@@ -1271,6 +1287,7 @@ fn build_interface<C: Connection>(
             exports_to: vec![],
             receives_from: vec![],
             angle: Some(interface_transform.right().truncate().to_angle()),
+            paired_with: None,
         });
 
     system.boundary.interfaces.len() - 1
@@ -1426,7 +1443,7 @@ fn build_system(
         history: system.history.clone(),
         transformation: system.transformation.clone(),
         member_autonomy: 1.0,
-        time_constant: system.time_unit.clone(),
+        time_constant: system.time_constant,
         archetype,
         agent: system.agent.clone(),
     };
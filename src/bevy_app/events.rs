@@ -1,5 +1,5 @@
 //! Custom defined events
-use crate::bevy_app::data_model::WorldModel;
+use crate::bevy_app::data_model::{Id, WorldModel};
 use crate::bevy_app::plugins::mouse_interaction::DragPosition;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -75,6 +75,14 @@ pub enum ZoomEvent {
 #[derive(Message, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct DeselectAllEvent;
 
+/// Fires from Leptos to Bevy to select the members of a feedback loop.
+///
+/// Ids refer to the model last sent in a [`TreeEvent`].
+#[derive(Message, Debug, Clone)]
+pub struct HighlightLoopEvent {
+    pub members: Vec<Id>,
+}
+
 /// Fires from Leptos palette panel to Bevy to enter placement mode
 #[derive(Message, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PaletteClickEvent {
@@ -114,6 +114,7 @@ pub fn init_bevy_app(
     save_success_bevy_sender: BevyMessageSender<SaveSuccessEvent>,
     mode_change_sender: BevyMessageSender<ModeChangeEvent>,
     cancel_mode_receiver: BevyMessageReceiver<CancelModeEvent>,
+    highlight_loop_receiver: BevyMessageReceiver<HighlightLoopEvent>,
 ) -> App {
     let mut app = App::new();
     app.add_plugins((
@@ -149,6 +150,8 @@ pub fn init_bevy_app(
     .init_resource::<ZoomTarget>() // Phase 3B: auto-zoom animation state
     .init_resource::<FixedSystemElementGeometriesByNestingLevel>()
     .init_resource::<IsSameAsIdCounter>()
+    .init_resource::<SerializedIds>()
+    .init_resource::<ModelValidationConfig>()
     // .add_systems(Startup, |mut commands: Commands| {
     //     init_save_notification_channel(&mut commands);
     // })
@@ -172,6 +175,7 @@ pub fn init_bevy_app(
     .add_message::<RedoEvent>()
     .add_message::<ModeChangeEvent>() // Bottom toolbar mode indicator
     .add_message::<CancelModeEvent>() // ESC cancel from JavaScript
+    .add_message::<HighlightLoopEvent>()
     .init_state::<AppState>()
     .sync_leptos_signal_with_query(selected_details_query)
     .sync_leptos_signal_with_query(interface_details_query)
@@ -189,6 +193,7 @@ pub fn init_bevy_app(
     .import_message_from_leptos(palette_click_receiver)
     .import_message_from_leptos(save_success_event_receiver)
     .import_message_from_leptos(cancel_mode_receiver)
+    .import_message_from_leptos(highlight_loop_receiver)
     .export_message_to_leptos(tree_event_sender)
     .export_message_to_leptos(save_success_bevy_sender)
     .export_message_to_leptos(mode_change_sender)
@@ -209,6 +214,7 @@ pub fn init_bevy_app(
             control_zoom_from_mouse_wheel.run_if(wheel_zoom_condition.clone()),
             handle_zoom_events,
             handle_deselect_events,
+            handle_highlight_loop_events,
         )
             .in_set(AllSet),
     );
@@ -220,6 +226,7 @@ pub fn init_bevy_app(
             control_zoom_from_mouse_wheel.run_if(wheel_zoom_condition.clone()),
             handle_zoom_events,
             handle_deselect_events,
+            handle_highlight_loop_events,
         )
             .in_set(AllSet),
     );
@@ -302,9 +309,11 @@ pub fn init_bevy_app(
                 cleanup_labelled_removal::<MarkerLabel>,
                 update_and_cleanup_source_sink_equivalence,
                 cleanup_interface_removal,
+                cleanup_paired_interface_removal,
                 cleanup_subsystem_removal,
                 cleanup_flow_removal,
                 cleanup_focused_system,
+                sync_port_directions,
             )
                 .in_set(RemovalCleanupSet),
             (
@@ -354,8 +363,16 @@ pub fn init_bevy_app(
                 apply_zoom_to_system_radii, // this is not in ZoomSet on purpose
                 update_is_same_as_id_label,
             ),
-            (apply_sink_and_source_equivalence
-                .run_if(in_state(AppState::Normal).and(input_just_pressed(KeyCode::KeyE))),),
+            (
+                apply_sink_and_source_equivalence
+                    .run_if(in_state(AppState::Normal).and(input_just_pressed(KeyCode::KeyE))),
+                // Ctrl/Cmd+P is the screenshot shortcut
+                pair_selected_interfaces.run_if(
+                    in_state(AppState::Normal)
+                        .and(input_just_pressed(KeyCode::KeyP))
+                        .and(not(input_pressed(MODIFIER))),
+                ),
+            ),
         )
             .in_set(AllSet),
     )
@@ -416,6 +433,7 @@ pub fn init_bevy_app(
                 update_interface_button_from_interaction,
                 update_text_color,
                 update_feedback_arcs,
+                update_port_brackets,
             )
                 .in_set(GeometryUpdateSet),
             apply_zoom_to_added_label.after(AutoSpawnLabelSet),
@@ -483,7 +501,9 @@ pub fn init_bevy_app(
     .register_type::<FocusedSystem>()
     .register_type::<Theme>()
     .register_type::<Zoom>()
-    .register_type::<FeedbackArc>();
+    .register_type::<FeedbackArc>()
+    .register_type::<PairedInterface>()
+    .register_type::<PortBracket>();
 
     let _ = app
         .world_mut()
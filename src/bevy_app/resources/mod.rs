@@ -4,7 +4,9 @@ mod system_element_geometry;
 pub use save_notifications::*;
 pub use system_element_geometry::*;
 
+use crate::bevy_app::data_model::{Id, RuleConfig};
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 #[derive(Debug, Resource, Deref, DerefMut, Copy, Clone, Reflect)]
 #[reflect(Resource)]
@@ -86,6 +88,19 @@ impl StrokeTessellator {
 #[reflect(Resource)]
 pub struct CurrentFile(pub Option<String>);
 
+/// The entity behind each id the last [`serialize_world`] assigned, so ids
+/// reported by analyses of the serialized model can be traced back to the canvas.
+///
+/// [`serialize_world`]: crate::bevy_app::data_model::save::serialize_world
+#[derive(Resource, Deref, DerefMut, Default, Debug)]
+pub struct SerializedIds(pub HashMap<Id, Entity>);
+
+/// Rule overrides of the loaded model, written back unchanged by [`serialize_world`].
+///
+/// [`serialize_world`]: crate::bevy_app::data_model::save::serialize_world
+#[derive(Resource, Deref, DerefMut, Default, Debug)]
+pub struct ModelValidationConfig(pub RuleConfig);
+
 /// Resource controlling the background color of the application.
 ///
 /// Simple toggle between the original BERT beige background and clean white background
@@ -8,6 +8,7 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 // ── Kernel contract types, extracted to bert-core ────────────────────────────
+use bert_core::Unit;
 pub use bert_core::{ParameterSuggestion, ParameterType, ParameterValue, SmartParameter};
 
 /// Static database providing context-aware parameter suggestions
@@ -392,6 +393,34 @@ impl SmartParameterDatabase {
             .unwrap_or_default()
     }
 
+    /// Validate the unit of a numeric parameter: it must parse, and when `name`
+    /// is a suggested parameter with a default unit, it must measure the same
+    /// kind of quantity (a "Weight" in kWh is rejected). Returns the parsed unit.
+    pub fn check_unit(
+        &self,
+        substance_type: &SubstanceType,
+        name: &str,
+        unit: &str,
+    ) -> Result<Unit, String> {
+        let parsed = Unit::parse(unit).map_err(|e| e.to_string())?;
+        let expected = self
+            .get_suggestions(substance_type)
+            .into_iter()
+            .find(|suggestion| suggestion.display_name == name)
+            .and_then(|suggestion| match &suggestion.default_value {
+                ParameterValue::Numeric { unit, .. } => Unit::parse(unit).ok(),
+                _ => None,
+            });
+        match expected {
+            Some(expected) if !expected.is_compatible(&parsed) => Err(format!(
+                "{name} is measured in {} (e.g. {expected}), but {parsed} is {}",
+                expected.dimension(),
+                parsed.dimension()
+            )),
+            _ => Ok(parsed),
+        }
+    }
+
     /// Search parameter suggestions with fuzzy matching
     pub fn search_suggestions(
         &self,
@@ -459,6 +488,33 @@ mod tests {
         assert_eq!(flow_results[0].display_name, "Flow Rate");
     }
 
+    #[test]
+    fn test_default_units_parse() {
+        let db = SmartParameterDatabase::new();
+        for suggestions in db.suggestions.values() {
+            for suggestion in suggestions {
+                if let ParameterValue::Numeric { unit, .. } = &suggestion.default_value {
+                    assert!(Unit::parse(unit).is_ok(), "{unit}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_unit() {
+        let db = SmartParameterDatabase::new();
+        let weight = |unit| db.check_unit(&SubstanceType::Material, "Weight", unit);
+
+        assert_eq!(weight("kg").unwrap().symbol(), "kg");
+        assert_eq!(weight("tonnes").unwrap().symbol(), "t");
+        assert!(weight("kWh").unwrap_err().contains("mass"));
+        assert!(weight("furlongs").is_err());
+        // Parameters without a suggestion only need a valid unit.
+        assert!(db
+            .check_unit(&SubstanceType::Material, "Custom", "kWh")
+            .is_ok());
+    }
+
     #[test]
     fn test_smart_parameter_creation() {
         let param = SmartParameter::new(
@@ -30,12 +30,13 @@ use crate::bevy_app::bundles::spawn_interaction_only;
 use crate::bevy_app::components::{
     EndTargetType, ExternalEntity, Flow, FlowCurve, FlowEndConnection, FlowEndInterfaceConnection,
     FlowStartConnection, FlowStartInterfaceConnection, InteractionType, InteractionUsability,
-    Interface, InterfaceBehavior, NestingLevel, Parameter, StartTargetType, SubstanceType,
-    Subsystem, System,
+    Interface, InterfaceBehavior, InterfaceType, NestingLevel, PairedInterface, Parameter,
+    StartTargetType, SubstanceType, Subsystem, System,
 };
 use crate::bevy_app::constants::INTERFACE_WIDTH_HALF;
 use crate::bevy_app::events::DeselectAllEvent;
 use crate::bevy_app::resources::{FocusedSystem, StrokeTessellator, Zoom};
+use crate::bevy_app::systems::flow_direction;
 use crate::bevy_app::utils::compute_end_and_direction_from_subsystem;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
@@ -201,7 +202,8 @@ pub fn finalize_connection(
     // Query subsystems with their parent system reference
     subsystem_query: Query<(&Subsystem, &NestingLevel)>,
     // Query interfaces (children of systems)
-    interface_query: Query<&NestingLevel, With<Interface>>,
+    // The pairing is here too, so a port's halves keep opposite directions
+    interface_query: Query<(&NestingLevel, Option<&PairedInterface>), With<Interface>>,
     interface_behavior_query: Query<&InterfaceBehavior>,
     // Query external entities
     external_entity_query: Query<&NestingLevel, With<ExternalEntity>>,
@@ -213,7 +215,13 @@ pub fn finalize_connection(
     parent_query: Query<&ChildOf>, // Used for finding parent system
     // Query flow connections to determine Source vs Sink for E-network validation
     // Combined query to stay under Bevy's 16-parameter limit
-    flow_connections_query: Query<(&FlowStartConnection, &FlowEndConnection)>,
+    // Interface connections are here too, for the directions of port halves
+    flow_connections_query: Query<(
+        &FlowStartConnection,
+        &FlowEndConnection,
+        Option<&FlowStartInterfaceConnection>,
+        Option<&FlowEndInterfaceConnection>,
+    )>,
     // FocusedSystem for E-network parent (external entities at SOI level)
     focused_system: Res<FocusedSystem>,
     mut commands: Commands,
@@ -271,21 +279,21 @@ pub fn finalize_connection(
         //
         // Check what roles each external entity plays based on existing flows
         let source_is_sink = source_is_external
-            && flow_connections_query.iter().any(|(_, end_conn)| {
+            && flow_connections_query.iter().any(|(_, end_conn, ..)| {
                 end_conn.target == source_entity && end_conn.target_type == EndTargetType::Sink
             });
         let source_is_source = source_is_external
-            && flow_connections_query.iter().any(|(start_conn, _)| {
+            && flow_connections_query.iter().any(|(start_conn, ..)| {
                 start_conn.target == source_entity
                     && start_conn.target_type == StartTargetType::Source
             });
         let dest_is_source = dest_is_external
-            && flow_connections_query.iter().any(|(start_conn, _)| {
+            && flow_connections_query.iter().any(|(start_conn, ..)| {
                 start_conn.target == destination_entity
                     && start_conn.target_type == StartTargetType::Source
             });
         let dest_is_sink = dest_is_external
-            && flow_connections_query.iter().any(|(_, end_conn)| {
+            && flow_connections_query.iter().any(|(_, end_conn, ..)| {
                 end_conn.target == destination_entity && end_conn.target_type == EndTargetType::Sink
             });
 
@@ -320,11 +328,36 @@ pub fn finalize_connection(
             continue;
         }
 
+        // Validate: The two halves of a port stay one Import and one Export
+        let clashing_direction = [
+            (source_entity, InterfaceType::Export),
+            (destination_entity, InterfaceType::Import),
+        ]
+        .into_iter()
+        .find_map(|(half, direction)| {
+            let partner = interface_query.get(half).ok()?.1?.partner;
+            let ends = flow_connections_query
+                .iter()
+                .map(|(_, _, start, end)| (start.map(|c| c.target), end.map(|c| c.target)));
+            (flow_direction(partner, ends) == Some(direction)).then_some(direction)
+        });
+        if let Some(direction) = clashing_direction {
+            match direction {
+                InterfaceType::Export => {
+                    warn!("❌ The other half of this port already exports - start the flow there")
+                }
+                InterfaceType::Import => {
+                    warn!("❌ The other half of this port already imports - end the flow there")
+                }
+            }
+            continue;
+        }
+
         // Get nesting levels for validation
         let source_nesting_level = if source_is_subsystem {
             *subsystem_query.get(source_entity).unwrap().1
         } else if source_is_interface {
-            *interface_query.get(source_entity).unwrap()
+            *interface_query.get(source_entity).unwrap().0
         } else {
             *external_entity_query.get(source_entity).unwrap()
         };
@@ -332,7 +365,7 @@ pub fn finalize_connection(
         let dest_nesting_level = if dest_is_subsystem {
             *subsystem_query.get(destination_entity).unwrap().1
         } else if dest_is_interface {
-            *interface_query.get(destination_entity).unwrap()
+            *interface_query.get(destination_entity).unwrap().0
         } else {
             *external_entity_query.get(destination_entity).unwrap()
         };
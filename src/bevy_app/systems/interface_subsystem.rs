@@ -116,7 +116,7 @@ pub fn create_interface_subsystem_on_keypress(
 /// - Export: flow starts from this interface (FlowStartInterfaceConnection)
 ///
 /// Defaults to Import if no flows are connected (can be changed later when flows added).
-fn determine_interface_type(
+pub fn determine_interface_type(
     interface_entity: Entity,
     flow_query: &Query<(
         Entity,
@@ -174,6 +174,23 @@ pub fn listen_to_remove_marker_label_event(
     }
 }
 
+/// Unpairs the surviving half of a bidirectional port when the other half is deleted.
+pub fn cleanup_paired_interface_removal(
+    mut commands: Commands,
+    mut removed_pairs: RemovedComponents<PairedInterface>,
+    paired_query: Query<(Entity, &PairedInterface)>,
+) {
+    for removed_interface in removed_pairs.read() {
+        for (interface_entity, paired) in &paired_query {
+            if paired.partner == removed_interface {
+                commands
+                    .entity(interface_entity)
+                    .remove::<PairedInterface>();
+            }
+        }
+    }
+}
+
 pub fn cleanup_interface_removal(
     mut commands: Commands,
     mut removed_interfaces: RemovedComponents<Interface>,
@@ -1,5 +1,6 @@
 //! Systems used in the Application Startup Schedule.
 use crate::bevy_app::bundles::spawn_main_system;
+use crate::bevy_app::components::{History, TimeConstant};
 use crate::bevy_app::constants::*;
 use crate::bevy_app::data_model::Complexity;
 use crate::bevy_app::resources::*;
@@ -41,7 +42,9 @@ pub fn setup(
         "System",
         "",
         "",
-        "Second",
+        Some(TimeConstant::Second.into()),
+        None,
+        History::default(),
         "Environment",
         "",
         &mut meshes,
@@ -0,0 +1,36 @@
+//! Highlighting of kernel feedback loops picked in the Leptos loop list.
+//!
+//! Unlike the environmental loops drawn by [`super::feedback_arc`], these are
+//! cycles among the model's own systems and flows, found by
+//! `WorldModel::feedback_loops` on the serialized world. Their ids are mapped
+//! back to entities through [`SerializedIds`] and selected, so the usual
+//! selection highlight marks the loop.
+
+use bevy::prelude::*;
+
+use crate::bevy_app::events::HighlightLoopEvent;
+use crate::bevy_app::plugins::mouse_interaction::{do_deselect_all, PickSelection};
+use crate::bevy_app::resources::SerializedIds;
+
+/// Replaces the current selection with the members of the requested loop.
+pub fn handle_highlight_loop_events(
+    mut highlight_events: MessageReader<HighlightLoopEvent>,
+    serialized_ids: Res<SerializedIds>,
+    mut pick_selection_query: Query<&mut PickSelection>,
+) {
+    let Some(event) = highlight_events.read().last() else {
+        return;
+    };
+
+    do_deselect_all(&mut pick_selection_query);
+
+    for id in &event.members {
+        let Some(&entity) = serialized_ids.get(id) else {
+            warn!("Feedback loop member {id} has no entity; was the model edited since?");
+            continue;
+        };
+        if let Ok(mut selection) = pick_selection_query.get_mut(entity) {
+            selection.is_selected = true;
+        }
+    }
+}
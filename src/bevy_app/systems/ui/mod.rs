@@ -2,9 +2,11 @@ mod add_remove_buttons; // DISABLED: Button systems not used in drag-and-drop wo
 mod color;
 mod drag;
 mod feedback_arc;
+mod feedback_loop;
 mod flow;
 mod hiding;
 mod label;
+mod paired_interface;
 mod selected_helper;
 mod source_sink_equivalence;
 mod zoom;
@@ -15,9 +17,11 @@ pub use add_remove_buttons::*;
 pub use color::*;
 pub use drag::*;
 pub use feedback_arc::*;
+pub use feedback_loop::*;
 pub use flow::*;
 pub use hiding::*;
 pub use label::*;
+pub use paired_interface::*;
 pub use selected_helper::*;
 pub use source_sink_equivalence::*;
 pub use zoom::*;
@@ -0,0 +1,284 @@
+//! Bidirectional ports.
+//!
+//! A port that both receives and emits is modelled as an Import and an Export
+//! interface on the same boundary, linked through [`PairedInterface`]. Pressing
+//! **P** with an interface selected creates its opposite half right next to it;
+//! from then on the two are dragged together and drawn joined by a bracket:
+//!
+//! ```text
+//!        ╭──╮
+//!     ──▶▐  ▌──▶        import half ▐ ▌ export half
+//!   ──────────────      system boundary
+//! ```
+//!
+//! A half without flows may still turn out to be the other direction once
+//! flows attach; [`sync_port_directions`] flips both halves to follow them.
+//! A flow that would give both halves the same direction is refused when it
+//! is drawn.
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::collections::HashMap;
+
+use crate::bevy_app::bundles::spawn_interface_only;
+use crate::bevy_app::components::*;
+use crate::bevy_app::constants::{INTERFACE_HEIGHT_HALF, INTERFACE_Z};
+use crate::bevy_app::resources::{
+    FixedSystemElementGeometriesByNestingLevel, StrokeTessellator, Zoom,
+};
+use crate::bevy_app::systems::determine_interface_type;
+
+/// Marker component for the bracket joining the two halves of a port.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct PortBracket {
+    /// The import half of the port this bracket belongs to.
+    pub import: Entity,
+}
+
+/// Z-level for port brackets (just behind the interfaces they join)
+const PORT_BRACKET_Z: f32 = INTERFACE_Z - 1.0;
+/// Line width for port brackets
+const PORT_BRACKET_LINE_WIDTH: f32 = 3.0;
+/// Distance between the centers of the two halves, in interface half-heights
+const PORT_GAP_IN_HALF_HEIGHTS: f32 = 2.4;
+/// How far the bracket bulges out of the boundary, relative to the gap
+const PORT_BRACKET_BULGE: f32 = 0.6;
+
+/// Creates the opposite half for every selected interface that isn't paired yet.
+///
+/// The new half copies name, description, protocol and substance, and is placed on the
+/// boundary one interface-width along from the original.
+#[allow(clippy::too_many_arguments)]
+pub fn pair_selected_interfaces(
+    mut commands: Commands,
+    selected_interface_query: Query<
+        (
+            Entity,
+            &Interface,
+            &InterfaceBehavior,
+            &Transform,
+            &NestingLevel,
+            &ChildOf,
+            &Name,
+            &ElementDescription,
+        ),
+        (With<SelectedHighlightHelperAdded>, Without<PairedInterface>),
+    >,
+    flow_interface_query: Query<(
+        Entity,
+        &Flow,
+        Option<&FlowEndInterfaceConnection>,
+        Option<&FlowStartInterfaceConnection>,
+    )>,
+    system_query: Query<&crate::bevy_app::components::System>,
+    zoom: Res<Zoom>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut stroke_tess: ResMut<StrokeTessellator>,
+    mut fixed_system_element_geometries: ResMut<FixedSystemElementGeometriesByNestingLevel>,
+) {
+    for (
+        interface_entity,
+        interface,
+        behavior,
+        transform,
+        nesting_level,
+        parent,
+        name,
+        description,
+    ) in &selected_interface_query
+    {
+        let Ok(system) = system_query.get(parent.parent()) else {
+            continue;
+        };
+
+        let direction = determine_interface_type(interface_entity, &flow_interface_query);
+        let partner_direction = opposite(direction);
+
+        let gap = PORT_GAP_IN_HALF_HEIGHTS
+            * INTERFACE_HEIGHT_HALF
+            * NestingLevel::compute_scale(**nesting_level, **zoom);
+        let rotation = Quat::from_rotation_z(gap / (system.radius * **zoom));
+
+        let mut partner_transform = *transform;
+        partner_transform.translation = rotation * transform.translation;
+        partner_transform.rotation = rotation * transform.rotation;
+        let initial_position =
+            InitialPosition::new(partner_transform.translation.truncate() / **zoom);
+
+        let partner_entity = spawn_interface_only(
+            &mut commands,
+            behavior.substance_type,
+            **nesting_level,
+            parent.parent(),
+            **zoom,
+            false,
+            name.as_str(),
+            &description.text,
+            interface.protocol.clone(),
+            partner_transform,
+            initial_position,
+            &mut stroke_tess,
+            &mut meshes,
+            &mut fixed_system_element_geometries,
+        );
+
+        commands.entity(interface_entity).insert(PairedInterface {
+            partner: partner_entity,
+            direction,
+        });
+        commands.entity(partner_entity).insert(PairedInterface {
+            partner: interface_entity,
+            direction: partner_direction,
+        });
+    }
+}
+
+fn opposite(direction: InterfaceType) -> InterfaceType {
+    match direction {
+        InterfaceType::Import => InterfaceType::Export,
+        InterfaceType::Export => InterfaceType::Import,
+    }
+}
+
+/// The direction flows give `interface`: Import if one ends there, Export if one starts there.
+/// `ends` are the interfaces each flow starts and ends at.
+pub fn flow_direction(
+    interface: Entity,
+    ends: impl IntoIterator<Item = (Option<Entity>, Option<Entity>)>,
+) -> Option<InterfaceType> {
+    ends.into_iter().find_map(|(start, end)| {
+        if end == Some(interface) {
+            Some(InterfaceType::Import)
+        } else if start == Some(interface) {
+            Some(InterfaceType::Export)
+        } else {
+            None
+        }
+    })
+}
+
+/// Keeps each half's direction in line with its flows when flows attach or go: a half takes the
+/// direction of its own flows, or else the opposite of its partner's.
+pub fn sync_port_directions(
+    mut paired_query: Query<(Entity, &mut PairedInterface)>,
+    flow_query: Query<(
+        Option<&FlowStartInterfaceConnection>,
+        Option<&FlowEndInterfaceConnection>,
+    )>,
+    changed_flows: Query<
+        (),
+        Or<(
+            Changed<FlowStartInterfaceConnection>,
+            Changed<FlowEndInterfaceConnection>,
+        )>,
+    >,
+    mut removed_starts: RemovedComponents<FlowStartInterfaceConnection>,
+    mut removed_ends: RemovedComponents<FlowEndInterfaceConnection>,
+) {
+    let removed = removed_starts.read().count() + removed_ends.read().count() > 0;
+    if changed_flows.is_empty() && !removed {
+        return;
+    }
+
+    let ends: Vec<_> = flow_query
+        .iter()
+        .map(|(start, end)| (start.map(|c| c.target), end.map(|c| c.target)))
+        .collect();
+    for (interface, mut paired) in &mut paired_query {
+        let direction = flow_direction(interface, ends.iter().copied())
+            .or_else(|| flow_direction(paired.partner, ends.iter().copied()).map(opposite));
+        if let Some(direction) = direction {
+            if paired.direction != direction {
+                paired.direction = direction;
+            }
+        }
+    }
+}
+
+/// Spawns, updates and despawns the brackets that draw each pair of interfaces as one port. A
+/// bracket is only reshaped when one of its halves or their system moved.
+pub fn update_port_brackets(
+    mut commands: Commands,
+    paired_query: Query<(
+        Entity,
+        &PairedInterface,
+        Ref<GlobalTransform>,
+        &ChildOf,
+        Has<Hidden>,
+    )>,
+    system_query: Query<Ref<GlobalTransform>, With<crate::bevy_app::components::System>>,
+    existing_brackets: Query<(Entity, &PortBracket)>,
+) {
+    let mut stale: HashMap<Entity, Entity> = existing_brackets
+        .iter()
+        .map(|(bracket_entity, bracket)| (bracket.import, bracket_entity))
+        .collect();
+
+    for (import, paired, import_transform, parent, import_hidden) in &paired_query {
+        if paired.direction != InterfaceType::Import || import_hidden {
+            continue;
+        }
+        let Ok((_, _, export_transform, _, export_hidden)) = paired_query.get(paired.partner)
+        else {
+            continue;
+        };
+        let Ok(system_transform) = system_query.get(parent.parent()) else {
+            continue;
+        };
+        if export_hidden {
+            continue;
+        }
+
+        let bracket_entity = stale.remove(&import);
+        let moved = import_transform.is_changed()
+            || export_transform.is_changed()
+            || system_transform.is_changed();
+        if bracket_entity.is_some() && !moved {
+            continue;
+        }
+
+        let shape = create_bracket_shape(
+            import_transform.translation().truncate(),
+            export_transform.translation().truncate(),
+            system_transform.translation().truncate(),
+        );
+
+        match bracket_entity {
+            Some(bracket_entity) => {
+                commands.entity(bracket_entity).insert(shape);
+            }
+            None => {
+                commands.spawn((
+                    PortBracket { import },
+                    shape,
+                    Transform::from_xyz(0.0, 0.0, PORT_BRACKET_Z),
+                ));
+            }
+        }
+    }
+
+    for bracket_entity in stale.into_values() {
+        commands.entity(bracket_entity).despawn();
+    }
+}
+
+/// Create the arc joining both halves, bulging out of the boundary.
+fn create_bracket_shape(import_pos: Vec2, export_pos: Vec2, system_center: Vec2) -> Shape {
+    let midpoint = (import_pos + export_pos) / 2.0;
+    let outward = (midpoint - system_center).normalize_or_zero();
+    let control_point = midpoint + outward * import_pos.distance(export_pos) * PORT_BRACKET_BULGE;
+
+    let bracket_path = ShapePath::new()
+        .move_to(import_pos)
+        .quadratic_bezier_to(control_point, export_pos);
+
+    let stroke = Stroke {
+        color: Color::srgba(0.2, 0.2, 0.2, 0.9),
+        options: StrokeOptions::default()
+            .with_line_width(PORT_BRACKET_LINE_WIDTH)
+            .with_line_cap(LineCap::Round),
+    };
+
+    ShapeBuilder::with(&bracket_path).stroke(stroke).build()
+}
@@ -0,0 +1,170 @@
+use crate::bevy_app::data_model::graph::{self, GraphFormat};
+use crate::bevy_app::data_model::WorldModel;
+use crate::events::TreeEvent;
+use leptos::prelude::*;
+use leptos_bevy_canvas::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Panel that downloads the current model as a graph file.
+///
+/// The model comes from the same [`TreeEvent`] the tree view uses. The depth
+/// select folds the system tree below that level; each format button
+/// downloads the graph and reports the file name through `on_exported`.
+#[component]
+pub fn ExportMenu(
+    #[prop(into)] visible: Signal<bool>,
+    event_receiver: LeptosMessageReceiver<TreeEvent>,
+    #[prop(into)] on_exported: Callback<String>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let (depth, set_depth) = signal(None::<usize>);
+
+    view! {
+        <Show when=move || visible.get()>
+            <div class="fixed top-16 left-4 z-20 w-80 bg-white rounded-lg shadow-xl p-4">
+                <div class="flex justify-between items-center mb-3">
+                    <h2 class="text-lg font-bold text-gray-900">"Export Graph"</h2>
+                    <button
+                        class="text-gray-400 hover:text-gray-600 text-xl font-bold"
+                        on:click=move |_| on_close.run(())
+                    >
+                        "×"
+                    </button>
+                </div>
+                {
+                    let event_receiver = event_receiver.clone();
+                    move || {
+                        event_receiver.read().as_ref().map(|TreeEvent { world_model }| {
+                            export_options(world_model.clone(), depth, set_depth, on_exported)
+                        })
+                    }
+                }
+            </div>
+        </Show>
+    }
+}
+
+fn export_options(
+    world_model: WorldModel,
+    depth: ReadSignal<Option<usize>>,
+    set_depth: WriteSignal<Option<usize>>,
+    on_exported: Callback<String>,
+) -> impl IntoView {
+    let deepest = world_model
+        .systems
+        .iter()
+        .map(|system| system.info.level.max(0) as usize)
+        .max()
+        .unwrap_or(0);
+    let name = world_model
+        .systems
+        .first()
+        .map(|system| file_stem(&system.info.name))
+        .unwrap_or_else(|| "model".to_string());
+
+    view! {
+        <label class="block text-sm font-medium text-gray-900 mb-1" for="export-depth">
+            "Depth"
+        </label>
+        <select
+            id="export-depth"
+            class="w-full mb-3 py-1.5 pl-3 text-sm text-gray-900 bg-white rounded-md outline outline-1 -outline-offset-1 outline-gray-300"
+            on:change=move |ev| set_depth.set(event_target_value(&ev).parse().ok())
+        >
+            <option value="all" prop:selected=move || depth.get().is_none()>
+                "All levels"
+            </option>
+            {(0..deepest)
+                .map(|level| {
+                    let label = match level {
+                        0 => "System of Interest only".to_string(),
+                        1 => "1 level".to_string(),
+                        n => format!("{n} levels"),
+                    };
+                    view! {
+                        <option
+                            value=level.to_string()
+                            prop:selected=move || depth.get() == Some(level)
+                        >
+                            {label}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+        <div class="grid grid-cols-2 gap-2">
+            {GraphFormat::ALL
+                .into_iter()
+                .map(|format| {
+                    let world_model = world_model.clone();
+                    let filename = format!("{name}.{}", format.extension());
+                    view! {
+                        <button
+                            class="px-3 py-2 rounded border border-gray-200 hover:bg-gray-50 text-sm text-gray-800"
+                            on:click=move |_| {
+                                let text = graph::export(&world_model, format, depth.get());
+                                match trigger_download(&text, &filename, format.mime_type()) {
+                                    Ok(()) => on_exported.run(format!("Exported {filename}")),
+                                    Err(e) => {
+                                        leptos::logging::error!("Graph export failed: {e:?}");
+                                        on_exported.run(format!("Failed to export {filename}"));
+                                    }
+                                }
+                            }
+                        >
+                            {format.to_string()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// A file name for a model named `name`: `Hash Rate` → `hash_rate`.
+fn file_stem(name: &str) -> String {
+    let stem = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase();
+    if stem.is_empty() {
+        "model".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Download `text` as `filename` through a hidden anchor, as the screenshot
+/// system does for PNGs.
+fn trigger_download(text: &str, filename: &str, mime_type: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document available"))?;
+
+    let array = js_sys::Array::new();
+    array.push(&js_sys::Uint8Array::from(text.as_bytes()));
+    let blob_options = BlobPropertyBag::new();
+    blob_options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &blob_options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let anchor = document
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.style().set_property("display", "none")?;
+
+    let body = document
+        .body()
+        .ok_or_else(|| JsValue::from_str("No body element"))?;
+    body.append_child(&anchor)?;
+    anchor.click();
+    body.remove_child(&anchor)?;
+    Url::revoke_object_url(&url)?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::bevy_app::data_model::{FeedbackLoop, Id, Polarity, WorldModel};
+use crate::events::TreeEvent;
+use leptos::prelude::*;
+use leptos_bevy_canvas::prelude::*;
+use std::collections::HashMap;
+
+/// Side panel listing the feedback loops of the current model.
+///
+/// The model comes from the same [`TreeEvent`] the tree view uses; clicking a
+/// loop hands its members and flows to `on_select` for highlighting.
+#[component]
+pub fn FeedbackLoops(
+    #[prop(into)] visible: Signal<bool>,
+    event_receiver: LeptosMessageReceiver<TreeEvent>,
+    #[prop(into)] on_select: Callback<Vec<Id>>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    view! {
+        <Show when=move || visible.get()>
+            <div class="fixed top-16 left-4 z-20 w-96 max-h-[80vh] overflow-y-auto bg-white rounded-lg shadow-xl p-4">
+                <div class="flex justify-between items-center mb-3">
+                    <h2 class="text-lg font-bold text-gray-900">"Feedback Loops"</h2>
+                    <button
+                        class="text-gray-400 hover:text-gray-600 text-xl font-bold"
+                        on:click=move |_| on_close.run(())
+                    >
+                        "×"
+                    </button>
+                </div>
+                {
+                    let event_receiver = event_receiver.clone();
+                    move || {
+                        event_receiver
+                            .read()
+                            .as_ref()
+                            .map(|TreeEvent { world_model }| loop_list(world_model, on_select))
+                    }
+                }
+            </div>
+        </Show>
+    }
+}
+
+/// Most loops the panel lists. A dense model can have far more cycles than
+/// anyone reads, and the list is rebuilt on every tree refresh.
+const MAX_LISTED: usize = 50;
+
+fn loop_list(world_model: &WorldModel, on_select: Callback<Vec<Id>>) -> AnyView {
+    let mut loops = world_model.feedback_loops_up_to(MAX_LISTED + 1);
+    let truncated = loops.len() > MAX_LISTED;
+    loops.truncate(MAX_LISTED);
+    if loops.is_empty() {
+        return view! {
+            <p class="text-sm text-gray-500">
+                "No feedback loops: no chain of flows returns to where it started."
+            </p>
+        }
+        .into_any();
+    }
+
+    let names = element_names(world_model);
+    view! {
+        <ul class="space-y-2">
+            {loops
+                .into_iter()
+                .map(|feedback_loop| loop_row(feedback_loop, &names, on_select))
+                .collect_view()}
+        </ul>
+        {truncated
+            .then(|| {
+                view! {
+                    <p class="text-sm text-gray-500 mt-2">
+                        {format!("Showing the first {MAX_LISTED} loops.")}
+                    </p>
+                }
+            })}
+    }
+    .into_any()
+}
+
+fn loop_row(
+    feedback_loop: FeedbackLoop,
+    names: &HashMap<Id, String>,
+    on_select: Callback<Vec<Id>>,
+) -> impl IntoView {
+    let badge = match feedback_loop.polarity {
+        Polarity::Reinforcing => "bg-orange-100 text-orange-800",
+        Polarity::Balancing => "bg-blue-100 text-blue-800",
+    };
+    let path = feedback_loop
+        .members
+        .iter()
+        .chain(feedback_loop.members.first())
+        .map(|id| names.get(id).cloned().unwrap_or_else(|| id.to_string()))
+        .collect::<Vec<_>>()
+        .join(" → ");
+    let polarity = feedback_loop.polarity.to_string();
+    let ids: Vec<Id> = feedback_loop
+        .members
+        .into_iter()
+        .chain(feedback_loop.interactions)
+        .collect();
+
+    view! {
+        <li
+            class="p-2 rounded border border-gray-200 hover:bg-gray-50 cursor-pointer"
+            on:click=move |_| on_select.run(ids.clone())
+        >
+            <span class=format!("inline-block text-xs font-semibold px-2 py-0.5 rounded mr-2 {badge}")>
+                {polarity}
+            </span>
+            <span class="text-sm text-gray-800">{path}</span>
+        </li>
+    }
+}
+
+fn element_names(world_model: &WorldModel) -> HashMap<Id, String> {
+    let environment = &world_model.environment;
+    let mut names = HashMap::new();
+    for external in environment.sources.iter().chain(&environment.sinks) {
+        names.insert(external.info.id.clone(), external.info.name.clone());
+    }
+    for system in &world_model.systems {
+        names.insert(system.info.id.clone(), system.info.name.clone());
+        for external in system.sources.iter().chain(&system.sinks) {
+            names.insert(external.info.id.clone(), external.info.name.clone());
+        }
+    }
+    names
+}
@@ -3,6 +3,8 @@ mod chat;
 mod checkbox; // Kept for potential future use
 mod controls_menu;
 mod divider;
+mod export_menu;
+mod feedback_loops;
 mod input_group;
 mod landing_screen;
 mod model_browser;
@@ -19,6 +21,8 @@ pub use chat::*;
 // pub use checkbox::*; // Replaced by RadioGroup for Complexity
 pub use controls_menu::*;
 pub use divider::*;
+pub use export_menu::*;
+pub use feedback_loops::*;
 pub use input_group::*;
 pub use landing_screen::*;
 pub use model_browser::*;
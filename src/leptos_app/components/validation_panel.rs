@@ -1,4 +1,5 @@
 use crate::bevy_app::data_model::validate::{Severity, ValidationIssue};
+use crate::bevy_app::data_model::Fix;
 use leptos::prelude::*;
 
 #[component]
@@ -6,6 +7,7 @@ pub fn ValidationPanel(
     #[prop(into)] issues: Signal<Option<Vec<ValidationIssue>>>,
     #[prop(into)] on_continue: Callback<()>,
     #[prop(into)] on_dismiss: Callback<()>,
+    #[prop(into)] on_fix: Callback<Fix>,
 ) -> impl IntoView {
     let has_errors = Memo::new(move |_| {
         issues
@@ -40,7 +42,7 @@ pub fn ValidationPanel(
                         Severity::Error => "ERROR",
                         Severity::Warning => "WARNING",
                     };
-                    let mut line = format!("[{sev}] {}: {}", i.location, i.message);
+                    let mut line = format!("[{sev} {}] {}: {}", i.code, i.location, i.message);
                     if let Some(ref s) = i.suggestion {
                         line.push_str(&format!("\n  Suggestion: {s}"));
                     }
@@ -97,6 +99,7 @@ pub fn ValidationPanel(
                                 };
                                 let badge_text = if is_error { "Error" } else { "Warning" };
                                 let suggestion = issue.suggestion.clone();
+                                let fix = issue.fix.clone();
 
                                 view! {
                                     <div class="border rounded-lg p-3 bg-gray-50">
@@ -104,15 +107,33 @@ pub fn ValidationPanel(
                                             <span class={format!("inline-block px-2 py-0.5 rounded text-xs font-medium border shrink-0 {badge_class}")}>
                                                 {badge_text}
                                             </span>
-                                            <div class="min-w-0">
+                                            <div class="min-w-0 flex-1">
                                                 <code class="text-xs font-mono text-gray-600 break-all">
-                                                    {issue.location.clone()}
+                                                    {format!("{} · {}", issue.code, issue.location)}
                                                 </code>
                                                 <p class="text-sm text-gray-800 mt-1">{issue.message.clone()}</p>
                                                 {suggestion.map(|s| view! {
                                                     <p class="text-xs italic text-gray-500 mt-1">{s}</p>
                                                 })}
                                             </div>
+                                            {fix.map(|fix| {
+                                                // Fixes that delete or re-address an element look like a warning.
+                                                let fix_class = if fix.safe {
+                                                    "bg-green-600 hover:bg-green-700"
+                                                } else {
+                                                    "bg-amber-600 hover:bg-amber-700"
+                                                };
+                                                let title = fix.description.clone();
+                                                view! {
+                                                    <button
+                                                        class={format!("px-2 py-0.5 text-xs font-medium text-white rounded shrink-0 {fix_class}")}
+                                                        title=title
+                                                        on:click=move |_| on_fix.run(fix.clone())
+                                                    >
+                                                        "Fix"
+                                                    </button>
+                                                }
+                                            })}
                                         </div>
                                     </div>
                                 }
@@ -1,6 +1,6 @@
 use crate::bevy_app::components::{HcgsArchetype, SpatialDetailPanelMode};
-use crate::bevy_app::data_model::{AgentKind, AgentModel, Complexity};
-use crate::bevy_app::smart_parameters::{ParameterValue, SmartParameter};
+use crate::bevy_app::data_model::{AgentKind, AgentModel, Complexity, Unit};
+use crate::bevy_app::smart_parameters::{ParameterValue, SmartParameter, SmartParameterDatabase};
 use crate::leptos_app::components::{
     Button, Divider, InputGroup, RadioGroup, SelectGroup, Slider, TextArea,
 };
@@ -322,6 +322,43 @@ pub fn InterfaceDetails(interface_query: RwSignalSynced<Option<InterfaceQuery>>)
     }
 }
 
+/// The suggestions are static, so the database is built once.
+fn parameter_database() -> &'static SmartParameterDatabase {
+    static DATABASE: std::sync::OnceLock<SmartParameterDatabase> = std::sync::OnceLock::new();
+    DATABASE.get_or_init(SmartParameterDatabase::new)
+}
+
+/// The parsed unit of a flow attribute and its dimension, or what is wrong
+/// with it. With the flow's substance, the unit must also measure what the
+/// suggested attribute of the same name measures. `None` while blank.
+fn check_attribute_unit(
+    substance_type: Option<SubstanceType>,
+    name: &str,
+    unit: &str,
+) -> Option<Result<String, String>> {
+    if unit.trim().is_empty() {
+        return None;
+    }
+    let checked = match substance_type {
+        Some(substance_type) => parameter_database().check_unit(&substance_type, name, unit),
+        None => Unit::parse(unit).map_err(|e| e.to_string()),
+    };
+    Some(checked.map(|u| format!("{u} · {}", u.dimension())))
+}
+
+fn unit_hint(checked: Option<Result<String, String>>) -> impl IntoView {
+    checked.map(|checked| match checked {
+        Ok(normalized) => view! {
+            <p class="text-xs text-gray-500">{normalized}</p>
+        }
+        .into_any(),
+        Err(problem) => view! {
+            <p class="text-xs text-amber-600">{problem}</p>
+        }
+        .into_any(),
+    })
+}
+
 #[component]
 pub fn SimpleParameterInput(
     interaction_query: RwSignalSynced<Option<InteractionQuery>>,
@@ -335,6 +372,13 @@ pub fn SimpleParameterInput(
             .unwrap_or_default()
     });
 
+    let substance_type = Memo::new(move |_| {
+        interaction_query
+            .read()
+            .as_ref()
+            .map(|(_, _, interaction)| interaction.substance_type)
+    });
+
     // State for adding new parameter
     let (new_param_name, set_new_param_name) = signal(String::new());
     let (new_param_value, set_new_param_value) = signal(String::new());
@@ -369,8 +413,10 @@ pub fn SimpleParameterInput(
         <Show when=move || !parameters.get().is_empty()>
             <div class="space-y-2 mb-3">
                 {move || {
+                    let substance_type = substance_type.get();
                     parameters.get().into_iter().map(|param| {
                         let param_id = param.id;
+                        let unit_check = check_attribute_unit(substance_type, &param.name, &param.unit);
                         let initial_name = param.name.clone();
                         let initial_value = param.value.clone();
                         let initial_unit = param.unit.clone();
@@ -421,6 +467,7 @@ pub fn SimpleParameterInput(
                                             placeholder="Unit"
                                         />
                                     </div>
+                                    {unit_hint(unit_check)}
                                 </div>
                                 <button
                                     type="button"
@@ -468,6 +515,11 @@ pub fn SimpleParameterInput(
                         on:input=move |ev| set_new_param_unit.set(event_target_value(&ev))
                     />
                 </div>
+                {move || unit_hint(check_attribute_unit(
+                    substance_type.get(),
+                    &new_param_name.get(),
+                    &new_param_unit.get(),
+                ))}
                 <button
                     type="button"
                     on:click=add_parameter
@@ -1257,7 +1309,8 @@ pub fn SystemDetails(
         system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.time_unit.clone())
+            .and_then(|(_, _, system, _)| system.time_constant)
+            .map(|tc| tc.to_string())
             .unwrap_or_default()
     });
 
@@ -1266,7 +1319,7 @@ pub fn SystemDetails(
         system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.history.clone())
+            .map(|(_, _, system, _)| system.history.to_string())
             .unwrap_or_default()
     });
 
@@ -1274,7 +1327,8 @@ pub fn SystemDetails(
         system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.transformation.clone())
+            .and_then(|(_, _, system, _)| system.transformation.as_ref())
+            .map(|t| t.to_string())
             .unwrap_or_default()
     });
 
@@ -1321,7 +1375,7 @@ pub fn SystemDetails(
             //         placeholder="e.g., Second, Minute, Year"
             //         value=system_time_unit
             //         on_input=move |value: String| {
-            //             system_query.write().as_mut().map(|(_, _, system, _)| system.time_unit = value);
+            //             system_query.write().as_mut().map(|(_, _, system, _)| system.time_constant = value.parse().ok());
             //         }
             //     />
             // </div>
@@ -1661,7 +1715,8 @@ pub fn SubSystemDetails(sub_system_query: RwSignalSynced<Option<SubSystemQuery>>
         sub_system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.time_unit.clone())
+            .and_then(|(_, _, system, _)| system.time_constant)
+            .map(|tc| tc.to_string())
             .unwrap_or_default()
     });
 
@@ -1670,7 +1725,7 @@ pub fn SubSystemDetails(sub_system_query: RwSignalSynced<Option<SubSystemQuery>>
         sub_system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.history.clone())
+            .map(|(_, _, system, _)| system.history.to_string())
             .unwrap_or_default()
     });
 
@@ -1678,7 +1733,8 @@ pub fn SubSystemDetails(sub_system_query: RwSignalSynced<Option<SubSystemQuery>>
         sub_system_query
             .read()
             .as_ref()
-            .map(|(_, _, system, _)| system.transformation.clone())
+            .and_then(|(_, _, system, _)| system.transformation.as_ref())
+            .map(|t| t.to_string())
             .unwrap_or_default()
     });
 
@@ -1769,7 +1825,7 @@ pub fn SubSystemDetails(sub_system_query: RwSignalSynced<Option<SubSystemQuery>>
         //         placeholder="e.g., Second, Minute, Year"
         //         value=time_unit
         //         on_input=move |value: String| {
-        //             sub_system_query.write().as_mut().map(|(_, _, system, _)| system.time_unit = value);
+        //             sub_system_query.write().as_mut().map(|(_, _, system, _)| system.time_constant = value.parse().ok());
         //         }
         //     />
         // </div>
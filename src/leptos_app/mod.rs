@@ -7,15 +7,17 @@ mod use_file_dialog;
 
 use crate::bevy_app::data_model::complexity_calculator::calculate_simonian_complexity;
 use crate::bevy_app::data_model::validate::{
-    classify_openness, validate, Severity, ValidationIssue,
+    classify_openness, validate, validate_conservation, ValidationIssue, CONSERVATION_TOLERANCE,
 };
+use crate::bevy_app::data_model::{migrate, rules, to_canonical_json, Fix};
 use crate::bevy_app::{
     init_bevy_app, DetachMarkerLabelEvent, ExternalEntityFilter, ExternalEntityQuery,
     InteractionQuery, InterfaceQuery, IsSameAsIdQuery, SelectedHighlightHelperAdded,
     SelectionFilter, SubSystemFilter, SubSystemQuery, SystemElement, SystemQuery,
 };
 use crate::leptos_app::components::{
-    AppMode, ChatPanel, ControlsMenu, LandingScreen, ModelBrowser, Palette, Toast, ValidationPanel,
+    AppMode, ChatPanel, ControlsMenu, ExportMenu, FeedbackLoops, LandingScreen, ModelBrowser,
+    Palette, Toast, ValidationPanel,
 };
 use crate::leptos_app::details::Details;
 use crate::leptos_app::simulation::SimPanel;
@@ -29,8 +31,8 @@ use leptos_meta::*;
 use use_file_dialog::use_file_dialog_with_options;
 
 use crate::events::{
-    CancelModeEvent, DeselectAllEvent, ModeChangeEvent, PaletteClickEvent, SaveSuccessEvent,
-    TreeEvent, TriggerEvent, ZoomEvent,
+    CancelModeEvent, DeselectAllEvent, HighlightLoopEvent, ModeChangeEvent, PaletteClickEvent,
+    SaveSuccessEvent, TreeEvent, TriggerEvent, ZoomEvent,
 };
 use crate::leptos_app::tree::Tree;
 use leptos_bevy_canvas::prelude::*;
@@ -54,6 +56,9 @@ pub fn App() -> impl IntoView {
     // Cancel mode event system for ESC key bypass
     let (cancel_mode_writer, cancel_mode_receiver) = message_l2b::<CancelModeEvent>();
 
+    // Feedback loop highlighting from the loop list panel
+    let (highlight_loop_writer, highlight_loop_receiver) = message_l2b::<HighlightLoopEvent>();
+
     // Save success event system for user feedback
     let (_save_success_event_writer, save_success_event_receiver) =
         message_l2b::<SaveSuccessEvent>();
@@ -144,7 +149,7 @@ pub fn App() -> impl IntoView {
                     }
                 }
 
-                match serde_json::from_slice::<crate::bevy_app::data_model::WorldModel>(&data) {
+                match crate::bevy_app::data_model::migrate::from_slice(&data).map(|(m, _)| m) {
                     Ok(world_model) => {
                         let complexity_result = calculate_simonian_complexity(&world_model);
                         set_complexity_inner.set(complexity_result.total_complexity);
@@ -165,8 +170,19 @@ pub fn App() -> impl IntoView {
                         set_loaded_model_name.set(mn.to_string());
                         set_app_mode.set(AppMode::Editing);
 
-                        // Non-blocking open/closed-with-respect-to-mass classification.
-                        set_toast_message.set(classify_openness(&world_model));
+                        // Non-blocking open/closed-with-respect-to-mass classification,
+                        // plus the first place where declared amounts leak.
+                        let mut openness = classify_openness(&world_model);
+                        let leaks =
+                            validate_conservation(&world_model, CONSERVATION_TOLERANCE).issues;
+                        if let Some(first) = leaks.first() {
+                            openness.push_str(&format!(
+                                " Flow amounts do not balance in {} place(s), e.g. {}.",
+                                leaks.len(),
+                                first.message
+                            ));
+                        }
+                        set_toast_message.set(openness);
                         set_toast_visible.set(true);
 
                         let result = validate(&world_model);
@@ -187,12 +203,12 @@ pub fn App() -> impl IntoView {
                     }
                     Err(e) => {
                         set_pending_load.set(None);
-                        set_validation_issues.set(Some(vec![ValidationIssue {
-                            severity: Severity::Error,
-                            location: "root".to_string(),
-                            message: format!("JSON parse error: {e}"),
-                            suggestion: Some("Check that the file is valid BERT JSON.".to_string()),
-                        }]));
+                        set_validation_issues.set(Some(vec![ValidationIssue::new(
+                            &rules::PARSE_ERROR,
+                            "root",
+                            format!("JSON parse error: {e}"),
+                            Some("Check that the file is valid BERT JSON."),
+                        )]));
                     }
                 }
             }
@@ -276,6 +292,8 @@ pub fn App() -> impl IntoView {
     }
 
     let (tree_visible, set_tree_visible) = signal(false);
+    let (loops_visible, set_loops_visible) = signal(false);
+    let (export_visible, set_export_visible) = signal(false);
     let (controls_visible, set_controls_visible) = signal(false);
     let (model_browser_visible, set_model_browser_visible) = signal(false);
     let (sim_panel_visible, set_sim_panel_visible) = signal(false);
@@ -368,7 +386,7 @@ pub fn App() -> impl IntoView {
                                 ).await;
 
                                 let json_data = result.json_data;
-                                match serde_json::from_str::<crate::bevy_app::data_model::WorldModel>(&json_data) {
+                                match crate::bevy_app::data_model::migrate::from_slice(json_data.as_bytes()).map(|(m, _)| m) {
                                     Ok(world_model) => {
                                         let complexity_result = calculate_simonian_complexity(&world_model);
                                         set_complexity.set(complexity_result.total_complexity);
@@ -413,7 +431,7 @@ pub fn App() -> impl IntoView {
                     let set_loaded_file_path = set_loaded_file_path;
                     move |_| {
                         let blank = include_str!("../../assets/models/examples/blank.json");
-                        if let Ok(world_model) = serde_json::from_str::<crate::bevy_app::data_model::WorldModel>(blank) {
+                        if let Ok(world_model) = crate::bevy_app::data_model::migrate::from_slice(blank.as_bytes()).map(|(m, _)| m) {
                             let complexity_result = calculate_simonian_complexity(&world_model);
                             set_complexity.set(complexity_result.total_complexity);
                             set_loaded_model_name.set("New System".to_string());
@@ -480,7 +498,7 @@ pub fn App() -> impl IntoView {
                                     "load_file", &Args { pb: std::path::PathBuf::from(&path) },
                                 ).await;
                                 let event = LoadFileEvent { data: file_data.data, file_path: file_data.path };
-                                if let Ok(world_model) = serde_json::from_slice::<crate::bevy_app::data_model::WorldModel>(&event.data) {
+                                if let Ok(world_model) = crate::bevy_app::data_model::migrate::from_slice(&event.data).map(|(m, _)| m) {
                                     let complexity_result = calculate_simonian_complexity(&world_model);
                                     set_complexity.set(complexity_result.total_complexity);
                                     if let Ok(json_str) = String::from_utf8(event.data.clone()) {
@@ -522,6 +540,8 @@ pub fn App() -> impl IntoView {
         <Show when=move || is_editing.get() && !tree_visible.get()>
             {
                 let trigger_event_sender = trigger_event_sender.clone();
+                let loops_trigger_sender = trigger_event_sender.clone();
+                let export_trigger_sender = trigger_event_sender.clone();
                 view! {
                     <div class="absolute top-4 left-4 z-20 flex gap-2">
                         <button
@@ -533,6 +553,24 @@ pub fn App() -> impl IntoView {
                         >
                             {"Show Tree"}
                         </button>
+                        <button
+                            class="px-4 py-2 rounded-lg bg-white shadow-md hover:shadow-lg transition-shadow"
+                            on:click=move |_| {
+                                loops_trigger_sender.send(TriggerEvent::ShowTree).ok();
+                                set_loops_visible.set(true);
+                            }
+                        >
+                            {"Feedback Loops"}
+                        </button>
+                        <button
+                            class="px-4 py-2 rounded-lg bg-white shadow-md hover:shadow-lg transition-shadow"
+                            on:click=move |_| {
+                                export_trigger_sender.send(TriggerEvent::ShowTree).ok();
+                                set_export_visible.set(true);
+                            }
+                        >
+                            {"Export"}
+                        </button>
                         <button
                             class="px-4 py-2 rounded-lg bg-white shadow-md hover:shadow-lg transition-shadow"
                             on:click=move |_| {
@@ -634,7 +672,24 @@ pub fn App() -> impl IntoView {
             })
             mode_text=Signal::derive(move || mode_text.get())
         />
-        <Tree visible=tree_visible event_receiver=tree_event_receiver />
+        <Tree visible=tree_visible event_receiver=tree_event_receiver.clone() />
+        <FeedbackLoops
+            visible=loops_visible
+            event_receiver=tree_event_receiver.clone()
+            on_select=Callback::new(move |members| {
+                highlight_loop_writer.send(HighlightLoopEvent { members }).ok();
+            })
+            on_close=Callback::new(move |_| set_loops_visible.set(false))
+        />
+        <ExportMenu
+            visible=export_visible
+            event_receiver=tree_event_receiver
+            on_exported=Callback::new(move |message: String| {
+                set_toast_message.set(message);
+                set_toast_visible.set(true);
+            })
+            on_close=Callback::new(move |_| set_export_visible.set(false))
+        />
         <ControlsMenu
             visible=controls_visible
             on_close=Callback::new(move |_| set_controls_visible.set(false))
@@ -648,7 +703,7 @@ pub fn App() -> impl IntoView {
                 let set_app_mode = set_app_mode;
                 let set_loaded_file_path = set_loaded_file_path;
                 move |event: LoadFileEvent| {
-                    match serde_json::from_slice::<crate::bevy_app::data_model::WorldModel>(&event.data) {
+                    match crate::bevy_app::data_model::migrate::from_slice(&event.data).map(|(m, _)| m) {
                         Ok(world_model) => {
                             let complexity_result = calculate_simonian_complexity(&world_model);
                             set_complexity.set(complexity_result.total_complexity);
@@ -675,12 +730,12 @@ pub fn App() -> impl IntoView {
                         }
                         Err(e) => {
                             set_pending_load.set(None);
-                            set_validation_issues.set(Some(vec![ValidationIssue {
-                                severity: Severity::Error,
-                                location: "root".to_string(),
-                                message: format!("JSON parse error: {e}"),
-                                suggestion: Some("Check that the file is valid BERT JSON.".to_string()),
-                            }]));
+                            set_validation_issues.set(Some(vec![ValidationIssue::new(
+                                &rules::PARSE_ERROR,
+                                "root",
+                                format!("JSON parse error: {e}"),
+                                Some("Check that the file is valid BERT JSON."),
+                            )]));
                         }
                     }
                     set_model_browser_visible.set(false);
@@ -703,6 +758,37 @@ pub fn App() -> impl IntoView {
                 set_validation_issues.set(None);
                 set_pending_load.set(None);
             })
+            on_fix=Callback::new({
+                let load_file_writer = load_file_writer.clone();
+                move |fix: Fix| {
+                    // Fixes patch the pending file before it reaches Bevy, then
+                    // the panel shows what is left of the re-validated model.
+                    let Some(mut event) = pending_load.get() else {
+                        return;
+                    };
+                    let Ok((mut world_model, _)) = migrate::from_slice(&event.data) else {
+                        return;
+                    };
+                    if let Err(e) = fix.apply(&mut world_model) {
+                        set_toast_message.set(e.to_string());
+                        set_toast_visible.set(true);
+                        return;
+                    }
+                    let json = to_canonical_json(&world_model);
+                    set_model_json_context.set(Some(json.clone()));
+                    event.data = json.into_bytes();
+
+                    let result = validate(&world_model);
+                    if result.is_clean() {
+                        load_file_writer.send(event).ok();
+                        set_validation_issues.set(None);
+                        set_pending_load.set(None);
+                    } else {
+                        set_pending_load.set(Some(event));
+                        set_validation_issues.set(Some(result.issues));
+                    }
+                }
+            })
         />
         <ChatPanel
             visible=Signal::derive(move || chat_visible.get())
@@ -720,7 +806,7 @@ pub fn App() -> impl IntoView {
                 let set_app_mode = set_app_mode;
                 let set_loaded_file_path = set_loaded_file_path;
                 move |json_data: Vec<u8>| {
-                    match serde_json::from_slice::<crate::bevy_app::data_model::WorldModel>(&json_data) {
+                    match crate::bevy_app::data_model::migrate::from_slice(&json_data).map(|(m, _)| m) {
                         Ok(world_model) => {
                             let complexity_result = calculate_simonian_complexity(&world_model);
                             set_complexity.set(complexity_result.total_complexity);
@@ -819,6 +905,7 @@ pub fn App() -> impl IntoView {
                     save_success_bevy_sender,
                     mode_change_sender,
                     cancel_mode_receiver,
+                    highlight_loop_receiver,
                 )
             } />
         </div>
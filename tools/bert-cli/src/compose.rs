@@ -0,0 +1,45 @@
+//! `bert compose`: embed one model as a subsystem of another.
+
+use crate::{load, save, Output};
+use bert_core::compose::{embed, Binding};
+use bert_core::Id;
+use std::path::PathBuf;
+
+/// Embed the System of Interest of GUEST into HOST as a subsystem.
+///
+/// Guest environment sources and sinks become ports on the new subsystem's
+/// boundary. Bound ones connect to the named host element; unbound ones are
+/// carried into the parent system as its own sources and sinks.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Model that receives the guest.
+    host: PathBuf,
+
+    /// Model whose System of Interest becomes a subsystem.
+    guest: PathBuf,
+
+    /// Host system to place the guest under.
+    #[arg(long, default_value = "S0")]
+    parent: Id,
+
+    /// Bind a guest environment source or sink to a host external, system,
+    /// interface or interaction, as GUEST_ID=HOST_ID. Repeatable.
+    #[arg(long = "bind", value_name = "GUEST_ID=HOST_ID")]
+    bindings: Vec<Binding>,
+
+    #[command(flatten)]
+    output: Output,
+}
+
+pub fn run(args: Args) -> Result<(), String> {
+    let host = load(&args.host)?;
+    let guest = load(&args.guest)?;
+    let composition =
+        embed(&host, &args.parent, &guest, &args.bindings).map_err(|e| e.to_string())?;
+    eprintln!(
+        "bert: embedded {} as {}",
+        args.guest.display(),
+        composition.subsystem
+    );
+    save(&composition.model, args.output.output.as_deref())
+}
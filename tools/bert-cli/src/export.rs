@@ -0,0 +1,72 @@
+//! `bert export`: draw a model's structure as a graph file.
+
+use crate::{load, Output};
+use bert_core::graph::{self, GraphFormat};
+use std::path::PathBuf;
+
+/// Write MODEL as a Graphviz, Mermaid, GraphML or GEXF graph.
+///
+/// Systems and sources/sinks become nodes and interactions become edges.
+/// `--depth` folds the system tree below that level into its systems. The
+/// format defaults to the output file's extension, or DOT.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Model to export.
+    model: PathBuf,
+
+    /// Graph format.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Open the system tree this many levels below the System of Interest;
+    /// 0 shows it alone in its environment. Every level by default.
+    #[arg(long)]
+    depth: Option<usize>,
+
+    #[command(flatten)]
+    output: Output,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// Graphviz DOT, with a cluster per subsystem.
+    Dot,
+    /// A Mermaid flowchart, with a subgraph per subsystem.
+    Mermaid,
+    /// GraphML with every field as an attribute, for yEd and NetworkX.
+    Graphml,
+    /// GEXF 1.3 with every field as an attribute, for Gephi.
+    Gexf,
+}
+
+impl From<Format> for GraphFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Dot => GraphFormat::Dot,
+            Format::Mermaid => GraphFormat::Mermaid,
+            Format::Graphml => GraphFormat::GraphMl,
+            Format::Gexf => GraphFormat::Gexf,
+        }
+    }
+}
+
+pub fn run(args: Args) -> Result<(), String> {
+    let output = args.output.output.as_deref();
+    let format = match args.format {
+        Some(format) => format.into(),
+        None => output
+            .and_then(|path| path.extension())
+            .and_then(|ext| ext.to_str()?.parse().ok())
+            .unwrap_or(GraphFormat::Dot),
+    };
+    let model = load(&args.model)?;
+    let text = graph::export(&model, format, args.depth);
+    match output {
+        Some(path) => std::fs::write(path, text)
+            .map_err(|e| format!("failed to write {}: {e}", path.display())),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Find where a JSON pointer lands in the text of a file.
+//!
+//! `serde_json` keeps no spans, so this walks the raw text just far enough to
+//! reach the pointed-at value. Only what a pointer needs is scanned: member
+//! keys and the extent of values to skip.
+
+/// 1-based line and column of the value `pointer` names in `text`.
+///
+/// If the value is missing (e.g. a required field that isn't there), this is
+/// the position of its deepest ancestor that exists.
+pub fn locate(text: &str, pointer: &str) -> (usize, usize) {
+    let offset = offset_of(text.as_bytes(), pointer);
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Byte offset of the value `pointer` names, or of its deepest existing ancestor.
+fn offset_of(bytes: &[u8], pointer: &str) -> usize {
+    let mut pos = skip_whitespace(bytes, 0);
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        let found = match bytes.get(pos) {
+            Some(b'{') => find_member(bytes, pos, &token),
+            Some(b'[') => token
+                .parse()
+                .ok()
+                .and_then(|index| find_element(bytes, pos, index)),
+            _ => None,
+        };
+        match found {
+            Some(next) => pos = next,
+            None => break,
+        }
+    }
+    pos
+}
+
+/// Start of the value of member `key` in the object at `pos`.
+fn find_member(bytes: &[u8], pos: usize, key: &str) -> Option<usize> {
+    let mut pos = skip_whitespace(bytes, pos + 1);
+    while bytes.get(pos) == Some(&b'"') {
+        let key_end = skip_string(bytes, pos);
+        let name = std::str::from_utf8(&bytes[pos + 1..key_end - 1]).ok()?;
+        pos = skip_whitespace(bytes, key_end);
+        if bytes.get(pos) != Some(&b':') {
+            return None;
+        }
+        pos = skip_whitespace(bytes, pos + 1);
+        if name == key {
+            return Some(pos);
+        }
+        pos = skip_whitespace(bytes, skip_value(bytes, pos));
+        if bytes.get(pos) != Some(&b',') {
+            return None;
+        }
+        pos = skip_whitespace(bytes, pos + 1);
+    }
+    None
+}
+
+/// Start of element `index` of the array at `pos`.
+fn find_element(bytes: &[u8], pos: usize, index: usize) -> Option<usize> {
+    let mut pos = skip_whitespace(bytes, pos + 1);
+    if bytes.get(pos) == Some(&b']') {
+        return None;
+    }
+    for _ in 0..index {
+        pos = skip_whitespace(bytes, skip_value(bytes, pos));
+        if bytes.get(pos) != Some(&b',') {
+            return None;
+        }
+        pos = skip_whitespace(bytes, pos + 1);
+    }
+    Some(pos)
+}
+
+/// Offset just past the value starting at `pos`.
+fn skip_value(bytes: &[u8], pos: usize) -> usize {
+    match bytes.get(pos) {
+        Some(b'"') => skip_string(bytes, pos),
+        Some(b'{' | b'[') => {
+            let mut depth = 0usize;
+            let mut pos = pos;
+            while let Some(&b) = bytes.get(pos) {
+                match b {
+                    b'"' => {
+                        pos = skip_string(bytes, pos);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return pos + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+            pos
+        }
+        _ => {
+            let mut pos = pos;
+            while bytes
+                .get(pos)
+                .is_some_and(|b| !matches!(b, b',' | b']' | b'}') && !b.is_ascii_whitespace())
+            {
+                pos += 1;
+            }
+            pos
+        }
+    }
+}
+
+/// Offset just past the string whose opening quote is at `pos`.
+fn skip_string(bytes: &[u8], pos: usize) -> usize {
+    let mut pos = pos + 1;
+    while let Some(&b) = bytes.get(pos) {
+        match b {
+            b'\\' => pos += 2,
+            b'"' => return pos + 1,
+            _ => pos += 1,
+        }
+    }
+    pos
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"{
+  "version": 3,
+  "systems": [
+    { "info": { "name": "a \"quoted\" [name]" }, "radius": 1 },
+    {
+      "info": { "name": "b" },
+      "boundary": { "interfaces": [] }
+    }
+  ]
+}"#;
+
+    #[test]
+    fn pointers_land_on_their_values() {
+        assert_eq!(locate(TEXT, ""), (1, 1));
+        assert_eq!(locate(TEXT, "/version"), (2, 14));
+        assert_eq!(locate(TEXT, "/systems/0/radius"), (4, 60));
+        assert_eq!(locate(TEXT, "/systems/1/info/name"), (6, 25));
+    }
+
+    #[test]
+    fn missing_values_fall_back_to_their_deepest_ancestor() {
+        assert_eq!(locate(TEXT, "/systems/1/boundary/interfaces/0"), (7, 35));
+        assert_eq!(locate(TEXT, "/systems/1/parent"), (5, 5));
+        assert_eq!(locate(TEXT, "/systems/9"), (3, 14));
+    }
+}
@@ -0,0 +1,98 @@
+//! `bert` — command-line tools for BERT model files.
+//!
+//! Usage:
+//!
+//! ```sh
+//! bert compose network.json mining.json --parent S0 -o assembled.json
+//! bert compose network.json mining.json --parent C0.2 \
+//!     --bind Src-1.0=Src-1.1 --bind Snk-1.0=F0.3
+//! bert validate assets/models --format sarif > bert.sarif
+//! bert export bitcoin.json --depth 1 -o bitcoin.dot
+//! ```
+//!
+//! Every command reads model files through the migration pipeline, so older
+//! file versions are accepted; commands that produce a model write canonical
+//! JSON.
+
+mod compose;
+mod export;
+mod locate;
+mod sarif;
+mod validate;
+
+use bert_core::{migrate, to_canonical_json, WorldModel};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "bert",
+    about = "Command-line tools for BERT model files",
+    version
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    Compose(compose::Args),
+    Export(export::Args),
+    Validate(validate::Args),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Compose(args) => compose::run(args),
+        Command::Export(args) => export::run(args),
+        Command::Validate(args) => validate::run(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("bert: error: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Read and migrate a model file, reporting any migration on stderr.
+fn load(path: &Path) -> Result<WorldModel, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let (model, report) = migrate::from_slice(&bytes)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+    if !report.is_noop() {
+        eprintln!(
+            "bert: migrated {}: version {} → {}",
+            path.display(),
+            report.original_version,
+            report.final_version
+        );
+    }
+    Ok(model)
+}
+
+/// Write `model` as canonical JSON to `output`, or to stdout.
+fn save(model: &WorldModel, output: Option<&Path>) -> Result<(), String> {
+    let json = to_canonical_json(model);
+    match output {
+        Some(path) => std::fs::write(path, json)
+            .map_err(|e| format!("failed to write {}: {e}", path.display())),
+        None => {
+            print!("{json}");
+            Ok(())
+        }
+    }
+}
+
+/// Output file argument shared by commands that produce a model.
+#[derive(clap::Args, Debug)]
+struct Output {
+    /// Write the result here instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
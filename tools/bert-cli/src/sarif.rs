@@ -0,0 +1,75 @@
+//! SARIF 2.1.0 output for `bert validate`, the format code-review tools read
+//! to annotate lines.
+//!
+//! Every rule in the registry is listed under the tool, so a result's
+//! `ruleIndex` is its position in [`RULES`]. The JSON pointer of an issue
+//! goes in its logical location, alongside the line and column it resolves to.
+
+use crate::validate::{severity_name, FileReport};
+use bert_core::rules::RULES;
+use serde_json::{json, Value};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub fn to_sarif(reports: &[FileReport]) -> Value {
+    let rules: Vec<_> = RULES
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.code,
+                "name": rule.name,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": severity_name(rule.severity) },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = reports
+        .iter()
+        .flat_map(|report| {
+            let uri = report.path.to_string_lossy().replace('\\', "/");
+            report.issues.iter().map(move |located| {
+                let issue = &located.issue;
+                let mut text = format!("{}: {}", issue.location, issue.message);
+                if let Some(suggestion) = &issue.suggestion {
+                    text.push_str("\n\n");
+                    text.push_str(suggestion);
+                }
+                json!({
+                    "ruleId": issue.code,
+                    "ruleIndex": RULES.iter().position(|rule| rule.code == issue.code),
+                    "level": severity_name(issue.severity),
+                    "message": { "text": text },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": {
+                                "startLine": located.line,
+                                "startColumn": located.column,
+                            },
+                        },
+                        "logicalLocations": [{
+                            "fullyQualifiedName": located.pointer,
+                            "kind": "object",
+                        }],
+                    }],
+                })
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "bert",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
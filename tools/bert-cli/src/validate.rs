@@ -0,0 +1,283 @@
+//! `bert validate`: check model files against bert-core's validation rules.
+
+use crate::locate::locate;
+use crate::sarif;
+use bert_core::files::model_files;
+use bert_core::rules::{self, RuleConfig};
+use bert_core::validate::{
+    validate_json_structure, validate_mode, Severity, ValidationIssue, ValidationResult,
+};
+use bert_core::{migrate, Mode};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Validate model files, or every `*.json` model under a directory.
+///
+/// Each model is checked in its own mode, after the raw JSON has been checked
+/// for required fields. Rule overrides come from the model's `validation`
+/// field and the nearest `bert-rules.json`; patterns there that name no rule
+/// are reported against that file. Exits non-zero if any file has an error.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Model files, or directories to search recursively.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Check entry into this mode instead of the one each model declares.
+    #[arg(long, value_parser = parse_mode)]
+    mode: Option<Mode>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Ignore `bert-rules.json` files.
+    #[arg(long)]
+    no_project_config: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// One line per issue, `file:line:column: ...`.
+    Text,
+    /// An array of files with their issues.
+    Json,
+    /// SARIF 2.1.0, for code-review annotations.
+    Sarif,
+}
+
+/// Issues found in one file.
+pub struct FileReport {
+    pub path: PathBuf,
+    pub issues: Vec<LocatedIssue>,
+}
+
+/// An issue with its position in the file it was found in.
+pub struct LocatedIssue {
+    pub issue: ValidationIssue,
+    /// JSON pointer to the offending value.
+    pub pointer: String,
+    /// 1-based line of the offending value, or of its nearest existing ancestor.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+}
+
+pub fn run(args: Args) -> Result<(), String> {
+    let mut files = Vec::new();
+    for path in &args.paths {
+        if path.is_dir() {
+            let found =
+                model_files(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            files.extend(found);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    if files.is_empty() {
+        return Err("no model files found".to_string());
+    }
+
+    let mut reports = Vec::new();
+    let mut configs = BTreeMap::new();
+    for path in files {
+        let (report, config) = check_file(path, args.mode, !args.no_project_config)?;
+        reports.push(report);
+        configs.extend(config);
+    }
+    let checked = reports.len();
+    for (path, config) in configs {
+        let report = check_config(path, &config)?;
+        if !report.issues.is_empty() {
+            reports.push(report);
+        }
+    }
+
+    match args.format {
+        Format::Text => print_text(&reports),
+        Format::Json => println!("{:#}", to_json(&reports)),
+        Format::Sarif => println!("{:#}", sarif::to_sarif(&reports)),
+    }
+
+    let count = |severity| {
+        reports
+            .iter()
+            .flat_map(|r| &r.issues)
+            .filter(|l| l.issue.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    let failed = reports
+        .iter()
+        .filter(|r| r.issues.iter().any(|l| l.issue.severity == Severity::Error))
+        .count();
+    eprintln!("bert: checked {checked} files: {errors} errors, {warnings} warnings");
+    if errors > 0 {
+        return Err(format!("{failed} of {checked} files failed validation"));
+    }
+    Ok(())
+}
+
+fn parse_mode(s: &str) -> Result<Mode, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string()))
+        .map_err(|_| "expected Core, Structural, Operational, Full or Cybernetic".to_string())
+}
+
+/// Run every check that applies to the file at `path`, and return the rule
+/// config that applied to it, if any.
+///
+/// A file that isn't JSON, or lacks required fields, is reported as such
+/// without going on to the checks that need a parsed model.
+fn check_file(
+    path: PathBuf,
+    mode: Option<Mode>,
+    project_config: bool,
+) -> Result<(FileReport, Option<(PathBuf, RuleConfig)>), String> {
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+
+    let mut result = match serde_json::from_str(&text) {
+        Ok(json) => {
+            let structure = validate_json_structure(&json);
+            if structure.has_errors() {
+                structure
+            } else {
+                match migrate::from_slice(text.as_bytes()) {
+                    Ok((model, _)) => validate_mode(&model, mode.unwrap_or(model.mode())),
+                    Err(e) => parse_error(e.to_string()),
+                }
+            }
+        }
+        Err(e) => {
+            let issue = parse_error(format!("JSON parse error: {e}"))
+                .issues
+                .remove(0);
+            let report = FileReport {
+                issues: vec![LocatedIssue {
+                    issue,
+                    pointer: String::new(),
+                    line: e.line(),
+                    column: e.column(),
+                }],
+                path,
+            };
+            return Ok((report, None));
+        }
+    };
+
+    let mut config = None;
+    if project_config {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        config = RuleConfig::discover(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        if let Some((_, config)) = &config {
+            result.apply(config);
+        }
+    }
+
+    Ok((located(path, &text, result.issues), config))
+}
+
+/// Report the patterns in a `bert-rules.json` that name no rule, or a typo
+/// there silently suppresses nothing in every model it covers.
+fn check_config(path: PathBuf, config: &RuleConfig) -> Result<FileReport, String> {
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let issues = config
+        .unknown_patterns()
+        .into_iter()
+        .map(|(field, i, pattern)| {
+            ValidationIssue::new(
+                &rules::UNKNOWN_RULE,
+                format!("{field}[{i}]"),
+                format!("'{pattern}' is not a rule code or layer"),
+                Some("Use a code such as BERT-L2-007 or a layer such as BERT-L4; see bert_core::rules::RULES"),
+            )
+        })
+        .collect();
+    Ok(located(path, &text, issues))
+}
+
+/// Pin each issue to the line and column of its value in `text`.
+fn located(path: PathBuf, text: &str, issues: Vec<ValidationIssue>) -> FileReport {
+    let issues = issues
+        .into_iter()
+        .map(|issue| {
+            let pointer = issue.json_pointer();
+            let (line, column) = locate(text, &pointer);
+            LocatedIssue {
+                issue,
+                pointer,
+                line,
+                column,
+            }
+        })
+        .collect();
+    FileReport { path, issues }
+}
+
+fn parse_error(message: String) -> ValidationResult {
+    ValidationResult {
+        issues: vec![ValidationIssue::new(
+            &rules::PARSE_ERROR,
+            "root",
+            message,
+            Some("Check that the file is valid BERT JSON."),
+        )],
+    }
+}
+
+pub fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+fn print_text(reports: &[FileReport]) {
+    for report in reports {
+        for located in &report.issues {
+            let issue = &located.issue;
+            println!(
+                "{}:{}:{}: {} [{}] {}: {}",
+                report.path.display(),
+                located.line,
+                located.column,
+                severity_name(issue.severity),
+                issue.code,
+                issue.location,
+                issue.message
+            );
+            if let Some(suggestion) = &issue.suggestion {
+                println!("    help: {suggestion}");
+            }
+        }
+    }
+}
+
+fn to_json(reports: &[FileReport]) -> serde_json::Value {
+    reports
+        .iter()
+        .map(|report| {
+            let issues: Vec<_> = report
+                .issues
+                .iter()
+                .map(|located| {
+                    let issue = &located.issue;
+                    json!({
+                        "code": issue.code,
+                        "rule": rules::rule(issue.code).map(|rule| rule.name),
+                        "severity": severity_name(issue.severity),
+                        "location": issue.location,
+                        "pointer": located.pointer,
+                        "line": located.line,
+                        "column": located.column,
+                        "message": issue.message,
+                        "suggestion": issue.suggestion,
+                    })
+                })
+                .collect();
+            json!({ "file": report.path.display().to_string(), "issues": issues })
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+//! `bert validate` end to end: exit status and SARIF locations.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+/// A fresh scratch directory for one test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bert-cli-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn bitcoin() -> Value {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../assets/models/examples/bitcoin.json"
+    );
+    serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+}
+
+fn write(path: &Path, json: &Value) -> String {
+    let text = serde_json::to_string_pretty(json).unwrap();
+    std::fs::write(path, &text).unwrap();
+    text
+}
+
+fn validate_sarif(path: &Path) -> (Output, Value) {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_bert"))
+        .args(["validate", "--format", "sarif"])
+        .arg(path)
+        .output()
+        .unwrap();
+    let sarif = serde_json::from_slice(&output.stdout).unwrap();
+    (output, sarif)
+}
+
+fn results(sarif: &Value) -> &Vec<Value> {
+    sarif["runs"][0]["results"].as_array().unwrap()
+}
+
+/// 1-based line of the first line of `text` containing `needle`.
+fn line_of(text: &str, needle: &str) -> u64 {
+    text.lines().position(|l| l.contains(needle)).unwrap() as u64 + 1
+}
+
+#[test]
+fn errors_fail_the_run_and_land_on_their_line() {
+    let dir = scratch("errors");
+    let path = dir.join("bitcoin.json");
+    write(&path, &bitcoin());
+    let (output, sarif) = validate_sarif(&path);
+    assert!(output.status.success(), "{sarif:#}");
+
+    let mut model = bitcoin();
+    model["interactions"][0]["source"] = Value::from("Src-1.99");
+    let text = write(&path, &model);
+    let (output, sarif) = validate_sarif(&path);
+    assert!(!output.status.success());
+
+    let dangling = results(&sarif)
+        .iter()
+        .find(|r| r["ruleId"] == "BERT-L2-003")
+        .unwrap_or_else(|| panic!("{sarif:#}"));
+    assert_eq!(dangling["level"], "error");
+    let location = &dangling["locations"][0];
+    assert_eq!(
+        location["logicalLocations"][0]["fullyQualifiedName"],
+        "/interactions/0/source"
+    );
+    let region = &location["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], line_of(&text, "\"Src-1.99\""));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unknown_patterns_in_the_project_config_are_reported_against_it() {
+    let dir = scratch("config");
+    write(&dir.join("bitcoin.json"), &bitcoin());
+    let config = write(
+        &dir.join("bert-rules.json"),
+        &serde_json::json!({ "suppress": ["BERT-L4"], "escalate": ["BERT-L9-001"] }),
+    );
+
+    let (output, sarif) = validate_sarif(&dir);
+    assert!(output.status.success(), "a warning alone passes");
+    let unknown: Vec<&Value> = results(&sarif)
+        .iter()
+        .filter(|r| r["ruleId"] == "BERT-L1-006")
+        .collect();
+    assert_eq!(unknown.len(), 1, "{sarif:#}");
+    let location = &unknown[0]["locations"][0]["physicalLocation"];
+    assert!(location["artifactLocation"]["uri"]
+        .as_str()
+        .unwrap()
+        .ends_with("bert-rules.json"));
+    assert_eq!(
+        location["region"]["startLine"],
+        line_of(&config, "BERT-L9-001")
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,335 @@
+//! WorldModel → triples.
+
+use crate::graph::*;
+use crate::{model_iri, NS};
+use bert_core::{
+    Boundary, Complexity, ExternalEntity, ExternalEntityType, Id, Info, Interaction, Interface,
+    InterfaceType, System, WorldModel,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub(crate) fn emit(model: &WorldModel, model_name: &str) -> Graph {
+    let base = model_iri(model_name);
+    let mut emitter = Emitter {
+        base: base.clone(),
+        graph: Graph::default(),
+    };
+    emitter.graph.prefixes = vec![
+        ("bert".to_string(), NS.to_string()),
+        ("rdf".to_string(), RDF.to_string()),
+        ("xsd".to_string(), XSD.to_string()),
+        (prefix_for(model_name), base),
+    ];
+    emitter.model(model, model_name);
+    emitter.graph
+}
+
+/// `model_name` itself when it is a usable Turtle prefix, else `model`.
+fn prefix_for(model_name: &str) -> String {
+    let mut chars = model_name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !["bert", "rdf", "rdfs", "xsd", "owl", "sh"].contains(&model_name);
+    if valid {
+        model_name.to_string()
+    } else {
+        "model".to_string()
+    }
+}
+
+/// The name serde gives a unit enum variant, as the model file spells it.
+fn variant<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+struct Emitter {
+    base: String,
+    graph: Graph,
+}
+
+impl Emitter {
+    fn node(&self, id: &Id) -> Term {
+        Term::Iri(format!("{}{id}", self.base))
+    }
+
+    fn add(&mut self, subject: &Term, property: &str, object: Term) {
+        self.graph
+            .add(subject.clone(), format!("{NS}{property}"), object);
+    }
+
+    fn class(&mut self, subject: &Term, class: &str) {
+        self.graph
+            .add(subject.clone(), RDF_TYPE, Term::Iri(format!("{NS}{class}")));
+    }
+
+    fn link(&mut self, subject: &Term, property: &str, id: &Id) {
+        let object = self.node(id);
+        self.add(subject, property, object);
+    }
+
+    /// Empty strings are left out; the reader reads them back as empty.
+    fn text(&mut self, subject: &Term, property: &str, value: &str) {
+        if !value.is_empty() {
+            self.add(subject, property, Term::Literal(Literal::string(value)));
+        }
+    }
+
+    fn typed(&mut self, subject: &Term, property: &str, value: impl ToString, datatype: &str) {
+        self.add(
+            subject,
+            property,
+            Term::Literal(Literal::new(value.to_string(), datatype)),
+        );
+    }
+
+    /// `value` as the model file's JSON. Maps come out in key order.
+    fn json<T: Serialize>(&mut self, subject: &Term, property: &str, value: &T) {
+        let json = serde_json::to_value(value)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        self.typed(subject, property, json, RDF_JSON);
+    }
+
+    fn info(&mut self, subject: &Term, info: &Info) {
+        self.text(subject, "localId", &info.id.to_string());
+        self.text(subject, "displayName", &info.name);
+        self.text(subject, "description", &info.description);
+        self.typed(subject, "level", info.level, XSD_INTEGER);
+    }
+
+    fn model(&mut self, model: &WorldModel, model_name: &str) {
+        let root = Term::Iri(self.base.clone());
+        self.class(&root, "Model");
+        self.text(&root, "modelName", model_name);
+        self.typed(&root, "version", model.version, XSD_INTEGER);
+        if let Some(mode) = &model.mode {
+            self.text(&root, "mode", &variant(mode));
+        }
+        if !model.hidden_entities.is_empty() {
+            self.json(&root, "hiddenEntities", &model.hidden_entities);
+        }
+        if !model.validation.is_empty() {
+            self.json(&root, "validation", &model.validation);
+        }
+
+        let environment = &model.environment;
+        let env = self.node(&environment.info.id);
+        self.class(&env, "Environment");
+        self.info(&env, &environment.info);
+        self.add(&env, "inModel", root);
+        for external in environment.sources.iter().chain(&environment.sinks) {
+            let node = self.external(external);
+            self.add(&node, "inEnvironment", env.clone());
+        }
+
+        for system in &model.systems {
+            self.system(system, &environment.info.id);
+        }
+        for interaction in &model.interactions {
+            self.interaction(interaction);
+        }
+        self.equivalences(model);
+    }
+
+    fn external(&mut self, external: &ExternalEntity) -> Term {
+        let node = self.node(&external.info.id);
+        self.class(&node, "ExternalEntity");
+        self.class(
+            &node,
+            match external.ty {
+                ExternalEntityType::Source => "Source",
+                ExternalEntityType::Sink => "Sink",
+            },
+        );
+        self.info(&node, &external.info);
+        self.text(&node, "equivalenceClass", &external.equivalence);
+        self.text(&node, "externalModel", &external.model);
+        if let Some(group) = external.is_same_as_id {
+            self.typed(&node, "sameAsGroup", group, XSD_INTEGER);
+        }
+        if let Some(transform) = &external.transform {
+            self.json(&node, "layout", transform);
+        }
+        node
+    }
+
+    fn system(&mut self, system: &System, environment: &Id) {
+        let node = self.node(&system.info.id);
+        self.class(&node, "System");
+        self.info(&node, &system.info);
+        if &system.parent == environment {
+            self.link(&node, "inEnvironment", &system.parent);
+        } else {
+            self.link(&node, "partOf", &system.parent);
+        }
+        match system.complexity {
+            Complexity::Complex {
+                adaptable,
+                evolveable,
+            } => {
+                self.text(&node, "complexityKind", "Complex");
+                self.typed(&node, "complexAdaptable", adaptable, XSD_BOOLEAN);
+                self.typed(&node, "complexEvolveable", evolveable, XSD_BOOLEAN);
+            }
+            Complexity::Atomic => self.text(&node, "complexityKind", "Atomic"),
+            Complexity::Multiset(count) => {
+                self.text(&node, "complexityKind", "Multiset");
+                self.typed(&node, "multisetCount", count, XSD_INTEGER);
+            }
+        }
+        self.link(&node, "hasBoundary", &system.boundary.info.id);
+        self.typed(&node, "radius", system.radius, XSD_FLOAT);
+        if let Some(transform) = &system.transform {
+            self.json(&node, "layout", transform);
+        }
+        self.text(&node, "equivalenceClass", &system.equivalence);
+        if !system.history.is_empty() {
+            self.json(&node, "history", &system.history);
+        }
+        if let Some(transformation) = &system.transformation {
+            self.json(&node, "transformation", transformation);
+        }
+        self.typed(&node, "memberAutonomy", system.member_autonomy, XSD_FLOAT);
+        if let Some(time_constant) = &system.time_constant {
+            self.text(&node, "timeConstant", &time_constant.to_string());
+        }
+        if let Some(archetype) = &system.archetype {
+            self.text(&node, "archetype", &variant(archetype));
+        }
+        if let Some(agent) = &system.agent {
+            let config = Term::Iri(format!("{}{}-agent", self.base, system.info.id));
+            self.add(&node, "hasAgentConfig", config.clone());
+            self.class(&config, "AgentModel");
+            self.text(&config, "agentKind", &variant(&agent.kind));
+            self.typed(&config, "agencyCapacity", agent.agency_capacity, XSD_FLOAT);
+            for primitive in &agent.primitives {
+                self.text(&config, "processPrimitive", &variant(primitive));
+            }
+            if !agent.cognitive_params.is_empty() {
+                self.json(&config, "cognitiveParameters", &agent.cognitive_params);
+            }
+            if !agent.process_configs.is_empty() {
+                self.json(&config, "processConfigs", &agent.process_configs);
+            }
+            if !agent.initial_state.is_empty() {
+                self.json(&config, "initialState", &agent.initial_state);
+            }
+            if let Some(network) = &agent.network_config {
+                self.json(&config, "networkConfig", network);
+            }
+        }
+
+        self.boundary(&system.boundary);
+        for external in system.sources.iter().chain(&system.sinks) {
+            let external = self.external(external);
+            self.add(&external, "externalTo", node.clone());
+        }
+    }
+
+    fn boundary(&mut self, boundary: &Boundary) {
+        let node = self.node(&boundary.info.id);
+        self.class(&node, "Boundary");
+        self.info(&node, &boundary.info);
+        self.typed(&node, "porosity", boundary.porosity, XSD_FLOAT);
+        self.typed(
+            &node,
+            "perceptiveFuzziness",
+            boundary.perceptive_fuzziness,
+            XSD_FLOAT,
+        );
+        if let Some(interface) = &boundary.parent_interface {
+            self.link(&node, "parentInterface", interface);
+        }
+        for interface in &boundary.interfaces {
+            self.link(&node, "hasInterface", &interface.info.id);
+        }
+        for interface in &boundary.interfaces {
+            self.interface(interface);
+        }
+    }
+
+    fn interface(&mut self, interface: &Interface) {
+        let node = self.node(&interface.info.id);
+        self.class(&node, "Interface");
+        self.info(&node, &interface.info);
+        self.text(&node, "protocol", &interface.protocol);
+        let ty = match interface.ty {
+            InterfaceType::Export => "Export",
+            InterfaceType::Import => "Import",
+            InterfaceType::Hybrid => "Hybrid",
+        };
+        self.text(&node, "interfaceType", ty);
+        for target in &interface.exports_to {
+            self.link(&node, "exportsTo", target);
+        }
+        for origin in &interface.receives_from {
+            self.link(&node, "receivesFrom", origin);
+        }
+        if let Some(angle) = interface.angle {
+            self.typed(&node, "interfaceAngle", angle, XSD_FLOAT);
+        }
+        if let Some(paired) = &interface.paired_with {
+            self.link(&node, "pairedWith", paired);
+        }
+    }
+
+    fn interaction(&mut self, interaction: &Interaction) {
+        let node = self.node(&interaction.info.id);
+        self.class(&node, "Interaction");
+        self.info(&node, &interaction.info);
+        self.text(&node, "substanceType", &variant(&interaction.substance.ty));
+        self.text(&node, "substanceSubType", &interaction.substance.sub_type);
+        self.text(&node, "interactionType", &variant(&interaction.ty));
+        self.text(&node, "usability", &variant(&interaction.usability));
+        self.link(&node, "source", &interaction.source);
+        if let Some(interface) = &interaction.source_interface {
+            self.link(&node, "sourceInterface", interface);
+        }
+        self.link(&node, "sink", &interaction.sink);
+        if let Some(interface) = &interaction.sink_interface {
+            self.link(&node, "sinkInterface", interface);
+        }
+        self.typed(&node, "amount", interaction.amount, XSD_DECIMAL);
+        self.text(&node, "unit", &interaction.unit);
+        if !interaction.parameters.is_empty() {
+            self.json(&node, "parameters", &interaction.parameters);
+        }
+        if !interaction.smart_parameters.is_empty() {
+            self.json(&node, "smartParameters", &interaction.smart_parameters);
+        }
+        if let Some(offset) = &interaction.endpoint_offset {
+            self.json(&node, "endpointOffset", offset);
+        }
+    }
+
+    /// `bert:isEquivalentTo` between every two external entities of one
+    /// same-as group, earlier first, as `bert-typedb` pairs them.
+    fn equivalences(&mut self, model: &WorldModel) {
+        let mut groups: BTreeMap<usize, Vec<&Id>> = BTreeMap::new();
+        let environment = &model.environment;
+        let externals = environment.sources.iter().chain(&environment.sinks).chain(
+            model
+                .systems
+                .iter()
+                .flat_map(|s| s.sources.iter().chain(&s.sinks)),
+        );
+        for external in externals {
+            if let Some(group) = external.is_same_as_id {
+                groups.entry(group).or_default().push(&external.info.id);
+            }
+        }
+        for members in groups.values() {
+            for (i, a) in members.iter().enumerate() {
+                for b in &members[i + 1..] {
+                    let node = self.node(a);
+                    self.link(&node, "isEquivalentTo", b);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! RDF terms, triples and graphs.
+
+pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+pub const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+pub const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+pub const OWL: &str = "http://www.w3.org/2002/07/owl#";
+
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+pub const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+pub const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+pub const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+pub const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+pub const XSD_DECIMAL: &str = "http://www.w3.org/2001/XMLSchema#decimal";
+pub const XSD_FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+pub const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+/// A node or value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    Iri(String),
+    /// A blank node, by its label within one document.
+    Blank(String),
+    Literal(Literal),
+}
+
+impl Term {
+    pub fn iri(&self) -> Option<&str> {
+        match self {
+            Term::Iri(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    pub fn literal(&self) -> Option<&Literal> {
+        match self {
+            Term::Literal(literal) => Some(literal),
+            _ => None,
+        }
+    }
+}
+
+/// A literal: its lexical form, its datatype IRI and, for `rdf:langString`,
+/// its language tag.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Literal {
+    pub lexical: String,
+    pub datatype: String,
+    pub language: Option<String>,
+}
+
+impl Literal {
+    pub fn new(lexical: impl Into<String>, datatype: &str) -> Self {
+        Self {
+            lexical: lexical.into(),
+            datatype: datatype.to_string(),
+            language: None,
+        }
+    }
+
+    pub fn string(lexical: impl Into<String>) -> Self {
+        Self::new(lexical, XSD_STRING)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term,
+}
+
+/// Triples in the order they were written or read, with the prefixes to
+/// abbreviate their IRIs by.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    pub prefixes: Vec<(String, String)>,
+    pub triples: Vec<Triple>,
+}
+
+impl Graph {
+    pub fn add(&mut self, subject: Term, predicate: impl Into<String>, object: Term) {
+        self.triples.push(Triple {
+            subject,
+            predicate: predicate.into(),
+            object,
+        });
+    }
+
+    /// Subjects in the order they first appear.
+    pub fn subjects(&self) -> Vec<&Term> {
+        let mut seen = std::collections::HashSet::new();
+        self.triples
+            .iter()
+            .map(|t| &t.subject)
+            .filter(|s| seen.insert(*s))
+            .collect()
+    }
+
+    /// `iri` as a prefixed name, when a prefix covers it and what is left is
+    /// a valid local name. The longest namespace wins.
+    pub fn compact(&self, iri: &str) -> Option<String> {
+        self.prefixes
+            .iter()
+            .filter(|(_, ns)| iri.starts_with(ns.as_str()))
+            .max_by_key(|(_, ns)| ns.len())
+            .map(|(prefix, ns)| (prefix, &iri[ns.len()..]))
+            .filter(|(_, local)| is_local_name(local))
+            .map(|(prefix, local)| format!("{prefix}:{local}"))
+    }
+}
+
+/// Whether `local` can follow `prefix:` unescaped: letters, digits, `_`,
+/// `-`, `.` and `:`, not starting with `-` or `.` nor ending with `.`.
+pub(crate) fn is_local_name(local: &str) -> bool {
+    let name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':');
+    local.chars().all(name_char) && !local.starts_with(['-', '.']) && !local.ends_with('.')
+}
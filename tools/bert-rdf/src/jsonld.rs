@@ -0,0 +1,289 @@
+//! JSON-LD, written as a flat `@graph` under an inline context of the graph's
+//! prefixes.
+
+use crate::graph::*;
+use crate::Error;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+pub(crate) fn write(graph: &Graph) -> String {
+    let context: Map<String, Value> = graph
+        .prefixes
+        .iter()
+        .map(|(prefix, ns)| (prefix.clone(), Value::from(ns.as_str())))
+        .collect();
+
+    let mut nodes: Vec<Map<String, Value>> = Vec::new();
+    let mut index: HashMap<&Term, usize> = HashMap::new();
+    for triple in &graph.triples {
+        let i = *index.entry(&triple.subject).or_insert_with(|| {
+            let mut node = Map::new();
+            node.insert("@id".into(), id(graph, &triple.subject).into());
+            nodes.push(node);
+            nodes.len() - 1
+        });
+        let (key, value) = if triple.predicate == RDF_TYPE {
+            let class = match &triple.object {
+                Term::Iri(class) => compact(graph, class),
+                other => id(graph, other),
+            };
+            ("@type".to_string(), Value::from(class))
+        } else {
+            (
+                compact(graph, &triple.predicate),
+                value(graph, &triple.object),
+            )
+        };
+        let node = &mut nodes[i];
+        match node.get_mut(&key) {
+            None => {
+                node.insert(key, value);
+            }
+            Some(Value::Array(values)) => values.push(value),
+            Some(single) => *single = json!([single.take(), value]),
+        }
+    }
+
+    let document = json!({
+        "@context": context,
+        "@graph": nodes,
+    });
+    let mut text = serde_json::to_string_pretty(&document).unwrap_or_default();
+    text.push('\n');
+    text
+}
+
+fn compact(graph: &Graph, iri: &str) -> String {
+    graph.compact(iri).unwrap_or_else(|| iri.to_string())
+}
+
+fn id(graph: &Graph, term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => compact(graph, iri),
+        Term::Blank(label) => format!("_:{label}"),
+        Term::Literal(literal) => literal.lexical.clone(),
+    }
+}
+
+fn value(graph: &Graph, term: &Term) -> Value {
+    let literal = match term {
+        Term::Literal(literal) => literal,
+        other => return json!({ "@id": id(graph, other) }),
+    };
+    if let Some(language) = &literal.language {
+        return json!({ "@value": literal.lexical, "@language": language });
+    }
+    let native = match literal.datatype.as_str() {
+        XSD_STRING => Some(Value::from(literal.lexical.as_str())),
+        XSD_INTEGER => literal.lexical.parse::<i64>().ok().map(Value::from),
+        XSD_BOOLEAN => literal.lexical.parse::<bool>().ok().map(Value::from),
+        RDF_JSON => {
+            let json: Option<Value> = serde_json::from_str(&literal.lexical).ok();
+            return match json {
+                Some(json) => json!({ "@value": json, "@type": "@json" }),
+                None => json!({ "@value": literal.lexical, "@type": compact(graph, RDF_JSON) }),
+            };
+        }
+        _ => None,
+    };
+    native.unwrap_or_else(|| {
+        json!({
+            "@value": literal.lexical,
+            "@type": compact(graph, &literal.datatype),
+        })
+    })
+}
+
+pub(crate) fn parse(text: &str) -> Result<Graph, Error> {
+    let document: Value = serde_json::from_str(text).map_err(|e| Error::JsonLd(e.to_string()))?;
+    let mut reader = Reader {
+        prefixes: Vec::new(),
+        graph: Graph::default(),
+        blanks: 0,
+    };
+    let nodes = match &document {
+        Value::Object(top) => {
+            if let Some(context) = top.get("@context") {
+                reader.context(context)?;
+            }
+            match top.get("@graph") {
+                Some(Value::Array(nodes)) => nodes.iter().collect(),
+                Some(node) => vec![node],
+                None => vec![&document],
+            }
+        }
+        Value::Array(nodes) => nodes.iter().collect(),
+        _ => return Err(Error::JsonLd("expected an object or an array".to_string())),
+    };
+    for node in nodes {
+        reader.node(node)?;
+    }
+    reader.graph.prefixes = reader.prefixes;
+    Ok(reader.graph)
+}
+
+struct Reader {
+    prefixes: Vec<(String, String)>,
+    graph: Graph,
+    blanks: usize,
+}
+
+impl Reader {
+    /// Reads the prefix definitions of a context; remote contexts and other
+    /// term definitions are ignored.
+    fn context(&mut self, context: &Value) -> Result<(), Error> {
+        match context {
+            Value::Array(contexts) => contexts.iter().try_for_each(|c| self.context(c)),
+            Value::Object(terms) => {
+                for (term, definition) in terms {
+                    let ns = match definition {
+                        Value::String(ns) => ns,
+                        Value::Object(d) => match d.get("@id") {
+                            Some(Value::String(ns)) => ns,
+                            _ => continue,
+                        },
+                        _ => continue,
+                    };
+                    if !term.starts_with('@') {
+                        self.prefixes.retain(|(p, _)| p != term);
+                        self.prefixes.push((term.clone(), ns.clone()));
+                    }
+                }
+                Ok(())
+            }
+            Value::String(_) | Value::Null => Ok(()),
+            _ => Err(Error::JsonLd("invalid @context".to_string())),
+        }
+    }
+
+    /// A compact IRI, term or blank node label, expanded.
+    fn expand(&self, name: &str) -> Term {
+        if let Some(label) = name.strip_prefix("_:") {
+            return Term::Blank(format!("l-{label}"));
+        }
+        if let Some((_, ns)) = self.prefixes.iter().find(|(p, _)| p == name) {
+            return Term::Iri(ns.clone());
+        }
+        if let Some((prefix, local)) = name.split_once(':') {
+            if !local.starts_with("//") {
+                if let Some((_, ns)) = self.prefixes.iter().find(|(p, _)| p == prefix) {
+                    return Term::Iri(format!("{ns}{local}"));
+                }
+            }
+        }
+        Term::Iri(name.to_string())
+    }
+
+    fn expand_iri(&self, name: &str) -> String {
+        match self.expand(name) {
+            Term::Iri(iri) => iri,
+            _ => name.to_string(),
+        }
+    }
+
+    fn fresh_blank(&mut self) -> Term {
+        self.blanks += 1;
+        Term::Blank(format!("b{}", self.blanks))
+    }
+
+    /// Adds a node object's triples and returns its subject.
+    fn node(&mut self, node: &Value) -> Result<Term, Error> {
+        let node = node
+            .as_object()
+            .ok_or_else(|| Error::JsonLd(format!("expected a node object, found {node}")))?;
+        let subject = match node.get("@id") {
+            Some(Value::String(id)) => self.expand(id),
+            Some(other) => return Err(Error::JsonLd(format!("invalid @id {other}"))),
+            None => self.fresh_blank(),
+        };
+        for (key, value) in node {
+            match key.as_str() {
+                "@type" => {
+                    let types = match value {
+                        Value::Array(types) => types.iter().collect(),
+                        single => vec![single],
+                    };
+                    for ty in types {
+                        let ty = ty
+                            .as_str()
+                            .ok_or_else(|| Error::JsonLd(format!("invalid @type {ty}")))?;
+                        let class = self.expand(ty);
+                        self.graph.add(subject.clone(), RDF_TYPE, class);
+                    }
+                }
+                key if key.starts_with('@') => {}
+                key => {
+                    let predicate = self.expand_iri(key);
+                    let values = match value {
+                        Value::Array(values) => values.iter().collect(),
+                        single => vec![single],
+                    };
+                    for value in values {
+                        if let Some(object) = self.object(value)? {
+                            self.graph.add(subject.clone(), predicate.clone(), object);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(subject)
+    }
+
+    fn object(&mut self, value: &Value) -> Result<Option<Term>, Error> {
+        let literal =
+            |lexical: String, datatype: &str| Some(Term::Literal(Literal::new(lexical, datatype)));
+        Ok(match value {
+            Value::Null => None,
+            Value::String(s) => Some(Term::Literal(Literal::string(s.as_str()))),
+            Value::Bool(b) => literal(b.to_string(), XSD_BOOLEAN),
+            Value::Number(n) if n.is_f64() => literal(n.to_string(), XSD_DOUBLE),
+            Value::Number(n) => literal(n.to_string(), XSD_INTEGER),
+            Value::Array(_) => {
+                return Err(Error::JsonLd("nested arrays are not supported".to_string()))
+            }
+            Value::Object(object) => {
+                if let Some(inner) = object.get("@value") {
+                    let lexical = match inner {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    match (object.get("@type"), object.get("@language")) {
+                        (Some(Value::String(ty)), _) if ty == "@json" => {
+                            literal(inner.to_string(), RDF_JSON)
+                        }
+                        (Some(Value::String(ty)), _) => {
+                            let datatype = self.expand_iri(ty);
+                            literal(lexical, &datatype)
+                        }
+                        (_, Some(Value::String(language))) => Some(Term::Literal(Literal {
+                            lexical,
+                            datatype: RDF_LANG_STRING.to_string(),
+                            language: Some(language.clone()),
+                        })),
+                        _ => self.object(inner)?,
+                    }
+                } else if let Some(Value::Array(items)) = object.get("@list") {
+                    let mut terms = Vec::new();
+                    for item in items {
+                        terms.extend(self.object(item)?);
+                    }
+                    let mut list = Term::Iri(format!("{RDF}nil"));
+                    for item in terms.into_iter().rev() {
+                        let cell = self.fresh_blank();
+                        self.graph.add(cell.clone(), format!("{RDF}first"), item);
+                        self.graph.add(cell.clone(), format!("{RDF}rest"), list);
+                        list = cell;
+                    }
+                    Some(list)
+                } else if object.len() == 1 && object.contains_key("@id") {
+                    let id = object["@id"]
+                        .as_str()
+                        .ok_or_else(|| Error::JsonLd(format!("invalid @id {}", object["@id"])))?;
+                    Some(self.expand(id))
+                } else {
+                    Some(self.node(value)?)
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,124 @@
+//! BERT models as RDF: Turtle and JSON-LD in a published OWL vocabulary.
+//!
+//! The vocabulary ([`ONTOLOGY`], `vocab/bert.ttl`) mirrors the TypeDB schema
+//! of `bert-typedb`: systems, boundaries, interfaces, interactions and agent
+//! configurations are classes, and composition, `participates_in`,
+//! `routes_through`, `is_equivalent_to` and the other relations are object
+//! properties. [`SHAPES`] (`vocab/bert-shapes.ttl`) holds one SHACL shape per
+//! L2 validation rule, tagged with the rule's code.
+//!
+//! Element IRIs are namespaced by model the way `bert_id`s are in TypeDB:
+//! `bitcoin:C0.1` there is `<https://bert.systems/models/bitcoin/C0.1>` here,
+//! written `bitcoin:C0.1` in Turtle and JSON-LD. The model itself is the
+//! namespace IRI, typed `bert:Model`. The command line names the model after
+//! the file stem unless given `--model-name`.
+//!
+//! Fields the vocabulary has no property structure for (layout, parameters,
+//! history, transformations, agent process configuration, view state) are
+//! `rdf:JSON` literals holding what the model file holds, so
+//! [`from_turtle`]`(`[`to_turtle`]`(model))` and the JSON-LD equivalent give
+//! back the model unchanged, up to [`bert_core::canonicalize`].
+
+mod emit;
+pub mod graph;
+mod jsonld;
+mod rebuild;
+mod turtle;
+
+use bert_core::{Id, WorldModel};
+use graph::Graph;
+use std::fmt;
+
+/// The BERT ontology, in Turtle.
+pub const ONTOLOGY: &str = include_str!("../vocab/bert.ttl");
+
+/// SHACL shapes for the L2 validation rules, in Turtle.
+pub const SHAPES: &str = include_str!("../vocab/bert-shapes.ttl");
+
+/// Namespace of the BERT vocabulary.
+pub const NS: &str = "https://bert.systems/ontology#";
+
+/// Namespace under which each model gets its own.
+pub const MODELS: &str = "https://bert.systems/models/";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Malformed Turtle, at a 1-based line.
+    Turtle { line: usize, message: String },
+    /// Malformed JSON-LD.
+    JsonLd(String),
+    /// Well-formed RDF that does not describe a BERT model.
+    Model(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Turtle { line, message } => write!(f, "Turtle line {line}: {message}"),
+            Error::JsonLd(message) => write!(f, "JSON-LD: {message}"),
+            Error::Model(message) => write!(f, "not a BERT model: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The IRI of the model named `model_name`, which its elements' IRIs extend.
+pub fn model_iri(model_name: &str) -> String {
+    let mut iri = MODELS.to_string();
+    for byte in model_name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            iri.push(byte as char);
+        } else {
+            iri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    iri.push('/');
+    iri
+}
+
+/// The IRI of element `id` in the model named `model_name`; the RDF
+/// counterpart of `bert-typedb`'s `namespaced_id`.
+pub fn element_iri(model_name: &str, id: &Id) -> String {
+    format!("{}{id}", model_iri(model_name))
+}
+
+/// `model` as RDF triples, in a fixed order.
+pub fn to_graph(model: &WorldModel, model_name: &str) -> Graph {
+    emit::emit(model, model_name)
+}
+
+/// `model` as Turtle.
+pub fn to_turtle(model: &WorldModel, model_name: &str) -> String {
+    turtle::write(&to_graph(model, model_name))
+}
+
+/// `model` as a JSON-LD document with an inline context.
+pub fn to_json_ld(model: &WorldModel, model_name: &str) -> String {
+    jsonld::write(&to_graph(model, model_name))
+}
+
+/// The model a graph written by [`to_graph`] describes.
+pub fn from_graph(graph: &Graph) -> Result<WorldModel, Error> {
+    rebuild::rebuild(graph)
+}
+
+/// The model a Turtle document describes.
+pub fn from_turtle(text: &str) -> Result<WorldModel, Error> {
+    from_graph(&parse_turtle(text)?)
+}
+
+/// The model a JSON-LD document describes.
+pub fn from_json_ld(text: &str) -> Result<WorldModel, Error> {
+    from_graph(&parse_json_ld(text)?)
+}
+
+/// The triples of a Turtle document.
+pub fn parse_turtle(text: &str) -> Result<Graph, Error> {
+    turtle::parse(text)
+}
+
+/// The triples of a JSON-LD document. Contexts are read for prefixes only.
+pub fn parse_json_ld(text: &str) -> Result<Graph, Error> {
+    jsonld::parse(text)
+}
@@ -0,0 +1,157 @@
+//! `bert-rdf` — convert BERT model files to and from RDF.
+//!
+//! Usage:
+//!
+//! ```sh
+//! bert-rdf export assets/models/examples/bitcoin.json -o bitcoin.ttl
+//! bert-rdf export assets/models/examples/bitcoin.json --format json-ld
+//! bert-rdf import bitcoin.ttl -o bitcoin.json
+//! bert-rdf vocab > bert.ttl
+//! bert-rdf shapes > bert-shapes.ttl
+//! ```
+//!
+//! Models are read through the migration pipeline, so older file versions
+//! are accepted; `import` writes canonical JSON.
+
+use bert_core::{migrate, to_canonical_json};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "bert-rdf",
+    about = "Convert BERT model files to and from RDF (Turtle, JSON-LD)",
+    version
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write a model as RDF in the BERT vocabulary.
+    Export {
+        /// The model file to export.
+        model: PathBuf,
+        /// RDF syntax; defaults to the output file's extension, or Turtle.
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Name that namespaces the model's IRIs; defaults to the file stem.
+        #[arg(long)]
+        model_name: Option<String>,
+        /// Write the RDF here instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Read a model back from RDF written by `export`.
+    Import {
+        /// The Turtle (.ttl) or JSON-LD (.jsonld, .json) file to import.
+        file: PathBuf,
+        /// RDF syntax; defaults to the file's extension.
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Write the model here instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print the BERT OWL ontology.
+    Vocab,
+    /// Print the SHACL shapes for the L2 validation rules.
+    Shapes,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Turtle,
+    JsonLd,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ttl" => Some(Format::Turtle),
+            "jsonld" | "json" => Some(Format::JsonLd),
+            _ => None,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("bert-rdf: error: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Command::Export {
+            model,
+            format,
+            model_name,
+            output,
+        } => {
+            let bytes = std::fs::read(&model)
+                .map_err(|e| format!("failed to read {}: {e}", model.display()))?;
+            let (world, report) = migrate::from_slice(&bytes)
+                .map_err(|e| format!("failed to parse {}: {e}", model.display()))?;
+            if !report.is_noop() {
+                eprintln!(
+                    "bert-rdf: migrated {}: version {} → {}",
+                    model.display(),
+                    report.original_version,
+                    report.final_version
+                );
+            }
+            let name = model_name
+                .or_else(|| Some(model.file_stem()?.to_str()?.to_string()))
+                .unwrap_or_else(|| "model".to_string());
+            let format = format
+                .or_else(|| output.as_deref().and_then(Format::from_path))
+                .unwrap_or(Format::Turtle);
+            let text = match format {
+                Format::Turtle => bert_rdf::to_turtle(&world, &name),
+                Format::JsonLd => bert_rdf::to_json_ld(&world, &name),
+            };
+            write(output.as_deref(), &text)
+        }
+        Command::Import {
+            file,
+            format,
+            output,
+        } => {
+            let text = std::fs::read_to_string(&file)
+                .map_err(|e| format!("failed to read {}: {e}", file.display()))?;
+            let format = format.or_else(|| Format::from_path(&file)).ok_or_else(|| {
+                format!(
+                    "cannot tell the RDF syntax of {}; pass --format",
+                    file.display()
+                )
+            })?;
+            let model = match format {
+                Format::Turtle => bert_rdf::from_turtle(&text),
+                Format::JsonLd => bert_rdf::from_json_ld(&text),
+            }
+            .map_err(|e| format!("failed to import {}: {e}", file.display()))?;
+            write(output.as_deref(), &to_canonical_json(&model))
+        }
+        Command::Vocab => write(None, bert_rdf::ONTOLOGY),
+        Command::Shapes => write(None, bert_rdf::SHAPES),
+    }
+}
+
+fn write(output: Option<&Path>, text: &str) -> Result<(), String> {
+    match output {
+        Some(path) => std::fs::write(path, text)
+            .map_err(|e| format!("failed to write {}: {e}", path.display())),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,441 @@
+//! Triples → WorldModel.
+//!
+//! The model is put back together as the model file's JSON and deserialized,
+//! so everything `rdf:JSON` carries goes back exactly as it came out. Element
+//! ids are read from `bert:localId`, or from the IRI under the model's
+//! namespace when a node has none (a reference to an element the graph does
+//! not describe).
+
+use crate::graph::*;
+use crate::{Error, NS};
+use bert_core::WorldModel;
+use serde_json::{json, Map, Number, Value};
+use std::collections::HashMap;
+
+pub(crate) fn rebuild(graph: &Graph) -> Result<WorldModel, Error> {
+    let nodes = Nodes::new(graph);
+    let model = nodes
+        .of_class("Model")
+        .next()
+        .ok_or_else(|| Error::Model("no bert:Model node".to_string()))?;
+    let base = model
+        .subject
+        .iri()
+        .ok_or_else(|| Error::Model("the bert:Model node is a blank node".to_string()))?;
+    let reader = Reader {
+        nodes: &nodes,
+        base,
+    };
+
+    let environment = nodes
+        .of_class("Environment")
+        .next()
+        .ok_or_else(|| Error::Model("no bert:Environment node".to_string()))?;
+    let environment_id = reader.id(environment.subject)?;
+
+    // Sources and sinks by the system they are external to, if not the
+    // environment.
+    let mut externals: HashMap<String, (Vec<Value>, Vec<Value>)> = HashMap::new();
+    for node in nodes.of_class("ExternalEntity") {
+        let owner = match node.object("externalTo") {
+            Some(system) => reader.id(system)?,
+            None => environment_id.clone(),
+        };
+        let (sources, sinks) = externals.entry(owner).or_default();
+        let (ty, list) = if node.has_class("Sink") {
+            ("Sink", sinks)
+        } else {
+            ("Source", sources)
+        };
+        let mut value = Map::new();
+        value.insert("info".into(), reader.info(node)?);
+        value.insert("type".into(), ty.into());
+        value.insert("transform".into(), node.json("layout")?);
+        value.insert("equivalence".into(), node.text("equivalenceClass").into());
+        value.insert("model".into(), node.text("externalModel").into());
+        value.insert("is_same_as_id".into(), node.integer("sameAsGroup")?);
+        list.push(value.into());
+    }
+
+    let mut systems = Vec::new();
+    for node in nodes.of_class("System") {
+        let id = reader.id(node.subject)?;
+        let (sources, sinks) = externals.remove(&id).unwrap_or_default();
+        systems.push(reader.system(node, sources, sinks)?);
+    }
+
+    let mut interactions = Vec::new();
+    for node in nodes.of_class("Interaction") {
+        interactions.push(reader.interaction(node)?);
+    }
+
+    let (sources, sinks) = externals.remove(&environment_id).unwrap_or_default();
+    let mut file = Map::new();
+    file.insert("version".into(), model.integer("version")?);
+    if let Some(mode) = model.literal("mode") {
+        file.insert("mode".into(), mode.lexical.clone().into());
+    }
+    file.insert(
+        "environment".into(),
+        json!({
+            "info": reader.info(environment)?,
+            "sources": sources,
+            "sinks": sinks,
+        }),
+    );
+    file.insert("systems".into(), systems.into());
+    file.insert("interactions".into(), interactions.into());
+    let hidden = model.json("hiddenEntities")?;
+    file.insert(
+        "hidden_entities".into(),
+        if hidden.is_null() { json!([]) } else { hidden },
+    );
+    let validation = model.json("validation")?;
+    if !validation.is_null() {
+        file.insert("validation".into(), validation);
+    }
+
+    serde_json::from_value(file.into()).map_err(|e| Error::Model(e.to_string()))
+}
+
+/// The triples about each subject, subjects in document order.
+struct Nodes<'g> {
+    nodes: Vec<Node<'g>>,
+}
+
+impl<'g> Nodes<'g> {
+    fn new(graph: &'g Graph) -> Self {
+        let mut index: HashMap<&Term, usize> = HashMap::new();
+        let mut nodes: Vec<Node> = Vec::new();
+        for triple in &graph.triples {
+            let i = *index.entry(&triple.subject).or_insert_with(|| {
+                nodes.push(Node {
+                    subject: &triple.subject,
+                    properties: Vec::new(),
+                });
+                nodes.len() - 1
+            });
+            nodes[i]
+                .properties
+                .push((triple.predicate.as_str(), &triple.object));
+        }
+        Self { nodes }
+    }
+
+    fn of_class<'a>(&'a self, class: &'a str) -> impl Iterator<Item = &'a Node<'g>> + 'a {
+        self.nodes.iter().filter(move |n| n.has_class(class))
+    }
+
+    fn get(&self, subject: &Term) -> Option<&Node<'g>> {
+        self.nodes.iter().find(|n| n.subject == subject)
+    }
+}
+
+struct Node<'g> {
+    subject: &'g Term,
+    properties: Vec<(&'g str, &'g Term)>,
+}
+
+impl<'g> Node<'g> {
+    /// Values of a `bert:` property, in document order.
+    fn objects<'a>(&'a self, property: &'a str) -> impl Iterator<Item = &'g Term> + 'a {
+        self.properties
+            .iter()
+            .filter(move |(p, _)| p.strip_prefix(NS) == Some(property))
+            .map(|(_, o)| *o)
+    }
+
+    fn object(&self, property: &str) -> Option<&'g Term> {
+        self.objects(property).next()
+    }
+
+    fn literal(&self, property: &str) -> Option<&'g Literal> {
+        self.object(property).and_then(Term::literal)
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.properties
+            .iter()
+            .any(|(p, o)| *p == RDF_TYPE && o.iri().and_then(|c| c.strip_prefix(NS)) == Some(class))
+    }
+
+    fn name(&self) -> String {
+        match self.subject {
+            Term::Iri(iri) => format!("<{iri}>"),
+            Term::Blank(label) => format!("_:{label}"),
+            Term::Literal(literal) => literal.lexical.clone(),
+        }
+    }
+
+    fn invalid(&self, property: &str, what: &str) -> Error {
+        Error::Model(format!("{} bert:{property} is not {what}", self.name()))
+    }
+
+    /// A string property, empty when absent.
+    fn text(&self, property: &str) -> String {
+        self.literal(property)
+            .map(|l| l.lexical.clone())
+            .unwrap_or_default()
+    }
+
+    fn required(&self, property: &str) -> Result<&'g Literal, Error> {
+        self.literal(property)
+            .ok_or_else(|| Error::Model(format!("{} has no bert:{property}", self.name())))
+    }
+
+    /// An integer property, null when absent.
+    fn integer(&self, property: &str) -> Result<Value, Error> {
+        match self.literal(property) {
+            None => Ok(Value::Null),
+            Some(l) => l
+                .lexical
+                .trim()
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| self.invalid(property, "an integer")),
+        }
+    }
+
+    /// A float property, null when absent.
+    fn number(&self, property: &str) -> Result<Value, Error> {
+        match self.literal(property) {
+            None => Ok(Value::Null),
+            Some(l) => l
+                .lexical
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| self.invalid(property, "a finite number")),
+        }
+    }
+
+    fn boolean(&self, property: &str) -> Result<bool, Error> {
+        match self.required(property)?.lexical.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(self.invalid(property, "a boolean")),
+        }
+    }
+
+    /// An `rdf:JSON` property, null when absent.
+    fn json(&self, property: &str) -> Result<Value, Error> {
+        match self.literal(property) {
+            None => Ok(Value::Null),
+            Some(l) => serde_json::from_str(&l.lexical).map_err(|_| self.invalid(property, "JSON")),
+        }
+    }
+}
+
+struct Reader<'a, 'g> {
+    nodes: &'a Nodes<'g>,
+    base: &'g str,
+}
+
+impl Reader<'_, '_> {
+    /// The id of the element a node is, or an IRI names.
+    fn id(&self, term: &Term) -> Result<String, Error> {
+        if let Some(id) = self.nodes.get(term).and_then(|n| n.literal("localId")) {
+            return Ok(id.lexical.clone());
+        }
+        term.iri()
+            .and_then(|iri| iri.strip_prefix(self.base))
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| Error::Model(format!("{term:?} is not an element of this model")))
+    }
+
+    fn link(&self, node: &Node, property: &str) -> Result<Value, Error> {
+        node.object(property)
+            .map(|o| self.id(o).map(Value::from))
+            .unwrap_or(Ok(Value::Null))
+    }
+
+    fn links(&self, node: &Node, property: &str) -> Result<Value, Error> {
+        node.objects(property)
+            .map(|o| self.id(o).map(Value::from))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::from)
+    }
+
+    fn info(&self, node: &Node) -> Result<Value, Error> {
+        Ok(json!({
+            "id": self.id(node.subject)?,
+            "level": node.integer("level")?,
+            "name": node.text("displayName"),
+            "description": node.text("description"),
+        }))
+    }
+
+    fn system(&self, node: &Node, sources: Vec<Value>, sinks: Vec<Value>) -> Result<Value, Error> {
+        let parent = match node.object("partOf") {
+            Some(parent) => self.id(parent)?,
+            None => self.id(node
+                .object("inEnvironment")
+                .ok_or_else(|| Error::Model(format!("{} has no parent", node.name())))?)?,
+        };
+        let complexity = match node.required("complexityKind")?.lexical.as_str() {
+            "Atomic" => json!("Atomic"),
+            "Complex" => json!({"Complex": {
+                "adaptable": node.boolean("complexAdaptable")?,
+                "evolveable": node.boolean("complexEvolveable")?,
+            }}),
+            "Multiset" => json!({"Multiset": node.integer("multisetCount")?}),
+            _ => return Err(node.invalid("complexityKind", "Atomic, Complex or Multiset")),
+        };
+        let boundary = node
+            .object("hasBoundary")
+            .and_then(|b| self.nodes.get(b))
+            .ok_or_else(|| Error::Model(format!("{} has no boundary", node.name())))?;
+        let interfaces = boundary
+            .objects("hasInterface")
+            .map(|i| {
+                let interface = self.nodes.get(i).ok_or_else(|| {
+                    Error::Model(format!("{} has an undescribed interface", boundary.name()))
+                })?;
+                self.interface(interface)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut system = Map::new();
+        system.insert("info".into(), self.info(node)?);
+        system.insert("sources".into(), sources.into());
+        system.insert("sinks".into(), sinks.into());
+        system.insert("parent".into(), parent.into());
+        system.insert("complexity".into(), complexity);
+        system.insert(
+            "boundary".into(),
+            json!({
+                "info": self.info(boundary)?,
+                "porosity": boundary.number("porosity")?,
+                "perceptive_fuzziness": boundary.number("perceptiveFuzziness")?,
+                "interfaces": interfaces,
+                "parent_interface": self.link(boundary, "parentInterface")?,
+            }),
+        );
+        system.insert("radius".into(), node.number("radius")?);
+        system.insert("transform".into(), node.json("layout")?);
+        system.insert("equivalence".into(), node.text("equivalenceClass").into());
+        system.insert("history".into(), node.json("history")?);
+        system.insert("transformation".into(), node.json("transformation")?);
+        system.insert("member_autonomy".into(), node.number("memberAutonomy")?);
+        system.insert(
+            "time_constant".into(),
+            node.literal("timeConstant")
+                .map_or(Value::Null, |l| l.lexical.clone().into()),
+        );
+        system.insert(
+            "archetype".into(),
+            node.literal("archetype")
+                .map_or(Value::Null, |l| l.lexical.clone().into()),
+        );
+        if let Some(config) = node.object("hasAgentConfig") {
+            let config = self.nodes.get(config).ok_or_else(|| {
+                Error::Model(format!("{} has an undescribed agent config", node.name()))
+            })?;
+            system.insert("agent".into(), self.agent(config)?);
+        }
+        // History and transformation are optional in the file but not null.
+        system.retain(|key, value| {
+            !(value.is_null() && matches!(key.as_str(), "history" | "transformation"))
+        });
+        Ok(system.into())
+    }
+
+    fn interface(&self, node: &Node) -> Result<Value, Error> {
+        let mut interface = Map::new();
+        interface.insert("info".into(), self.info(node)?);
+        interface.insert("protocol".into(), node.text("protocol").into());
+        interface.insert(
+            "type".into(),
+            node.required("interfaceType")?.lexical.clone().into(),
+        );
+        interface.insert("exports_to".into(), self.links(node, "exportsTo")?);
+        interface.insert("receives_from".into(), self.links(node, "receivesFrom")?);
+        interface.insert("angle".into(), node.number("interfaceAngle")?);
+        let paired = self.link(node, "pairedWith")?;
+        if !paired.is_null() {
+            interface.insert("paired_with".into(), paired);
+        }
+        Ok(interface.into())
+    }
+
+    fn agent(&self, node: &Node) -> Result<Value, Error> {
+        let mut agent = Map::new();
+        agent.insert(
+            "kind".into(),
+            node.required("agentKind")?.lexical.clone().into(),
+        );
+        agent.insert("agency_capacity".into(), node.number("agencyCapacity")?);
+        let primitives: Vec<Value> = node
+            .objects("processPrimitive")
+            .filter_map(Term::literal)
+            .map(|l| l.lexical.clone().into())
+            .collect();
+        agent.insert("primitives".into(), primitives.into());
+        for (property, key) in [
+            ("cognitiveParameters", "cognitive_params"),
+            ("processConfigs", "process_configs"),
+            ("initialState", "initial_state"),
+            ("networkConfig", "network_config"),
+        ] {
+            let value = node.json(property)?;
+            if !value.is_null() {
+                agent.insert(key.into(), value);
+            }
+        }
+        Ok(agent.into())
+    }
+
+    fn interaction(&self, node: &Node) -> Result<Value, Error> {
+        let mut interaction = Map::new();
+        interaction.insert("info".into(), self.info(node)?);
+        interaction.insert(
+            "substance".into(),
+            json!({
+                "sub_type": node.text("substanceSubType"),
+                "type": node.required("substanceType")?.lexical,
+            }),
+        );
+        interaction.insert(
+            "type".into(),
+            node.required("interactionType")?.lexical.clone().into(),
+        );
+        interaction.insert(
+            "usability".into(),
+            node.required("usability")?.lexical.clone().into(),
+        );
+        interaction.insert("source".into(), self.link(node, "source")?);
+        interaction.insert(
+            "source_interface".into(),
+            self.link(node, "sourceInterface")?,
+        );
+        interaction.insert("sink".into(), self.link(node, "sink")?);
+        interaction.insert("sink_interface".into(), self.link(node, "sinkInterface")?);
+        interaction.insert(
+            "amount".into(),
+            node.required("amount")?.lexical.trim().into(),
+        );
+        interaction.insert("unit".into(), node.text("unit").into());
+        let parameters = node.json("parameters")?;
+        interaction.insert(
+            "parameters".into(),
+            if parameters.is_null() {
+                json!([])
+            } else {
+                parameters
+            },
+        );
+        for (property, key) in [
+            ("smartParameters", "smart_parameters"),
+            ("endpointOffset", "endpoint_offset"),
+        ] {
+            let value = node.json(property)?;
+            if !value.is_null() {
+                interaction.insert(key.into(), value);
+            }
+        }
+        Ok(interaction.into())
+    }
+}
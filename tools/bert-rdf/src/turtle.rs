@@ -0,0 +1,590 @@
+//! Turtle, written one subject per block and read in full.
+
+use crate::graph::*;
+use crate::Error;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub(crate) fn write(graph: &Graph) -> String {
+    let mut out = String::new();
+    for (prefix, ns) in &graph.prefixes {
+        let _ = writeln!(out, "@prefix {prefix}: <{}> .", escape_iri(ns));
+    }
+
+    let mut by_subject: HashMap<&Term, Vec<&Triple>> = HashMap::new();
+    for triple in &graph.triples {
+        by_subject.entry(&triple.subject).or_default().push(triple);
+    }
+    for subject in graph.subjects() {
+        out.push('\n');
+        out.push_str(&term(graph, subject));
+        let mut previous: Option<&str> = None;
+        for triple in &by_subject[subject] {
+            if previous == Some(triple.predicate.as_str()) {
+                out.push_str(", ");
+            } else {
+                if previous.is_some() {
+                    out.push_str(" ;");
+                }
+                out.push_str("\n    ");
+                if triple.predicate == RDF_TYPE {
+                    out.push('a');
+                } else {
+                    out.push_str(&iri(graph, &triple.predicate));
+                }
+                out.push(' ');
+            }
+            out.push_str(&term(graph, &triple.object));
+            previous = Some(&triple.predicate);
+        }
+        out.push_str(" .\n");
+    }
+    out
+}
+
+fn iri(graph: &Graph, iri: &str) -> String {
+    graph
+        .compact(iri)
+        .unwrap_or_else(|| format!("<{}>", escape_iri(iri)))
+}
+
+fn term(graph: &Graph, term: &Term) -> String {
+    match term {
+        Term::Iri(i) => iri(graph, i),
+        Term::Blank(label) => format!("_:{label}"),
+        Term::Literal(literal) => {
+            let bare = match literal.datatype.as_str() {
+                XSD_INTEGER => is_integer(&literal.lexical),
+                XSD_BOOLEAN => matches!(literal.lexical.as_str(), "true" | "false"),
+                _ => false,
+            };
+            if bare {
+                return literal.lexical.clone();
+            }
+            let quoted = format!("\"{}\"", escape_string(&literal.lexical));
+            match (&literal.language, literal.datatype.as_str()) {
+                (Some(language), _) => format!("{quoted}@{language}"),
+                (None, XSD_STRING) => quoted,
+                (None, datatype) => format!("{quoted}^^{}", iri(graph, datatype)),
+            }
+        }
+    }
+}
+
+fn is_integer(lexical: &str) -> bool {
+    let digits = lexical.strip_prefix(['+', '-']).unwrap_or(lexical);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_iri(iri: &str) -> String {
+    let mut out = String::with_capacity(iri.len());
+    for c in iri.chars() {
+        if c <= ' ' || "<>\"{}|^`\\".contains(c) {
+            let _ = write!(out, "\\u{:04X}", c as u32);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+pub(crate) fn parse(text: &str) -> Result<Graph, Error> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        line: 1,
+        base: String::new(),
+        prefixes: HashMap::new(),
+        graph: Graph::default(),
+        blanks: 0,
+    };
+    parser.document()?;
+    Ok(parser.graph)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    base: String,
+    prefixes: HashMap<String, String>,
+    graph: Graph,
+    blanks: usize,
+}
+
+impl Parser {
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::Turtle {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn looking_at(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    /// Skips whitespace and comments.
+    fn skip(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        self.skip();
+        match self.peek() {
+            Some(found) if found == c => {
+                self.bump();
+                Ok(())
+            }
+            Some(found) => Err(self.error(format!("expected '{c}', found '{found}'"))),
+            None => Err(self.error(format!("expected '{c}', found end of input"))),
+        }
+    }
+
+    fn fresh_blank(&mut self) -> Term {
+        self.blanks += 1;
+        Term::Blank(format!("b{}", self.blanks))
+    }
+
+    fn document(&mut self) -> Result<(), Error> {
+        loop {
+            self.skip();
+            match self.peek() {
+                None => return Ok(()),
+                Some('@') => self.directive()?,
+                Some(_) if self.keyword("PREFIX") || self.keyword("BASE") => self.directive()?,
+                Some(_) => self.triples()?,
+            }
+        }
+    }
+
+    /// Whether a SPARQL-style directive keyword starts here.
+    fn keyword(&self, keyword: &str) -> bool {
+        keyword
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i).is_some_and(|p| p.eq_ignore_ascii_case(&c)))
+            && self
+                .peek_at(keyword.len())
+                .is_some_and(|c| c.is_whitespace())
+    }
+
+    fn directive(&mut self) -> Result<(), Error> {
+        let turtle_style = self.peek() == Some('@');
+        if turtle_style {
+            self.bump();
+        }
+        let word = self.name();
+        self.skip();
+        match word.to_ascii_lowercase().as_str() {
+            "prefix" => {
+                let prefix = self.name();
+                let prefix = prefix
+                    .strip_suffix(':')
+                    .ok_or_else(|| self.error(format!("expected a prefix, found '{prefix}'")))?
+                    .to_string();
+                self.skip();
+                let ns = self.iri_ref()?;
+                self.prefixes.insert(prefix.clone(), ns.clone());
+                self.graph.prefixes.retain(|(p, _)| *p != prefix);
+                self.graph.prefixes.push((prefix, ns));
+            }
+            "base" => self.base = self.iri_ref()?,
+            _ => return Err(self.error(format!("unknown directive '{word}'"))),
+        }
+        if turtle_style {
+            self.expect('.')?;
+        }
+        Ok(())
+    }
+
+    fn triples(&mut self) -> Result<(), Error> {
+        let subject = match self.peek() {
+            Some('[') => {
+                let subject = self.blank_property_list()?;
+                self.skip();
+                if self.peek() == Some('.') {
+                    self.bump();
+                    return Ok(());
+                }
+                subject
+            }
+            Some('(') => self.collection()?,
+            _ => self.resource()?,
+        };
+        self.predicate_objects(&subject)?;
+        self.expect('.')
+    }
+
+    fn predicate_objects(&mut self, subject: &Term) -> Result<(), Error> {
+        loop {
+            self.skip();
+            let predicate = self.verb()?;
+            loop {
+                let object = self.object()?;
+                self.graph.add(subject.clone(), predicate.clone(), object);
+                self.skip();
+                if self.peek() != Some(',') {
+                    break;
+                }
+                self.bump();
+            }
+            // `;` may repeat, and may end the list.
+            let mut separated = false;
+            loop {
+                self.skip();
+                if self.peek() != Some(';') {
+                    break;
+                }
+                self.bump();
+                separated = true;
+            }
+            self.skip();
+            if !separated || matches!(self.peek(), Some('.' | ']') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<String, Error> {
+        if self.peek() == Some('a')
+            && self
+                .peek_at(1)
+                .is_some_and(|c| c.is_whitespace() || matches!(c, '<' | '[' | '"' | '\'' | '('))
+        {
+            self.bump();
+            return Ok(RDF_TYPE.to_string());
+        }
+        match self.resource()? {
+            Term::Iri(iri) => Ok(iri),
+            _ => Err(self.error("a predicate must be an IRI")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Term, Error> {
+        self.skip();
+        match self.peek() {
+            Some('[') => self.blank_property_list(),
+            Some('(') => self.collection(),
+            Some('"' | '\'') => self.string_literal(),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-') => self.number(),
+            Some('.') if self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) => self.number(),
+            _ => {
+                let start = self.pos;
+                let word = self.name();
+                if word == "true" || word == "false" {
+                    return Ok(Term::Literal(Literal::new(word, XSD_BOOLEAN)));
+                }
+                self.pos = start;
+                self.resource()
+            }
+        }
+    }
+
+    /// An IRI, a prefixed name or a labelled blank node.
+    fn resource(&mut self) -> Result<Term, Error> {
+        self.skip();
+        match self.peek() {
+            Some('<') => Ok(Term::Iri(self.iri_ref()?)),
+            Some('_') if self.peek_at(1) == Some(':') => {
+                self.pos += 2;
+                let label = self.name();
+                Ok(Term::Blank(format!("l-{label}")))
+            }
+            Some(_) => {
+                let name = self.name();
+                let (prefix, local) = name
+                    .split_once(':')
+                    .ok_or_else(|| self.error(format!("expected an IRI, found '{name}'")))?;
+                let ns = self
+                    .prefixes
+                    .get(prefix)
+                    .ok_or_else(|| self.error(format!("undeclared prefix '{prefix}'")))?;
+                Ok(Term::Iri(format!("{ns}{}", unescape_local(local))))
+            }
+            None => Err(self.error("expected an IRI, found end of input")),
+        }
+    }
+
+    /// A run of name characters, without a trailing `.` (which ends the
+    /// statement).
+    fn name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '%') {
+                name.push(c);
+                self.bump();
+            } else if c == '\\' && self.peek_at(1).is_some() {
+                name.push(c);
+                self.bump();
+                name.push(self.bump().unwrap_or_default());
+            } else {
+                break;
+            }
+        }
+        while name.ends_with('.') && !name.ends_with("\\.") {
+            name.pop();
+            self.pos -= 1;
+        }
+        name
+    }
+
+    fn iri_ref(&mut self) -> Result<String, Error> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.bump() {
+                Some('>') => break,
+                Some('\\') => {
+                    let c = self.unicode_escape()?;
+                    iri.push(c);
+                }
+                Some(c) if c.is_whitespace() => {
+                    return Err(self.error("whitespace in an IRI"));
+                }
+                Some(c) => iri.push(c),
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+        Ok(self.resolve(iri))
+    }
+
+    fn resolve(&self, iri: String) -> String {
+        let scheme = iri
+            .split_once(':')
+            .is_some_and(|(scheme, _)| !scheme.is_empty() && !scheme.contains(['/', '#', '?']));
+        if scheme || self.base.is_empty() {
+            iri
+        } else if iri.starts_with('#') {
+            let base = self.base.split('#').next().unwrap_or_default();
+            format!("{base}{iri}")
+        } else {
+            format!("{}{iri}", self.base)
+        }
+    }
+
+    /// The character a `\u` or `\U` escape names; the backslash is consumed.
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let digits = match self.bump() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("invalid escape")),
+        };
+        let mut hex = String::new();
+        for _ in 0..digits {
+            hex.push(self.bump().unwrap_or_default());
+        }
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid escape '{hex}'")))
+    }
+
+    fn blank_property_list(&mut self) -> Result<Term, Error> {
+        self.expect('[')?;
+        let node = self.fresh_blank();
+        self.skip();
+        if self.peek() != Some(']') {
+            self.predicate_objects(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn collection(&mut self) -> Result<Term, Error> {
+        self.expect('(')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip();
+            if self.peek() == Some(')') {
+                self.bump();
+                break;
+            }
+            if self.peek().is_none() {
+                return Err(self.error("unterminated collection"));
+            }
+            items.push(self.object()?);
+        }
+        let mut list = Term::Iri(format!("{RDF}nil"));
+        for item in items.into_iter().rev() {
+            let cell = self.fresh_blank();
+            self.graph.add(cell.clone(), format!("{RDF}first"), item);
+            self.graph.add(cell.clone(), format!("{RDF}rest"), list);
+            list = cell;
+        }
+        Ok(list)
+    }
+
+    fn string_literal(&mut self) -> Result<Term, Error> {
+        let quote = self.bump().unwrap_or('"');
+        let long = self.peek() == Some(quote) && self.peek_at(1) == Some(quote);
+        if long {
+            self.pos += 2;
+        }
+        let mut lexical = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    if !long {
+                        break;
+                    }
+                    if self.peek() == Some(quote) && self.peek_at(1) == Some(quote) {
+                        self.pos += 2;
+                        // A long string may end with further quotes.
+                        while self.peek() == Some(quote) {
+                            lexical.push(quote);
+                            self.pos += 1;
+                        }
+                        break;
+                    }
+                    lexical.push(c);
+                }
+                Some('\n' | '\r') if !long => {
+                    return Err(self.error("line break in a short string"));
+                }
+                Some('\\') => {
+                    let c = match self.peek() {
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('f') => '\u{c}',
+                        Some(c @ ('"' | '\'' | '\\')) => c,
+                        Some('u' | 'U') => {
+                            lexical.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape in a string")),
+                    };
+                    self.bump();
+                    lexical.push(c);
+                }
+                Some(c) => lexical.push(c),
+            }
+        }
+
+        if self.peek() == Some('@') {
+            self.bump();
+            let mut language = String::new();
+            while let Some(c) = self
+                .peek()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            {
+                language.push(c);
+                self.bump();
+            }
+            return Ok(Term::Literal(Literal {
+                lexical,
+                datatype: RDF_LANG_STRING.to_string(),
+                language: Some(language),
+            }));
+        }
+        if self.looking_at("^^") {
+            self.pos += 2;
+            let datatype = match self.resource()? {
+                Term::Iri(iri) => iri,
+                _ => return Err(self.error("a datatype must be an IRI")),
+            };
+            return Ok(Term::Literal(Literal::new(lexical, &datatype)));
+        }
+        Ok(Term::Literal(Literal::string(lexical)))
+    }
+
+    fn number(&mut self) -> Result<Term, Error> {
+        let mut lexical = String::new();
+        let digits = |p: &mut Self, lexical: &mut String| {
+            while let Some(c) = p.peek().filter(char::is_ascii_digit) {
+                lexical.push(c);
+                p.bump();
+            }
+        };
+        if let Some(sign) = self.peek().filter(|c| matches!(c, '+' | '-')) {
+            lexical.push(sign);
+            self.bump();
+        }
+        digits(self, &mut lexical);
+        let mut datatype = XSD_INTEGER;
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            lexical.push('.');
+            self.bump();
+            digits(self, &mut lexical);
+            datatype = XSD_DECIMAL;
+        }
+        if let Some(e) = self.peek().filter(|c| matches!(c, 'e' | 'E')) {
+            lexical.push(e);
+            self.bump();
+            if let Some(sign) = self.peek().filter(|c| matches!(c, '+' | '-')) {
+                lexical.push(sign);
+                self.bump();
+            }
+            digits(self, &mut lexical);
+            datatype = XSD_DOUBLE;
+        }
+        if !lexical.chars().any(|c| c.is_ascii_digit()) {
+            return Err(self.error(format!("invalid number '{lexical}'")));
+        }
+        Ok(Term::Literal(Literal::new(lexical, datatype)))
+    }
+}
+
+/// A local name with its `\` escapes removed.
+fn unescape_local(local: &str) -> String {
+    let mut out = String::with_capacity(local.len());
+    let mut chars = local.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            out.extend(chars.next());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,190 @@
+//! Every bundled example and agent test model survives Turtle and JSON-LD,
+//! and the vocabulary covers what the exporter writes and the shapes check.
+
+use bert_core::{migrate, to_canonical_json, WorldModel};
+use bert_rdf::graph::{Graph, Term, RDF_TYPE};
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+
+const EXAMPLES: [&str; 6] = [
+    "bitcoin",
+    "blank",
+    "cosmos-hub",
+    "ethereum",
+    "llm",
+    "solana",
+];
+
+fn assets() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../assets/models")
+}
+
+fn load(path: PathBuf) -> WorldModel {
+    migrate::from_slice(&std::fs::read(path).unwrap())
+        .unwrap()
+        .0
+}
+
+/// The bundled examples, then the agent models (not their specs).
+fn models() -> Vec<(String, WorldModel)> {
+    let mut models: Vec<_> = EXAMPLES
+        .iter()
+        .map(|name| {
+            let path = assets().join(format!("examples/{name}.json"));
+            (name.to_string(), load(path))
+        })
+        .collect();
+    let mut agents: Vec<_> = std::fs::read_dir(assets().join("local/test-primitives"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && !path.to_string_lossy().ends_with("-spec.json")
+        })
+        .collect();
+    agents.sort();
+    for path in agents {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        models.push((name, load(path)));
+    }
+    models
+}
+
+#[test]
+fn turtle_round_trips() {
+    for (name, model) in models() {
+        let turtle = bert_rdf::to_turtle(&model, &name);
+        let back = bert_rdf::from_turtle(&turtle).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(
+            to_canonical_json(&back),
+            to_canonical_json(&model),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn json_ld_round_trips() {
+    for (name, model) in models() {
+        let json_ld = bert_rdf::to_json_ld(&model, &name);
+        let back = bert_rdf::from_json_ld(&json_ld).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(
+            to_canonical_json(&back),
+            to_canonical_json(&model),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn turtle_and_json_ld_carry_the_same_triples() {
+    for (name, model) in models() {
+        let triples = |graph: Graph| graph.triples.into_iter().collect::<HashSet<_>>();
+        let expected = triples(bert_rdf::to_graph(&model, &name));
+        let turtle = bert_rdf::parse_turtle(&bert_rdf::to_turtle(&model, &name)).unwrap();
+        let json_ld = bert_rdf::parse_json_ld(&bert_rdf::to_json_ld(&model, &name)).unwrap();
+        assert!(triples(turtle) == expected, "{name}: Turtle");
+        assert!(triples(json_ld) == expected, "{name}: JSON-LD");
+    }
+}
+
+#[test]
+fn export_is_deterministic() {
+    for (name, model) in models() {
+        assert_eq!(
+            bert_rdf::to_turtle(&model, &name),
+            bert_rdf::to_turtle(&model, &name),
+            "{name}"
+        );
+        assert_eq!(
+            bert_rdf::to_json_ld(&model, &name),
+            bert_rdf::to_json_ld(&model, &name),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn element_iris_are_namespaced_by_model() {
+    let model = load(assets().join("examples/bitcoin.json"));
+    let graph = bert_rdf::to_graph(&model, "bitcoin");
+    let system = &model.systems[0].info.id;
+    let iri = bert_rdf::element_iri("bitcoin", system);
+    assert_eq!(iri, format!("https://bert.systems/models/bitcoin/{system}"));
+    assert!(graph
+        .triples
+        .iter()
+        .any(|t| t.subject == Term::Iri(iri.clone()) && t.predicate == RDF_TYPE));
+    assert_eq!(
+        bert_rdf::model_iri("my model"),
+        "https://bert.systems/models/my%20model/"
+    );
+}
+
+/// `bert:` terms a graph uses, as predicates or classes.
+fn bert_terms(graph: &Graph) -> BTreeSet<String> {
+    let mut terms = BTreeSet::new();
+    for triple in &graph.triples {
+        for iri in [Some(triple.predicate.as_str()), triple.object.iri()]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(local) = iri.strip_prefix(bert_rdf::NS) {
+                terms.insert(local.to_string());
+            }
+        }
+    }
+    terms
+}
+
+#[test]
+fn vocabulary_declares_every_term_used() {
+    let ontology = bert_rdf::parse_turtle(bert_rdf::ONTOLOGY).unwrap();
+    let declared: BTreeSet<String> = ontology
+        .triples
+        .iter()
+        .filter(|t| t.predicate == RDF_TYPE)
+        .filter_map(|t| t.subject.iri()?.strip_prefix(bert_rdf::NS))
+        .map(str::to_string)
+        .collect();
+
+    let mut used = bert_terms(&bert_rdf::parse_turtle(bert_rdf::SHAPES).unwrap());
+    for (name, model) in models() {
+        used.extend(bert_terms(&bert_rdf::to_graph(&model, &name)));
+    }
+    let undeclared: Vec<_> = used.difference(&declared).collect();
+    assert!(undeclared.is_empty(), "undeclared terms: {undeclared:?}");
+}
+
+#[test]
+fn every_l2_rule_has_a_shape() {
+    let shapes = bert_rdf::parse_turtle(bert_rdf::SHAPES).unwrap();
+    let rules: BTreeSet<&str> = shapes
+        .triples
+        .iter()
+        .filter(|t| t.predicate == format!("{}rule", bert_rdf::NS))
+        .filter_map(|t| Some(t.object.literal()?.lexical.as_str()))
+        .collect();
+    for rule in bert_core::rules::RULES {
+        if rule.code.starts_with("BERT-L2-") {
+            assert!(rules.contains(rule.code), "no shape for {}", rule.code);
+        }
+    }
+}
+
+#[test]
+fn documents_that_are_not_models_are_rejected() {
+    assert!(matches!(
+        bert_rdf::from_turtle("@prefix ex: <http://example.org/> .\nex:a ex:b ex:c ."),
+        Err(bert_rdf::Error::Model(_))
+    ));
+    assert!(matches!(
+        bert_rdf::from_turtle("@prefix ex: <http://example.org/> .\n\nex:a ex:b"),
+        Err(bert_rdf::Error::Turtle { line: 3, .. })
+    ));
+    assert!(matches!(
+        bert_rdf::from_json_ld("[1, 2]"),
+        Err(bert_rdf::Error::JsonLd(_))
+    ));
+}
+
@@ -0,0 +1,910 @@
+//! BERT models as SysML v2 textual notation.
+//!
+//! [`to_sysml`] writes a model as one package, named after its root system:
+//!
+//! - Each substance a flow carries or a system transforms is an `item def`:
+//!   the substance type, and a sub-type specializing it
+//!   (`item def Electricity :> Energy;`).
+//! - Each system is a `part def`, used as a `part` of its parent's definition.
+//!   The environment is a `part` holding its sources and sinks and a usage of
+//!   the root system.
+//! - Each interface is a `port` of its system's definition with one directed
+//!   `item` per substance crossing it: `in` for flows entering, `out` for flows
+//!   leaving, `inout` for both.
+//! - Each interaction is a `flow of` its substance's item, connecting the port
+//!   items at either end, in the innermost definition holding both ends.
+//! - Flow amounts and parameters are `attribute`s.
+//!
+//! Fields with no SysML v2 element — ids, complexity, archetype, time
+//! constant, boundary, usability, units, agent models and the like — go into a
+//! `comment` starting with `[BERT]`, owned by the element they describe. Layout
+//! and view state are not exported.
+//!
+//! The output for the bundled examples is checked in under `tests/golden/`,
+//! and the export of `llm.json` is also checked against the hand-written
+//! reference `llm.sysml.example`.
+
+mod names;
+
+use bert_core::{
+    Complexity, ExternalEntity, ExternalEntityType, Id, Interaction, InteractionType, Interface,
+    InterfaceType, ParameterValue, Substance, SubstanceType, System, WorldModel,
+};
+use names::{is_real_literal, string, type_name, usage_name, usage_of, Names};
+use std::collections::{HashMap, HashSet};
+
+/// Write `model` as a SysML v2 package. Any model exports; flows whose ends
+/// are not in the model are listed in a comment of the environment instead.
+pub fn to_sysml(model: &WorldModel) -> String {
+    let mut exporter = Exporter::new(model);
+    exporter.package();
+    exporter.out
+}
+
+/// A substance type and a trimmed sub-type, blank for the type itself.
+type ItemKey = (SubstanceType, String);
+
+fn item_key(substance: &Substance) -> ItemKey {
+    (substance.ty, substance.sub_type.trim().to_string())
+}
+
+/// A directed item of a port: the item definition is `None` for the
+/// placeholder of an interface no flow crosses.
+struct PortItem {
+    direction: &'static str,
+    name: String,
+    def: Option<String>,
+}
+
+/// One end of a flow.
+struct End<'m> {
+    /// The systems containing the end from the root down, ending with the end
+    /// itself when it is a system.
+    chain: Vec<&'m Id>,
+    external: Option<&'m Id>,
+    interface: Option<&'m Id>,
+}
+
+/// A flow placed in the definition holding both its ends.
+struct Flow<'m> {
+    interaction: &'m Interaction,
+    from: String,
+    to: String,
+}
+
+struct Exporter<'m> {
+    model: &'m WorldModel,
+    out: String,
+    depth: usize,
+    /// Systems in the order they are defined: depth first from the roots.
+    /// Systems outside the tree (under a missing parent, or on a cycle) are
+    /// not exported.
+    order: Vec<&'m System>,
+    children: HashMap<&'m Id, Vec<&'m System>>,
+    /// The systems from the root down to each system, itself included.
+    chains: HashMap<&'m Id, Vec<&'m Id>>,
+    interface_owners: HashMap<&'m Id, &'m Id>,
+    /// The system holding each system-level source or sink.
+    external_owners: HashMap<&'m Id, &'m Id>,
+    environment_externals: HashSet<&'m Id>,
+    /// Item definitions in the order they are written.
+    items: Vec<ItemKey>,
+    item_names: HashMap<ItemKey, String>,
+    /// The part definition of each system.
+    defs: HashMap<&'m Id, String>,
+    /// The name of each system, interface, source and sink in its namespace.
+    usages: HashMap<&'m Id, String>,
+    /// Names taken in each system's definition, or in the environment (`None`).
+    namespaces: HashMap<Option<&'m Id>, Names>,
+    ports: HashMap<&'m Id, Vec<PortItem>>,
+    flows: HashMap<Option<&'m Id>, Vec<Flow<'m>>>,
+    /// Interactions left out, with the end that is not in the model.
+    unplaced: Vec<(&'m Interaction, &'m Id)>,
+}
+
+impl<'m> Exporter<'m> {
+    fn new(model: &'m WorldModel) -> Self {
+        let mut exporter = Self {
+            model,
+            out: String::new(),
+            depth: 0,
+            order: Vec::new(),
+            children: HashMap::new(),
+            chains: HashMap::new(),
+            interface_owners: HashMap::new(),
+            external_owners: HashMap::new(),
+            environment_externals: HashSet::new(),
+            items: Vec::new(),
+            item_names: HashMap::new(),
+            defs: HashMap::new(),
+            usages: HashMap::new(),
+            namespaces: HashMap::new(),
+            ports: HashMap::new(),
+            flows: HashMap::new(),
+            unplaced: Vec::new(),
+        };
+        exporter.tree();
+        exporter.name_definitions();
+        exporter.name_usages();
+        exporter.port_items();
+        exporter.place_flows();
+        exporter
+    }
+
+    fn tree(&mut self) {
+        let systems = &self.model.systems;
+        let ids: HashSet<&Id> = systems.iter().map(|s| &s.info.id).collect();
+        let mut under: HashMap<&Id, Vec<&System>> = HashMap::new();
+        let mut roots = Vec::new();
+        for system in systems {
+            if system.parent != system.info.id && ids.contains(&system.parent) {
+                under.entry(&system.parent).or_default().push(system);
+            } else {
+                roots.push(system);
+            }
+        }
+
+        let mut stack: Vec<(&System, Vec<&Id>)> =
+            roots.into_iter().rev().map(|s| (s, Vec::new())).collect();
+        while let Some((system, mut chain)) = stack.pop() {
+            let id = &system.info.id;
+            if self.chains.contains_key(id) {
+                continue;
+            }
+            if let Some(parent) = chain.last() {
+                self.children.entry(parent).or_default().push(system);
+            }
+            chain.push(id);
+            for child in under.get(id).into_iter().flatten().rev() {
+                stack.push((child, chain.clone()));
+            }
+            self.chains.insert(id, chain);
+            self.order.push(system);
+            for interface in &system.boundary.interfaces {
+                self.interface_owners
+                    .entry(&interface.info.id)
+                    .or_insert(id);
+            }
+            for external in system.sources.iter().chain(&system.sinks) {
+                self.external_owners.entry(&external.info.id).or_insert(id);
+            }
+        }
+
+        let environment = &self.model.environment;
+        self.environment_externals = environment
+            .sources
+            .iter()
+            .chain(&environment.sinks)
+            .map(|e| &e.info.id)
+            .collect();
+    }
+
+    /// Item and part definitions share the package's namespace. The three
+    /// substance types are reserved even when unused, so a sub-type or a
+    /// system never takes their names.
+    fn name_definitions(&mut self) {
+        let mut types = Names::default();
+        for ty in [
+            SubstanceType::Energy,
+            SubstanceType::Material,
+            SubstanceType::Message,
+        ] {
+            types.claim(ty.to_string());
+        }
+
+        let transformed = self
+            .order
+            .clone()
+            .into_iter()
+            .filter_map(|s| s.transformation.as_ref())
+            .flat_map(|t| t.inputs.iter().chain(&t.outputs));
+        let substances = self
+            .model
+            .interactions
+            .iter()
+            .map(|i| &i.substance)
+            .chain(transformed)
+            .collect::<Vec<_>>();
+        for substance in substances {
+            let base = (substance.ty, String::new());
+            if !self.item_names.contains_key(&base) {
+                self.item_names
+                    .insert(base.clone(), substance.ty.to_string());
+                self.items.push(base);
+            }
+            let key = item_key(substance);
+            if key.1.is_empty() || self.item_names.contains_key(&key) {
+                continue;
+            }
+            let mut name = type_name(&key.1, "Item");
+            if name == substance.ty.to_string() {
+                self.item_names.insert(key, name);
+                continue;
+            }
+            if types.contains(&name) {
+                name.push_str(&substance.ty.to_string());
+            }
+            self.item_names.insert(key.clone(), types.claim(name));
+            self.items.push(key);
+        }
+
+        for system in &self.order {
+            let name = types.claim(type_name(&system.info.name, "System"));
+            self.defs.insert(&system.info.id, name);
+        }
+    }
+
+    fn name_usages(&mut self) {
+        let environment = &self.model.environment;
+        let mut names = Names::default();
+        self.name_externals(&mut names, &environment.sources, &environment.sinks);
+        for system in self
+            .order
+            .iter()
+            .filter(|s| self.chains[&s.info.id].len() == 1)
+        {
+            let name = names.claim(usage_name(&system.info.name, "system"));
+            self.usages.insert(&system.info.id, name);
+        }
+        self.namespaces.insert(None, names);
+
+        for system in self.order.clone() {
+            let mut names = Names::default();
+            for interface in &system.boundary.interfaces {
+                let name = names.claim(usage_name(&interface.info.name, "port"));
+                self.usages.entry(&interface.info.id).or_insert(name);
+            }
+            self.name_externals(&mut names, &system.sources, &system.sinks);
+            for child in self
+                .children
+                .get(&system.info.id)
+                .cloned()
+                .unwrap_or_default()
+            {
+                let name = names.claim(usage_name(&child.info.name, "system"));
+                self.usages.insert(&child.info.id, name);
+            }
+            self.namespaces.insert(Some(&system.info.id), names);
+        }
+    }
+
+    fn name_externals(
+        &mut self,
+        names: &mut Names,
+        sources: &'m [ExternalEntity],
+        sinks: &'m [ExternalEntity],
+    ) {
+        for (externals, fallback) in [(sources, "source"), (sinks, "sink")] {
+            for external in externals {
+                let name = names.claim(usage_name(&external.info.name, fallback));
+                self.usages.entry(&external.info.id).or_insert(name);
+            }
+        }
+    }
+
+    /// `interface` when it is one of `system`'s own.
+    fn interface_of(&self, system: &Id, interface: Option<&'m Id>) -> Option<&'m Id> {
+        interface.filter(|i| self.interface_owners.get(i).copied() == Some(system))
+    }
+
+    fn port_items(&mut self) {
+        // Per interface, each item definition crossing it, entering and leaving.
+        let mut crossing: HashMap<&Id, Vec<(String, bool, bool)>> = HashMap::new();
+        for interaction in &self.model.interactions {
+            let def = &self.item_names[&item_key(&interaction.substance)];
+            let ends = [
+                (&interaction.source, &interaction.source_interface, false),
+                (&interaction.sink, &interaction.sink_interface, true),
+            ];
+            for (end, interface, entering) in ends {
+                let Some(interface) = self.interface_of(end, interface.as_ref()) else {
+                    continue;
+                };
+                let items = crossing.entry(interface).or_default();
+                let index = match items.iter().position(|(d, _, _)| d == def) {
+                    Some(index) => index,
+                    None => {
+                        items.push((def.clone(), false, false));
+                        items.len() - 1
+                    }
+                };
+                if entering {
+                    items[index].1 = true;
+                } else {
+                    items[index].2 = true;
+                }
+            }
+        }
+
+        for system in &self.order {
+            for interface in &system.boundary.interfaces {
+                let mut names = Names::default();
+                let items = match crossing.get(&interface.info.id) {
+                    Some(items) => items
+                        .iter()
+                        .map(|(def, entering, leaving)| PortItem {
+                            direction: match (entering, leaving) {
+                                (true, true) => "inout",
+                                (true, false) => "in",
+                                _ => "out",
+                            },
+                            name: names.claim(usage_of(def)),
+                            def: Some(def.clone()),
+                        })
+                        .collect(),
+                    None => {
+                        let (direction, name) = match interface.ty {
+                            InterfaceType::Import => ("in", "inflow"),
+                            InterfaceType::Export => ("out", "outflow"),
+                            InterfaceType::Hybrid => ("inout", "exchange"),
+                        };
+                        vec![PortItem {
+                            direction,
+                            name: name.to_string(),
+                            def: None,
+                        }]
+                    }
+                };
+                self.ports.entry(&interface.info.id).or_insert(items);
+            }
+        }
+    }
+
+    fn end(&self, id: &'m Id, interface: Option<&'m Id>) -> Option<End<'m>> {
+        if let Some(chain) = self.chains.get(id) {
+            return Some(End {
+                chain: chain.clone(),
+                external: None,
+                interface: self.interface_of(id, interface),
+            });
+        }
+        let chain = if self.environment_externals.contains(id) {
+            Vec::new()
+        } else {
+            self.chains[self.external_owners.get(id)?].clone()
+        };
+        Some(End {
+            chain,
+            external: Some(id),
+            interface: None,
+        })
+    }
+
+    /// The path to `end` from the definition at `depth` of its chain, through
+    /// the port item carrying `def`.
+    fn reference(&self, end: &End, depth: usize, def: &str) -> String {
+        let mut path: Vec<&str> = end.chain[depth..]
+            .iter()
+            .map(|id| self.usages[id].as_str())
+            .collect();
+        if let Some(external) = end.external {
+            path.push(&self.usages[external]);
+        }
+        if let Some(interface) = end.interface {
+            path.push(&self.usages[interface]);
+            let item = self.ports[interface]
+                .iter()
+                .find(|item| item.def.as_deref() == Some(def));
+            path.extend(item.map(|item| item.name.as_str()));
+        }
+        if path.is_empty() {
+            "self".to_string()
+        } else {
+            path.join(".")
+        }
+    }
+
+    fn place_flows(&mut self) {
+        for interaction in &self.model.interactions {
+            let source = self.end(&interaction.source, interaction.source_interface.as_ref());
+            let sink = self.end(&interaction.sink, interaction.sink_interface.as_ref());
+            let (source, sink) = match (source, sink) {
+                (Some(source), Some(sink)) => (source, sink),
+                (None, _) => {
+                    self.unplaced.push((interaction, &interaction.source));
+                    continue;
+                }
+                (_, None) => {
+                    self.unplaced.push((interaction, &interaction.sink));
+                    continue;
+                }
+            };
+            let depth = source
+                .chain
+                .iter()
+                .zip(&sink.chain)
+                .take_while(|(a, b)| a == b)
+                .count();
+            let container = depth.checked_sub(1).map(|d| source.chain[d]);
+            let def = &self.item_names[&item_key(&interaction.substance)];
+            let flow = Flow {
+                interaction,
+                from: self.reference(&source, depth, def),
+                to: self.reference(&sink, depth, def),
+            };
+            self.flows.entry(container).or_default().push(flow);
+        }
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.depth {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(text.as_ref());
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, head: impl AsRef<str>) {
+        self.line(format!("{} {{", head.as_ref()));
+        self.depth += 1;
+    }
+
+    fn close(&mut self) {
+        self.depth -= 1;
+        self.line("}");
+    }
+
+    /// A blank line, unless at the start of a body or after another one.
+    fn gap(&mut self) {
+        if !(self.out.ends_with("{\n") || self.out.ends_with("\n\n")) {
+            self.out.push('\n');
+        }
+    }
+
+    /// A `doc` or `comment` with one line of text per entry.
+    fn note(&mut self, keyword: &str, text: &[String]) {
+        let lines: Vec<String> = text
+            .iter()
+            .flat_map(|t| t.split('\n'))
+            .map(|l| l.trim_end().replace("*/", "* /"))
+            .collect();
+        match lines.as_slice() {
+            [] => {}
+            [line] => self.line(format!("{keyword} /* {line} */")),
+            _ => {
+                self.line(format!("{keyword} /*"));
+                for line in lines {
+                    self.line(format!(" * {line}").trim_end());
+                }
+                self.line(" */");
+            }
+        }
+    }
+
+    fn doc(&mut self, description: &str) {
+        match description.trim() {
+            "" => {}
+            description => self.note("doc", &[description.to_string()]),
+        }
+    }
+
+    fn bert(&mut self, notes: Vec<String>) {
+        self.note("comment", &notes);
+    }
+
+    fn package(&mut self) {
+        let root = match self.order.first() {
+            Some(system) => system.info.name.clone(),
+            None => self.model.environment.info.name.clone(),
+        };
+        self.line("// Exported from BERT. Comments starting with [BERT] hold the fields");
+        self.line("// SysML v2 has no element for.");
+        self.open(format!("package {}", type_name(&root, "Model")));
+        self.line("private import ScalarValues::*;");
+
+        self.gap();
+        for key in self.items.clone() {
+            let name = &self.item_names[&key];
+            let line = match key.1.as_str() {
+                "" => format!("item def {name};"),
+                _ => format!("item def {name} :> {};", key.0),
+            };
+            self.line(line);
+        }
+
+        for system in self.order.clone() {
+            self.gap();
+            self.definition(system);
+        }
+
+        self.gap();
+        self.environment();
+        self.close();
+    }
+
+    fn definition(&mut self, system: &'m System) {
+        let id = &system.info.id;
+        self.open(format!("part def {}", self.defs[id]));
+        self.doc(&system.info.description);
+        let notes = self.system_notes(system);
+        self.bert(notes);
+
+        if !system.boundary.interfaces.is_empty() {
+            self.gap();
+        }
+        for interface in &system.boundary.interfaces {
+            self.port(interface);
+        }
+        self.externals(&system.sources, &system.sinks);
+        let children = self.children.get(id).cloned().unwrap_or_default();
+        if !children.is_empty() {
+            self.gap();
+        }
+        for child in children {
+            let id = &child.info.id;
+            self.line(format!("part {} : {};", self.usages[id], self.defs[id]));
+        }
+        self.flows(Some(id));
+        self.close();
+    }
+
+    fn environment(&mut self) {
+        let environment = &self.model.environment;
+        self.open(format!(
+            "part {}",
+            usage_name(&environment.info.name, "environment")
+        ));
+        self.doc(&environment.info.description);
+        let mut notes = vec![format!("[BERT] {}", environment.info.id)];
+        if let Some(mode) = self.model.mode {
+            notes.push(format!("mode: {mode:?}"));
+        }
+        if !self.model.validation.is_empty() {
+            let json = serde_json::to_string(&self.model.validation).unwrap_or_default();
+            notes.push(format!("validation: {json}"));
+        }
+        self.bert(notes);
+
+        self.externals(&environment.sources, &environment.sinks);
+        let roots: Vec<_> = self
+            .order
+            .iter()
+            .filter(|s| self.chains[&s.info.id].len() == 1)
+            .map(|s| &s.info.id)
+            .collect();
+        if !roots.is_empty() {
+            self.gap();
+        }
+        for id in roots {
+            self.line(format!("part {} : {};", self.usages[id], self.defs[id]));
+        }
+        self.flows(None);
+
+        if !self.unplaced.is_empty() {
+            self.gap();
+            let notes = self
+                .unplaced
+                .iter()
+                .map(|(interaction, missing)| {
+                    format!(
+                        "[BERT] {} {} is not exported: '{missing}' is not in the model",
+                        interaction.info.id,
+                        string(&interaction.info.name)
+                    )
+                })
+                .collect();
+            self.bert(notes);
+        }
+        self.close();
+    }
+
+    fn port(&mut self, interface: &'m Interface) {
+        let id = &interface.info.id;
+        self.open(format!("port {}", self.usages[id]));
+        self.doc(&interface.info.description);
+        let ty = match interface.ty {
+            InterfaceType::Import => "Import",
+            InterfaceType::Export => "Export",
+            InterfaceType::Hybrid => "Hybrid",
+        };
+        let mut notes = vec![format!("[BERT] {id}"), format!("type: {ty}")];
+        if !interface.protocol.trim().is_empty() {
+            notes.push(format!("protocol: {}", interface.protocol.trim()));
+        }
+        if let Some(paired) = &interface.paired_with {
+            let name = self
+                .usages
+                .get(paired)
+                .map_or(paired.to_string(), Clone::clone);
+            notes.push(format!("paired_with: {name}"));
+        }
+        self.bert(notes);
+        let items: Vec<String> = self.ports[id]
+            .iter()
+            .map(|item| match &item.def {
+                Some(def) => format!("{} item {} : {def};", item.direction, item.name),
+                None => format!("{} item {};", item.direction, item.name),
+            })
+            .collect();
+        for item in items {
+            self.line(item);
+        }
+        self.close();
+    }
+
+    fn externals(&mut self, sources: &'m [ExternalEntity], sinks: &'m [ExternalEntity]) {
+        if sources.is_empty() && sinks.is_empty() {
+            return;
+        }
+        self.gap();
+        for external in sources.iter().chain(sinks) {
+            let id = &external.info.id;
+            self.open(format!("part {}", self.usages[id]));
+            self.doc(&external.info.description);
+            let ty = match external.ty {
+                ExternalEntityType::Source => "Source",
+                ExternalEntityType::Sink => "Sink",
+            };
+            let mut notes = vec![format!("[BERT] {id}"), format!("type: {ty}")];
+            for (key, value) in [
+                ("equivalence", &external.equivalence),
+                ("model", &external.model),
+            ] {
+                if !value.trim().is_empty() {
+                    notes.push(format!("{key}: {}", value.trim()));
+                }
+            }
+            if let Some(same) = external.is_same_as_id {
+                notes.push(format!("is_same_as_id: {same}"));
+            }
+            self.bert(notes);
+            self.close();
+        }
+    }
+
+    fn flows(&mut self, container: Option<&'m Id>) {
+        let Some(flows) = self.flows.remove(&container) else {
+            return;
+        };
+        self.gap();
+        for flow in flows {
+            let interaction = flow.interaction;
+            let name = self
+                .namespaces
+                .entry(container)
+                .or_default()
+                .claim(usage_name(&interaction.info.name, "flow"));
+            let item = &self.item_names[&item_key(&interaction.substance)];
+            self.open(format!(
+                "flow {name} of {item} from {} to {}",
+                flow.from, flow.to
+            ));
+            self.doc(&interaction.info.description);
+
+            let mut notes = vec![format!("[BERT] {}", interaction.info.id)];
+            if matches!(interaction.ty, InteractionType::Force) {
+                notes.push("type: Force".to_string());
+            }
+            notes.push(format!("usability: {}", interaction.usability));
+            let mut attributes = Vec::new();
+            let mut names = Names::default();
+
+            let amount = names.claim("amount".to_string());
+            attributes.push(attribute(&amount, &interaction.amount.to_string()));
+            if !interaction.unit.trim().is_empty() {
+                notes.push(format!("unit of {amount}: {}", interaction.unit.trim()));
+            }
+            for parameter in &interaction.parameters {
+                let name = names.claim(usage_name(&parameter.name, "parameter"));
+                attributes.push(attribute(&name, &parameter.value));
+                if !parameter.unit.trim().is_empty() {
+                    notes.push(format!("unit of {name}: {}", parameter.unit.trim()));
+                }
+            }
+            for parameter in &interaction.smart_parameters {
+                let name = names.claim(usage_name(&parameter.name, "parameter"));
+                match &parameter.value {
+                    ParameterValue::Numeric { value, unit } => {
+                        attributes.push(attribute(&name, value));
+                        if !unit.trim().is_empty() {
+                            notes.push(format!("unit of {name}: {}", unit.trim()));
+                        }
+                    }
+                    ParameterValue::Ordinal {
+                        level: value,
+                        options,
+                    }
+                    | ParameterValue::Categorical { value, options } => {
+                        attributes.push(attribute(&name, value));
+                        notes.push(format!("options of {name}: {}", options.join(", ")));
+                    }
+                    ParameterValue::Boolean {
+                        value,
+                        true_label,
+                        false_label,
+                    } => {
+                        attributes.push(format!("attribute {name} : Boolean = {value};"));
+                        notes.push(format!("labels of {name}: {true_label}/{false_label}"));
+                    }
+                }
+            }
+            self.bert(notes);
+            for attribute in attributes {
+                self.line(attribute);
+            }
+            self.close();
+        }
+    }
+
+    fn system_notes(&self, system: &System) -> Vec<String> {
+        let mut notes = vec![format!("[BERT] {}", system.info.id)];
+        notes.push(format!("complexity: {}", complexity(&system.complexity)));
+        if let Some(interface) = &system.boundary.parent_interface {
+            let name = self
+                .usages
+                .get(interface)
+                .map_or(interface.to_string(), Clone::clone);
+            notes.push(format!("parent_interface: {name}"));
+        }
+        if let Some(archetype) = &system.archetype {
+            notes.push(format!("archetype: {archetype}"));
+        }
+        if let Some(time_constant) = &system.time_constant {
+            notes.push(format!("time_constant: {time_constant}"));
+        }
+        notes.push(format!("member_autonomy: {}", system.member_autonomy));
+        if !system.equivalence.trim().is_empty() {
+            notes.push(format!("equivalence: {}", system.equivalence.trim()));
+        }
+
+        let boundary = &system.boundary;
+        if !boundary.info.name.trim().is_empty() {
+            notes.push(format!("boundary.name: {}", boundary.info.name.trim()));
+        }
+        if !boundary.info.description.trim().is_empty() {
+            notes.push(format!(
+                "boundary.description: {}",
+                boundary.info.description.trim()
+            ));
+        }
+        notes.push(format!("boundary.porosity: {}", boundary.porosity));
+        notes.push(format!(
+            "boundary.perceptive_fuzziness: {}",
+            boundary.perceptive_fuzziness
+        ));
+
+        if let Some(transformation) = system.transformation.as_ref().filter(|t| !t.is_empty()) {
+            notes.push(format!("transformation: {transformation}"));
+        }
+        for event in system.history.iter() {
+            notes.push(format!("history: {event}"));
+        }
+        if let Some(agent) = &system.agent {
+            let json = serde_json::to_string(agent).unwrap_or_default();
+            notes.push(format!("agent: {json}"));
+        }
+        notes
+    }
+}
+
+/// A `Real` attribute when `value` reads as one, a `String` otherwise, and
+/// without a value when `value` is blank.
+fn attribute(name: &str, value: &str) -> String {
+    if value.trim().is_empty() {
+        format!("attribute {name} : String;")
+    } else if is_real_literal(value) {
+        format!("attribute {name} : Real = {value};")
+    } else {
+        format!("attribute {name} : String = {};", string(value))
+    }
+}
+
+fn complexity(complexity: &Complexity) -> String {
+    match complexity {
+        Complexity::Complex {
+            adaptable,
+            evolveable,
+        } => {
+            let traits: Vec<_> = [(adaptable, "adaptable"), (evolveable, "evolveable")]
+                .into_iter()
+                .filter(|(on, _)| **on)
+                .map(|(_, name)| name)
+                .collect();
+            if traits.is_empty() {
+                "Complex".to_string()
+            } else {
+                format!("Complex ({})", traits.join(", "))
+            }
+        }
+        Complexity::Atomic => "Atomic".to_string(),
+        Complexity::Multiset(n) => format!("Multiset ({n})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOWN: &str = r#"
+environment "Town" {
+    source mill "Mill"
+    sink customers "Customers"
+
+    system bakery "Bakery" {
+        import door "Loading door" { protocol "Pallet" }
+        hybrid counter "Counter"
+        system oven "Oven" {
+            source gas "Gas main"
+        }
+        system intake "Intake" on door
+    }
+
+    flow flour "Flour": mill -> door {
+        substance Material "Wheat flour"
+        usability Resource
+        amount 200 "kg/day"
+        param "Moisture" "14" "%"
+    }
+    flow dough "Dough": intake -> oven {
+        substance Material "Wheat flour"
+        usability Resource
+        smart_param "Grade" "{\"Ordinal\": {\"level\": \"fine\", \"options\": [\"coarse\", \"fine\"]}}"
+    }
+    flow heat "Heat": gas -> oven {
+        substance Energy "Heat"
+        usability Resource
+    }
+    flow orders "Orders": mill -> counter {
+        substance Message "Order"
+        usability Resource
+    }
+    force receipts "Receipts": bakery.counter -> customers {
+        substance Message "Order"
+        usability Product
+    }
+}
+"#;
+
+    fn assert_lines(sysml: &str, lines: &[&str]) {
+        for line in lines {
+            assert!(
+                sysml.lines().any(|l| l.trim() == *line),
+                "missing {line:?} in:\n{sysml}"
+            );
+        }
+    }
+
+    #[test]
+    fn flows_connect_port_items_in_the_innermost_definition() {
+        let model = bert_core::sl::parse(TOWN).unwrap();
+        let sysml = to_sysml(&model);
+        assert_lines(
+            &sysml,
+            &[
+                "package Bakery {",
+                "item def WheatFlour :> Material;",
+                "port counter {",
+                "inout item order : Order;",
+                "flow flour of WheatFlour from mill to bakery.loadingDoor.wheatFlour {",
+                "attribute amount : Real = 200;",
+                "attribute moisture : Real = 14;",
+                "* unit of moisture: %",
+                "flow dough of WheatFlour from intake to oven {",
+                "attribute grade : String = \"fine\";",
+                "* options of grade: coarse, fine",
+                "flow heat of Heat from gasMain to self {",
+                "* type: Force",
+            ],
+        );
+        let bakery = sysml.find("part def Bakery {").unwrap();
+        let oven = sysml.find("part def Oven {").unwrap();
+        let dough = sysml.find("flow dough").unwrap();
+        let heat = sysml.find("flow heat").unwrap();
+        assert!(bakery < dough && dough < oven && oven < heat);
+    }
+
+    #[test]
+    fn flows_with_missing_ends_are_listed() {
+        let mut model = bert_core::sl::parse(TOWN).unwrap();
+        let mut stray = model.interactions[0].clone();
+        stray.info.id = "F-1.9".parse().unwrap();
+        stray.sink = "Snk-1.9".parse().unwrap();
+        model.interactions.push(stray);
+        let sysml = to_sysml(&model);
+        assert_lines(
+            &sysml,
+            &["comment /* [BERT] F-1.9 \"Flour\" is not exported: 'Snk-1.9' is not in the model */"],
+        );
+        assert_eq!(sysml.matches("flow flour").count(), 1);
+    }
+}
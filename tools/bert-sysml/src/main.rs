@@ -0,0 +1,65 @@
+//! `bert-sysml` — write a BERT model file as SysML v2 textual notation.
+//!
+//! Usage:
+//!
+//! ```sh
+//! bert-sysml assets/models/examples/llm.json -o llm.sysml
+//! ```
+//!
+//! The model is read through the migration pipeline, so older file versions
+//! are accepted.
+
+use bert_core::migrate;
+use clap::Parser;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "bert-sysml",
+    about = "Write a BERT model file as SysML v2 textual notation",
+    version
+)]
+struct Cli {
+    /// The model file to export.
+    model: PathBuf,
+    /// Write the SysML here instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("bert-sysml: error: {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let path = &cli.model;
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let (model, report) = migrate::from_slice(&bytes)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+    if !report.is_noop() {
+        eprintln!(
+            "bert-sysml: migrated {}: version {} → {}",
+            path.display(),
+            report.original_version,
+            report.final_version
+        );
+    }
+
+    let sysml = bert_sysml::to_sysml(&model);
+    match &cli.output {
+        Some(output) => std::fs::write(output, sysml)
+            .map_err(|e| format!("failed to write {}: {e}", output.display())),
+        None => {
+            print!("{sysml}");
+            Ok(())
+        }
+    }
+}
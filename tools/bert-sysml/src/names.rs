@@ -0,0 +1,272 @@
+//! SysML names for BERT's free-text names.
+
+use std::collections::HashSet;
+
+/// SysML v2 reserved words. Names are case-sensitive and every keyword is
+/// lowercase, so only usage names can collide with one.
+const KEYWORDS: &[&str] = &[
+    "about",
+    "abstract",
+    "accept",
+    "action",
+    "actor",
+    "after",
+    "alias",
+    "all",
+    "allocate",
+    "allocation",
+    "analysis",
+    "and",
+    "as",
+    "assert",
+    "assign",
+    "assume",
+    "at",
+    "attribute",
+    "bind",
+    "binding",
+    "by",
+    "calc",
+    "case",
+    "comment",
+    "concern",
+    "connect",
+    "connection",
+    "constant",
+    "constraint",
+    "crosses",
+    "decide",
+    "def",
+    "default",
+    "defined",
+    "dependency",
+    "derived",
+    "do",
+    "doc",
+    "else",
+    "end",
+    "entry",
+    "enum",
+    "event",
+    "exhibit",
+    "exit",
+    "expose",
+    "false",
+    "filter",
+    "first",
+    "flow",
+    "for",
+    "fork",
+    "frame",
+    "from",
+    "hastype",
+    "if",
+    "implies",
+    "import",
+    "in",
+    "include",
+    "individual",
+    "inout",
+    "interface",
+    "istype",
+    "item",
+    "join",
+    "language",
+    "library",
+    "locale",
+    "loop",
+    "merge",
+    "message",
+    "meta",
+    "metadata",
+    "new",
+    "nonunique",
+    "not",
+    "null",
+    "objective",
+    "occurrence",
+    "of",
+    "or",
+    "ordered",
+    "out",
+    "package",
+    "parallel",
+    "part",
+    "perform",
+    "port",
+    "private",
+    "protected",
+    "public",
+    "redefines",
+    "ref",
+    "references",
+    "render",
+    "rendering",
+    "rep",
+    "require",
+    "requirement",
+    "return",
+    "satisfy",
+    "send",
+    "snapshot",
+    "specializes",
+    "stakeholder",
+    "standard",
+    "state",
+    "subject",
+    "subsets",
+    "succession",
+    "terminate",
+    "then",
+    "timeslice",
+    "to",
+    "transition",
+    "true",
+    "until",
+    "use",
+    "variant",
+    "variation",
+    "verification",
+    "verify",
+    "via",
+    "view",
+    "viewpoint",
+    "when",
+    "while",
+    "xor",
+];
+
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+fn capitalize(word: &str) -> String {
+    let lower = word.to_ascii_lowercase();
+    let mut chars = lower.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// `fallback` for a name without letters or digits; a leading digit gets an
+/// underscore, since basic names start with a letter or `_`.
+fn finish(name: String, fallback: &str) -> String {
+    match name.chars().next() {
+        None => fallback.to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{name}"),
+        Some(_) => name,
+    }
+}
+
+/// `"Hash production"` → `HashProduction`, for definitions.
+pub(crate) fn type_name(name: &str, fallback: &str) -> String {
+    finish(words(name).map(capitalize).collect(), fallback)
+}
+
+/// `"Hash production"` → `hashProduction`, for usages.
+pub(crate) fn usage_name(name: &str, fallback: &str) -> String {
+    let camel = words(name)
+        .enumerate()
+        .map(|(i, w)| {
+            if i == 0 {
+                w.to_ascii_lowercase()
+            } else {
+                capitalize(w)
+            }
+        })
+        .collect();
+    finish(camel, fallback)
+}
+
+/// `WheatFlour` → `wheatFlour`: a usage named after its definition.
+pub(crate) fn usage_of(def: &str) -> String {
+    let mut chars = def.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// The names taken in one namespace.
+#[derive(Default)]
+pub(crate) struct Names(HashSet<String>);
+
+impl Names {
+    /// Take `base`, or `base2`, `base3`, ... when it is taken. Keywords come
+    /// back quoted as unrestricted names.
+    pub(crate) fn claim(&mut self, base: String) -> String {
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.0.insert(name.clone()) {
+            n += 1;
+            name = format!("{base}{n}");
+        }
+        if KEYWORDS.contains(&name.as_str()) {
+            format!("'{name}'")
+        } else {
+            name
+        }
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+}
+
+/// A SysML string literal.
+pub(crate) fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Whether `s` can be written as a SysML real literal as it is.
+pub(crate) fn is_real_literal(s: &str) -> bool {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match unsigned.split_once('.') {
+        Some((whole, frac)) => digits(whole) && digits(frac),
+        None => digits(unsigned),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_camel_cased_and_unique() {
+        assert_eq!(type_name("Hash production", "Part"), "HashProduction");
+        assert_eq!(type_name("JSON-RPC gateway", "Part"), "JsonRpcGateway");
+        assert_eq!(usage_name("JSON-RPC gateway", "part"), "jsonRpcGateway");
+        assert_eq!(usage_name("3D printer", "part"), "_3dPrinter");
+        assert_eq!(usage_name(" -- ", "part"), "part");
+        assert_eq!(usage_of("WheatFlour"), "wheatFlour");
+
+        let mut names = Names::default();
+        assert_eq!(names.claim("miner".into()), "miner");
+        assert_eq!(names.claim("miner".into()), "miner2");
+        assert_eq!(names.claim("message".into()), "'message'");
+    }
+
+    #[test]
+    fn strings_and_numbers() {
+        assert_eq!(string("a \"b\"\n"), r#""a \"b\"\n""#);
+        assert!(is_real_literal("-0.5"));
+        assert!(!is_real_literal("1e5"));
+        assert!(!is_real_literal("5."));
+    }
+}
@@ -0,0 +1,152 @@
+//! The SysML written for each bundled example, compared with a checked-in
+//! golden file in `tests/golden/`. Run with `BERT_BLESS=1` to rewrite them
+//! after a deliberate change.
+//!
+//! Blessing only pins what the exporter already does, so the LLM model is also
+//! checked against `llm.sysml.example`, the hand-written reference the exporter
+//! was built from: every element it declares must be in the export.
+
+use std::path::PathBuf;
+
+const EXAMPLES: [&str; 6] = [
+    "bitcoin",
+    "blank",
+    "cosmos-hub",
+    "ethereum",
+    "llm",
+    "solana",
+];
+
+fn golden(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.sysml"))
+}
+
+fn export(name: &str) -> String {
+    let path = format!(
+        "{}/../../assets/models/examples/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let model = bert_core::migrate::from_slice(&std::fs::read(path).unwrap())
+        .unwrap()
+        .0;
+    bert_sysml::to_sysml(&model)
+}
+
+#[test]
+fn examples_match_their_golden_files() {
+    let bless = std::env::var_os("BERT_BLESS").is_some();
+    let mut stale = Vec::new();
+    for name in EXAMPLES {
+        let sysml = export(name);
+        let path = golden(name);
+        if bless {
+            std::fs::write(&path, &sysml).unwrap();
+        } else if std::fs::read_to_string(&path).ok().as_deref() != Some(sysml.as_str()) {
+            stale.push(path.display().to_string());
+        }
+    }
+    assert!(
+        stale.is_empty(),
+        "stale golden files (rerun with BERT_BLESS=1 to update): {stale:?}"
+    );
+}
+
+#[test]
+fn export_is_deterministic() {
+    for name in EXAMPLES {
+        assert_eq!(export(name), export(name), "{name}");
+    }
+}
+
+/// A flow's substance and endpoints, with any item on a port endpoint
+/// (`part.port.'item'`) dropped: the reference connects to ports only.
+fn flow_of(line: &str) -> Option<(String, String, String)> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let of = words.iter().position(|w| *w == "of")?;
+    if words.first() != Some(&"flow") || words.get(of + 2) != Some(&"from") {
+        return None;
+    }
+    let endpoint = |w: &str| {
+        w.split(".'")
+            .next()
+            .unwrap()
+            .trim_end_matches(';')
+            .to_string()
+    };
+    Some((
+        words[of + 1].to_string(),
+        endpoint(words[of + 3]),
+        endpoint(words.get(of + 5)?),
+    ))
+}
+
+/// The lines of the `port <name> { ... }` block in `sysml`.
+fn port_block<'a>(sysml: &'a str, name: &str) -> Vec<&'a str> {
+    sysml
+        .lines()
+        .skip_while(|l| l.trim() != format!("port {name} {{"))
+        .take_while(|l| l.trim() != "}")
+        .collect()
+}
+
+#[test]
+fn llm_export_has_the_reference_structure() {
+    let reference = std::fs::read_to_string(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("llm.sysml.example"),
+    )
+    .unwrap();
+    let sysml = export("llm");
+    let lines: Vec<&str> = sysml.lines().map(str::trim).collect();
+    let flows: Vec<_> = lines.iter().filter_map(|l| flow_of(l)).collect();
+
+    let mut checked = 0;
+    for line in reference.lines() {
+        // Drop the trailing `// ...` note the reference keeps on most lines.
+        let line = line.split("//").next().unwrap().trim();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["package", name, "{"] | ["part", "def", name, "{"] => {
+                let opening = words.join(" ");
+                assert!(
+                    lines.contains(&opening.as_str()),
+                    "missing `{opening}` for {name}"
+                );
+            }
+            ["item", "def", _] | ["part", _, ":", _] => {
+                assert!(lines.contains(&line), "missing `{line}`");
+            }
+            [direction @ ("in" | "out"), "port", name, ":", item] => {
+                let block = port_block(&sysml, name);
+                assert!(!block.is_empty(), "missing port {name}");
+                let item = item.trim_end_matches(';');
+                assert!(
+                    block.iter().any(|l| {
+                        let l = l.trim();
+                        l.starts_with(&format!("{direction} item "))
+                            && l.ends_with(&format!(": {item};"))
+                    }),
+                    "port {name} should carry `{direction}` {item}: {block:#?}"
+                );
+            }
+            ["part", name] => {
+                let name = name.trim_end_matches(';');
+                assert!(
+                    lines.contains(&format!("part {name} {{").as_str())
+                        || lines.contains(&format!("part {name};").as_str()),
+                    "missing part {name}"
+                );
+            }
+            ["flow", ..] => {
+                let flow = flow_of(line).unwrap();
+                assert!(flows.contains(&flow), "missing flow {flow:?} in {flows:#?}");
+            }
+            _ => continue,
+        }
+        checked += 1;
+    }
+    // The package, its item def, four part defs, two ports, four typed
+    // parts, two environment parts and four flows.
+    assert_eq!(checked, 18, "reference elements recognized");
+}
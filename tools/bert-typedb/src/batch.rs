@@ -0,0 +1,369 @@
+//! Batch transpile: many model files, one Write transaction each.
+//!
+//! [`collect_models`] expands files, directories and glob patterns into a
+//! sorted list of model files. [`prepare`] then loads, validates and emits
+//! every one of them before anything is pushed, so a bad file is reported up
+//! front instead of halfway through the batch. [`crate::transpile_batch`]
+//! pushes the prepared models a few at a time; a model that fails is
+//! recorded on its [`ModelReport`] and the rest go on.
+//!
+//! Each model's namespace is its file stem, as for a single push. Two files
+//! with the same stem would write into one namespace, so both are rejected.
+//!
+//! Everything here is pure except reading the model files.
+
+use crate::driver::SyncSummary;
+use crate::error::TranspilerError;
+use crate::insert::model_to_typeql;
+use crate::lifecycle::replace_typeql;
+use crate::query::Row;
+use crate::validate::{validate, Severity};
+use bert_core::files::model_files;
+use bert_core::migrate;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A model file, loaded and emitted but not yet pushed.
+#[derive(Debug, Clone)]
+pub struct Prepared {
+    pub path: PathBuf,
+    pub model_name: String,
+    /// The statements to push, or every reason the model cannot be pushed.
+    pub statements: Result<Vec<String>, Vec<String>>,
+}
+
+/// Where a model got to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    /// Did not load or failed validation; nothing was pushed.
+    Invalid,
+    /// Valid, and not pushed because the batch is a dry run.
+    Validated,
+    /// Committed.
+    Pushed,
+    /// Valid, but TypeDB rejected the transaction; nothing was committed.
+    Failed,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Invalid => "invalid",
+            Status::Validated => "validated",
+            Status::Pushed => "pushed",
+            Status::Failed => "failed",
+        }
+    }
+
+    /// Whether the model counts against the batch.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Invalid | Status::Failed)
+    }
+}
+
+/// The outcome for one model file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModelReport {
+    pub model_name: String,
+    pub path: PathBuf,
+    pub status: Status,
+    /// Statements emitted for the model; 0 if it is invalid.
+    pub statements: usize,
+    /// Time the push took, for a model that was pushed.
+    pub elapsed: Option<Duration>,
+    pub errors: Vec<String>,
+}
+
+impl Prepared {
+    /// The report for a model that is not pushed: an invalid one, or a valid
+    /// one in a dry run.
+    pub fn unpushed(self) -> ModelReport {
+        let (status, statements, errors) = match self.statements {
+            Ok(statements) => (Status::Validated, statements.len(), Vec::new()),
+            Err(errors) => (Status::Invalid, 0, errors),
+        };
+        ModelReport {
+            model_name: self.model_name,
+            path: self.path,
+            status,
+            statements,
+            elapsed: None,
+            errors,
+        }
+    }
+
+    /// The report for a model whose push gave `result`.
+    pub fn pushed(self, result: Result<SyncSummary, TranspilerError>) -> ModelReport {
+        let mut report = self.unpushed();
+        match result {
+            Ok(summary) => {
+                report.status = Status::Pushed;
+                report.statements = summary.statements_executed;
+                report.elapsed = Some(summary.elapsed);
+            }
+            Err(e) => {
+                report.status = Status::Failed;
+                report.errors.push(e.to_string());
+            }
+        }
+        report
+    }
+}
+
+impl Row for ModelReport {
+    const COLUMNS: &'static [&'static str] =
+        &["model", "status", "statements", "elapsed", "errors"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.model_name.clone(),
+            self.status.as_str().to_string(),
+            self.statements.to_string(),
+            self.elapsed
+                .map_or_else(|| "-".to_string(), |e| format!("{e:.2?}")),
+            self.errors.len().to_string(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "model": self.model_name,
+            "path": self.path.display().to_string(),
+            "status": self.status.as_str(),
+            "statements": self.statements,
+            "elapsed_ms": self.elapsed.map(|e| e.as_secs_f64() * 1000.0),
+            "errors": self.errors,
+        })
+    }
+}
+
+/// Every model file named by `inputs`, sorted and without repeats.
+///
+/// An input is a file, a directory — searched recursively for `*.json`
+/// models, skipping hidden directories and rule configs — or a glob pattern
+/// such as `models/**/*.json`. A pattern that matches nothing is an error; a
+/// file that does not exist is kept, and fails to load in [`prepare`].
+pub fn collect_models(inputs: &[PathBuf]) -> Result<Vec<PathBuf>, TranspilerError> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let pattern = input.to_string_lossy();
+        if input.is_dir() {
+            files.extend(model_files(input)?);
+        } else if !input.exists() && pattern.contains(['*', '?', '[']) {
+            let matches = glob::glob(&pattern)
+                .map_err(|e| TranspilerError::InvalidModel(format!("bad pattern {pattern}: {e}")))?
+                .filter_map(Result::ok)
+                .filter(|p| p.is_file())
+                .collect::<Vec<_>>();
+            if matches.is_empty() {
+                return Err(TranspilerError::InvalidModel(format!(
+                    "no model files match {pattern}"
+                )));
+            }
+            files.extend(matches);
+        } else {
+            files.push(input.clone());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Load, validate and emit every file, in order. With `replace`, each
+/// model's statements delete its namespace first, like
+/// [`replace_typeql`].
+pub fn prepare(files: &[PathBuf], replace: bool) -> Vec<Prepared> {
+    let mut by_name: BTreeMap<String, Vec<&Path>> = BTreeMap::new();
+    for path in files {
+        by_name.entry(model_name_from(path)).or_default().push(path);
+    }
+
+    files
+        .iter()
+        .map(|path| {
+            let model_name = model_name_from(path);
+            let others: Vec<String> = by_name[&model_name]
+                .iter()
+                .filter(|other| **other != path.as_path())
+                .map(|other| other.display().to_string())
+                .collect();
+            let statements = if others.is_empty() {
+                emit(path, &model_name, replace)
+            } else {
+                Err(vec![format!(
+                    "model name '{model_name}' is also derived from {}",
+                    others.join(", ")
+                )])
+            };
+            Prepared {
+                path: path.clone(),
+                model_name,
+                statements,
+            }
+        })
+        .collect()
+}
+
+fn emit(path: &Path, model_name: &str, replace: bool) -> Result<Vec<String>, Vec<String>> {
+    let bytes = std::fs::read(path).map_err(|e| vec![format!("failed to read: {e}")])?;
+    let (model, _) =
+        migrate::from_slice(&bytes).map_err(|e| vec![format!("failed to parse: {e}")])?;
+    let errors: Vec<String> = validate(&model)
+        .into_iter()
+        .filter(|i| i.severity == Severity::Error)
+        .map(|i| format!("{} {}: {}", i.code, i.location, i.message))
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    if replace {
+        replace_typeql(&model, model_name)
+    } else {
+        model_to_typeql(&model, model_name)
+    }
+    .map_err(|e| vec![e.to_string()])
+}
+
+/// The file stem, or the whole path when there is none.
+fn model_name_from(path: &Path) -> String {
+    path.file_stem()
+        .map_or_else(|| path.to_string_lossy(), |s| s.to_string_lossy())
+        .into_owned()
+}
+
+/// The batch as one JSON object: every model's report, and totals.
+pub fn report_json(reports: &[ModelReport]) -> Value {
+    let count = |status: Status| reports.iter().filter(|r| r.status == status).count();
+    json!({
+        "models": reports.iter().map(Row::to_json).collect::<Vec<_>>(),
+        "totals": {
+            "models": reports.len(),
+            "pushed": count(Status::Pushed),
+            "validated": count(Status::Validated),
+            "invalid": count(Status::Invalid),
+            "failed": count(Status::Failed),
+            "statements": reports.iter().map(|r| r.statements).sum::<usize>(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::render_table;
+
+    fn examples() -> PathBuf {
+        PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../assets/models/examples"
+        ))
+    }
+
+    fn names(files: &[PathBuf]) -> Vec<String> {
+        files.iter().map(|p| model_name_from(p)).collect()
+    }
+
+    #[test]
+    fn directories_and_globs_expand_to_sorted_model_files() {
+        let dir = examples();
+        let from_dir = collect_models(std::slice::from_ref(&dir)).unwrap();
+        assert!(names(&from_dir).contains(&"bitcoin".to_string()));
+        assert!(from_dir.windows(2).all(|w| w[0] < w[1]));
+
+        let pattern = dir.join("*.json");
+        let from_glob = collect_models(&[pattern, dir.join("bitcoin.json")]).unwrap();
+        assert_eq!(from_glob, from_dir, "repeats are dropped");
+
+        let only_b = collect_models(&[dir.join("b*.json")]).unwrap();
+        assert_eq!(names(&only_b), ["bitcoin", "blank"]);
+
+        assert!(collect_models(&[dir.join("nothing-*.json")]).is_err());
+    }
+
+    #[test]
+    fn every_example_prepares() {
+        let files = collect_models(&[examples()]).unwrap();
+        let prepared = prepare(&files, false);
+        assert_eq!(prepared.len(), files.len());
+        for p in &prepared {
+            assert!(
+                p.statements.as_ref().is_ok_and(|s| !s.is_empty()),
+                "{} should prepare: {:?}",
+                p.model_name,
+                p.statements.as_ref().err()
+            );
+        }
+
+        let replaced = prepare(&files[..1], true);
+        let inserted = prepare(&files[..1], false);
+        assert!(
+            replaced[0].statements.as_ref().unwrap().len()
+                > inserted[0].statements.as_ref().unwrap().len(),
+            "a replace deletes the namespace first"
+        );
+    }
+
+    #[test]
+    fn bad_files_are_reported_and_the_rest_go_on() {
+        let dir = examples();
+        let files = vec![
+            dir.join("bitcoin.json"),
+            dir.join("missing.json"),
+            dir.join("sub").join("bitcoin.json"),
+        ];
+        let reports: Vec<ModelReport> = prepare(&files, false)
+            .into_iter()
+            .map(Prepared::unpushed)
+            .collect();
+
+        // Both bitcoins would write into one namespace.
+        assert_eq!(reports[0].status, Status::Invalid);
+        assert!(reports[0].errors[0].contains("also derived from"));
+        assert_eq!(reports[1].status, Status::Invalid);
+        assert!(reports[1].errors[0].starts_with("failed to read"));
+
+        let reports: Vec<ModelReport> = prepare(&files[..2], false)
+            .into_iter()
+            .map(Prepared::unpushed)
+            .collect();
+        assert_eq!(reports[0].status, Status::Validated);
+        assert!(reports[0].statements > 0);
+        assert_eq!(reports[1].status, Status::Invalid);
+        assert_eq!(reports[1].statements, 0);
+
+        let json = report_json(&reports);
+        assert_eq!(json["totals"]["validated"], 1);
+        assert_eq!(json["totals"]["invalid"], 1);
+        assert_eq!(json["models"][1]["path"], files[1].display().to_string());
+
+        let table = render_table(&reports);
+        assert!(table.starts_with("model    status     statements  elapsed  errors\n"));
+        assert!(table.contains("missing  invalid    0           -        1\n"));
+    }
+
+    #[test]
+    fn a_failed_push_keeps_its_statement_count() {
+        let prepared = Prepared {
+            path: PathBuf::from("m.json"),
+            model_name: "m".to_string(),
+            statements: Ok(vec!["insert $x isa bert_model;".to_string()]),
+        };
+        let failed = prepared
+            .clone()
+            .pushed(Err(TranspilerError::QueryParse("rejected".to_string())));
+        assert_eq!(failed.status, Status::Failed);
+        assert_eq!(failed.statements, 1);
+        assert!(failed.status.is_failure());
+
+        let pushed = prepared.pushed(Ok(SyncSummary {
+            statements_executed: 1,
+            elapsed: Duration::from_millis(5),
+        }));
+        assert_eq!(pushed.status, Status::Pushed);
+        assert_eq!(pushed.cells()[3], "5.00ms");
+        assert!(!pushed.status.is_failure());
+    }
+}
@@ -13,12 +13,13 @@
 //!   concurrent writes. Done once at setup and on schema updates.
 //! - **Write** transaction for all data inserts. Many `.query()` calls,
 //!   one commit at the end — batches are cheap server-side buffering.
-//! - **Read** transaction would be used for query-back verification
-//!   (e.g. in integration tests); not needed in the transpiler's
-//!   happy path.
+//! - **Read** transaction for `fetch` queries: reading a model back
+//!   (`bert-typedb pull`, every query of it in one transaction) and
+//!   query-back verification in tests.
 
 use crate::error::TranspilerError;
 use crate::schema::SCHEMA_TQL;
+use futures::TryStreamExt;
 use std::time::{Duration, Instant};
 use typedb_driver::{
     Addresses, Credentials, DriverOptions, DriverTlsConfig, TransactionType, TypeDBDriver,
@@ -130,6 +131,43 @@ impl Transpiler {
         })
     }
 
+    /// Run one `fetch` query in a Read transaction and collect its documents
+    /// as JSON.
+    pub async fn fetch(&self, query: &str) -> Result<Vec<serde_json::Value>, TranspilerError> {
+        let mut documents = self.fetch_all(&[query.to_string()]).await?;
+        Ok(documents.pop().unwrap_or_default())
+    }
+
+    /// Run several `fetch` queries in one Read transaction, so that they all
+    /// see the same snapshot, and collect each query's documents as JSON.
+    pub async fn fetch_all(
+        &self,
+        queries: &[String],
+    ) -> Result<Vec<Vec<serde_json::Value>>, TranspilerError> {
+        let tx = self
+            .driver
+            .transaction(&self.db_name, TransactionType::Read)
+            .await
+            .map_err(map_driver_error)?;
+        let mut results = Vec::with_capacity(queries.len());
+        for query in queries {
+            let documents: Vec<_> = tx
+                .query(query)
+                .await
+                .map_err(map_driver_error)?
+                .into_documents()
+                .try_collect()
+                .await
+                .map_err(map_driver_error)?;
+            let documents = documents
+                .into_iter()
+                .map(|doc| Ok(serde_json::from_str(&doc.into_json().to_string())?))
+                .collect::<Result<_, TranspilerError>>()?;
+            results.push(documents);
+        }
+        Ok(results)
+    }
+
     /// Drop the database. Intended for test teardown — calling this in
     /// production flow would be surprising.
     pub async fn drop_database(&self) -> Result<(), TranspilerError> {
@@ -0,0 +1,228 @@
+//! Offline TypeQL dump: the schema and a model's insert statements as a
+//! TypeDB console script.
+//!
+//! [`model_to_typeql`] is pure, so nothing here needs a server. The script
+//! replays what the CLI's push does — create the database, load the schema
+//! in a Schema transaction, run every statement in one Write transaction —
+//! and loads later with:
+//!
+//! ```sh
+//! typedb console --address http://localhost:1729 --tls-disabled \
+//!     --username admin --password password --script model.tql
+//! ```
+//!
+//! # Script format
+//!
+//! The console ends a query at an empty line, so the schema is written with
+//! its blank lines dropped and every statement is followed by one. Statements
+//! are single-line already: [`escape_typeql_string`](crate::escape_typeql_string)
+//! writes newlines in values as `\n`.
+//!
+//! # Determinism
+//!
+//! The model is put in canonical form ([`canonicalize`]) before emission, so
+//! systems, interfaces, external entities and interactions come out sorted by
+//! id whatever order the file lists them in, and dumping the same model twice
+//! gives the same bytes. Dumps are meant to be committed and diffed.
+
+use crate::check_transpilable;
+use crate::error::TranspilerResult;
+use crate::insert::model_to_typeql;
+use crate::schema::SCHEMA_TQL;
+use bert_core::{canonicalize, WorldModel};
+
+/// What a dump script does besides inserting the model.
+#[derive(Debug, Clone)]
+pub struct DumpOptions {
+    /// Database the script loads into.
+    pub database: String,
+    /// Start with `database create`. The console stops at the first failing
+    /// command, so leave this off to load into an existing database.
+    pub create_database: bool,
+    /// Load [`SCHEMA_TQL`] before the data. Re-defining is idempotent, but
+    /// needs a Schema transaction, which waits for other writers.
+    pub schema: bool,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            database: "bert-models".to_string(),
+            create_database: true,
+            schema: true,
+        }
+    }
+}
+
+/// The TypeDB console script that loads `model` under the `model_name`
+/// namespace. Fails, like a push would, on a model with validation errors.
+pub fn dump_script(
+    model: &WorldModel,
+    model_name: &str,
+    options: &DumpOptions,
+) -> TranspilerResult<String> {
+    let model = canonicalize(model);
+    check_transpilable(&model)?;
+    let statements = model_to_typeql(&model, model_name)?;
+
+    let db = &options.database;
+    let mut out = String::new();
+    if options.create_database {
+        out.push_str(&format!("database create {db}\n"));
+    }
+    if options.schema {
+        out.push_str(&format!("transaction schema {db}\n"));
+        for line in SCHEMA_TQL.lines().filter(|l| !l.trim().is_empty()) {
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out.push_str("\ncommit\n");
+    }
+    out.push_str(&format!("transaction write {db}\n"));
+    for statement in &statements {
+        out.push_str(statement);
+        out.push_str("\n\n");
+    }
+    out.push_str("commit\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(path: &str) -> WorldModel {
+        let path = format!(
+            "{}/../../assets/models/{path}.json",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let bytes = std::fs::read(&path).expect("model should exist");
+        bert_core::migrate::from_slice(&bytes)
+            .expect("model should load")
+            .0
+    }
+
+    /// The queries of a write transaction: blocks between empty lines.
+    fn write_queries(script: &str) -> Vec<&str> {
+        let data = &script[script.find("transaction write").unwrap()..];
+        data.lines()
+            .skip(1)
+            .filter(|l| !l.is_empty() && *l != "commit")
+            .collect()
+    }
+
+    #[test]
+    fn script_creates_database_loads_schema_then_data() {
+        let model = load("examples/bitcoin");
+        let script = dump_script(&model, "bitcoin", &DumpOptions::default()).unwrap();
+        let commands: Vec<&str> = script
+            .lines()
+            .filter(|l| {
+                l.starts_with("database ") || l.starts_with("transaction ") || *l == "commit"
+            })
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                "database create bert-models",
+                "transaction schema bert-models",
+                "commit",
+                "transaction write bert-models",
+                "commit",
+            ]
+        );
+        assert!(script.ends_with("commit\n"));
+    }
+
+    #[test]
+    fn schema_is_one_query() {
+        let model = load("examples/bitcoin");
+        let script = dump_script(&model, "bitcoin", &DumpOptions::default()).unwrap();
+        let schema = &script[script.find("define").unwrap()..script.find("\n\ncommit").unwrap()];
+        assert!(!schema.contains("\n\n"), "a blank line would end the query");
+        assert!(schema.contains("entity system"));
+    }
+
+    #[test]
+    fn every_statement_is_its_own_query() {
+        let model = load("examples/bitcoin");
+        let statements = model_to_typeql(&canonicalize(&model), "bitcoin").unwrap();
+        let script = dump_script(&model, "bitcoin", &DumpOptions::default()).unwrap();
+        assert_eq!(write_queries(&script), statements);
+        assert!(statements.iter().all(|s| !s.contains('\n')));
+    }
+
+    #[test]
+    fn options_drop_database_creation_and_schema() {
+        let model = load("examples/bitcoin");
+        let options = DumpOptions {
+            database: "partners".to_string(),
+            create_database: false,
+            schema: false,
+        };
+        let script = dump_script(&model, "bitcoin", &options).unwrap();
+        assert!(script.starts_with("transaction write partners\n"));
+        assert!(!script.contains("transaction schema"));
+    }
+
+    #[test]
+    fn dump_does_not_depend_on_file_order() {
+        let model = load("examples/bitcoin");
+        let mut shuffled = model.clone();
+        shuffled.systems.reverse();
+        shuffled.interactions.reverse();
+        shuffled.environment.sources.reverse();
+        shuffled.environment.sinks.reverse();
+        for system in &mut shuffled.systems {
+            system.boundary.interfaces.reverse();
+        }
+        let options = DumpOptions::default();
+        assert_eq!(
+            dump_script(&model, "bitcoin", &options).unwrap(),
+            dump_script(&shuffled, "bitcoin", &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn agent_dump_is_deterministic() {
+        // Cognitive parameters live in a HashMap, whose order differs between
+        // instances; the dump sorts them.
+        let mut model = load("local/test-primitives/test-buffering-v2");
+        let agent = model
+            .systems
+            .iter_mut()
+            .find_map(|s| s.agent.as_mut())
+            .expect("test-buffering-v2 should have an agent");
+        for (i, name) in ["alpha", "beta", "gamma", "delta", "epsilon"]
+            .iter()
+            .enumerate()
+        {
+            agent.cognitive_params.insert(name.to_string(), i as f64);
+        }
+        let options = DumpOptions::default();
+        let first = dump_script(&model, "test-buffering", &options).unwrap();
+        assert!(first.contains("has cognitive_param_name \"alpha\""));
+        for _ in 0..8 {
+            // A fresh map gets a fresh hasher, and so its own order.
+            let mut again = model.clone();
+            for agent in again.systems.iter_mut().filter_map(|s| s.agent.as_mut()) {
+                agent.cognitive_params = agent.cognitive_params.drain().collect();
+            }
+            assert_eq!(
+                dump_script(&again, "test-buffering", &options).unwrap(),
+                first
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_model_is_not_dumped() {
+        let mut model = load("examples/bitcoin");
+        let duplicate = model.interactions[0].clone();
+        model.interactions.push(duplicate);
+        assert!(matches!(
+            dump_script(&model, "bitcoin", &DumpOptions::default()),
+            Err(crate::TranspilerError::InvalidModel(_))
+        ));
+    }
+}
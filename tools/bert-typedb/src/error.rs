@@ -23,6 +23,9 @@ pub enum TranspilerError {
     #[error("invalid BERT JSON: {0}")]
     InvalidModel(String),
 
+    #[error("no model named {0} in the database")]
+    ModelNotFound(String),
+
     #[error("required field missing on {entity}: {field}")]
     MissingRequiredField { entity: String, field: String },
 
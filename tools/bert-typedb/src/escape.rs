@@ -12,10 +12,10 @@
 //!
 //! 1. Escape backslashes first (`\` → `\\`)
 //! 2. Escape double quotes (`"` → `\"`)
-//! 3. Replace newlines with a space (TypeQL string literals can't safely
-//!    span lines; collapsing to space preserves word boundaries)
-//! 4. Strip carriage returns (trailing `\r` in CRLF lines)
-//! 5. Strip null bytes (unrepresentable in TypeQL string literals)
+//! 3. Escape newlines and carriage returns (`\n`, `\r`) — TypeQL string
+//!    literals can't safely span lines, and TypeDB unescapes these on insert,
+//!    so multi-line descriptions come back from a read unchanged
+//! 4. Strip null bytes (unrepresentable in TypeQL string literals)
 //!
 //! ## Source
 //!
@@ -33,13 +33,13 @@
 /// use bert_typedb::escape::escape_typeql_string;
 /// assert_eq!(escape_typeql_string("hello"), "hello");
 /// assert_eq!(escape_typeql_string(r#"say "hi""#), r#"say \"hi\""#);
-/// assert_eq!(escape_typeql_string("a\nb"), "a b");
+/// assert_eq!(escape_typeql_string("a\nb"), "a\\nb");
 /// ```
 pub fn escape_typeql_string(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('"', "\\\"")
-        .replace('\n', " ")
-        .replace('\r', "")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
         .replace('\0', "")
 }
 
@@ -79,13 +79,19 @@ mod tests {
     }
 
     #[test]
-    fn newline_collapses_to_space() {
-        assert_eq!(escape_typeql_string("line1\nline2"), "line1 line2");
+    fn newline_escaped() {
+        assert_eq!(escape_typeql_string("line1\nline2"), r"line1\nline2");
     }
 
     #[test]
-    fn carriage_return_stripped() {
-        assert_eq!(escape_typeql_string("line1\r\nline2"), "line1 line2");
+    fn carriage_return_escaped() {
+        assert_eq!(escape_typeql_string("line1\r\nline2"), r"line1\r\nline2");
+    }
+
+    #[test]
+    fn escaped_backslash_before_n_is_not_a_newline() {
+        // A literal backslash followed by `n` must not read back as a newline.
+        assert_eq!(escape_typeql_string(r"a\nb"), r"a\\nb");
     }
 
     #[test]
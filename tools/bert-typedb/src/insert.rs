@@ -20,32 +20,33 @@
 //! schema design's multi-model story. `model_name` comes from the caller
 //! (typically the JSON file stem like `"bitcoin"`).
 //!
-//! # Known deferrals (not in Phase 1)
+//! # Round trip
+//!
+//! Besides the typed graph, every field the editor needs to reopen the model
+//! is emitted — levels, canvas layout, parameters, interface links, the
+//! model envelope — so [`crate::read`] can rebuild the `WorldModel`.
+//!
+//! # Known deferrals
 //!
-//! - `parameters: Vec<Parameter>` on interactions — no schema entity yet
 //! - `smart_parameters: Vec<SmartParameter>` — no schema entity yet
-//! - `hidden_entities` — UI state, not meaningful in a typed graph
-//! - `transform: Option<Transform2d>` — visual position, not in schema
-//! - `parent_interface: Option<Id>` on boundaries — interface processor
-//!   linkage not represented in the current schema
-//! - `exports_to` / `receives_from` on interfaces — implicit in the
-//!   `routes_through` relations emitted in phase 2
+//! - `process_configs`, `initial_state`, `network_config` on agents —
+//!   free-form JSON, no schema entity yet
+//! - `sources` / `sinks` local to a system — only environment-level
+//!   external entities are emitted
+//! - `validation` rule configuration — editor settings, not model content
+//! - `history` / `transformation` — stored as their display notes, so
+//!   dated events and typed substances read back as plain text
+//! - `archetype: Some(Unspecified)` — not emitted, reads back as `None`
+//! - `paired_with` that is not mutual — only `port_pairing`s are stored
 //! - `ExternalEntityType` (Source/Sink) — role derived from flow direction
-//!   per SL spec §1.2; not an attribute on `external_entity`
+//!   per SL spec §1.2, and from the `Src`/`Snk` id prefix on read
 
 use crate::error::TranspilerResult;
 use crate::escape::escape_typeql_string;
-// The serializable data-model types live under `data_model`. The enum
-// definitions they reference (SubstanceType, InteractionUsability, HcgsArchetype)
-// live under `components::system_elements` — `data_model/mod.rs` does
-// `use crate::bevy_app::components::*;` but doesn't re-export, so we import
-// them from their real home.
-use bert::bevy_app::components::{
-    HcgsArchetype, InteractionType, InteractionUsability, SubstanceType,
-};
-use bert::bevy_app::data_model::{
-    AgentKind, AgentModel, Boundary, Complexity, ExternalEntity, Id, Interaction, Interface,
-    InterfaceType, ProcessPrimitive, System, WorldModel,
+use bert_core::{
+    AgentKind, AgentModel, Boundary, Complexity, Environment, ExternalEntity, HcgsArchetype, Id,
+    IdType, Interaction, InteractionType, InteractionUsability, Interface, InterfaceType, Mode,
+    Parameter, ProcessPrimitive, SubstanceType, System, Transform2d, WorldModel,
 };
 
 /// Produce every TypeQL `insert` statement needed to materialize `model` in
@@ -59,10 +60,7 @@ pub fn model_to_typeql(model: &WorldModel, model_name: &str) -> TranspilerResult
     let mut out = Vec::new();
 
     // Phase 1: entities
-    out.push(emit_bert_model(
-        model_name,
-        &model.environment.info.description,
-    ));
+    out.push(emit_bert_model(model, model_name));
     for ee in model
         .environment
         .sources
@@ -87,6 +85,17 @@ pub fn model_to_typeql(model: &WorldModel, model_name: &str) -> TranspilerResult
         out.push(emit_has_boundary(system, model_name));
         for iface in &system.boundary.interfaces {
             out.push(emit_has_interface(&system.boundary, iface, model_name));
+            out.extend(emit_interface_links(iface, model_name));
+        }
+        if let Some(iface_id) = &system.boundary.parent_interface {
+            out.push(emit_parent_interface(
+                &system.boundary,
+                iface_id,
+                model_name,
+            ));
+        }
+        for (import, export) in system.boundary.port_pairs() {
+            out.push(emit_port_pairing(import, export, model_name));
         }
         // Parent relationship: root system plays in_environment with the bert_model;
         // subsystems play composition with their parent system.
@@ -117,6 +126,9 @@ pub fn model_to_typeql(model: &WorldModel, model_name: &str) -> TranspilerResult
         if let Some(iface_id) = &ix.sink_interface {
             out.push(emit_routes_through(ix, iface_id, model_name, "end"));
         }
+        for (i, param) in ix.parameters.iter().enumerate() {
+            out.push(emit_parameter(ix, i, param, model_name));
+        }
     }
     out.extend(emit_equivalence_pairs(&model.environment, model_name));
 
@@ -127,22 +139,57 @@ pub fn model_to_typeql(model: &WorldModel, model_name: &str) -> TranspilerResult
 // Entity emitters
 // ---------------------------------------------------------------------------
 
-fn emit_bert_model(model_name: &str, description: &str) -> String {
-    format!(
-        r#"insert $m isa bert_model, has model_name "{name}", has description "{desc}";"#,
-        name = escape_typeql_string(model_name),
-        desc = escape_typeql_string(description),
-    )
+fn emit_bert_model(model: &WorldModel, model_name: &str) -> String {
+    let mut attrs = vec![
+        format!(r#"has model_name "{}""#, escape_typeql_string(model_name)),
+        format!(
+            r#"has display_name "{}""#,
+            escape_typeql_string(&model.environment.info.name)
+        ),
+        format!(
+            r#"has description "{}""#,
+            escape_typeql_string(&model.environment.info.description)
+        ),
+        format!("has format_version {}", model.version),
+    ];
+    if let Some(mode) = model.mode {
+        attrs.push(format!(r#"has model_mode "{}""#, mode_str(mode)));
+    }
+    for id in &model.hidden_entities {
+        attrs.push(format!(
+            r#"has hidden_entity "{}""#,
+            escape_typeql_string(&serialize_id(id))
+        ));
+    }
+    format!("insert $m isa bert_model, {};", attrs.join(", "))
 }
 
 fn emit_external_entity(ee: &ExternalEntity, model_name: &str) -> String {
-    format!(
-        r#"insert $ee isa external_entity, has bert_id "{id}", has display_name "{name}", has description "{desc}", has equivalence_class "{equiv}";"#,
-        id = namespaced_id(model_name, &ee.info.id),
-        name = escape_typeql_string(&ee.info.name),
-        desc = escape_typeql_string(&ee.info.description),
-        equiv = escape_typeql_string(&ee.equivalence),
-    )
+    let mut attrs = vec![
+        format!(
+            r#"has bert_id "{}""#,
+            namespaced_id(model_name, &ee.info.id)
+        ),
+        format!(
+            r#"has display_name "{}""#,
+            escape_typeql_string(&ee.info.name)
+        ),
+        format!(
+            r#"has description "{}""#,
+            escape_typeql_string(&ee.info.description)
+        ),
+        format!("has system_level {}", ee.info.level),
+        format!(
+            r#"has equivalence_class "{}""#,
+            escape_typeql_string(&ee.equivalence)
+        ),
+        format!(r#"has model_ref "{}""#, escape_typeql_string(&ee.model)),
+    ];
+    if let Some(key) = ee.is_same_as_id {
+        attrs.push(format!("has same_as_id {key}"));
+    }
+    attrs.extend(transform_attrs(ee.transform.as_ref()));
+    format!("insert $ee isa external_entity, {};", attrs.join(", "))
 }
 
 fn emit_system(system: &System, model_name: &str) -> String {
@@ -167,19 +214,30 @@ fn emit_system(system: &System, model_name: &str) -> String {
         ),
         format!(
             r#"has history_note "{}""#,
-            escape_typeql_string(&system.history)
+            escape_typeql_string(&system.history.to_string())
         ),
         format!(
             r#"has transformation_note "{}""#,
-            escape_typeql_string(&system.transformation)
+            escape_typeql_string(
+                &system
+                    .transformation
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
+            )
         ),
         format!("has member_autonomy {}", system.member_autonomy),
-        format!(
-            r#"has time_constant "{}""#,
-            escape_typeql_string(&system.time_constant)
-        ),
     ];
 
+    // Time constant: the schema's @values list is the bare vocabulary, so the
+    // magnitude goes in an attribute of its own.
+    if let Some(tc) = system.time_constant {
+        attrs.push(format!(r#"has time_constant "{}""#, tc.unit));
+        if let Some(magnitude) = tc.magnitude {
+            attrs.push(format!(r#"has time_constant_magnitude "{magnitude}""#));
+        }
+    }
+
     // Archetype: emit only when Some and not Unspecified. The @values list
     // in the schema includes "Unspecified" for forward-compat, but emitting
     // it in practice would be noise.
@@ -207,16 +265,18 @@ fn emit_system(system: &System, model_name: &str) -> String {
             attrs.push(format!("has multiset_count {}", n));
         }
     }
+    attrs.extend(transform_attrs(system.transform.as_ref()));
 
     format!("insert $s isa system, {};", attrs.join(", "))
 }
 
 fn emit_boundary(boundary: &Boundary, model_name: &str) -> String {
     format!(
-        r#"insert $b isa boundary, has bert_id "{id}", has display_name "{name}", has description "{desc}", has porosity {porosity}, has perceptive_fuzziness {fuzz};"#,
+        r#"insert $b isa boundary, has bert_id "{id}", has display_name "{name}", has description "{desc}", has system_level {level}, has porosity {porosity}, has perceptive_fuzziness {fuzz};"#,
         id = namespaced_id(model_name, &boundary.info.id),
         name = escape_typeql_string(&boundary.info.name),
         desc = escape_typeql_string(&boundary.info.description),
+        level = boundary.info.level,
         porosity = boundary.porosity,
         fuzz = boundary.perceptive_fuzziness,
     )
@@ -236,6 +296,7 @@ fn emit_interface(iface: &Interface, model_name: &str) -> String {
             r#"has description "{}""#,
             escape_typeql_string(&iface.info.description)
         ),
+        format!("has system_level {}", iface.info.level),
         format!(
             r#"has protocol "{}""#,
             escape_typeql_string(&iface.protocol)
@@ -249,11 +310,21 @@ fn emit_interface(iface: &Interface, model_name: &str) -> String {
 }
 
 fn emit_interaction(ix: &Interaction, model_name: &str) -> String {
+    let mut offsets = String::new();
+    if let Some(offset) = ix.endpoint_offset {
+        if let Some(angle) = offset.start_angle {
+            offsets.push_str(&format!(", has start_angle {angle}"));
+        }
+        if let Some(angle) = offset.end_angle {
+            offsets.push_str(&format!(", has end_angle {angle}"));
+        }
+    }
     format!(
-        r#"insert $f isa interaction, has bert_id "{id}", has display_name "{name}", has description "{desc}", has substance_type "{stype}", has substance_sub_type "{ssub}", has interaction_type "{ity}", has usability "{use_}", has amount "{amt}", has unit "{unit}";"#,
+        r#"insert $f isa interaction, has bert_id "{id}", has display_name "{name}", has description "{desc}", has system_level {level}, has substance_type "{stype}", has substance_sub_type "{ssub}", has interaction_type "{ity}", has usability "{use_}", has amount "{amt}", has unit "{unit}"{offsets};"#,
         id = namespaced_id(model_name, &ix.info.id),
         name = escape_typeql_string(&ix.info.name),
         desc = escape_typeql_string(&ix.info.description),
+        level = ix.info.level,
         stype = substance_type_str(ix.substance.ty),
         ssub = escape_typeql_string(&ix.substance.sub_type),
         ity = interaction_type_str(ix.ty),
@@ -284,6 +355,60 @@ fn emit_has_interface(boundary: &Boundary, iface: &Interface, model_name: &str)
     )
 }
 
+fn emit_port_pairing(import: &Interface, export: &Interface, model_name: &str) -> String {
+    format!(
+        r#"match $i isa interface, has bert_id "{imp}"; $e isa interface, has bert_id "{exp}"; insert (import_side: $i, export_side: $e) isa port_pairing;"#,
+        imp = namespaced_id(model_name, &import.info.id),
+        exp = namespaced_id(model_name, &export.info.id),
+    )
+}
+
+/// One `exports_to` per id in `iface.exports_to`, one `receives_from` per id
+/// in `iface.receives_from`. The counterpart is a system or an external
+/// entity, told apart by its id prefix like `participates_in`'s endpoint.
+fn emit_interface_links(iface: &Interface, model_name: &str) -> Vec<String> {
+    let link = |relation: &str, role: &str, other: &Id| {
+        format!(
+            r#"match $i isa interface, has bert_id "{ifc}"; $o isa {otype}, has bert_id "{oid}"; insert (interface: $i, {role}: $o) isa {relation};"#,
+            ifc = namespaced_id(model_name, &iface.info.id),
+            otype = concrete_type_for_id(other),
+            oid = namespaced_id(model_name, other),
+        )
+    };
+    iface
+        .exports_to
+        .iter()
+        .map(|id| link("exports_to", "target", id))
+        .chain(
+            iface
+                .receives_from
+                .iter()
+                .map(|id| link("receives_from", "origin", id)),
+        )
+        .collect()
+}
+
+fn emit_parent_interface(boundary: &Boundary, iface_id: &Id, model_name: &str) -> String {
+    format!(
+        r#"match $i isa interface, has bert_id "{ifc}"; $b isa boundary, has bert_id "{bnd}"; insert (interface: $i, boundary: $b) isa parent_interface;"#,
+        ifc = namespaced_id(model_name, iface_id),
+        bnd = namespaced_id(model_name, &boundary.info.id),
+    )
+}
+
+/// One parameter of an interaction, created together with its `has_parameter`
+/// relation: like the agent bundle's entities it has no `@key` to match on
+/// later. `list_index` keeps the parameters in order.
+fn emit_parameter(ix: &Interaction, index: usize, param: &Parameter, model_name: &str) -> String {
+    format!(
+        r#"match $f isa interaction, has bert_id "{fid}"; insert $p isa interaction_parameter, has parameter_name "{name}", has parameter_value "{value}", has unit "{unit}", has list_index {index}; (interaction: $f, param: $p) isa has_parameter;"#,
+        fid = namespaced_id(model_name, &ix.info.id),
+        name = escape_typeql_string(&param.name),
+        value = escape_typeql_string(&param.value),
+        unit = escape_typeql_string(&param.unit),
+    )
+}
+
 fn emit_composition(child_system: &System, model_name: &str) -> String {
     format!(
         r#"match $parent isa system, has bert_id "{parent}"; $child isa system, has bert_id "{child}"; insert (whole: $parent, part: $child) isa composition;"#,
@@ -371,7 +496,7 @@ fn emit_agent_bundle(agent: &AgentModel, system: &System, model_name: &str) -> S
 
     for (i, prim) in agent.primitives.iter().enumerate() {
         inserts.push(format!(
-            r#"$p{idx} isa primitive_assignment, has process_primitive "{val}""#,
+            r#"$p{idx} isa primitive_assignment, has process_primitive "{val}", has list_index {idx}"#,
             idx = i,
             val = process_primitive_str(*prim),
         ));
@@ -381,7 +506,10 @@ fn emit_agent_bundle(agent: &AgentModel, system: &System, model_name: &str) -> S
         ));
     }
 
-    for (i, (k, v)) in agent.cognitive_params.iter().enumerate() {
+    // Sorted by name: the map's iteration order varies between runs.
+    let mut cognitive_params: Vec<_> = agent.cognitive_params.iter().collect();
+    cognitive_params.sort_by(|a, b| a.0.cmp(b.0));
+    for (i, (k, v)) in cognitive_params.into_iter().enumerate() {
         inserts.push(format!(
             r#"$cp{idx} isa cognitive_parameter, has cognitive_param_name "{key}", has cognitive_param_value {val}"#,
             idx = i,
@@ -409,19 +537,16 @@ fn emit_agent_bundle(agent: &AgentModel, system: &System, model_name: &str) -> S
 /// chain in output. If cross-model linking scales N, revisit with a
 /// TypeDB inference rule for transitive closure.
 ///
-/// Canonical ordering: within a group, relations are emitted for all
-/// index pairs (i, j) where i < j (so deterministic across runs). The
-/// earlier-listed entity plays the `primary` role, the later plays
-/// `equivalent`. This assignment is arbitrary for symmetric equivalence
-/// — downstream queries should match both `(primary, equivalent)` and
-/// `(equivalent, primary)` orderings, or define a symmetric-closure
-/// inference rule when needed.
-fn emit_equivalence_pairs(
-    env: &bert::bevy_app::data_model::Environment,
-    model_name: &str,
-) -> Vec<String> {
-    use std::collections::HashMap;
-    let mut groups: HashMap<usize, Vec<&ExternalEntity>> = HashMap::new();
+/// Canonical ordering: groups in `is_same_as_id` order and, within a
+/// group, relations for all index pairs (i, j) where i < j (so
+/// deterministic across runs). The earlier-listed entity plays the
+/// `primary` role, the later plays `equivalent`. This assignment is
+/// arbitrary for symmetric equivalence — downstream queries should match
+/// both `(primary, equivalent)` and `(equivalent, primary)` orderings, or
+/// define a symmetric-closure inference rule when needed.
+fn emit_equivalence_pairs(env: &Environment, model_name: &str) -> Vec<String> {
+    use std::collections::BTreeMap;
+    let mut groups: BTreeMap<usize, Vec<&ExternalEntity>> = BTreeMap::new();
     for ee in env.sources.iter().chain(env.sinks.iter()) {
         if let Some(key) = ee.is_same_as_id {
             groups.entry(key).or_default().push(ee);
@@ -450,18 +575,39 @@ fn emit_equivalence_pairs(
 /// The environment sentinel ID `E-1`. Used as the `parent` of the root
 /// system — distinguishing it from subsystems whose parent is another system.
 fn is_environment_id(id: &Id) -> bool {
-    serialize_id(id) == "E-1"
+    id.ty == IdType::Environment
 }
 
-/// Determine the TypeDB entity type name from an Id's type prefix.
+/// Determine the TypeDB entity type name from an Id's type.
 /// Needed for `participates_in` match queries where the entity endpoint
 /// may be either a `system` or an `external_entity`.
 fn concrete_type_for_id(id: &Id) -> &'static str {
-    let s = serialize_id(id);
-    if s.starts_with("Src") || s.starts_with("Snk") {
-        "external_entity"
-    } else {
-        "system"
+    match id.ty {
+        IdType::Source | IdType::Sink => "external_entity",
+        _ => "system",
+    }
+}
+
+/// Canvas position and rotation, when the element has been placed.
+fn transform_attrs(transform: Option<&Transform2d>) -> Vec<String> {
+    transform
+        .map(|t| {
+            vec![
+                format!("has position_x {}", t.translation.x),
+                format!("has position_y {}", t.translation.y),
+                format!("has rotation {}", t.rotation),
+            ]
+        })
+        .unwrap_or_default()
+}
+
+fn mode_str(m: Mode) -> &'static str {
+    match m {
+        Mode::Core => "Core",
+        Mode::Structural => "Structural",
+        Mode::Operational => "Operational",
+        Mode::Full => "Full",
+        Mode::Cybernetic => "Cybernetic",
     }
 }
 
@@ -473,7 +619,7 @@ fn agent_kind_str(k: AgentKind) -> &'static str {
     }
 }
 
-fn process_primitive_str(p: ProcessPrimitive) -> &'static str {
+pub(crate) fn process_primitive_str(p: ProcessPrimitive) -> &'static str {
     match p {
         ProcessPrimitive::Combining => "Combining",
         ProcessPrimitive::Splitting => "Splitting",
@@ -495,22 +641,13 @@ pub(crate) fn namespaced_id(model_name: &str, id: &Id) -> String {
     format!("{}:{}", escaped_prefix, escape_typeql_string(&local))
 }
 
-/// Serialize an Id to its wire representation (e.g. `"S0"`, `"C0.1.2"`)
-/// by round-tripping through serde_json and stripping the surrounding quotes.
-/// Small surface — Id's Serialize impl is the authoritative source of the
-/// string format, so this avoids duplicating the format logic here.
+/// An Id's wire representation (e.g. `"S0"`, `"C0.1.2"`), as bert-core
+/// formats it.
 pub(crate) fn serialize_id(id: &Id) -> String {
-    serde_json::to_string(id)
-        .ok()
-        .and_then(|s| {
-            s.strip_prefix('"')
-                .and_then(|s| s.strip_suffix('"'))
-                .map(String::from)
-        })
-        .unwrap_or_default()
+    id.to_string()
 }
 
-fn archetype_str(a: HcgsArchetype) -> &'static str {
+pub(crate) fn archetype_str(a: HcgsArchetype) -> &'static str {
     match a {
         HcgsArchetype::Unspecified => "Unspecified",
         HcgsArchetype::Governance => "Governance",
@@ -519,7 +656,7 @@ fn archetype_str(a: HcgsArchetype) -> &'static str {
     }
 }
 
-fn substance_type_str(s: SubstanceType) -> &'static str {
+pub(crate) fn substance_type_str(s: SubstanceType) -> &'static str {
     match s {
         SubstanceType::Energy => "Energy",
         SubstanceType::Material => "Material",
@@ -904,6 +1041,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mutual_import_export_pair_produces_one_port_pairing() {
+        let mut model = load_bitcoin();
+        let stmts = model_to_typeql(&model, "bitcoin").unwrap();
+        assert!(!stmts.iter().any(|s| s.contains("isa port_pairing")));
+
+        let interfaces = &mut model.systems[0].boundary.interfaces;
+        let import = interfaces
+            .iter()
+            .position(|i| matches!(i.ty, InterfaceType::Import))
+            .expect("bitcoin S0 has an import");
+        let export = interfaces
+            .iter()
+            .position(|i| matches!(i.ty, InterfaceType::Export))
+            .expect("bitcoin S0 has an export");
+        interfaces[import].paired_with = Some(interfaces[export].info.id.clone());
+        interfaces[export].paired_with = Some(interfaces[import].info.id.clone());
+        let import_id = namespaced_id("bitcoin", &interfaces[import].info.id);
+        let export_id = namespaced_id("bitcoin", &interfaces[export].info.id);
+
+        let stmts = model_to_typeql(&model, "bitcoin").unwrap();
+        let pairings: Vec<_> = stmts
+            .iter()
+            .filter(|s| s.contains("isa port_pairing"))
+            .collect();
+        assert_eq!(pairings.len(), 1, "one relation per pair, not per half");
+        let stmt = pairings[0];
+        let imp = stmt.find(&import_id).unwrap();
+        let exp = stmt.find(&export_id).unwrap();
+        assert!(imp < exp, "import side is matched first: {stmt}");
+    }
+
     #[test]
     fn routes_through_emitted_only_when_interface_set() {
         // F0.3 Protocol → Mining is an internal flow — its source_interface
@@ -6,21 +6,31 @@
 //! See `docs/bert-typedb-schema.md` in the BERT repo for the schema design
 //! this transpiler targets.
 
+pub mod batch;
 pub mod driver;
+pub mod dump;
 pub mod error;
 pub mod escape;
 pub mod insert;
+pub mod lifecycle;
+pub mod query;
+pub mod read;
 pub mod schema;
 pub mod validate;
 
+pub use batch::{collect_models, prepare, report_json, ModelReport, Prepared, Status};
 pub use driver::{SyncSummary, Transpiler};
+pub use dump::{dump_script, DumpOptions};
 pub use error::TranspilerError;
 pub use escape::escape_typeql_string;
 pub use insert::model_to_typeql;
+pub use lifecycle::{delete_model_typeql, replace_typeql, sync_typeql};
+pub use query::{render_table, CannedQuery, Row};
+pub use read::{assemble, read_queries, Documents, Part};
 pub use schema::SCHEMA_TQL;
-pub use validate::{validate, Severity, ValidationIssue};
+pub use validate::{validate, Severity, ValidationIssue, TRANSPILE_RULES};
 
-use bert::bevy_app::data_model::WorldModel;
+use bert_core::WorldModel;
 
 /// End-to-end transpile: validate the model, emit TypeQL, and push to TypeDB.
 /// Assumes the target database already exists and the schema is loaded.
@@ -32,6 +42,104 @@ pub async fn transpile_and_push(
     transpiler: &Transpiler,
 ) -> Result<SyncSummary, TranspilerError> {
     // 1. Validate — fail fast on any errors before touching TypeDB.
+    check_transpilable(model)?;
+
+    // 2. Emit TypeQL.
+    let statements = model_to_typeql(model, model_name)?;
+
+    // 3. Push in one Write transaction.
+    transpiler.push_statements(&statements).await
+}
+
+/// Remove everything under the `model_name` namespace in one Write
+/// transaction. Deleting a model that is not there does nothing.
+pub async fn delete_model(
+    model_name: &str,
+    transpiler: &Transpiler,
+) -> Result<SyncSummary, TranspilerError> {
+    transpiler
+        .push_statements(&delete_model_typeql(model_name))
+        .await
+}
+
+/// Idempotent push: delete the `model_name` namespace and insert `model` in
+/// one Write transaction, so readers see either the old model or the new one.
+pub async fn transpile_and_replace(
+    model: &WorldModel,
+    model_name: &str,
+    transpiler: &Transpiler,
+) -> Result<SyncSummary, TranspilerError> {
+    let statements = replace_typeql(model, model_name)?;
+    transpiler.push_statements(&statements).await
+}
+
+/// Incremental push: bring the `model_name` namespace from `previous` — the
+/// model last pushed there — to `model`, touching only the elements that
+/// changed. Pushes nothing when no stored field changed.
+pub async fn transpile_and_sync(
+    previous: &WorldModel,
+    model: &WorldModel,
+    model_name: &str,
+    transpiler: &Transpiler,
+) -> Result<SyncSummary, TranspilerError> {
+    let statements = sync_typeql(previous, model, model_name)?;
+    transpiler.push_statements(&statements).await
+}
+
+/// Read the `model_name` namespace back into a `WorldModel`. Every
+/// [`read_queries`] query runs in one Read transaction, so a concurrent push
+/// cannot leave the model half old and half new. Fails with
+/// [`TranspilerError::ModelNotFound`] if nothing is stored under that name.
+pub async fn read_model(
+    model_name: &str,
+    transpiler: &Transpiler,
+) -> Result<WorldModel, TranspilerError> {
+    let (parts, queries): (Vec<Part>, Vec<String>) =
+        read_queries(model_name).into_iter().unzip();
+    let fetched = transpiler.fetch_all(&queries).await?;
+    let documents: Documents = parts.into_iter().zip(fetched).collect();
+    assemble(model_name, &documents)
+}
+
+/// Push [`prepare`]d models, each in its own Write transaction and at most
+/// `jobs` at once. Invalid models are reported without being pushed, and a
+/// model TypeDB rejects does not stop the others. Reports come back in the
+/// order the models were given.
+pub async fn transpile_batch(
+    prepared: Vec<Prepared>,
+    jobs: usize,
+    transpiler: &Transpiler,
+) -> Vec<ModelReport> {
+    use futures::StreamExt;
+
+    futures::stream::iter(prepared)
+        .map(|p| async move {
+            match &p.statements {
+                Ok(statements) => {
+                    let result = transpiler.push_statements(statements).await;
+                    p.pushed(result)
+                }
+                Err(_) => p.unpushed(),
+            }
+        })
+        .buffered(jobs.max(1))
+        .collect()
+        .await
+}
+
+/// Run a canned query across every model in the database.
+pub async fn run_query<Q: CannedQuery>(
+    query: &Q,
+    transpiler: &Transpiler,
+) -> Result<Vec<Q::Row>, TranspilerError> {
+    let documents = transpiler.fetch(&query.typeql()).await?;
+    query.rows(&documents)
+}
+
+/// Fail with [`TranspilerError::InvalidModel`] listing every error-severity
+/// issue [`validate`] finds, so nothing is emitted for a model TypeDB would
+/// reject.
+pub fn check_transpilable(model: &WorldModel) -> Result<(), TranspilerError> {
     let issues = validate(model);
     let errors: Vec<&ValidationIssue> = issues
         .iter()
@@ -43,15 +151,10 @@ pub async fn transpile_and_push(
             errors.len(),
             errors
                 .iter()
-                .map(|e| format!("{}: {}", e.location, e.message))
+                .map(|e| format!("{} {}: {}", e.code, e.location, e.message))
                 .collect::<Vec<_>>()
                 .join("; ")
         )));
     }
-
-    // 2. Emit TypeQL.
-    let statements = model_to_typeql(model, model_name)?;
-
-    // 3. Push in one Write transaction.
-    transpiler.push_statements(&statements).await
+    Ok(())
 }
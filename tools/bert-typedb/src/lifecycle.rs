@@ -0,0 +1,433 @@
+//! Model lifecycle in TypeDB: delete, replace and incremental sync.
+//!
+//! [`model_to_typeql`] only inserts, and `bert_id` is a `@key`, so pushing a
+//! model a second time fails on the first entity it already holds. The
+//! statement lists here make a push repeatable. Like the inserts they are
+//! pure and run in one Write transaction, deletes first.
+//!
+//! - [`delete_model_typeql`] removes everything in a model's namespace: every
+//!   entity whose `bert_id` starts with `{model_name}:`, the keyless agent
+//!   bundles and parameters hanging off its systems and interactions, and its
+//!   `bert_model`.
+//! - [`replace_typeql`] deletes the namespace and inserts the model again: an
+//!   idempotent upsert.
+//! - [`sync_typeql`] uses the structural [`diff`] between the model TypeDB
+//!   holds and the new one to delete and re-insert only the elements that
+//!   changed, with every relation they play in.
+//!
+//! # Relations
+//!
+//! Deleting a role player does not delete its relations in TypeDB 3 — they
+//! stay behind with the remaining players. When the namespace goes, every
+//! player goes and the relations with it. A sync keeps most of the graph,
+//! so it deletes the relations of each element it re-inserts explicitly
+//! (`$r links ($e)`). Every relation change shows up in the diff as a
+//! change to one of its players — an interaction's source, a system's
+//! parent, an interface's pairing — so re-inserting the relations that
+//! touch a changed element restores all of them.
+
+use crate::check_transpilable;
+use crate::error::TranspilerResult;
+use crate::escape::escape_typeql_string;
+use crate::insert::{model_to_typeql, namespaced_id};
+use bert_core::diff::{diff, ElementChange, ElementDiff, ElementKind};
+use bert_core::WorldModel;
+use std::collections::HashSet;
+
+/// Element fields that are not stored in TypeDB (see the deferrals in
+/// [`crate::insert`]), so changing only these needs no sync.
+const NOT_STORED: &[&str] = &[
+    "smart_parameters",
+    "agent.process_configs",
+    "agent.initial_state",
+    "agent.network_config",
+];
+
+/// Statements that delete the `model_name` namespace, in the order they must
+/// run. Matching nothing is not an error, so deleting a model that was never
+/// pushed is a no-op.
+pub fn delete_model_typeql(model_name: &str) -> Vec<String> {
+    let systems = format!(
+        r#"$s isa system, has bert_id $id; $id like "{pattern}";"#,
+        pattern = namespace_pattern(model_name),
+    );
+    vec![
+        format!(
+            "match {systems} (system: $s, config: $a) isa has_agent_config; (agent: $a, param: $p) isa has_cognitive_param; delete $p;"
+        ),
+        format!(
+            "match {systems} (system: $s, config: $a) isa has_agent_config; (agent: $a, primitive: $p) isa has_primitive; delete $p;"
+        ),
+        format!("match {systems} (system: $s, config: $a) isa has_agent_config; delete $a;"),
+        format!(
+            r#"match $f isa interaction, has bert_id $id; $id like "{pattern}"; (interaction: $f, param: $p) isa has_parameter; delete $p;"#,
+            pattern = namespace_pattern(model_name),
+        ),
+        format!(
+            r#"match $e has bert_id $id; $id like "{pattern}"; delete $e;"#,
+            pattern = namespace_pattern(model_name),
+        ),
+        format!(
+            r#"match $m isa bert_model, has model_name "{name}"; delete $m;"#,
+            name = escape_typeql_string(model_name),
+        ),
+    ]
+}
+
+/// Delete the `model_name` namespace and insert `model` into it, so the
+/// result is the same whether or not the model was pushed before. Fails,
+/// like a push, on a model with validation errors.
+pub fn replace_typeql(model: &WorldModel, model_name: &str) -> TranspilerResult<Vec<String>> {
+    check_transpilable(model)?;
+    let mut statements = delete_model_typeql(model_name);
+    statements.extend(model_to_typeql(model, model_name)?);
+    Ok(statements)
+}
+
+/// Statements that turn `before`, as pushed under `model_name`, into
+/// `after`. Elements that were removed or modified are deleted with their
+/// relations; elements that were added or modified are inserted with
+/// theirs. Unchanged elements are not touched. Identical models — or ones
+/// that differ only in fields TypeDB does not store — give no statements.
+///
+/// `before` must be what TypeDB actually holds for the namespace; if it is
+/// not, the inserts can collide with existing `bert_id`s and the
+/// transaction fails without committing. [`replace_typeql`] needs no
+/// previous model.
+pub fn sync_typeql(
+    before: &WorldModel,
+    after: &WorldModel,
+    model_name: &str,
+) -> TranspilerResult<Vec<String>> {
+    check_transpilable(after)?;
+    let changes = diff(before, after);
+    let model_changed = changes
+        .model
+        .iter()
+        .any(|f| !f.path.starts_with("validation"));
+    let changed: Vec<&ElementDiff> = changes.elements.iter().filter(|e| is_stored(e)).collect();
+
+    let mut statements = Vec::new();
+    if model_changed {
+        let model = format!(
+            r#"$m isa bert_model, has model_name "{}";"#,
+            escape_typeql_string(model_name)
+        );
+        statements.push(format!("match {model} $r links ($m); delete $r;"));
+        statements.push(format!("match {model} delete $m;"));
+    }
+    for element in changed.iter().filter(|e| e.change != ElementChange::Added) {
+        let id = namespaced_id(model_name, &element.id);
+        if element.kind == ElementKind::System {
+            statements.extend(delete_agent_typeql(&id));
+            if let Some(system) = before.systems.iter().find(|s| s.info.id == element.id) {
+                statements.extend(delete_element_typeql(&namespaced_id(
+                    model_name,
+                    &system.boundary.info.id,
+                )));
+            }
+        }
+        if element.kind == ElementKind::Interaction {
+            statements.push(format!(
+                r#"match $f isa interaction, has bert_id "{id}"; (interaction: $f, param: $p) isa has_parameter; delete $p;"#
+            ));
+        }
+        statements.extend(delete_element_typeql(&id));
+    }
+
+    // Everything `after` holds that touches a re-inserted element.
+    let mut fresh: HashSet<String> = HashSet::new();
+    for element in changed
+        .iter()
+        .filter(|e| e.change != ElementChange::Removed)
+    {
+        fresh.insert(namespaced_id(model_name, &element.id));
+        if element.kind == ElementKind::System {
+            if let Some(system) = after.systems.iter().find(|s| s.info.id == element.id) {
+                fresh.insert(namespaced_id(model_name, &system.boundary.info.id));
+            }
+        }
+    }
+    for statement in model_to_typeql(after, model_name)? {
+        let touches_model = model_changed && statement.contains("isa bert_model");
+        if touches_model || bert_ids(&statement).any(|id| fresh.contains(id)) {
+            statements.push(statement);
+        }
+    }
+    Ok(statements)
+}
+
+/// Whether a change reaches anything TypeDB stores.
+fn is_stored(element: &ElementDiff) -> bool {
+    match &element.change {
+        ElementChange::Modified(fields) => fields.iter().any(|f| {
+            !NOT_STORED
+                .iter()
+                .any(|p| f.path == *p || f.path.starts_with(&format!("{p}.")))
+        }),
+        ElementChange::Added | ElementChange::Removed => true,
+    }
+}
+
+/// Delete the entity with namespaced `bert_id` `id` and every relation it
+/// plays in.
+fn delete_element_typeql(id: &str) -> [String; 2] {
+    [
+        format!(r#"match $e has bert_id "{id}"; $r links ($e); delete $r;"#),
+        format!(r#"match $e has bert_id "{id}"; delete $e;"#),
+    ]
+}
+
+/// Delete the agent bundle of the system with namespaced `bert_id` `id`.
+/// Its entities have no `@key`, so they are reached through the system.
+fn delete_agent_typeql(id: &str) -> [String; 3] {
+    let system = format!(r#"$s isa system, has bert_id "{id}";"#);
+    [
+        format!(
+            "match {system} (system: $s, config: $a) isa has_agent_config; (agent: $a, param: $p) isa has_cognitive_param; delete $p;"
+        ),
+        format!(
+            "match {system} (system: $s, config: $a) isa has_agent_config; (agent: $a, primitive: $p) isa has_primitive; delete $p;"
+        ),
+        format!("match {system} (system: $s, config: $a) isa has_agent_config; delete $a;"),
+    ]
+}
+
+/// The `like` pattern for every `bert_id` in the namespace, as a TypeQL
+/// string body: the model name with regex metacharacters escaped, anchored,
+/// followed by the `:` separator.
+pub(crate) fn namespace_pattern(model_name: &str) -> String {
+    let mut regex = String::from("^");
+    for c in model_name.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            regex.push('\\');
+        }
+        regex.push(c);
+    }
+    regex.push(':');
+    escape_typeql_string(&regex)
+}
+
+/// The `bert_id` values a statement inserts or matches, still escaped as
+/// they appear in it.
+fn bert_ids(statement: &str) -> impl Iterator<Item = &str> {
+    const MARKER: &str = r#"has bert_id ""#;
+    statement.match_indices(MARKER).map(|(at, _)| {
+        let rest = &statement[at + MARKER.len()..];
+        let mut escaped = false;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                let closes = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                closes
+            })
+            .map_or(rest.len(), |(i, _)| i);
+        &rest[..end]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bert_core::{Interaction, System};
+
+    /// Loaded the way the CLI loads it, migrated to the current version.
+    fn load_bitcoin() -> WorldModel {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../assets/models/examples/bitcoin.json"
+        );
+        let bytes = std::fs::read(path).expect("bitcoin.json should exist");
+        bert_core::migrate::from_slice(&bytes)
+            .expect("bitcoin.json should load")
+            .0
+    }
+
+    fn interaction<'a>(model: &'a mut WorldModel, id: &str) -> &'a mut Interaction {
+        model
+            .interactions
+            .iter_mut()
+            .find(|ix| crate::insert::serialize_id(&ix.info.id) == id)
+            .expect("interaction should exist")
+    }
+
+    fn leaf_system(model: &WorldModel) -> &System {
+        model
+            .systems
+            .iter()
+            .find(|s| {
+                !model.systems.iter().any(|c| c.parent == s.info.id)
+                    && !model
+                        .interactions
+                        .iter()
+                        .any(|ix| ix.source == s.info.id || ix.sink == s.info.id)
+            })
+            .unwrap_or_else(|| model.systems.last().unwrap())
+    }
+
+    #[test]
+    fn delete_matches_the_namespace_only() {
+        let statements = delete_model_typeql("bitcoin");
+        assert_eq!(statements.len(), 6);
+        for statement in &statements[..5] {
+            assert!(statement.contains(r#"like "^bitcoin:""#), "{statement}");
+        }
+        assert!(statements[5].contains(r#"has model_name "bitcoin""#));
+        // Keyless entities first: once their owners are gone they are
+        // unreachable.
+        assert!(statements[2].ends_with("delete $a;"));
+        assert!(statements[3].contains("isa has_parameter"));
+        assert!(statements[4].ends_with("delete $e;"));
+    }
+
+    #[test]
+    fn namespace_pattern_escapes_regex_and_typeql() {
+        assert_eq!(namespace_pattern("v1.2"), r"^v1\\.2:");
+        assert_eq!(namespace_pattern(r#"a"b"#), r#"^a\"b:"#);
+        assert_eq!(namespace_pattern("x(y)"), r"^x\\(y\\):");
+    }
+
+    #[test]
+    fn replace_deletes_then_inserts() {
+        let model = load_bitcoin();
+        let statements = replace_typeql(&model, "bitcoin").unwrap();
+        let deletes = delete_model_typeql("bitcoin");
+        let inserts = model_to_typeql(&model, "bitcoin").unwrap();
+        assert_eq!(statements[..deletes.len()], deletes[..]);
+        assert_eq!(statements[deletes.len()..], inserts[..]);
+    }
+
+    #[test]
+    fn sync_of_identical_models_is_empty() {
+        let model = load_bitcoin();
+        assert!(sync_typeql(&model, &model, "bitcoin").unwrap().is_empty());
+    }
+
+    #[test]
+    fn sync_ignores_fields_typedb_does_not_store() {
+        let before = load_bitcoin();
+        let mut after = before.clone();
+        after.interactions[0]
+            .smart_parameters
+            .push(bert_core::SmartParameter {
+                id: Default::default(),
+                name: "Latency".to_string(),
+                value: bert_core::ParameterValue::Boolean {
+                    value: true,
+                    true_label: "high".to_string(),
+                    false_label: "low".to_string(),
+                },
+            });
+        assert!(sync_typeql(&before, &after, "bitcoin").unwrap().is_empty());
+
+        // Layout is stored, so the editor gets it back on read.
+        if let Some(transform) = &mut after.systems[0].transform {
+            transform.translation.x += 10.0;
+        }
+        assert!(!sync_typeql(&before, &after, "bitcoin").unwrap().is_empty());
+    }
+
+    #[test]
+    fn modified_interaction_is_reinserted_with_its_relations() {
+        let before = load_bitcoin();
+        let mut after = before.clone();
+        interaction(&mut after, "F0.3").info.name = "Block rewards".to_string();
+        let statements = sync_typeql(&before, &after, "bitcoin").unwrap();
+
+        let id = r#""bitcoin:F0.3""#;
+        assert!(statements.iter().all(|s| s.contains(id)), "{statements:#?}");
+        assert_eq!(
+            statements[..3],
+            [
+                format!("match $f isa interaction, has bert_id {id}; (interaction: $f, param: $p) isa has_parameter; delete $p;"),
+                format!("match $e has bert_id {id}; $r links ($e); delete $r;"),
+                format!("match $e has bert_id {id}; delete $e;"),
+            ]
+        );
+        let inserts = &statements[3..];
+        assert!(inserts[0].starts_with("insert $f isa interaction"));
+        assert!(inserts[0].contains(r#"has display_name "Block rewards""#));
+        // F0.3 is internal: its source and sink, no interfaces.
+        let params = interaction(&mut after, "F0.3").parameters.len();
+        let count = |relation: &str| inserts.iter().filter(|s| s.contains(relation)).count();
+        assert_eq!(count("isa participates_in"), 2);
+        assert_eq!(count("isa has_parameter"), params);
+        assert_eq!(inserts.len(), 3 + params);
+    }
+
+    #[test]
+    fn removed_system_takes_its_boundary_and_agent() {
+        let before = load_bitcoin();
+        let mut after = before.clone();
+        let system = leaf_system(&before).clone();
+        after.systems.retain(|s| s.info.id != system.info.id);
+        after.interactions.retain(|ix| {
+            ix.source != system.info.id
+                && ix.sink != system.info.id
+                && !system.boundary.interfaces.iter().any(|i| {
+                    ix.source_interface.as_ref() == Some(&i.info.id)
+                        || ix.sink_interface.as_ref() == Some(&i.info.id)
+                })
+        });
+        let statements = sync_typeql(&before, &after, "bitcoin").unwrap();
+
+        let sys = namespaced_id("bitcoin", &system.info.id);
+        let bnd = namespaced_id("bitcoin", &system.boundary.info.id);
+        assert!(statements
+            .iter()
+            .any(|s| s.contains(&sys) && s.ends_with("delete $a;")));
+        assert!(statements.contains(&format!(r#"match $e has bert_id "{bnd}"; delete $e;"#)));
+        assert!(statements.contains(&format!(r#"match $e has bert_id "{sys}"; delete $e;"#)));
+        assert!(
+            !statements.iter().any(|s| s.starts_with("insert ")),
+            "nothing was added or modified: {statements:#?}"
+        );
+    }
+
+    #[test]
+    fn added_interaction_is_inserted_without_deletes() {
+        let mut before = load_bitcoin();
+        let after = before.clone();
+        let added = before.interactions.pop().unwrap();
+        let statements = sync_typeql(&before, &after, "bitcoin").unwrap();
+
+        let id = namespaced_id("bitcoin", &added.info.id);
+        assert!(statements.iter().all(|s| !s.contains("delete")));
+        assert!(statements.iter().all(|s| s.contains(&id)));
+        assert!(statements[0].starts_with("insert $f isa interaction"));
+    }
+
+    #[test]
+    fn model_description_change_reinserts_bert_model() {
+        let before = load_bitcoin();
+        let mut after = before.clone();
+        after.environment.info.description = "Peer-to-peer cash".to_string();
+        let statements = sync_typeql(&before, &after, "bitcoin").unwrap();
+
+        assert!(statements[0].contains("isa bert_model") && statements[0].contains("links ($m)"));
+        assert!(statements[1].ends_with("delete $m;"));
+        assert!(statements[2].contains(r#"has description "Peer-to-peer cash""#));
+        let in_environment = model_to_typeql(&after, "bitcoin")
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.contains("isa in_environment"))
+            .count();
+        assert_eq!(
+            statements
+                .iter()
+                .filter(|s| s.contains("isa in_environment"))
+                .count(),
+            in_environment
+        );
+    }
+
+    #[test]
+    fn bert_ids_reads_escaped_values() {
+        let statement = r#"match $a has bert_id "m\"1:S0"; $b has bert_id "m:C0.1"; insert $x;"#;
+        assert_eq!(
+            bert_ids(statement).collect::<Vec<_>>(),
+            [r#"m\"1:S0"#, "m:C0.1"]
+        );
+    }
+}
@@ -6,27 +6,105 @@
 //! bert-typedb path/to/model.json
 //! bert-typedb path/to/model.json --host localhost:1729 --db bert-models
 //! bert-typedb path/to/model.json --model-name ethereum  # override file stem
+//! bert-typedb replace path/to/model.json                # delete + insert
+//! bert-typedb sync path/to/model.json --previous old.json
+//! bert-typedb delete bitcoin                            # remove a model
+//! bert-typedb pull bitcoin -o bitcoin.json              # read a model back
+//! bert-typedb query archetype Economy                   # canned queries
+//! bert-typedb query depth 2 --under bitcoin:S0 --json
+//! bert-typedb dump path/to/model.json -o model.tql      # no server needed
+//! bert-typedb dump assets/models/examples -o dumps/     # one script per model
+//! bert-typedb batch assets/models 'more/**/*.json' --report report.json
+//! bert-typedb batch assets/models --dry-run             # validate and count
 //! ```
 //!
 //! Connects to the target TypeDB instance, creates the database if
 //! missing, loads the BERT schema (idempotent), transpiles the JSON
 //! model, and pushes all statements in one Write transaction.
+//!
+//! A plain push fails if the model is already in the database. `replace`
+//! deletes its namespace first, in the same transaction; `sync` re-inserts
+//! only what changed since `--previous`, the file that was last pushed —
+//! see [`bert_typedb::lifecycle`].
+//!
+//! `pull` reads a model back out of the database and writes it as a model
+//! file — see [`bert_typedb::read`].
+//!
+//! `query` runs one of the canned cross-model queries and prints a table or
+//! JSON — see [`bert_typedb::query`].
+//!
+//! `batch` pushes many models — files, directories, glob patterns — each in
+//! its own transaction, a few at a time. Every model is validated before the
+//! first push, failures do not stop the rest, and it ends with a summary
+//! table and, with `--report`, a JSON report — see [`bert_typedb::batch`].
+//!
+//! `dump` writes the same work as a TypeDB console script instead, without
+//! connecting anywhere — see [`bert_typedb::dump`].
 
-use bert::bevy_app::data_model::WorldModel;
-use bert_typedb::{transpile_and_push, Transpiler};
-use clap::Parser;
-use std::path::PathBuf;
+use bert_core::{
+    migrate, to_canonical_json, HcgsArchetype, ProcessPrimitive, SubstanceType, WorldModel,
+};
+use bert_typedb::query::{
+    AgentsWithPrimitive, EquivalentExternals, FlowsAcrossBoundary, SubsystemsAtDepth,
+    SystemsByArchetype,
+};
+use bert_typedb::{
+    collect_models, delete_model, dump_script, prepare, read_model, render_table, report_json,
+    run_query, transpile_and_push, transpile_and_replace, transpile_and_sync, transpile_batch,
+    CannedQuery, DumpOptions, Prepared, Row, Transpiler,
+};
+use clap::{Parser, Subcommand};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(
     name = "bert-typedb",
     about = "Transpile a BERT JSON model into a TypeDB typed graph",
-    version
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    push: PushArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write the schema and insert statements as a TypeDB console script,
+    /// without connecting to a server.
+    Dump(DumpArgs),
+    /// Push a model, replacing whatever its namespace holds.
+    Replace(PushArgs),
+    /// Push only the changes between the previously pushed file and this one.
+    Sync(SyncArgs),
+    /// Remove a model's namespace from the database.
+    Delete(DeleteArgs),
+    /// Read a model back from the database as a BERT JSON model file.
+    Pull(PullArgs),
+    /// Run a canned cross-model query.
+    Query(QueryArgs),
+    /// Push many models, each in its own transaction.
+    Batch(BatchArgs),
+}
+
+/// How a push treats what the database already holds for the model.
+enum Mode {
+    Insert,
+    Replace,
+    Sync { previous: PathBuf },
+}
+
+#[derive(clap::Args, Debug)]
+struct PushArgs {
     /// Path to a BERT JSON model file (e.g. bitcoin.json).
-    path: PathBuf,
+    #[arg(required = true)]
+    path: Option<PathBuf>,
 
     /// TypeDB server address.
     #[arg(long, default_value = "localhost:1729")]
@@ -46,10 +124,196 @@ struct Cli {
     skip_schema: bool,
 }
 
+#[derive(clap::Args, Debug)]
+struct SyncArgs {
+    #[command(flatten)]
+    push: PushArgs,
+
+    /// The model file as it was last pushed under the same model name.
+    #[arg(long)]
+    previous: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+struct DeleteArgs {
+    /// Model name whose `bert_id` namespace is removed.
+    model_name: String,
+
+    /// TypeDB server address.
+    #[arg(long, default_value = "localhost:1729")]
+    host: String,
+
+    /// TypeDB database name.
+    #[arg(long, default_value = "bert-models")]
+    db: String,
+}
+
+#[derive(clap::Args, Debug)]
+struct PullArgs {
+    /// Model name whose `bert_id` namespace is read.
+    model_name: String,
+
+    /// Output file. The model goes to stdout when omitted.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// TypeDB server address.
+    #[arg(long, default_value = "localhost:1729")]
+    host: String,
+
+    /// TypeDB database name.
+    #[arg(long, default_value = "bert-models")]
+    db: String,
+}
+
+#[derive(clap::Args, Debug)]
+struct QueryArgs {
+    #[command(subcommand)]
+    query: QueryCommand,
+
+    /// Print a JSON array instead of a table.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// TypeDB server address.
+    #[arg(long, global = true, default_value = "localhost:1729")]
+    host: String,
+
+    /// TypeDB database name.
+    #[arg(long, global = true, default_value = "bert-models")]
+    db: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum QueryCommand {
+    /// Systems of one HCGS archetype (Governance, Economy, Agent).
+    Archetype {
+        #[arg(value_parser = vocabulary::<HcgsArchetype>)]
+        archetype: HcgsArchetype,
+        /// Only this model.
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Flows of one substance type (Energy, Material, Message) crossing a
+    /// system boundary.
+    BoundaryFlows {
+        #[arg(value_parser = vocabulary::<SubstanceType>)]
+        substance: SubstanceType,
+        /// Only this model.
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Agents that list one process primitive (e.g. Sensing).
+    Primitive {
+        #[arg(value_parser = vocabulary::<ProcessPrimitive>)]
+        primitive: ProcessPrimitive,
+        /// Only this model.
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Pairs of equivalent external entities.
+    Equivalents {
+        /// Leave out pairs within one model.
+        #[arg(long)]
+        across_models: bool,
+    },
+    /// Systems a number of composition steps below each model's root.
+    Depth {
+        depth: usize,
+        /// Start from this system instead, e.g. `bitcoin:C0.1`.
+        #[arg(long)]
+        under: Option<String>,
+        /// Only this model.
+        #[arg(long)]
+        model: Option<String>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct BatchArgs {
+    /// Model files, directories to search recursively, or quoted glob
+    /// patterns such as 'models/**/*.json'. Each file stem is its model name.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// TypeDB server address.
+    #[arg(long, default_value = "localhost:1729")]
+    host: String,
+
+    /// TypeDB database name. Created if missing.
+    #[arg(long, default_value = "bert-models")]
+    db: String,
+
+    /// Skip the schema load step (useful if schema is already current).
+    #[arg(long)]
+    skip_schema: bool,
+
+    /// Replace each model's namespace instead of failing on one that is
+    /// already in the database.
+    #[arg(long)]
+    replace: bool,
+
+    /// Models pushed at once.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+
+    /// Write the JSON report to this file.
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Only validate and count statements, without connecting to a server.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct DumpArgs {
+    /// A BERT JSON model file, or a directory whose `*.json` models, found
+    /// the way `batch` finds them, are each dumped to `<stem>.tql`.
+    path: PathBuf,
+
+    /// Output file, or directory in directory mode (created if missing).
+    /// A single model goes to stdout when omitted.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Database the script loads into.
+    #[arg(long, default_value = "bert-models")]
+    db: String,
+
+    /// Model name used as the `bert_id` namespace prefix. Defaults to the
+    /// JSON file stem; not allowed in directory mode.
+    #[arg(long)]
+    model_name: Option<String>,
+
+    /// Leave out `database create`, to load into an existing database.
+    #[arg(long)]
+    no_create_database: bool,
+
+    /// Leave out the schema definition.
+    #[arg(long)]
+    skip_schema: bool,
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     let cli = Cli::parse();
-    match run(cli).await {
+    let result = match cli.command {
+        Some(Command::Dump(args)) => dump(args),
+        Some(Command::Replace(args)) => run(args, Mode::Replace).await,
+        Some(Command::Sync(args)) => {
+            let mode = Mode::Sync {
+                previous: args.previous,
+            };
+            run(args.push, mode).await
+        }
+        Some(Command::Delete(args)) => delete(args).await,
+        Some(Command::Pull(args)) => pull(args).await,
+        Some(Command::Query(args)) => query(args).await,
+        Some(Command::Batch(args)) => batch(args).await,
+        None => run(cli.push, Mode::Insert).await,
+    };
+    match result {
         Ok(()) => ExitCode::SUCCESS,
         Err(msg) => {
             eprintln!("bert-typedb: error: {msg}");
@@ -58,24 +322,14 @@ async fn main() -> ExitCode {
     }
 }
 
-async fn run(cli: Cli) -> Result<(), String> {
-    // Derive model_name from file stem if not provided.
+async fn run(cli: PushArgs, mode: Mode) -> Result<(), String> {
+    let path = cli.path.clone().ok_or("no model path given")?;
     let model_name = cli
         .model_name
         .clone()
-        .or_else(|| {
-            cli.path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .map(String::from)
-        })
-        .ok_or("unable to derive model_name from path; pass --model-name")?;
+        .map_or_else(|| model_name_from(&path), Ok)?;
 
-    eprintln!("bert-typedb: loading {}", cli.path.display());
-    let bytes = std::fs::read(&cli.path)
-        .map_err(|e| format!("failed to read {}: {e}", cli.path.display()))?;
-    let model: WorldModel = serde_json::from_slice(&bytes)
-        .map_err(|e| format!("failed to parse {}: {e}", cli.path.display()))?;
+    let model = load(&path)?;
     eprintln!(
         "  parsed: {} systems, {} interactions, {} external entities",
         model.systems.len(),
@@ -100,7 +354,35 @@ async fn run(cli: Cli) -> Result<(), String> {
         "bert-typedb: transpiling model '{}' into {}",
         model_name, cli.db
     );
-    let summary = transpile_and_push(&model, &model_name, &t)
+    let summary = match &mode {
+        Mode::Insert => transpile_and_push(&model, &model_name, &t).await,
+        Mode::Replace => transpile_and_replace(&model, &model_name, &t).await,
+        Mode::Sync { previous } => {
+            let previous = load(previous)?;
+            transpile_and_sync(&previous, &model, &model_name, &t).await
+        }
+    }
+    .map_err(|e| format!("{e}"))?;
+
+    println!(
+        "✓ {} statements executed in {:?}",
+        summary.statements_executed, summary.elapsed
+    );
+
+    Ok(())
+}
+
+async fn delete(args: DeleteArgs) -> Result<(), String> {
+    eprintln!("bert-typedb: connecting to {}", args.host);
+    let t = Transpiler::connect(&args.host, &args.db)
+        .await
+        .map_err(|e| format!("{e}"))?;
+
+    eprintln!(
+        "bert-typedb: deleting model '{}' from {}",
+        args.model_name, args.db
+    );
+    let summary = delete_model(&args.model_name, &t)
         .await
         .map_err(|e| format!("{e}"))?;
 
@@ -111,3 +393,226 @@ async fn run(cli: Cli) -> Result<(), String> {
 
     Ok(())
 }
+
+async fn pull(args: PullArgs) -> Result<(), String> {
+    eprintln!("bert-typedb: connecting to {}", args.host);
+    let t = Transpiler::connect(&args.host, &args.db)
+        .await
+        .map_err(|e| format!("{e}"))?;
+
+    eprintln!(
+        "bert-typedb: reading model '{}' from {}",
+        args.model_name, args.db
+    );
+    let model = read_model(&args.model_name, &t)
+        .await
+        .map_err(|e| format!("{e}"))?;
+    eprintln!(
+        "  read: {} systems, {} interactions, {} external entities",
+        model.systems.len(),
+        model.interactions.len(),
+        model.environment.sources.len() + model.environment.sinks.len()
+    );
+
+    let json = to_canonical_json(&model);
+    match &args.output {
+        Some(output) => write(output, &json),
+        None => {
+            print!("{json}");
+            Ok(())
+        }
+    }
+}
+
+async fn query(args: QueryArgs) -> Result<(), String> {
+    eprintln!("bert-typedb: connecting to {}", args.host);
+    let t = Transpiler::connect(&args.host, &args.db)
+        .await
+        .map_err(|e| format!("{e}"))?;
+
+    match args.query {
+        QueryCommand::Archetype { archetype, model } => {
+            print_rows(&SystemsByArchetype { archetype, model }, &t, args.json).await
+        }
+        QueryCommand::BoundaryFlows { substance, model } => {
+            print_rows(&FlowsAcrossBoundary { substance, model }, &t, args.json).await
+        }
+        QueryCommand::Primitive { primitive, model } => {
+            print_rows(&AgentsWithPrimitive { primitive, model }, &t, args.json).await
+        }
+        QueryCommand::Equivalents { across_models } => {
+            print_rows(&EquivalentExternals { across_models }, &t, args.json).await
+        }
+        QueryCommand::Depth {
+            depth,
+            under,
+            model,
+        } => {
+            let query = SubsystemsAtDepth {
+                depth,
+                under,
+                model,
+            };
+            print_rows(&query, &t, args.json).await
+        }
+    }
+}
+
+async fn print_rows<Q: CannedQuery>(query: &Q, t: &Transpiler, json: bool) -> Result<(), String> {
+    let rows = run_query(query, t).await.map_err(|e| format!("{e}"))?;
+    if json {
+        let rows: Vec<_> = rows.iter().map(Row::to_json).collect();
+        println!("{:#}", serde_json::Value::Array(rows));
+    } else {
+        print!("{}", render_table(&rows));
+        eprintln!("bert-typedb: {} row(s)", rows.len());
+    }
+    Ok(())
+}
+
+async fn batch(args: BatchArgs) -> Result<(), String> {
+    let files = collect_models(&args.paths).map_err(|e| format!("{e}"))?;
+    if files.is_empty() {
+        return Err("no model files found".into());
+    }
+
+    eprintln!("bert-typedb: validating {} model(s)", files.len());
+    let prepared = prepare(&files, args.replace);
+    let valid = prepared.iter().filter(|p| p.statements.is_ok()).count();
+
+    let reports = if args.dry_run || valid == 0 {
+        prepared.into_iter().map(Prepared::unpushed).collect()
+    } else {
+        eprintln!("bert-typedb: connecting to {}", args.host);
+        let t = Transpiler::connect(&args.host, &args.db)
+            .await
+            .map_err(|e| format!("{e}"))?;
+
+        eprintln!("bert-typedb: ensuring database '{}'", args.db);
+        t.ensure_database().await.map_err(|e| format!("{e}"))?;
+
+        if !args.skip_schema {
+            eprintln!("bert-typedb: loading schema (idempotent)");
+            t.load_schema().await.map_err(|e| format!("{e}"))?;
+        }
+
+        eprintln!(
+            "bert-typedb: pushing {valid} model(s) into {}, {} at a time",
+            args.db, args.jobs
+        );
+        transpile_batch(prepared, args.jobs as usize, &t).await
+    };
+
+    print!("{}", render_table(&reports));
+    for report in &reports {
+        for error in &report.errors {
+            eprintln!("  {}: {error}", report.path.display());
+        }
+    }
+    if let Some(path) = &args.report {
+        write(path, &format!("{:#}\n", report_json(&reports)))?;
+    }
+
+    let failed = reports.iter().filter(|r| r.status.is_failure()).count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} models failed", reports.len()));
+    }
+    Ok(())
+}
+
+/// A controlled-vocabulary value by its model-file spelling, e.g. `Economy`.
+fn vocabulary<T: serde::de::DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|e| format!("{e}"))
+}
+
+fn dump(args: DumpArgs) -> Result<(), String> {
+    let options = DumpOptions {
+        database: args.db.clone(),
+        create_database: !args.no_create_database,
+        schema: !args.skip_schema,
+    };
+
+    if !args.path.is_dir() {
+        let model_name = args
+            .model_name
+            .clone()
+            .map_or_else(|| model_name_from(&args.path), Ok)?;
+        let model = load(&args.path)?;
+        let script = dump_script(&model, &model_name, &options).map_err(|e| format!("{e}"))?;
+        return match &args.output {
+            Some(output) => write(output, &script),
+            None => {
+                print!("{script}");
+                Ok(())
+            }
+        };
+    }
+
+    if args.model_name.is_some() {
+        return Err("--model-name names one model; directory mode uses each file stem".into());
+    }
+    let output = args
+        .output
+        .as_ref()
+        .ok_or("directory mode needs an output directory (-o)")?;
+    // The files `batch` would push, sorted, so a failing directory fails on
+    // the same file every time.
+    let models = collect_models(std::slice::from_ref(&args.path)).map_err(|e| format!("{e}"))?;
+    let mut names: BTreeMap<String, &Path> = BTreeMap::new();
+    for path in &models {
+        let model_name = model_name_from(path)?;
+        if let Some(other) = names.insert(model_name.clone(), path) {
+            return Err(format!(
+                "{} and {} would both be dumped to {model_name}.tql",
+                other.display(),
+                path.display()
+            ));
+        }
+    }
+    std::fs::create_dir_all(output)
+        .map_err(|e| format!("failed to create {}: {e}", output.display()))?;
+    for (model_name, path) in names {
+        let model = load(path)?;
+        let script = dump_script(&model, &model_name, &options)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        write(&output.join(format!("{model_name}.tql")), &script)?;
+    }
+    eprintln!(
+        "bert-typedb: dumped {} model(s) into {}",
+        models.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Derive the model name from the file stem.
+fn model_name_from(path: &Path) -> Result<String, String> {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .map(String::from)
+        .ok_or_else(|| {
+            format!(
+                "unable to derive model_name from {}; pass --model-name",
+                path.display()
+            )
+        })
+}
+
+fn load(path: &Path) -> Result<WorldModel, String> {
+    eprintln!("bert-typedb: loading {}", path.display());
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let (model, report) = migrate::from_slice(&bytes)
+        .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+    if !report.is_noop() {
+        eprintln!(
+            "  migrated: version {} → {}",
+            report.original_version, report.final_version
+        );
+    }
+    Ok(model)
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
@@ -0,0 +1,631 @@
+//! Canned cross-model queries over the typed graph.
+//!
+//! Each query is a struct holding its parameters. [`CannedQuery::typeql`]
+//! renders the `fetch` query and [`CannedQuery::rows`] reads its answers
+//! into typed rows, both pure — [`crate::run_query`] runs them. Every query
+//! spans all models in the database unless given a `model` to stay in.
+//!
+//! Rows come back sorted and deduplicated, so output is stable across runs
+//! whatever order TypeDB answers in. See `docs/typedb-query-patterns.md` for
+//! the hand-written patterns these grew out of.
+
+use crate::error::{TranspilerError, TranspilerResult};
+use crate::escape::escape_typeql_string;
+use crate::insert::{archetype_str, process_primitive_str, substance_type_str};
+use crate::lifecycle::namespace_pattern;
+use bert_core::{HcgsArchetype, ProcessPrimitive, SubstanceType};
+use serde_json::{json, Value};
+
+/// A parameterised query and how to read its answers.
+pub trait CannedQuery {
+    type Row: Row;
+
+    /// The TypeQL `fetch` query.
+    fn typeql(&self) -> String;
+
+    /// Rows from the documents the query returned, sorted and deduplicated.
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<Self::Row>>;
+}
+
+/// One answer, printable as a table row or a JSON object.
+pub trait Row: Ord {
+    /// Column headings, in [`Row::cells`] order.
+    const COLUMNS: &'static [&'static str];
+
+    fn cells(&self) -> Vec<String>;
+
+    fn to_json(&self) -> Value;
+}
+
+/// Systems of one HCGS archetype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemsByArchetype {
+    pub archetype: HcgsArchetype,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArchetypeSystem {
+    pub model: String,
+    pub id: String,
+    pub name: String,
+    pub level: i64,
+}
+
+impl CannedQuery for SystemsByArchetype {
+    type Row = ArchetypeSystem;
+
+    fn typeql(&self) -> String {
+        format!(
+            r#"match $s isa system, has archetype "{archetype}", has bert_id $id, has display_name $name, has system_level $level;{scope} fetch {{ "id": $id, "name": $name, "level": $level }};"#,
+            archetype = archetype_str(self.archetype),
+            scope = scope("$id", self.model.as_deref()),
+        )
+    }
+
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<ArchetypeSystem>> {
+        collect(documents, |doc| {
+            let (model, id) = split_id(&text(doc, "id")?)?;
+            Ok(ArchetypeSystem {
+                model,
+                id,
+                name: text(doc, "name")?,
+                level: integer(doc, "level")?,
+            })
+        })
+    }
+}
+
+impl Row for ArchetypeSystem {
+    const COLUMNS: &'static [&'static str] = &["model", "id", "name", "level"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.model.clone(),
+            self.id.clone(),
+            self.name.clone(),
+            self.level.to_string(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({ "model": self.model, "id": self.id, "name": self.name, "level": self.level })
+    }
+}
+
+/// Interactions of one substance type that cross a system's boundary: routed
+/// through an interface on it, or exchanged between the system and an
+/// external entity in its environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowsAcrossBoundary {
+    pub substance: SubstanceType,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BoundaryFlow {
+    pub model: String,
+    /// The system whose boundary is crossed.
+    pub system: String,
+    pub system_name: String,
+    pub flow: String,
+    pub flow_name: String,
+    pub sub_type: String,
+}
+
+impl CannedQuery for FlowsAcrossBoundary {
+    type Row = BoundaryFlow;
+
+    fn typeql(&self) -> String {
+        format!(
+            concat!(
+                r#"match $f isa interaction, has substance_type "{substance}", has substance_sub_type $sub, has bert_id $fid, has display_name $fname; "#,
+                r#"$s isa system, has bert_id $sid, has display_name $sname; "#,
+                r#"{{ (interaction: $f, interface: $i) isa routes_through; (boundary: $b, interface: $i) isa has_interface; (enclosed: $s, enclosure: $b) isa has_boundary; }} or "#,
+                r#"{{ $e isa external_entity; (participant: $e, interaction: $f) isa participates_in; (participant: $s, interaction: $f) isa participates_in; }};"#,
+                r#"{scope} fetch {{ "flow": $fid, "flow_name": $fname, "sub_type": $sub, "system": $sid, "system_name": $sname }};"#,
+            ),
+            substance = substance_type_str(self.substance),
+            scope = scope("$fid", self.model.as_deref()),
+        )
+    }
+
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<BoundaryFlow>> {
+        collect(documents, |doc| {
+            let (model, flow) = split_id(&text(doc, "flow")?)?;
+            let (_, system) = split_id(&text(doc, "system")?)?;
+            Ok(BoundaryFlow {
+                model,
+                system,
+                system_name: text(doc, "system_name")?,
+                flow,
+                flow_name: text(doc, "flow_name")?,
+                sub_type: text(doc, "sub_type")?,
+            })
+        })
+    }
+}
+
+impl Row for BoundaryFlow {
+    const COLUMNS: &'static [&'static str] = &[
+        "model",
+        "system",
+        "system name",
+        "flow",
+        "flow name",
+        "sub type",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.model.clone(),
+            self.system.clone(),
+            self.system_name.clone(),
+            self.flow.clone(),
+            self.flow_name.clone(),
+            self.sub_type.clone(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "model": self.model,
+            "system": self.system,
+            "system_name": self.system_name,
+            "flow": self.flow,
+            "flow_name": self.flow_name,
+            "sub_type": self.sub_type,
+        })
+    }
+}
+
+/// Agents whose model lists one process primitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentsWithPrimitive {
+    pub primitive: ProcessPrimitive,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrimitiveAgent {
+    pub model: String,
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+}
+
+impl CannedQuery for AgentsWithPrimitive {
+    type Row = PrimitiveAgent;
+
+    fn typeql(&self) -> String {
+        format!(
+            concat!(
+                r#"match $s isa system, has bert_id $id, has display_name $name; "#,
+                r#"(system: $s, config: $a) isa has_agent_config; $a has agent_kind $kind; "#,
+                r#"(agent: $a, primitive: $p) isa has_primitive; $p has process_primitive "{primitive}";"#,
+                r#"{scope} fetch {{ "id": $id, "name": $name, "kind": $kind }};"#,
+            ),
+            primitive = process_primitive_str(self.primitive),
+            scope = scope("$id", self.model.as_deref()),
+        )
+    }
+
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<PrimitiveAgent>> {
+        collect(documents, |doc| {
+            let (model, id) = split_id(&text(doc, "id")?)?;
+            Ok(PrimitiveAgent {
+                model,
+                id,
+                name: text(doc, "name")?,
+                kind: text(doc, "kind")?,
+            })
+        })
+    }
+}
+
+impl Row for PrimitiveAgent {
+    const COLUMNS: &'static [&'static str] = &["model", "id", "name", "kind"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.model.clone(),
+            self.id.clone(),
+            self.name.clone(),
+            self.kind.clone(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({ "model": self.model, "id": self.id, "name": self.name, "kind": self.kind })
+    }
+}
+
+/// Pairs of equivalent external entities: declared equivalent (the schema's
+/// `cross_model_equivalents` function), or sharing a non-empty
+/// `equivalence_class` — the only link between entities of different models.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquivalentExternals {
+    /// Drop pairs within one model.
+    pub across_models: bool,
+}
+
+/// A pair ordered so that `left` sorts before `right`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EquivalentPair {
+    pub left_model: String,
+    pub left: String,
+    pub left_name: String,
+    pub right_model: String,
+    pub right: String,
+    pub right_name: String,
+}
+
+impl CannedQuery for EquivalentExternals {
+    type Row = EquivalentPair;
+
+    fn typeql(&self) -> String {
+        concat!(
+            r#"match $a isa external_entity, has bert_id $aid, has display_name $aname; "#,
+            r#"$b isa external_entity, has bert_id $bid, has display_name $bname; "#,
+            r#"{ let $a, $b in cross_model_equivalents(); } or "#,
+            r#"{ $a has equivalence_class $c; $b has equivalence_class $c; $c != ""; $aid != $bid; }; "#,
+            r#"fetch { "left": $aid, "left_name": $aname, "right": $bid, "right_name": $bname };"#,
+        )
+        .to_string()
+    }
+
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<EquivalentPair>> {
+        let pairs = collect(documents, |doc| {
+            let mut ends = [
+                (split_id(&text(doc, "left")?)?, text(doc, "left_name")?),
+                (split_id(&text(doc, "right")?)?, text(doc, "right_name")?),
+            ];
+            // Both orders come back; keep one.
+            ends.sort();
+            let [((left_model, left), left_name), ((right_model, right), right_name)] = ends;
+            Ok(EquivalentPair {
+                left_model,
+                left,
+                left_name,
+                right_model,
+                right,
+                right_name,
+            })
+        })?;
+        Ok(pairs
+            .into_iter()
+            .filter(|p| !self.across_models || p.left_model != p.right_model)
+            .collect())
+    }
+}
+
+impl Row for EquivalentPair {
+    const COLUMNS: &'static [&'static str] = &["model", "id", "name", "model", "id", "name"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.left_model.clone(),
+            self.left.clone(),
+            self.left_name.clone(),
+            self.right_model.clone(),
+            self.right.clone(),
+            self.right_name.clone(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "left": { "model": self.left_model, "id": self.left, "name": self.left_name },
+            "right": { "model": self.right_model, "id": self.right, "name": self.right_name },
+        })
+    }
+}
+
+/// Systems `depth` composition steps below a root: every model's root
+/// system (the one that is nobody's part), or the system `under`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsystemsAtDepth {
+    pub depth: usize,
+    /// A namespaced `bert_id`, e.g. `bitcoin:C0.1`.
+    pub under: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DepthSystem {
+    pub model: String,
+    pub id: String,
+    pub name: String,
+    pub level: i64,
+    /// The system one step up, unless `depth` is 0.
+    pub parent: Option<String>,
+}
+
+impl CannedQuery for SubsystemsAtDepth {
+    type Row = DepthSystem;
+
+    fn typeql(&self) -> String {
+        let root = match &self.under {
+            Some(id) => format!(
+                r#"$s0 isa system, has bert_id "{}";"#,
+                escape_typeql_string(id)
+            ),
+            None => "$s0 isa system; not { (part: $s0) isa composition; };".to_string(),
+        };
+        let steps: String = (1..=self.depth)
+            .map(|n| format!(" (whole: $s{}, part: $s{n}) isa composition;", n - 1))
+            .collect();
+        let (parent, parent_field) = match self.depth {
+            0 => (String::new(), ""),
+            n => (
+                format!(" $s{} has bert_id $parent;", n - 1),
+                r#" "parent": $parent,"#,
+            ),
+        };
+        format!(
+            r#"match {root}{steps}{parent} $s{n} has bert_id $id, has display_name $name, has system_level $level;{scope} fetch {{{parent_field} "id": $id, "name": $name, "level": $level }};"#,
+            n = self.depth,
+            scope = scope("$id", self.model.as_deref()),
+        )
+    }
+
+    fn rows(&self, documents: &[Value]) -> TranspilerResult<Vec<DepthSystem>> {
+        collect(documents, |doc| {
+            let (model, id) = split_id(&text(doc, "id")?)?;
+            let parent = match doc.get("parent") {
+                Some(_) => Some(split_id(&text(doc, "parent")?)?.1),
+                None => None,
+            };
+            Ok(DepthSystem {
+                model,
+                id,
+                name: text(doc, "name")?,
+                level: integer(doc, "level")?,
+                parent,
+            })
+        })
+    }
+}
+
+impl Row for DepthSystem {
+    const COLUMNS: &'static [&'static str] = &["model", "id", "name", "level", "parent"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.model.clone(),
+            self.id.clone(),
+            self.name.clone(),
+            self.level.to_string(),
+            self.parent.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "model": self.model,
+            "id": self.id,
+            "name": self.name,
+            "level": self.level,
+            "parent": self.parent,
+        })
+    }
+}
+
+/// `rows` as a plain-text table: a header, a rule, and one line per row,
+/// each column as wide as its widest cell.
+pub fn render_table<R: Row>(rows: &[R]) -> String {
+    let cells: Vec<Vec<String>> = rows.iter().map(Row::cells).collect();
+    let widths: Vec<usize> = R::COLUMNS
+        .iter()
+        .enumerate()
+        .map(|(i, heading)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([heading.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |row: &[String]| {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        padded.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let header: Vec<String> = R::COLUMNS.iter().map(|c| c.to_string()).collect();
+    let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    let mut out = line(&header) + &line(&rule);
+    for row in &cells {
+        out += &line(row);
+    }
+    out
+}
+
+/// ` $var like "^model:";`, or nothing without a model.
+fn scope(var: &str, model: Option<&str>) -> String {
+    model.map_or_else(String::new, |m| {
+        format!(r#" {var} like "{}";"#, namespace_pattern(m))
+    })
+}
+
+fn collect<R: Ord>(
+    documents: &[Value],
+    row: impl Fn(&Value) -> TranspilerResult<R>,
+) -> TranspilerResult<Vec<R>> {
+    let mut rows = documents
+        .iter()
+        .map(row)
+        .collect::<TranspilerResult<Vec<_>>>()?;
+    rows.sort();
+    rows.dedup();
+    Ok(rows)
+}
+
+/// A `bert_id` as (model name, local id). Local ids never contain `:`.
+fn split_id(bert_id: &str) -> TranspilerResult<(String, String)> {
+    bert_id
+        .rsplit_once(':')
+        .map(|(model, local)| (model.to_string(), local.to_string()))
+        .ok_or_else(|| TranspilerError::Internal(format!("bert_id {bert_id} has no namespace")))
+}
+
+fn text(doc: &Value, key: &str) -> TranspilerResult<String> {
+    doc[key]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| missing(key))
+}
+
+fn integer(doc: &Value, key: &str) -> TranspilerResult<i64> {
+    doc[key]
+        .as_i64()
+        .or_else(|| doc[key].as_f64().map(|f| f as i64))
+        .ok_or_else(|| missing(key))
+}
+
+fn missing(field: &str) -> TranspilerError {
+    TranspilerError::MissingRequiredField {
+        entity: "query answer".to_string(),
+        field: field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archetype_query_filters_and_scopes() {
+        let q = SystemsByArchetype {
+            archetype: HcgsArchetype::Economy,
+            model: None,
+        };
+        assert!(q.typeql().contains(r#"has archetype "Economy""#));
+        assert!(!q.typeql().contains("like"));
+
+        let q = SystemsByArchetype {
+            model: Some("bitcoin".to_string()),
+            ..q
+        };
+        assert!(q.typeql().contains(r#"$id like "^bitcoin:";"#));
+    }
+
+    #[test]
+    fn rows_are_split_sorted_and_deduplicated() {
+        let q = SystemsByArchetype {
+            archetype: HcgsArchetype::Agent,
+            model: None,
+        };
+        let docs = [
+            json!({ "id": "solana:C0.2", "name": "Validators", "level": 1 }),
+            json!({ "id": "bitcoin:C0.1", "name": "Miners", "level": 1.0 }),
+            json!({ "id": "solana:C0.2", "name": "Validators", "level": 1 }),
+        ];
+        let rows = q.rows(&docs).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].model, "bitcoin");
+        assert_eq!(rows[0].id, "C0.1");
+        assert_eq!(rows[0].level, 1);
+    }
+
+    #[test]
+    fn missing_fields_are_reported() {
+        let q = AgentsWithPrimitive {
+            primitive: ProcessPrimitive::Sensing,
+            model: None,
+        };
+        assert!(q.typeql().contains(r#"has process_primitive "Sensing""#));
+        assert!(matches!(
+            q.rows(&[json!({ "id": "m:S0", "name": "A" })]),
+            Err(TranspilerError::MissingRequiredField { field, .. }) if field == "kind"
+        ));
+    }
+
+    #[test]
+    fn boundary_flows_cover_both_crossings() {
+        let q = FlowsAcrossBoundary {
+            substance: SubstanceType::Energy,
+            model: Some("m".to_string()),
+        };
+        let typeql = q.typeql();
+        assert!(typeql.contains(r#"has substance_type "Energy""#));
+        assert!(typeql.contains("isa routes_through"));
+        assert!(typeql.contains("} or {"));
+        assert!(typeql.contains(r#"$fid like "^m:";"#));
+    }
+
+    #[test]
+    fn equivalent_pairs_keep_one_order() {
+        let docs = [
+            json!({ "left": "b:Src-1.0", "left_name": "Users", "right": "a:Snk-1.1", "right_name": "Users" }),
+            json!({ "left": "a:Snk-1.1", "left_name": "Users", "right": "b:Src-1.0", "right_name": "Users" }),
+            json!({ "left": "a:Src-1.0", "left_name": "Users", "right": "a:Snk-1.1", "right_name": "Users" }),
+        ];
+        let all = EquivalentExternals {
+            across_models: false,
+        };
+        let rows = all.rows(&docs).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            (rows[0].left_model.as_str(), rows[0].left.as_str()),
+            ("a", "Snk-1.1")
+        );
+
+        let across = EquivalentExternals {
+            across_models: true,
+        };
+        let rows = across.rows(&docs).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].right_model, "b");
+    }
+
+    #[test]
+    fn depth_chains_composition_steps() {
+        let q = SubsystemsAtDepth {
+            depth: 2,
+            under: None,
+            model: None,
+        };
+        let typeql = q.typeql();
+        assert!(typeql.contains("not { (part: $s0) isa composition; };"));
+        assert!(typeql.contains("(whole: $s0, part: $s1) isa composition;"));
+        assert!(typeql.contains("(whole: $s1, part: $s2) isa composition;"));
+        assert!(typeql.contains("$s1 has bert_id $parent;"));
+        assert!(typeql.contains("$s2 has bert_id $id"));
+
+        let root = SubsystemsAtDepth {
+            depth: 0,
+            under: Some("bitcoin:S0".to_string()),
+            model: None,
+        };
+        let typeql = root.typeql();
+        assert!(typeql.contains(r#"$s0 isa system, has bert_id "bitcoin:S0";"#));
+        assert!(!typeql.contains("composition"));
+        assert!(!typeql.contains("parent"));
+
+        let rows = q
+            .rows(&[json!({ "parent": "m:C0.1", "id": "m:C0.1.0", "name": "", "level": 2 })])
+            .unwrap();
+        assert_eq!(rows[0].parent.as_deref(), Some("C0.1"));
+    }
+
+    #[test]
+    fn table_pads_columns_to_the_widest_cell() {
+        let rows = [PrimitiveAgent {
+            model: "bitcoin".to_string(),
+            id: "C0.1".to_string(),
+            name: "Miners".to_string(),
+            kind: "Reactive".to_string(),
+        }];
+        assert_eq!(
+            render_table(&rows),
+            "model    id    name    kind\n\
+             -------  ----  ------  --------\n\
+             bitcoin  C0.1  Miners  Reactive\n"
+        );
+    }
+}
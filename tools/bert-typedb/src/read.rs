@@ -0,0 +1,887 @@
+//! TypeDB → WorldModel: read a namespaced model back out of the graph.
+//!
+//! The inverse of [`model_to_typeql`](crate::model_to_typeql). [`read_queries`]
+//! lists one `fetch` query per entity type and relation the transpiler writes,
+//! each scoped to the model's `bert_id` namespace; [`assemble`] puts the
+//! documents they return back together in the shape of a model file and
+//! deserializes that, so a read model gets the same defaults as a loaded one.
+//! Both are pure — [`crate::read_model`] runs the queries.
+//!
+//! Whatever the transpiler does not store (see the deferrals in
+//! [`crate::insert`]) comes back empty or in its stored form: no smart
+//! parameters, no agent process configs, no system-local sources and sinks.
+//!
+//! # Order
+//!
+//! Fetch answers come in no particular order. Elements are sorted by id, as
+//! [`bert_core::canonicalize`] sorts them; parameters and primitives by the
+//! `list_index` they were written with.
+
+use crate::error::{TranspilerError, TranspilerResult};
+use crate::escape::escape_typeql_string;
+use crate::lifecycle::namespace_pattern;
+use bert_core::{Id, WorldModel};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// One thing [`read_queries`] fetches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    Model,
+    Systems,
+    ExternalEntities,
+    Boundaries,
+    Interfaces,
+    Interactions,
+    Composition,
+    HasBoundary,
+    HasInterface,
+    Participation,
+    Routing,
+    PortPairing,
+    ExportsTo,
+    ReceivesFrom,
+    ParentInterface,
+    Parameters,
+    Agents,
+    Primitives,
+    CognitiveParams,
+}
+
+/// The fetched documents of each [`Part`], as JSON.
+pub type Documents = BTreeMap<Part, Vec<Value>>;
+
+/// The `fetch` queries that read the `model_name` namespace, one per [`Part`].
+pub fn read_queries(model_name: &str) -> Vec<(Part, String)> {
+    let ns = namespace_pattern(model_name);
+    let entities = |ty: &str| {
+        format!(
+            r#"match $e isa {ty}, has bert_id $id; $id like "{ns}"; fetch {{ "entity": {{ $e.* }} }};"#
+        )
+    };
+    // A relation between two keyed entities, fetched as their two ids. The
+    // namespace is checked on the `a` end, an entity of type `owner`.
+    let pair = |relation: &str, owner: &str, a: &str, b: &str| {
+        format!(
+            r#"match $a isa {owner}, has bert_id $aid; $aid like "{ns}"; $b has bert_id $bid; ({a}: $a, {b}: $b) isa {relation}; fetch {{ "{a}": $aid, "{b}": $bid }};"#
+        )
+    };
+    // The keyless entities hanging off a keyed one, fetched with its id.
+    let owned = |owner: &str, pattern: &str, key: &str| {
+        format!(
+            r#"match $o isa {owner}, has bert_id $oid; $oid like "{ns}"; {pattern} fetch {{ "owner": $oid, "{key}": {{ $p.* }} }};"#
+        )
+    };
+    let agent = "(system: $o, config: $a) isa has_agent_config;";
+
+    vec![
+        (
+            Part::Model,
+            format!(
+                r#"match $m isa bert_model, has model_name "{}"; fetch {{ "entity": {{ $m.* }} }};"#,
+                escape_typeql_string(model_name)
+            ),
+        ),
+        (Part::Systems, entities("system")),
+        (Part::ExternalEntities, entities("external_entity")),
+        (Part::Boundaries, entities("boundary")),
+        (Part::Interfaces, entities("interface")),
+        (Part::Interactions, entities("interaction")),
+        (
+            Part::Composition,
+            pair("composition", "system", "part", "whole"),
+        ),
+        (
+            Part::HasBoundary,
+            pair("has_boundary", "system", "enclosed", "enclosure"),
+        ),
+        (
+            Part::HasInterface,
+            pair("has_interface", "interface", "interface", "boundary"),
+        ),
+        (
+            Part::Participation,
+            format!(
+                r#"match $f isa interaction, has bert_id $fid; $fid like "{ns}"; $e has bert_id $eid; $r isa participates_in, links (participant: $e, interaction: $f), has participation_role $role; fetch {{ "interaction": $fid, "participant": $eid, "role": $role }};"#
+            ),
+        ),
+        (
+            Part::Routing,
+            format!(
+                r#"match $f isa interaction, has bert_id $fid; $fid like "{ns}"; $i isa interface, has bert_id $iid; $r isa routes_through, links (interaction: $f, interface: $i), has endpoint $end; fetch {{ "interaction": $fid, "interface": $iid, "endpoint": $end }};"#
+            ),
+        ),
+        (
+            Part::PortPairing,
+            pair("port_pairing", "interface", "import_side", "export_side"),
+        ),
+        (
+            Part::ExportsTo,
+            pair("exports_to", "interface", "interface", "target"),
+        ),
+        (
+            Part::ReceivesFrom,
+            pair("receives_from", "interface", "interface", "origin"),
+        ),
+        (
+            Part::ParentInterface,
+            pair("parent_interface", "boundary", "boundary", "interface"),
+        ),
+        (
+            Part::Parameters,
+            owned(
+                "interaction",
+                "(interaction: $o, param: $p) isa has_parameter;",
+                "parameter",
+            ),
+        ),
+        (
+            Part::Agents,
+            format!(
+                r#"match $o isa system, has bert_id $oid; $oid like "{ns}"; {agent} fetch {{ "owner": $oid, "agent": {{ $a.* }} }};"#
+            ),
+        ),
+        (
+            Part::Primitives,
+            owned(
+                "system",
+                &format!("{agent} (agent: $a, primitive: $p) isa has_primitive;"),
+                "primitive",
+            ),
+        ),
+        (
+            Part::CognitiveParams,
+            owned(
+                "system",
+                &format!("{agent} (agent: $a, param: $p) isa has_cognitive_param;"),
+                "param",
+            ),
+        ),
+    ]
+}
+
+/// Rebuild the `model_name` model from the documents of [`read_queries`].
+/// Fails with [`TranspilerError::ModelNotFound`] when there is no
+/// `bert_model` of that name.
+pub fn assemble(model_name: &str, documents: &Documents) -> TranspilerResult<WorldModel> {
+    let reader = Reader {
+        prefix: format!("{model_name}:"),
+        documents,
+    };
+    let model = reader
+        .docs(Part::Model)
+        .first()
+        .map(|d| Attrs::of(d, "entity"))
+        .ok_or_else(|| TranspilerError::ModelNotFound(model_name.to_string()))?;
+
+    // Relations, keyed by the local id of the element that holds the field.
+    let parent: BTreeMap<String, String> = reader.pairs(Part::Composition, "part", "whole")?;
+    let boundary_of: BTreeMap<String, String> =
+        reader.pairs(Part::HasBoundary, "enclosed", "enclosure")?;
+    let owner_of_interface: BTreeMap<String, String> =
+        reader.pairs(Part::HasInterface, "interface", "boundary")?;
+    let parent_interface: BTreeMap<String, String> =
+        reader.pairs(Part::ParentInterface, "boundary", "interface")?;
+    let mut paired_with: BTreeMap<String, String> = BTreeMap::new();
+    for (import, export) in reader.pairs(Part::PortPairing, "import_side", "export_side")? {
+        paired_with.insert(export.clone(), import.clone());
+        paired_with.insert(import, export);
+    }
+    let mut exports_to: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for doc in reader.docs(Part::ExportsTo) {
+        exports_to
+            .entry(reader.id_at(doc, "interface")?)
+            .or_default()
+            .push(reader.id_at(doc, "target")?);
+    }
+    let mut receives_from: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for doc in reader.docs(Part::ReceivesFrom) {
+        receives_from
+            .entry(reader.id_at(doc, "interface")?)
+            .or_default()
+            .push(reader.id_at(doc, "origin")?);
+    }
+
+    // Interfaces, grouped under their boundary.
+    let mut interfaces: BTreeMap<String, Vec<(Id, Value)>> = BTreeMap::new();
+    for doc in reader.docs(Part::Interfaces) {
+        let a = Attrs::of(doc, "entity");
+        let id = reader.local_id(&a)?;
+        let links = |map: &mut BTreeMap<String, Vec<String>>| {
+            let mut ids = map.remove(&id).unwrap_or_default();
+            ids.sort();
+            ids
+        };
+        let mut iface = json!({
+            "info": a.info(&id),
+            "protocol": a.str("protocol"),
+            "type": a.str("interface_type"),
+            "exports_to": links(&mut exports_to),
+            "receives_from": links(&mut receives_from),
+            "angle": a.get("interface_angle"),
+        });
+        if let Some(other) = paired_with.get(&id) {
+            iface["paired_with"] = json!(other);
+        }
+        let boundary = owner_of_interface
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| missing(&id, "has_interface"))?;
+        interfaces
+            .entry(boundary)
+            .or_default()
+            .push((parse_id(&id)?, iface));
+    }
+
+    let mut boundaries: BTreeMap<String, Value> = BTreeMap::new();
+    for doc in reader.docs(Part::Boundaries) {
+        let a = Attrs::of(doc, "entity");
+        let id = reader.local_id(&a)?;
+        let boundary = json!({
+            "info": a.info(&id),
+            "porosity": a.get("porosity"),
+            "perceptive_fuzziness": a.get("perceptive_fuzziness"),
+            "interfaces": sorted(interfaces.remove(&id).unwrap_or_default()),
+            "parent_interface": parent_interface.get(&id),
+        });
+        boundaries.insert(id, boundary);
+    }
+
+    let agents = reader.agents()?;
+    let mut systems = Vec::new();
+    for doc in reader.docs(Part::Systems) {
+        let a = Attrs::of(doc, "entity");
+        let id = reader.local_id(&a)?;
+        let boundary = boundary_of
+            .get(&id)
+            .and_then(|b| boundaries.remove(b))
+            .ok_or_else(|| missing(&id, "has_boundary"))?;
+        let complexity = match a.str("complexity_kind").as_str() {
+            "Complex" => json!({ "Complex": {
+                "adaptable": a.get("complex_adaptable"),
+                "evolveable": a.get("complex_evolveable"),
+            }}),
+            "Multiset" => json!({ "Multiset": a.get("multiset_count") }),
+            _ => json!("Atomic"),
+        };
+        let time_constant = match (a.get("time_constant"), a.get("time_constant_magnitude")) {
+            (Value::String(unit), Value::String(magnitude)) => json!(format!("{magnitude} {unit}")),
+            (unit, _) => unit,
+        };
+        let mut system = json!({
+            "info": a.info(&id),
+            "sources": [],
+            "sinks": [],
+            "parent": parent.get(&id).map_or("E-1", String::as_str),
+            "complexity": complexity,
+            "boundary": boundary,
+            "radius": a.get("radius"),
+            "transform": a.transform(),
+            "equivalence": a.str("equivalence_class"),
+            "history": a.str("history_note"),
+            "transformation": a.str("transformation_note"),
+            "member_autonomy": a.get("member_autonomy"),
+            "time_constant": time_constant,
+            "archetype": a.get("archetype"),
+        });
+        if let Some(agent) = agents.get(&id) {
+            system["agent"] = agent.clone();
+        }
+        systems.push((parse_id(&id)?, system));
+    }
+
+    let mut sources = Vec::new();
+    let mut sinks = Vec::new();
+    for doc in reader.docs(Part::ExternalEntities) {
+        let a = Attrs::of(doc, "entity");
+        let id = reader.local_id(&a)?;
+        let parsed = parse_id(&id)?;
+        let sink = id.starts_with("Snk");
+        let entity = json!({
+            "info": a.info(&id),
+            "type": if sink { "Sink" } else { "Source" },
+            "transform": a.transform(),
+            "equivalence": a.str("equivalence_class"),
+            "model": a.str("model_ref"),
+            "is_same_as_id": a.get("same_as_id"),
+        });
+        if sink {
+            sinks.push((parsed, entity));
+        } else {
+            sources.push((parsed, entity));
+        }
+    }
+
+    let interactions = reader.interactions()?;
+
+    let env_info = json!({
+        "id": "E-1",
+        "level": -1,
+        "name": model.str("display_name"),
+        "description": model.str("description"),
+    });
+    let mut hidden: Vec<Value> = model.list("hidden_entity");
+    hidden.sort_by_key(|v| v.as_str().map(str::to_string));
+    let mut world = json!({
+        "version": model.get("format_version"),
+        "environment": {
+            "info": env_info,
+            "sources": sorted(sources),
+            "sinks": sorted(sinks),
+        },
+        "systems": sorted(systems),
+        "interactions": interactions,
+        "hidden_entities": hidden,
+    });
+    if let Value::String(mode) = model.get("model_mode") {
+        world["mode"] = json!(mode);
+    }
+    Ok(serde_json::from_value(world)?)
+}
+
+struct Reader<'a> {
+    /// `{model_name}:`, stripped from every `bert_id`.
+    prefix: String,
+    documents: &'a Documents,
+}
+
+impl Reader<'_> {
+    fn docs(&self, part: Part) -> &[Value] {
+        self.documents.get(&part).map_or(&[], Vec::as_slice)
+    }
+
+    /// The local id under `key` of a relation document.
+    fn id_at(&self, doc: &Value, key: &str) -> TranspilerResult<String> {
+        let bert_id = doc[key].as_str().ok_or_else(|| missing(key, "bert_id"))?;
+        self.strip(bert_id)
+    }
+
+    /// An entity's local id.
+    fn local_id(&self, attrs: &Attrs) -> TranspilerResult<String> {
+        let bert_id = attrs.get("bert_id");
+        let bert_id = bert_id
+            .as_str()
+            .ok_or_else(|| missing("entity", "bert_id"))?;
+        self.strip(bert_id)
+    }
+
+    fn strip(&self, bert_id: &str) -> TranspilerResult<String> {
+        bert_id
+            .strip_prefix(&self.prefix)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                TranspilerError::Internal(format!(
+                    "bert_id {bert_id} is outside the {} namespace",
+                    self.prefix
+                ))
+            })
+    }
+
+    /// A relation's two ends as local ids, keyed by the first.
+    fn pairs(
+        &self,
+        part: Part,
+        key: &str,
+        value: &str,
+    ) -> TranspilerResult<BTreeMap<String, String>> {
+        self.docs(part)
+            .iter()
+            .map(|doc| Ok((self.id_at(doc, key)?, self.id_at(doc, value)?)))
+            .collect()
+    }
+
+    /// Keyless entities of `part`, grouped by their owner's local id and
+    /// ordered by `list_index`.
+    fn owned(&self, part: Part, key: &str) -> TranspilerResult<BTreeMap<String, Vec<Attrs<'_>>>> {
+        let mut by_owner: BTreeMap<String, Vec<Attrs>> = BTreeMap::new();
+        for doc in self.docs(part) {
+            by_owner
+                .entry(self.id_at(doc, "owner")?)
+                .or_default()
+                .push(Attrs::of(doc, key));
+        }
+        for items in by_owner.values_mut() {
+            items.sort_by_key(|a| a.get("list_index").as_f64().map(|i| i as i64));
+        }
+        Ok(by_owner)
+    }
+
+    /// Agent models, keyed by their system's local id.
+    fn agents(&self) -> TranspilerResult<BTreeMap<String, Value>> {
+        let mut primitives = self.owned(Part::Primitives, "primitive")?;
+        let mut params = self.owned(Part::CognitiveParams, "param")?;
+        let mut agents = BTreeMap::new();
+        for doc in self.docs(Part::Agents) {
+            let system = self.id_at(doc, "owner")?;
+            let a = Attrs::of(doc, "agent");
+            let primitives: Vec<Value> = primitives
+                .remove(&system)
+                .unwrap_or_default()
+                .iter()
+                .map(|p| p.get("process_primitive"))
+                .collect();
+            let params: Map<String, Value> = params
+                .remove(&system)
+                .unwrap_or_default()
+                .iter()
+                .map(|p| {
+                    (
+                        p.str("cognitive_param_name"),
+                        p.get("cognitive_param_value"),
+                    )
+                })
+                .collect();
+            let agent = json!({
+                "kind": a.get("agent_kind"),
+                "agency_capacity": a.get("agency_capacity"),
+                "primitives": primitives,
+                "cognitive_params": params,
+            });
+            agents.insert(system, agent);
+        }
+        Ok(agents)
+    }
+
+    fn interactions(&self) -> TranspilerResult<Vec<Value>> {
+        // (participant, interface) per interaction and endpoint.
+        let mut ends: BTreeMap<(String, &str), Value> = BTreeMap::new();
+        for doc in self.docs(Part::Participation) {
+            let role = match doc["role"].as_str() {
+                Some("source") => "source",
+                Some("sink") => "sink",
+                other => return Err(unknown("role", other)),
+            };
+            let participant = self.id_at(doc, "participant")?;
+            ends.insert((self.id_at(doc, "interaction")?, role), json!(participant));
+        }
+        for doc in self.docs(Part::Routing) {
+            let end = match doc["endpoint"].as_str() {
+                Some("start") => "source_interface",
+                Some("end") => "sink_interface",
+                other => return Err(unknown("endpoint", other)),
+            };
+            let iface = self.id_at(doc, "interface")?;
+            ends.insert((self.id_at(doc, "interaction")?, end), json!(iface));
+        }
+        let mut parameters = self.owned(Part::Parameters, "parameter")?;
+
+        let mut interactions = Vec::new();
+        for doc in self.docs(Part::Interactions) {
+            let a = Attrs::of(doc, "entity");
+            let id = self.local_id(&a)?;
+            let mut end =
+                |key: &'static str| ends.remove(&(id.clone(), key)).unwrap_or(Value::Null);
+            let parameters: Vec<Value> = parameters
+                .remove(&id)
+                .unwrap_or_default()
+                .iter()
+                .map(|p| {
+                    json!({
+                        "name": p.str("parameter_name"),
+                        "value": p.str("parameter_value"),
+                        "unit": p.str("unit"),
+                    })
+                })
+                .collect();
+            let mut interaction = json!({
+                "info": a.info(&id),
+                "substance": {
+                    "sub_type": a.str("substance_sub_type"),
+                    "type": a.get("substance_type"),
+                },
+                "type": a.get("interaction_type"),
+                "usability": a.get("usability"),
+                "source": end("source"),
+                "source_interface": end("source_interface"),
+                "sink": end("sink"),
+                "sink_interface": end("sink_interface"),
+                "amount": a.get("amount"),
+                "unit": a.str("unit"),
+                "parameters": parameters,
+            });
+            let (start, finish) = (a.get("start_angle"), a.get("end_angle"));
+            if !start.is_null() || !finish.is_null() {
+                interaction["endpoint_offset"] =
+                    json!({ "start_angle": start, "end_angle": finish });
+            }
+            interactions.push((parse_id(&id)?, interaction));
+        }
+        Ok(sorted(interactions))
+    }
+}
+
+/// The attributes of one fetched entity, as `{ $e.* }` returns them.
+struct Attrs<'a>(Option<&'a Map<String, Value>>);
+
+impl<'a> Attrs<'a> {
+    fn of(doc: &'a Value, key: &str) -> Self {
+        Self(doc[key].as_object())
+    }
+
+    /// An attribute's value, or `null` if the entity does not own one. A
+    /// single value fetched as a one-element list is unwrapped.
+    fn get(&self, key: &str) -> Value {
+        match self.0.and_then(|m| m.get(key)) {
+            Some(Value::Array(values)) if values.len() <= 1 => {
+                values.first().map_or(Value::Null, normalize)
+            }
+            Some(value) => normalize(value),
+            None => Value::Null,
+        }
+    }
+
+    fn str(&self, key: &str) -> String {
+        self.get(key).as_str().unwrap_or_default().to_string()
+    }
+
+    /// A multi-valued attribute, in any order.
+    fn list(&self, key: &str) -> Vec<Value> {
+        match self.0.and_then(|m| m.get(key)) {
+            Some(Value::Array(values)) => values.clone(),
+            Some(Value::Null) | None => Vec::new(),
+            Some(value) => vec![value.clone()],
+        }
+    }
+
+    /// The shared `Info` block; the level is `system_level`.
+    fn info(&self, id: &str) -> Value {
+        json!({
+            "id": id,
+            "level": self.get("system_level"),
+            "name": self.str("display_name"),
+            "description": self.str("description"),
+        })
+    }
+
+    fn transform(&self) -> Value {
+        match (self.get("position_x"), self.get("position_y")) {
+            (Value::Null, _) | (_, Value::Null) => Value::Null,
+            (x, y) => json!({ "translation": [x, y], "rotation": self.get("rotation") }),
+        }
+    }
+}
+
+/// Integral numbers as integers: the driver hands every number over as a
+/// double, and `level`, `multiset_count` and friends deserialize as integers.
+fn normalize(value: &Value) -> Value {
+    match value.as_f64() {
+        Some(f) if value.is_f64() && f.fract() == 0.0 && f.abs() < 2f64.powi(53) => {
+            json!(f as i64)
+        }
+        _ => value.clone(),
+    }
+}
+
+fn parse_id(local: &str) -> TranspilerResult<Id> {
+    local
+        .parse()
+        .map_err(|e| TranspilerError::InvalidModel(format!("bad id {local:?}: {e}")))
+}
+
+/// Values in [`Id`] order.
+fn sorted(mut items: Vec<(Id, Value)>) -> Vec<Value> {
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items.into_iter().map(|(_, v)| v).collect()
+}
+
+fn missing(entity: &str, field: &str) -> TranspilerError {
+    TranspilerError::MissingRequiredField {
+        entity: entity.to_string(),
+        field: field.to_string(),
+    }
+}
+
+fn unknown(attribute: &str, value: Option<&str>) -> TranspilerError {
+    TranspilerError::Internal(format!("unknown {attribute} {value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bert_core::{Complexity, InterfaceType, ProcessPrimitive};
+
+    fn entity(attrs: Value) -> Value {
+        json!({ "entity": attrs })
+    }
+
+    /// One system with an agent and a paired port, fed by a source with two
+    /// parameters fetched out of order, draining to a sink.
+    fn documents() -> Documents {
+        let mut d = Documents::new();
+        d.insert(
+            Part::Model,
+            vec![entity(json!({
+                "model_name": "m", "display_name": "Env", "description": "",
+                "format_version": 3, "model_mode": "Full", "hidden_entity": ["Src-1.0"],
+            }))],
+        );
+        d.insert(
+            Part::Systems,
+            vec![entity(json!({
+                "bert_id": "m:S0", "display_name": "Plant", "description": "a\nb",
+                "system_level": 0, "radius": 30.0, "equivalence_class": "",
+                "history_note": "", "transformation_note": "", "member_autonomy": 1.0,
+                "time_constant": "Minute", "time_constant_magnitude": "15",
+                "archetype": "Agent", "complexity_kind": "Complex",
+                "complex_adaptable": true, "complex_evolveable": false,
+                "position_x": 1.5, "position_y": -2.0, "rotation": 0.0,
+            }))],
+        );
+        d.insert(
+            Part::ExternalEntities,
+            ["Src-1.0", "Snk-1.0"]
+                .iter()
+                .map(|id| {
+                    entity(json!({
+                        "bert_id": format!("m:{id}"), "display_name": id, "description": "",
+                        "system_level": -1, "equivalence_class": "", "model_ref": "",
+                    }))
+                })
+                .collect(),
+        );
+        d.insert(
+            Part::Boundaries,
+            vec![entity(json!({
+                "bert_id": "m:B0", "display_name": "", "description": "",
+                "system_level": 0, "porosity": 0.0, "perceptive_fuzziness": 0.0,
+            }))],
+        );
+        d.insert(
+            Part::Interfaces,
+            [("I0.1", "Export"), ("I0.0", "Import")]
+                .iter()
+                .map(|(id, ty)| {
+                    entity(json!({
+                        "bert_id": format!("m:{id}"), "display_name": id, "description": "",
+                        "system_level": 1, "protocol": "", "interface_type": ty,
+                        "interface_angle": 0.5,
+                    }))
+                })
+                .collect(),
+        );
+        d.insert(
+            Part::Interactions,
+            [("F-1.1", "Product"), ("F-1.0", "Resource")]
+                .iter()
+                .map(|(id, usability)| {
+                    entity(json!({
+                        "bert_id": format!("m:{id}"), "display_name": id, "description": "",
+                        "system_level": -1, "substance_type": "Energy",
+                        "substance_sub_type": "", "interaction_type": "Flow",
+                        "usability": usability, "amount": "1", "unit": "",
+                    }))
+                })
+                .collect(),
+        );
+        d.insert(Part::Composition, Vec::new());
+        d.insert(
+            Part::HasBoundary,
+            vec![json!({ "enclosed": "m:S0", "enclosure": "m:B0" })],
+        );
+        d.insert(
+            Part::HasInterface,
+            vec![
+                json!({ "interface": "m:I0.0", "boundary": "m:B0" }),
+                json!({ "interface": "m:I0.1", "boundary": "m:B0" }),
+            ],
+        );
+        d.insert(
+            Part::PortPairing,
+            vec![json!({ "import_side": "m:I0.0", "export_side": "m:I0.1" })],
+        );
+        d.insert(
+            Part::ReceivesFrom,
+            vec![json!({ "interface": "m:I0.0", "origin": "m:Src-1.0" })],
+        );
+        d.insert(
+            Part::Participation,
+            vec![
+                json!({ "interaction": "m:F-1.0", "participant": "m:Src-1.0", "role": "source" }),
+                json!({ "interaction": "m:F-1.0", "participant": "m:S0", "role": "sink" }),
+                json!({ "interaction": "m:F-1.1", "participant": "m:S0", "role": "source" }),
+                json!({ "interaction": "m:F-1.1", "participant": "m:Snk-1.0", "role": "sink" }),
+            ],
+        );
+        d.insert(
+            Part::Routing,
+            vec![json!({ "interaction": "m:F-1.0", "interface": "m:I0.0", "endpoint": "end" })],
+        );
+        d.insert(
+            Part::Parameters,
+            [("second", 1), ("first", 0)]
+                .iter()
+                .map(|(name, i)| {
+                    json!({ "owner": "m:F-1.0", "parameter": {
+                        "parameter_name": name, "parameter_value": "1",
+                        "unit": "kg", "list_index": i,
+                    }})
+                })
+                .collect(),
+        );
+        d.insert(
+            Part::Agents,
+            vec![json!({ "owner": "m:S0", "agent": {
+                "agent_kind": "Reactive", "agency_capacity": 0.5,
+            }})],
+        );
+        d.insert(
+            Part::Primitives,
+            [("Sensing", 1), ("Combining", 0)]
+                .iter()
+                .map(|(p, i)| {
+                    json!({ "owner": "m:S0", "primitive": {
+                        "process_primitive": p, "list_index": i,
+                    }})
+                })
+                .collect(),
+        );
+        d.insert(
+            Part::CognitiveParams,
+            vec![json!({ "owner": "m:S0", "param": {
+                "cognitive_param_name": "alpha", "cognitive_param_value": 0.25,
+            }})],
+        );
+        d
+    }
+
+    #[test]
+    fn one_query_per_part_scoped_to_the_namespace() {
+        let queries = read_queries("a.b");
+        let parts: std::collections::BTreeSet<Part> = queries.iter().map(|(p, _)| *p).collect();
+        assert_eq!(parts.len(), queries.len());
+        assert_eq!(parts.len(), 19);
+        for (part, query) in &queries {
+            assert!(query.trim_end().ends_with("};"), "{part:?}: {query}");
+            if *part == Part::Model {
+                assert!(query.contains(r#"has model_name "a.b""#));
+            } else {
+                assert!(query.contains(r#"like "^a\\.b:""#), "{part:?}: {query}");
+            }
+        }
+    }
+
+    #[test]
+    fn assembles_every_stored_field() {
+        let model = assemble("m", &documents()).unwrap();
+
+        assert_eq!(model.version, 3);
+        assert_eq!(model.environment.info.name, "Env");
+        assert_eq!(model.hidden_entities.len(), 1);
+        assert_eq!(model.environment.sources.len(), 1);
+        assert_eq!(model.environment.sinks.len(), 1);
+
+        let system = &model.systems[0];
+        assert_eq!(system.info.description, "a\nb");
+        assert_eq!(system.parent.to_string(), "E-1");
+        assert_eq!(system.time_constant.unwrap().to_string(), "15 Minute");
+        assert!(system.history.is_empty());
+        assert!(system.transformation.is_none());
+        assert_eq!(
+            system.complexity,
+            Complexity::Complex {
+                adaptable: true,
+                evolveable: false
+            }
+        );
+        assert_eq!(system.transform.unwrap().translation.x, 1.5);
+
+        let agent = system.agent.as_ref().unwrap();
+        assert_eq!(
+            agent.primitives,
+            [ProcessPrimitive::Combining, ProcessPrimitive::Sensing]
+        );
+        assert_eq!(agent.cognitive_params["alpha"], 0.25);
+
+        let interfaces = &system.boundary.interfaces;
+        assert_eq!(interfaces[0].info.id.to_string(), "I0.0");
+        assert!(matches!(interfaces[0].ty, InterfaceType::Import));
+        assert_eq!(
+            interfaces[0].paired_with,
+            Some(interfaces[1].info.id.clone())
+        );
+        assert_eq!(
+            interfaces[1].paired_with,
+            Some(interfaces[0].info.id.clone())
+        );
+        assert_eq!(interfaces[0].receives_from.len(), 1);
+
+        let flow = &model.interactions[0];
+        assert_eq!(flow.info.id.to_string(), "F-1.0");
+        assert_eq!(flow.source.to_string(), "Src-1.0");
+        assert_eq!(flow.sink_interface, Some(interfaces[0].info.id.clone()));
+        assert_eq!(flow.source_interface, None);
+        let names: Vec<&str> = flow.parameters.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["first", "second"]);
+        assert!(flow.endpoint_offset.is_none());
+    }
+
+    #[test]
+    fn single_values_may_arrive_as_lists() {
+        let mut d = documents();
+        d.insert(
+            Part::Model,
+            vec![entity(json!({
+                "model_name": ["m"], "display_name": ["Env"], "format_version": [3.0],
+            }))],
+        );
+        let model = assemble("m", &d).unwrap();
+        assert_eq!(model.version, 3);
+        assert_eq!(model.environment.info.name, "Env");
+        assert!(model.mode.is_none());
+        assert!(model.hidden_entities.is_empty());
+    }
+
+    #[test]
+    fn missing_model_is_reported() {
+        let mut d = documents();
+        d.remove(&Part::Model);
+        assert!(matches!(
+            assemble("m", &d),
+            Err(TranspilerError::ModelNotFound(name)) if name == "m"
+        ));
+    }
+
+    #[test]
+    fn ids_outside_the_namespace_are_rejected() {
+        let mut d = documents();
+        d.insert(
+            Part::HasBoundary,
+            vec![json!({ "enclosed": "other:S0", "enclosure": "m:B0" })],
+        );
+        assert!(matches!(
+            assemble("m", &d),
+            Err(TranspilerError::Internal(_))
+        ));
+    }
+
+    #[test]
+    fn interfaces_without_a_boundary_are_reported() {
+        let mut d = documents();
+        d.insert(
+            Part::HasInterface,
+            vec![json!({ "interface": "m:I0.0", "boundary": "m:B0" })],
+        );
+        assert!(matches!(
+            assemble("m", &d),
+            Err(TranspilerError::MissingRequiredField { entity, field })
+                if entity == "I0.1" && field == "has_interface"
+        ));
+    }
+
+    #[test]
+    fn unknown_roles_and_endpoints_are_rejected() {
+        let mut d = documents();
+        d.get_mut(&Part::Participation).unwrap()[0]["role"] = json!("origin");
+        assert!(matches!(
+            assemble("m", &d),
+            Err(TranspilerError::Internal(m)) if m == r#"unknown role Some("origin")"#
+        ));
+
+        let mut d = documents();
+        d.get_mut(&Part::Routing).unwrap()[0]["endpoint"] = json!(null);
+        assert!(matches!(
+            assemble("m", &d),
+            Err(TranspilerError::Internal(m)) if m == "unknown endpoint None"
+        ));
+    }
+}
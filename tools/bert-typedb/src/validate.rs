@@ -2,196 +2,72 @@
 //!
 //! BERT validator 3 of 3 — pre-transpile. Siblings:
 //! `general-systems-reasoner/core/src/constraints.rs` (generation-time, spec `Value`),
-//! `bert/src/bevy_app/data_model/validate.rs` (pre-render). See the bert-dev skill "Validators".
+//! `bert-core/src/validate.rs` (pre-render). See the bert-dev skill "Validators".
 //!
 //! Runs before the transpiler emits any TypeQL. Catches:
 //!
-//! - Controlled-vocabulary violations that would fail TypeDB `@values`
-//!   constraints at insert time (e.g. unknown `time_constant`). Surfacing
-//!   these pre-insert lets us batch-report issues instead of bailing on
-//!   the first bad value.
 //! - Duplicate entity IDs (TypeDB's `@key` uniqueness fires at commit, by
 //!   which point the whole transaction is wasted).
 //! - Unresolved references (a `source`/`sink` ID that doesn't name a real
 //!   entity). TypeDB's `match ... insert` pattern for relations requires
 //!   both endpoints to exist; a dangling reference turns into a confusing
 //!   "0 matches" at insert time.
+//! - Unresolved interface links (`exports_to`, `receives_from`,
+//!   `parent_interface`). Those relations are `match ... insert`s too, so a
+//!   dangling id silently drops the link instead of failing.
 //!
-//! All checks are pure functions over `&WorldModel` — no TypeDB required,
-//! no async. Intentionally narrow: structural and referential checks in
+//! The checks themselves are bert-core's, so both validators report the same
+//! rule codes (`BERT-L2-006` for a duplicate id) and cannot drift apart; this
+//! module only picks the rules whose violation breaks an insert, and ignores
+//! the model's suppressions of them. Pure functions over `&WorldModel` — no
+//! TypeDB required, no async. Intentionally narrow: structural and
+//! referential checks in
 //! `bert/docs/bert-schema-reference.md` §L1 and §L2 are already enforced
-//! by serde deserialization. This module covers the subset that matters
-//! for TypeDB-specific failure modes.
-
-use bert::bevy_app::data_model::WorldModel;
-
-/// Controlled vocabulary for `time_constant` per SL spec §1.3 (and enforced
-/// by the TypeDB schema's `@values` constraint on `attribute time_constant`).
-const VALID_TIME_CONSTANTS: &[&str] = &[
-    "Millisecond",
-    "Second",
-    "Minute",
-    "Hour",
-    "Day",
-    "Week",
-    "Month",
-    "Year",
-    "Decade",
-    "Century",
-    "Epoch",
+//! by serde deserialization, and so is the `time_constant` vocabulary behind
+//! the schema's `@values` constraint — it is bert-core's `TimeConstant` enum,
+//! and `migrate::from_slice`, which every loader goes through, keeps an
+//! unknown value as an undated history event instead, so it never reaches an
+//! insert. This module covers the subset that matters for TypeDB-specific
+//! failure modes.
+
+use bert_core::rules::{self, Rule, RuleConfig};
+use bert_core::validate::validate_with;
+use bert_core::WorldModel;
+
+pub use bert_core::validate::{Severity, ValidationIssue};
+
+/// Rules whose violation makes TypeDB reject the insert, or silently drop part
+/// of it.
+pub const TRANSPILE_RULES: &[Rule] = &[
+    rules::DANGLING_ENDPOINT,
+    rules::DANGLING_INTERFACE,
+    rules::DANGLING_PARENT,
+    rules::DUPLICATE_ID,
+    rules::DANGLING_LINK,
 ];
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Severity {
-    Error,
-    Warning,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ValidationIssue {
-    pub severity: Severity,
-    pub location: String,
-    pub message: String,
-}
-
-impl ValidationIssue {
-    fn error(location: impl Into<String>, message: impl Into<String>) -> Self {
-        Self {
-            severity: Severity::Error,
-            location: location.into(),
-            message: message.into(),
-        }
-    }
-}
-
 /// Validates a BERT `WorldModel` against TypeDB-specific constraints.
 ///
 /// Returns every issue found (does not short-circuit on the first). An empty
 /// vector means the model is ready to transpile. Any `Severity::Error` issue
 /// means transpilation should not proceed.
 pub fn validate(model: &WorldModel) -> Vec<ValidationIssue> {
-    let mut issues = Vec::new();
-    check_time_constants(model, &mut issues);
-    check_duplicate_ids(model, &mut issues);
-    check_references_resolve(model, &mut issues);
-    issues
-}
-
-fn check_time_constants(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
-    for (idx, system) in model.systems.iter().enumerate() {
-        if !VALID_TIME_CONSTANTS.contains(&system.time_constant.as_str()) {
-            issues.push(ValidationIssue::error(
-                format!("systems[{idx}].time_constant"),
-                format!(
-                    "'{}' not in controlled vocabulary; expected one of: {}",
-                    system.time_constant,
-                    VALID_TIME_CONSTANTS.join(", ")
-                ),
-            ));
-        }
-    }
-}
-
-fn check_duplicate_ids(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
-    use std::collections::HashMap;
-    let mut seen: HashMap<String, String> = HashMap::new();
-
-    let mut record = |id: String, location: String, issues: &mut Vec<ValidationIssue>| {
-        if let Some(prior) = seen.insert(id.clone(), location.clone()) {
-            issues.push(ValidationIssue::error(
-                location,
-                format!("duplicate id '{id}' (previously seen at {prior})"),
-            ));
-        }
-    };
-
-    for (i, system) in model.systems.iter().enumerate() {
-        record(
-            serialize_id(&system.info.id),
-            format!("systems[{i}].info.id"),
-            issues,
-        );
-    }
-    for (i, src) in model.environment.sources.iter().enumerate() {
-        record(
-            serialize_id(&src.info.id),
-            format!("environment.sources[{i}].info.id"),
-            issues,
-        );
-    }
-    for (i, snk) in model.environment.sinks.iter().enumerate() {
-        record(
-            serialize_id(&snk.info.id),
-            format!("environment.sinks[{i}].info.id"),
-            issues,
-        );
-    }
-    for (i, interaction) in model.interactions.iter().enumerate() {
-        record(
-            serialize_id(&interaction.info.id),
-            format!("interactions[{i}].info.id"),
-            issues,
-        );
-    }
-}
-
-fn check_references_resolve(model: &WorldModel, issues: &mut Vec<ValidationIssue>) {
-    use std::collections::HashSet;
-    let mut known: HashSet<String> = HashSet::new();
-    known.insert("E-1".to_string());
-    for s in &model.systems {
-        known.insert(serialize_id(&s.info.id));
-    }
-    for src in &model.environment.sources {
-        known.insert(serialize_id(&src.info.id));
-    }
-    for snk in &model.environment.sinks {
-        known.insert(serialize_id(&snk.info.id));
-    }
-
-    for (i, system) in model.systems.iter().enumerate() {
-        let parent = serialize_id(&system.parent);
-        if !known.contains(&parent) {
-            issues.push(ValidationIssue::error(
-                format!("systems[{i}].parent"),
-                format!("parent '{parent}' does not resolve to a known entity"),
-            ));
-        }
-    }
-
-    for (i, ix) in model.interactions.iter().enumerate() {
-        let src = serialize_id(&ix.source);
-        let snk = serialize_id(&ix.sink);
-        if !known.contains(&src) {
-            issues.push(ValidationIssue::error(
-                format!("interactions[{i}].source"),
-                format!("source '{src}' does not resolve to a known entity"),
-            ));
-        }
-        if !known.contains(&snk) {
-            issues.push(ValidationIssue::error(
-                format!("interactions[{i}].sink"),
-                format!("sink '{snk}' does not resolve to a known entity"),
-            ));
-        }
-    }
-}
-
-fn serialize_id(id: &bert::bevy_app::data_model::Id) -> String {
-    serde_json::to_string(id)
-        .ok()
-        .and_then(|s| {
-            // serde quotes the string; strip the surrounding double quotes
-            s.strip_prefix('"')
-                .and_then(|s| s.strip_suffix('"'))
-                .map(|s| s.to_string())
-        })
-        .unwrap_or_default()
+    validate_with(model, &RuleConfig::default())
+        .issues
+        .into_iter()
+        .filter(|issue| TRANSPILE_RULES.iter().any(|rule| rule.code == issue.code))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bert_core::{migrate, TimeConstant};
+
+    /// Load `bytes` the way the CLI and batch do, migrating older versions.
+    fn load(bytes: &[u8]) -> WorldModel {
+        migrate::from_slice(bytes).expect("model should load").0
+    }
 
     /// Loads the canonical reference model — already spec-compliant per #14,
     /// so should validate with zero issues. Guards against regressions where
@@ -203,8 +79,7 @@ mod tests {
             "/../../assets/models/examples/bitcoin.json"
         );
         let bytes = std::fs::read(path).expect("bitcoin.json should exist");
-        let model: WorldModel =
-            serde_json::from_slice(&bytes).expect("bitcoin.json should deserialize");
+        let model = load(&bytes);
         let issues = validate(&model);
         assert!(
             issues.is_empty(),
@@ -215,14 +90,14 @@ mod tests {
 
     #[test]
     fn time_constant_vocabulary_matches_schema() {
-        // If schema.tql's @values list diverges from VALID_TIME_CONSTANTS,
-        // validate() will report false negatives. This test ensures they
-        // stay in sync.
+        // If schema.tql's @values list diverges from bert-core's TimeConstant,
+        // a model that loads cleanly can still fail at insert. This test
+        // ensures they stay in sync.
         let schema = crate::schema::SCHEMA_TQL;
-        for tc in VALID_TIME_CONSTANTS {
+        for tc in TimeConstant::all() {
             assert!(
                 schema.contains(&format!("\"{tc}\"")),
-                "time_constant '{tc}' from validator not found in schema's @values"
+                "time_constant '{tc}' from bert-core not found in schema's @values"
             );
         }
     }
@@ -240,7 +115,7 @@ mod tests {
                 continue;
             }
             let bytes = std::fs::read(&path).unwrap();
-            let model: WorldModel = serde_json::from_slice(&bytes).unwrap();
+            let model = load(&bytes);
             let issues = validate(&model);
             let errors: Vec<_> = issues
                 .iter()
@@ -258,7 +133,7 @@ mod tests {
     }
 
     #[test]
-    fn invalid_time_constant_is_flagged() {
+    fn unknown_time_constant_becomes_history_on_load() {
         let json = r#"{
             "version": 1,
             "environment": {"info": {"id": "E-1", "level": -1, "name": "", "description": ""}, "sources": [], "sinks": []},
@@ -279,12 +154,12 @@ mod tests {
             }],
             "interactions": []
         }"#;
-        let model: WorldModel = serde_json::from_str(json).unwrap();
-        let issues = validate(&model);
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].severity, Severity::Error);
-        assert!(issues[0].message.contains("HorseYear"));
-        assert!(issues[0].location.contains("time_constant"));
+        let model = load(json.as_bytes());
+        let sys = &model.systems[0];
+        assert_eq!(sys.time_constant, None);
+        let events: Vec<&str> = sys.history.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(events, ["Time constant: HorseYear"]);
+        assert!(validate(&model).is_empty());
     }
 
     #[test]
@@ -321,10 +196,46 @@ mod tests {
                 "parameters": []
             }]
         }"#;
-        let model: WorldModel = serde_json::from_str(json).unwrap();
+        let model = load(json.as_bytes());
         let issues = validate(&model);
         assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, rules::DANGLING_ENDPOINT.code);
         assert!(issues[0].message.contains("Src-1.99"));
         assert!(issues[0].location.contains("source"));
     }
+
+    #[test]
+    fn unresolved_interface_link_is_flagged() {
+        let json = r#"{
+            "version": 1,
+            "environment": {"info": {"id": "E-1", "level": -1, "name": "", "description": ""}, "sources": [], "sinks": []},
+            "systems": [{
+                "info": {"id": "S0", "level": 0, "name": "Test", "description": ""},
+                "sources": [], "sinks": [],
+                "parent": "E-1",
+                "complexity": "Atomic",
+                "boundary": {
+                    "info": {"id": "B0", "level": 0, "name": "", "description": ""},
+                    "porosity": 0.0, "perceptive_fuzziness": 0.0,
+                    "interfaces": [{
+                        "info": {"id": "I0.0", "level": 1, "name": "Out", "description": ""},
+                        "protocol": "", "type": "Export",
+                        "exports_to": ["Snk-1.7"], "receives_from": []
+                    }],
+                    "parent_interface": null
+                },
+                "radius": 100.0,
+                "equivalence": "", "history": "", "transformation": "",
+                "member_autonomy": 1.0,
+                "time_constant": "Second"
+            }],
+            "interactions": []
+        }"#;
+        let model = load(json.as_bytes());
+        let issues = validate(&model);
+        assert_eq!(issues.len(), 1, "{issues:#?}");
+        assert_eq!(issues[0].code, rules::DANGLING_LINK.code);
+        assert!(issues[0].message.contains("Snk-1.7"));
+        assert!(issues[0].location.ends_with("exports_to[0]"));
+    }
 }
@@ -0,0 +1,61 @@
+//! Batch push of the example models against a live TypeDB.
+//!
+//! Gated by the `integration` feature. Requires TypeDB listening on
+//! `localhost:1729` with default credentials. Run:
+//!
+//! ```sh
+//! cargo test -p bert-typedb --features integration --test batch
+//! ```
+
+#![cfg(feature = "integration")]
+
+use bert_typedb::{collect_models, prepare, read_model, transpile_batch, Status};
+use std::path::PathBuf;
+
+mod common;
+
+fn examples() -> Vec<PathBuf> {
+    collect_models(&[common::examples_dir()]).expect("examples should be found")
+}
+
+#[tokio::test]
+async fn every_example_pushes_and_failures_do_not_stop_the_rest() {
+    let t = common::setup("bert_batch").await;
+
+    let files = examples();
+    let reports = transpile_batch(prepare(&files, false), 3, &t).await;
+    assert_eq!(reports.len(), files.len());
+    for report in &reports {
+        assert_eq!(
+            report.status,
+            Status::Pushed,
+            "{}: {:?}",
+            report.model_name,
+            report.errors
+        );
+    }
+    read_model("ethereum", &t)
+        .await
+        .expect("a batch-pushed model reads back");
+
+    // bitcoin is already there, so its plain push fails; the ethereum
+    // replace and the missing file go on regardless.
+    let named = |name: &str| {
+        let file = files.iter().find(|p| p.ends_with(name)).unwrap();
+        std::slice::from_ref(file)
+    };
+    let mut again = prepare(named("bitcoin.json"), false);
+    again.extend(prepare(named("ethereum.json"), true));
+    again.extend(prepare(&[PathBuf::from("missing.json")], false));
+    let reports = transpile_batch(again, 1, &t).await;
+    let statuses: Vec<Status> = reports.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        [Status::Failed, Status::Pushed, Status::Invalid],
+        "{reports:#?}"
+    );
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
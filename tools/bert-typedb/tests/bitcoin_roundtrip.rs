@@ -13,7 +13,7 @@
 
 #![cfg(feature = "integration")]
 
-use bert::bevy_app::data_model::WorldModel;
+use bert_core::WorldModel;
 use bert_typedb::{transpile_and_push, Transpiler};
 
 fn unique_db_name() -> String {
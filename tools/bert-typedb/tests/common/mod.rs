@@ -0,0 +1,45 @@
+//! Helpers shared by the integration tests. Each test binary uses a subset.
+
+#![allow(dead_code)]
+
+use bert_core::{migrate, WorldModel};
+use bert_typedb::Transpiler;
+use std::path::PathBuf;
+
+/// A database name no other run uses, so concurrent runs don't collide.
+pub fn unique_db_name(prefix: &str) -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("{prefix}_{nanos}")
+}
+
+/// A fresh database on the local TypeDB, with the BERT schema loaded.
+pub async fn setup(prefix: &str) -> Transpiler {
+    let t = Transpiler::connect("localhost:1729", &unique_db_name(prefix))
+        .await
+        .expect("connect to TypeDB");
+    t.ensure_database().await.expect("create test database");
+    t.load_schema().await.expect("load schema");
+    t
+}
+
+/// `assets/models/examples`.
+pub fn examples_dir() -> PathBuf {
+    PathBuf::from(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../assets/models/examples"
+    ))
+}
+
+/// An example model loaded the way the CLI loads it, migrated to the current
+/// version.
+pub fn example(name: &str) -> WorldModel {
+    let path = examples_dir().join(format!("{name}.json"));
+    let bytes = std::fs::read(&path).expect("example model should exist");
+    migrate::from_slice(&bytes)
+        .expect("example model should load")
+        .0
+}
@@ -0,0 +1,111 @@
+//! Model lifecycle against a live TypeDB: replace, delete and sync.
+//!
+//! `bert_id` is a `@key`, so a plain push of a model whose ids are already
+//! in the database fails. These tests use that as the probe for what a
+//! namespace holds.
+//!
+//! Gated by the `integration` feature. Requires TypeDB listening on
+//! `localhost:1729` with default credentials. Run:
+//!
+//! ```sh
+//! cargo test -p bert-typedb --features integration --test lifecycle
+//! ```
+
+#![cfg(feature = "integration")]
+
+use bert_typedb::{delete_model, transpile_and_push, transpile_and_replace, transpile_and_sync};
+
+mod common;
+use common::{example, setup};
+
+#[tokio::test]
+async fn replace_is_idempotent() {
+    let model = example("bitcoin");
+    let t = setup("bert_lifecycle").await;
+
+    transpile_and_replace(&model, "bitcoin", &t)
+        .await
+        .expect("replace into an empty namespace");
+    transpile_and_replace(&model, "bitcoin", &t)
+        .await
+        .expect("replace the same model again");
+    assert!(
+        transpile_and_push(&model, "bitcoin", &t).await.is_err(),
+        "the replaced model should still be there"
+    );
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
+
+#[tokio::test]
+async fn delete_removes_only_its_namespace() {
+    let model = example("bitcoin");
+    let t = setup("bert_lifecycle").await;
+
+    delete_model("bitcoin", &t)
+        .await
+        .expect("deleting an absent model is a no-op");
+    transpile_and_push(&model, "left", &t)
+        .await
+        .expect("push left");
+    transpile_and_push(&model, "right", &t)
+        .await
+        .expect("push right");
+
+    delete_model("left", &t).await.expect("delete left");
+    transpile_and_push(&model, "left", &t)
+        .await
+        .expect("left's ids are free again");
+    assert!(
+        transpile_and_push(&model, "right", &t).await.is_err(),
+        "right should be untouched"
+    );
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
+
+#[tokio::test]
+async fn sync_applies_changes_in_both_directions() {
+    let before = example("bitcoin");
+    let mut after = before.clone();
+    after.interactions[0].info.name = "Renamed".to_string();
+    after.environment.info.description = "Synced".to_string();
+    let removed = after.interactions.pop().unwrap();
+    let t = setup("bert_lifecycle").await;
+
+    transpile_and_push(&before, "bitcoin", &t)
+        .await
+        .expect("push before");
+    transpile_and_sync(&before, &after, "bitcoin", &t)
+        .await
+        .expect("sync before → after");
+    transpile_and_sync(&after, &before, "bitcoin", &t)
+        .await
+        .expect("sync back: the removed interaction's id must be free");
+    let summary = transpile_and_sync(&before, &before, "bitcoin", &t)
+        .await
+        .expect("empty sync");
+    assert_eq!(summary.statements_executed, 0);
+
+    // A sync from the wrong previous model collides with what is stored.
+    let mut stale = before.clone();
+    stale
+        .interactions
+        .retain(|ix| ix.info.id != removed.info.id);
+    assert!(transpile_and_sync(&stale, &before, "bitcoin", &t)
+        .await
+        .is_err());
+
+    delete_model("bitcoin", &t).await.expect("delete bitcoin");
+    transpile_and_push(&before, "bitcoin", &t)
+        .await
+        .expect("sync left nothing behind the delete missed");
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
@@ -0,0 +1,163 @@
+//! Canned queries against a live TypeDB holding several models.
+//!
+//! Gated by the `integration` feature. Requires TypeDB listening on
+//! `localhost:1729` with default credentials. Run:
+//!
+//! ```sh
+//! cargo test -p bert-typedb --features integration --test query
+//! ```
+
+#![cfg(feature = "integration")]
+
+use bert_core::{AgentModel, HcgsArchetype, ProcessPrimitive, SubstanceType, WorldModel};
+use bert_typedb::query::{
+    AgentsWithPrimitive, EquivalentExternals, FlowsAcrossBoundary, SubsystemsAtDepth,
+    SystemsByArchetype,
+};
+use bert_typedb::{run_query, transpile_and_push, Transpiler};
+
+mod common;
+use common::example;
+
+/// bitcoin and ethereum as shipped, plus a bitcoin copy whose first
+/// subsystem is an agent that senses.
+async fn setup() -> (Transpiler, WorldModel) {
+    let t = common::setup("bert_query").await;
+
+    let bitcoin = example("bitcoin");
+    let mut agents = bitcoin.clone();
+    let system = &mut agents.systems[1];
+    system.archetype = Some(HcgsArchetype::Agent);
+    system.agent = Some(AgentModel {
+        primitives: vec![ProcessPrimitive::Sensing, ProcessPrimitive::Combining],
+        ..AgentModel::default()
+    });
+    for (model, name) in [
+        (&bitcoin, "bitcoin"),
+        (&example("ethereum"), "ethereum"),
+        (&agents, "agents"),
+    ] {
+        transpile_and_push(model, name, &t)
+            .await
+            .unwrap_or_else(|e| panic!("push {name}: {e}"));
+    }
+    (t, bitcoin)
+}
+
+#[tokio::test]
+async fn canned_queries_span_models() {
+    let (t, _) = setup().await;
+
+    let economies = run_query(
+        &SystemsByArchetype {
+            archetype: HcgsArchetype::Economy,
+            model: None,
+        },
+        &t,
+    )
+    .await
+    .expect("systems by archetype");
+    let models: Vec<&str> = economies.iter().map(|r| r.model.as_str()).collect();
+    assert!(models.contains(&"bitcoin") && models.contains(&"ethereum"));
+
+    let scoped = run_query(
+        &SystemsByArchetype {
+            archetype: HcgsArchetype::Economy,
+            model: Some("bitcoin".to_string()),
+        },
+        &t,
+    )
+    .await
+    .expect("scoped systems by archetype");
+    assert!(!scoped.is_empty());
+    assert!(scoped.iter().all(|r| r.model == "bitcoin"));
+
+    let sensing = run_query(
+        &AgentsWithPrimitive {
+            primitive: ProcessPrimitive::Sensing,
+            model: None,
+        },
+        &t,
+    )
+    .await
+    .expect("agents with primitive");
+    assert_eq!(sensing.len(), 1);
+    assert_eq!(sensing[0].model, "agents");
+
+    let flows = run_query(
+        &FlowsAcrossBoundary {
+            substance: SubstanceType::Message,
+            model: Some("bitcoin".to_string()),
+        },
+        &t,
+    )
+    .await
+    .expect("flows across boundary");
+    assert!(!flows.is_empty());
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
+
+#[tokio::test]
+async fn depth_and_equivalents() {
+    let (t, bitcoin) = setup().await;
+
+    let root = bitcoin.systems.iter().find(|s| s.info.level == 0).unwrap();
+    let children = bitcoin
+        .systems
+        .iter()
+        .filter(|s| s.parent == root.info.id)
+        .count();
+    let depth_one = run_query(
+        &SubsystemsAtDepth {
+            depth: 1,
+            under: None,
+            model: Some("bitcoin".to_string()),
+        },
+        &t,
+    )
+    .await
+    .expect("subsystems at depth 1");
+    assert_eq!(depth_one.len(), children);
+    assert!(depth_one.iter().all(|r| r.level == 1));
+
+    let roots = run_query(
+        &SubsystemsAtDepth {
+            depth: 0,
+            under: None,
+            model: None,
+        },
+        &t,
+    )
+    .await
+    .expect("roots");
+    assert_eq!(roots.len(), 3);
+
+    // bitcoin's Users source and sink share an is_same_as_id.
+    let within = run_query(
+        &EquivalentExternals {
+            across_models: false,
+        },
+        &t,
+    )
+    .await
+    .expect("equivalent externals");
+    assert!(within
+        .iter()
+        .any(|p| p.left_model == "bitcoin" && p.right_model == "bitcoin"));
+    let across = run_query(
+        &EquivalentExternals {
+            across_models: true,
+        },
+        &t,
+    )
+    .await
+    .expect("equivalent externals across models");
+    assert!(across.iter().all(|p| p.left_model != p.right_model));
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
@@ -0,0 +1,61 @@
+//! JSON → TypeDB → JSON against a live TypeDB: a model read back from the
+//! graph is the model that was pushed.
+//!
+//! Gated by the `integration` feature. Requires TypeDB listening on
+//! `localhost:1729` with default credentials. Run:
+//!
+//! ```sh
+//! cargo test -p bert-typedb --features integration --test read_roundtrip
+//! ```
+
+#![cfg(feature = "integration")]
+
+use bert_core::to_canonical_json;
+use bert_typedb::{read_model, transpile_and_push, validate, Severity};
+
+mod common;
+use common::{example, setup};
+
+#[tokio::test]
+async fn bitcoin_round_trips_losslessly() {
+    let model = example("bitcoin");
+    let t = setup("bert_read").await;
+
+    transpile_and_push(&model, "bitcoin", &t)
+        .await
+        .expect("push bitcoin");
+    let read = read_model("bitcoin", &t).await.expect("read bitcoin back");
+
+    assert_eq!(to_canonical_json(&read), to_canonical_json(&model));
+    let errors: Vec<_> = validate(&read)
+        .into_iter()
+        .filter(|i| i.severity == Severity::Error)
+        .collect();
+    assert!(errors.is_empty(), "{errors:?}");
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}
+
+#[tokio::test]
+async fn reads_only_its_namespace() {
+    let model = example("bitcoin");
+    let t = setup("bert_read").await;
+
+    assert!(read_model("bitcoin", &t).await.is_err());
+    transpile_and_push(&model, "bitcoin", &t)
+        .await
+        .expect("push bitcoin");
+    transpile_and_push(&model, "bitcoin2", &t)
+        .await
+        .expect("push bitcoin2");
+
+    let read = read_model("bitcoin", &t).await.expect("read bitcoin back");
+    assert_eq!(read.systems.len(), model.systems.len());
+    assert_eq!(read.interactions.len(), model.interactions.len());
+
+    t.drop_database()
+        .await
+        .expect("drop test database on teardown");
+}